@@ -63,10 +63,7 @@ pub trait FarmContract {
             return BigUint::zero();
         }
 
-        let per_block_reward = sc.per_block_reward_amount().get();
-        let block_nonce_diff = current_block_nonce - last_reward_block_nonce;
-
-        per_block_reward * block_nonce_diff
+        sc.calculate_emission(last_reward_block_nonce, current_block_nonce)
     }
 
     fn mint_per_block_rewards(
@@ -1,6 +1,44 @@
 #![no_std]
 
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_errors::ERROR_PARAMETERS;
+use common_structs::Nonce;
+
+pub const MAX_EMISSION_SEGMENTS: usize = 20;
+pub const MAX_DECAY_PERCENT: u64 = 10_000;
+pub const MIN_DECAY_PERCENT: u64 = 10;
+pub const MIN_DECAY_PERIOD_BLOCKS: Nonce = 600;
+pub const DECAY_FACTOR_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+#[type_abi]
+#[derive(
+    ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug,
+)]
+pub struct EmissionSegment<M: ManagedTypeApi> {
+    pub start_block: Nonce,
+    pub per_block_amount: BigUint<M>,
+}
+
+/// The per block amount is decreased by `decay_percent` every `period_blocks` blocks,
+/// starting from `start_block`. A decay percent of 5_000 halves the emission each period.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct EmissionDecayCurve<M: ManagedTypeApi> {
+    pub start_block: Nonce,
+    pub initial_per_block_amount: BigUint<M>,
+    pub period_blocks: Nonce,
+    pub decay_percent: u64,
+}
+
+/// Until the schedule starts, the flat `per_block_reward_amount` is used.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub enum EmissionSchedule<M: ManagedTypeApi> {
+    Segments(ManagedVec<M, EmissionSegment<M>>),
+    DecayCurve(EmissionDecayCurve<M>),
+}
 
 #[multiversx_sc::module]
 pub trait RewardsModule:
@@ -8,7 +46,7 @@ pub trait RewardsModule:
 {
     fn start_produce_rewards(&self) {
         require!(
-            self.per_block_reward_amount().get() != 0u64,
+            self.per_block_reward_amount().get() != 0u64 || !self.emission_schedule().is_empty(),
             "Cannot produce zero reward amount"
         );
         require!(
@@ -25,6 +63,186 @@ pub trait RewardsModule:
         self.produce_rewards_enabled().get()
     }
 
+    /// The rewards have to be aggregated before changing the schedule.
+    fn set_emission_schedule(&self, schedule: EmissionSchedule<Self::Api>) {
+        let current_block = self.blockchain().get_block_nonce();
+        match &schedule {
+            EmissionSchedule::Segments(segments) => {
+                require!(
+                    !segments.is_empty() && segments.len() <= MAX_EMISSION_SEGMENTS,
+                    ERROR_PARAMETERS
+                );
+
+                let mut last_start_block = None;
+                for segment in segments.iter() {
+                    require!(
+                        segment.start_block >= current_block,
+                        "Segment starts in the past"
+                    );
+                    if let Some(last_start_block) = last_start_block {
+                        require!(
+                            segment.start_block > last_start_block,
+                            "Segments not sorted by start block"
+                        );
+                    }
+                    last_start_block = Some(segment.start_block);
+                }
+            }
+            EmissionSchedule::DecayCurve(curve) => {
+                require!(
+                    curve.start_block >= current_block,
+                    "Curve starts in the past"
+                );
+                require!(curve.initial_per_block_amount > 0, ERROR_PARAMETERS);
+                require!(
+                    curve.period_blocks >= MIN_DECAY_PERIOD_BLOCKS,
+                    "Decay period too short"
+                );
+                require!(
+                    curve.decay_percent >= MIN_DECAY_PERCENT
+                        && curve.decay_percent < MAX_DECAY_PERCENT,
+                    "Invalid decay percent"
+                );
+            }
+        }
+
+        self.emission_schedule().set(schedule);
+    }
+
+    /// Rewards emitted for the blocks in the `[from_block, to_block)` range.
+    #[view(getEmissionForBlocks)]
+    fn calculate_emission(&self, from_block: Nonce, to_block: Nonce) -> BigUint {
+        if to_block <= from_block {
+            return BigUint::zero();
+        }
+
+        let per_block_reward_amount = self.per_block_reward_amount().get();
+        let emission_schedule_mapper = self.emission_schedule();
+        if emission_schedule_mapper.is_empty() {
+            return per_block_reward_amount * (to_block - from_block);
+        }
+
+        match emission_schedule_mapper.get() {
+            EmissionSchedule::Segments(segments) => self.calculate_segments_emission(
+                &segments,
+                per_block_reward_amount,
+                from_block,
+                to_block,
+            ),
+            EmissionSchedule::DecayCurve(curve) => self.calculate_decay_curve_emission(
+                &curve,
+                per_block_reward_amount,
+                from_block,
+                to_block,
+            ),
+        }
+    }
+
+    fn calculate_segments_emission(
+        &self,
+        segments: &ManagedVec<EmissionSegment<Self::Api>>,
+        per_block_reward_amount: BigUint,
+        from_block: Nonce,
+        to_block: Nonce,
+    ) -> BigUint {
+        let mut total = BigUint::zero();
+        let mut current_amount = per_block_reward_amount;
+        let mut current_block = from_block;
+        for segment in segments.iter() {
+            if segment.start_block >= to_block {
+                break;
+            }
+            if segment.start_block > current_block {
+                total += &current_amount * (segment.start_block - current_block);
+                current_block = segment.start_block;
+            }
+
+            current_amount = segment.per_block_amount.clone();
+        }
+
+        total + current_amount * (to_block - current_block)
+    }
+
+    /// The full periods are summed as a geometric series, so the cost does not depend
+    /// on the number of periods in the range.
+    fn calculate_decay_curve_emission(
+        &self,
+        curve: &EmissionDecayCurve<Self::Api>,
+        per_block_reward_amount: BigUint,
+        from_block: Nonce,
+        to_block: Nonce,
+    ) -> BigUint {
+        let mut total = BigUint::zero();
+        let mut current_block = from_block;
+        if current_block < curve.start_block {
+            let blocks_before_curve = core::cmp::min(to_block, curve.start_block) - current_block;
+            total += per_block_reward_amount * blocks_before_curve;
+            current_block = curve.start_block;
+        }
+        if current_block >= to_block {
+            return total;
+        }
+
+        let first_period = (current_block - curve.start_block) / curve.period_blocks;
+        let last_period = (to_block - 1 - curve.start_block) / curve.period_blocks;
+        let first_period_amount = self.get_decay_curve_period_amount(curve, first_period);
+        if first_period == last_period {
+            return total + first_period_amount * (to_block - current_block);
+        }
+
+        let first_period_end_block = curve.start_block + (first_period + 1) * curve.period_blocks;
+        total += first_period_amount * (first_period_end_block - current_block);
+
+        // sum of r^k for k in [first_period + 1, last_period) is (r^first - r^last) / (1 - r)
+        let full_periods_start_factor = self.get_decay_factor(curve, first_period + 1);
+        let last_period_factor = self.get_decay_factor(curve, last_period);
+        total += &curve.initial_per_block_amount
+            * curve.period_blocks
+            * (full_periods_start_factor - &last_period_factor)
+            * MAX_DECAY_PERCENT
+            / (BigUint::from(curve.decay_percent) * DECAY_FACTOR_PRECISION);
+
+        let last_period_start_block = curve.start_block + last_period * curve.period_blocks;
+        let last_period_amount =
+            &curve.initial_per_block_amount * &last_period_factor / DECAY_FACTOR_PRECISION;
+        total + last_period_amount * (to_block - last_period_start_block)
+    }
+
+    fn get_decay_curve_period_amount(
+        &self,
+        curve: &EmissionDecayCurve<Self::Api>,
+        period: u64,
+    ) -> BigUint {
+        &curve.initial_per_block_amount * &self.get_decay_factor(curve, period)
+            / DECAY_FACTOR_PRECISION
+    }
+
+    /// `(1 - decay_percent / MAX_DECAY_PERCENT) ^ periods`, scaled by `DECAY_FACTOR_PRECISION`.
+    /// Computed by squaring, rounding down at each step.
+    fn get_decay_factor(&self, curve: &EmissionDecayCurve<Self::Api>, periods: u64) -> BigUint {
+        let precision = BigUint::from(DECAY_FACTOR_PRECISION);
+        let mut factor = precision.clone();
+        let mut base =
+            BigUint::from(MAX_DECAY_PERCENT - curve.decay_percent) * &precision / MAX_DECAY_PERCENT;
+        let mut exponent = periods;
+        while exponent > 0 && factor > 0 {
+            if exponent % 2 == 1 {
+                factor = factor * &base / &precision;
+            }
+
+            exponent /= 2;
+            if exponent > 0 {
+                base = &base * &base / &precision;
+            }
+        }
+
+        factor
+    }
+
+    #[view(getEmissionSchedule)]
+    #[storage_mapper("emission_schedule")]
+    fn emission_schedule(&self) -> SingleValueMapper<EmissionSchedule<Self::Api>>;
+
     #[view(getRewardPerShare)]
     #[storage_mapper("reward_per_share")]
     fn reward_per_share(&self) -> SingleValueMapper<BigUint>;
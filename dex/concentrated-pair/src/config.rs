@@ -0,0 +1,76 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use pausable::State;
+
+use crate::errors::*;
+use crate::math::tick_math::Tick;
+
+pub const MAX_PERCENTAGE: u64 = 100_000;
+pub const MAX_FEE_PERCENTAGE: u64 = 5_000;
+pub const MAX_TICK_SPACING: Tick = 16_384;
+
+#[multiversx_sc::module]
+pub trait ConfigModule:
+    token_send::TokenSendModule + permissions_module::PermissionsModule + pausable::PausableModule
+{
+    #[endpoint(setStateActiveNoSwaps)]
+    fn set_state_active_no_swaps(&self) {
+        self.require_caller_has_owner_permissions();
+        self.state().set(State::PartialActive);
+    }
+
+    #[endpoint(setFeePercent)]
+    fn set_fee_percent_endpoint(&self, total_fee_percent: u64) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.set_fee_percent(total_fee_percent);
+    }
+
+    fn set_fee_percent(&self, total_fee_percent: u64) {
+        require!(total_fee_percent <= MAX_FEE_PERCENTAGE, ERROR_BAD_PERCENTS);
+        self.total_fee_percent().set(total_fee_percent);
+    }
+
+    #[view(getTotalFeePercent)]
+    #[storage_mapper("total_fee_percent")]
+    fn total_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getRouterManagedAddress)]
+    #[storage_mapper("router_address")]
+    fn router_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getFirstTokenId)]
+    #[storage_mapper("first_token_id")]
+    fn first_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getSecondTokenId)]
+    #[storage_mapper("second_token_id")]
+    fn second_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getTickSpacing)]
+    #[storage_mapper("tick_spacing")]
+    fn tick_spacing(&self) -> SingleValueMapper<Tick>;
+
+    /// Square root of the price of the first token in terms of the second one, as a Q64.96 number
+    #[view(getSqrtPrice)]
+    #[storage_mapper("sqrt_price")]
+    fn sqrt_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getCurrentTick)]
+    #[storage_mapper("current_tick")]
+    fn current_tick(&self) -> SingleValueMapper<Tick>;
+
+    /// Liquidity of the positions whose range contains the current tick
+    #[view(getActiveLiquidity)]
+    #[storage_mapper("active_liquidity")]
+    fn active_liquidity(&self) -> SingleValueMapper<BigUint>;
+
+    /// Fees earned per unit of liquidity over the lifetime of the pool, as Q128.128 numbers
+    #[view(getFeeGrowthGlobal)]
+    #[storage_mapper("fee_growth_global")]
+    fn fee_growth_global(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getReserve)]
+    #[storage_mapper("reserve")]
+    fn pair_reserve(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
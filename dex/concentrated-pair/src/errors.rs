@@ -0,0 +1,26 @@
+pub static ERROR_NOT_ACTIVE: &[u8] = b"Not active";
+pub static ERROR_NOT_AN_ESDT: &[u8] = b"Not a valid esdt id";
+pub static ERROR_SAME_TOKENS: &[u8] = b"Exchange tokens cannot be the same";
+pub static ERROR_BAD_PERCENTS: &[u8] = b"Bad percents";
+pub static ERROR_INVALID_ARGS: &[u8] = b"Invalid args";
+pub static ERROR_BAD_PAYMENT_TOKENS: &[u8] = b"Bad payment tokens";
+pub static ERROR_ZERO_AMOUNT: &[u8] = b"Zero amount";
+pub static ERROR_UNKNOWN_TOKEN: &[u8] = b"Unknown token";
+
+pub static ERROR_SWAP_NOT_ENABLED: &[u8] = b"Swap is not enabled";
+pub static ERROR_SLIPPAGE_EXCEEDED: &[u8] = b"Slippage exceeded";
+pub static ERROR_NOT_ENOUGH_RESERVE: &[u8] = b"Not enough reserve";
+pub static ERROR_NOT_ENOUGH_LIQUIDITY: &[u8] = b"Not enough liquidity";
+
+pub static ERROR_INVALID_TICK_SPACING: &[u8] = b"Invalid tick spacing";
+pub static ERROR_TICK_OUT_OF_RANGE: &[u8] = b"Tick out of range";
+pub static ERROR_SQRT_PRICE_OUT_OF_RANGE: &[u8] = b"Sqrt price out of range";
+pub static ERROR_INVALID_TICK_RANGE: &[u8] = b"Invalid tick range";
+pub static ERROR_TICK_NOT_SPACED: &[u8] = b"Tick is not a multiple of the tick spacing";
+
+pub static ERROR_POSITION_TOKEN_NOT_ISSUED: &[u8] = b"Position token not issued";
+pub static ERROR_INVALID_POSITION: &[u8] = b"Invalid position";
+pub static ERROR_INSUFFICIENT_LIQUIDITY: &[u8] = b"Insufficient liquidity minted";
+pub static ERROR_INSUFFICIENT_FIRST_TOKEN: &[u8] = b"Insufficient first token computed amount";
+pub static ERROR_INSUFFICIENT_SECOND_TOKEN: &[u8] = b"Insufficient second token computed amount";
+pub static ERROR_NOT_ENOUGH_POSITION_LIQUIDITY: &[u8] = b"Not enough liquidity in position";
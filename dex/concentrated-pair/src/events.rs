@@ -0,0 +1,177 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::math::tick_math::Tick;
+use crate::storage_cache::StorageCache;
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct SwapEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    token_id_in: TokenIdentifier<M>,
+    token_amount_in: BigUint<M>,
+    token_id_out: TokenIdentifier<M>,
+    token_amount_out: BigUint<M>,
+    fee_amount: BigUint<M>,
+    sqrt_price: BigUint<M>,
+    current_tick: Tick,
+    active_liquidity: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct PositionEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    position_nonce: u64,
+    tick_lower: Tick,
+    tick_upper: Tick,
+    liquidity_delta: BigInt<M>,
+    position_liquidity: BigUint<M>,
+    first_token_id: TokenIdentifier<M>,
+    first_token_amount: BigUint<M>,
+    second_token_id: TokenIdentifier<M>,
+    second_token_amount: BigUint<M>,
+    first_token_reserves: BigUint<M>,
+    second_token_reserves: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+pub struct SwapEventData<M: ManagedTypeApi> {
+    pub token_id_in: TokenIdentifier<M>,
+    pub token_amount_in: BigUint<M>,
+    pub token_id_out: TokenIdentifier<M>,
+    pub token_amount_out: BigUint<M>,
+    pub fee_amount: BigUint<M>,
+}
+
+pub struct PositionEventData<M: ManagedTypeApi> {
+    pub position_nonce: u64,
+    pub tick_lower: Tick,
+    pub tick_upper: Tick,
+    pub liquidity_delta: BigInt<M>,
+    pub position_liquidity: BigUint<M>,
+    pub first_token_amount: BigUint<M>,
+    pub second_token_amount: BigUint<M>,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule:
+    crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    fn emit_swap_event(&self, storage_cache: &StorageCache<Self>, data: SwapEventData<Self::Api>) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.swap_event(
+            &data.token_id_in.clone(),
+            &data.token_id_out.clone(),
+            &caller,
+            epoch,
+            &SwapEvent {
+                caller: caller.clone(),
+                token_id_in: data.token_id_in,
+                token_amount_in: data.token_amount_in,
+                token_id_out: data.token_id_out,
+                token_amount_out: data.token_amount_out,
+                fee_amount: data.fee_amount,
+                sqrt_price: storage_cache.sqrt_price.clone(),
+                current_tick: storage_cache.current_tick,
+                active_liquidity: storage_cache.active_liquidity.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_update_position_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        data: PositionEventData<Self::Api>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.update_position_event(
+            &storage_cache.first_token_id,
+            &storage_cache.second_token_id,
+            &caller,
+            epoch,
+            &PositionEvent {
+                caller: caller.clone(),
+                position_nonce: data.position_nonce,
+                tick_lower: data.tick_lower,
+                tick_upper: data.tick_upper,
+                liquidity_delta: data.liquidity_delta,
+                position_liquidity: data.position_liquidity,
+                first_token_id: storage_cache.first_token_id.clone(),
+                first_token_amount: data.first_token_amount,
+                second_token_id: storage_cache.second_token_id.clone(),
+                second_token_amount: data.second_token_amount,
+                first_token_reserves: storage_cache.first_token_reserve.clone(),
+                second_token_reserves: storage_cache.second_token_reserve.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_collect_fees_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        position_nonce: u64,
+        first_token_amount: BigUint,
+        second_token_amount: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.collect_fees_event(
+            &caller,
+            position_nonce,
+            epoch,
+            &EsdtTokenPayment::new(storage_cache.first_token_id.clone(), 0, first_token_amount),
+            &EsdtTokenPayment::new(
+                storage_cache.second_token_id.clone(),
+                0,
+                second_token_amount,
+            ),
+        )
+    }
+
+    #[event("swap")]
+    fn swap_event(
+        &self,
+        #[indexed] token_in: &TokenIdentifier,
+        #[indexed] token_out: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        swap_event: &SwapEvent<Self::Api>,
+    );
+
+    #[event("update_position")]
+    fn update_position_event(
+        &self,
+        #[indexed] first_token: &TokenIdentifier,
+        #[indexed] second_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        position_event: &PositionEvent<Self::Api>,
+    );
+
+    #[event("collect_fees")]
+    fn collect_fees_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] position_nonce: u64,
+        #[indexed] epoch: u64,
+        #[indexed] first_token_fees: &EsdtTokenPayment,
+        second_token_fees: &EsdtTokenPayment,
+    );
+}
@@ -0,0 +1,97 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod math;
+pub mod position_token;
+pub mod positions;
+mod storage_cache;
+pub mod swap;
+pub mod ticks;
+pub mod views;
+
+use crate::config::MAX_TICK_SPACING;
+use crate::errors::*;
+use crate::math::tick_math::{get_tick_at_sqrt_ratio, Tick, MAX_TICK, MIN_TICK};
+
+use pausable::State;
+use permissions_module::Permissions;
+
+/// Pair with concentrated liquidity: liquidity providers choose the price range
+/// in which their liquidity is used, and receive a position NFT instead of LP tokens.
+/// The swap endpoints are compatible with the constant-product pair, for routing.
+#[multiversx_sc::contract]
+pub trait ConcentratedPair:
+    config::ConfigModule
+    + events::EventsModule
+    + ticks::TicksModule
+    + position_token::PositionTokenModule
+    + positions::PositionsModule
+    + swap::SwapModule
+    + views::ViewsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// `initial_sqrt_price` is the square root of the price of the first token
+    /// in terms of the second one, as a Q64.96 number
+    #[init]
+    fn init(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        router_address: ManagedAddress,
+        router_owner_address: ManagedAddress,
+        total_fee_percent: u64,
+        tick_spacing: Tick,
+        initial_sqrt_price: BigUint,
+        admins: MultiValueEncoded<ManagedAddress>,
+    ) {
+        require!(first_token_id.is_valid_esdt_identifier(), ERROR_NOT_AN_ESDT);
+        require!(
+            second_token_id.is_valid_esdt_identifier(),
+            ERROR_NOT_AN_ESDT
+        );
+        require!(first_token_id != second_token_id, ERROR_SAME_TOKENS);
+        require!(
+            tick_spacing > 0 && tick_spacing <= MAX_TICK_SPACING,
+            ERROR_INVALID_TICK_SPACING
+        );
+
+        self.set_fee_percent(total_fee_percent);
+        self.state().set(State::Inactive);
+
+        self.router_address().set(&router_address);
+        self.first_token_id().set_if_empty(&first_token_id);
+        self.second_token_id().set_if_empty(&second_token_id);
+        self.tick_spacing().set_if_empty(tick_spacing);
+
+        if self.sqrt_price().is_empty() {
+            let current_tick = get_tick_at_sqrt_ratio(&initial_sqrt_price);
+            self.sqrt_price().set(&initial_sqrt_price);
+            self.current_tick().set(current_tick);
+            self.lowest_initialized_tick().set(MAX_TICK);
+            self.highest_initialized_tick().set(MIN_TICK);
+        }
+
+        if admins.is_empty() {
+            let all_permissions = Permissions::OWNER | Permissions::ADMIN | Permissions::PAUSE;
+            self.add_permissions(router_address, all_permissions.clone());
+            self.add_permissions(router_owner_address, all_permissions);
+        } else {
+            self.add_permissions(router_address, Permissions::OWNER | Permissions::PAUSE);
+            self.add_permissions(
+                router_owner_address,
+                Permissions::OWNER | Permissions::PAUSE,
+            );
+            self.add_permissions_for_all(admins, Permissions::ADMIN);
+        };
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
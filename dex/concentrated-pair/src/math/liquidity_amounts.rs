@@ -0,0 +1,82 @@
+multiversx_sc::imports!();
+
+use crate::errors::ERROR_NOT_ENOUGH_LIQUIDITY;
+
+use super::sqrt_price_math::{get_amount_first_delta, get_amount_second_delta, mul_div, q96};
+
+/// liquidity = amount * (sqrt_lower * sqrt_upper) / (sqrt_upper - sqrt_lower)
+pub fn get_liquidity_for_amount_first<M: ManagedTypeApi>(
+    sqrt_lower: &BigUint<M>,
+    sqrt_upper: &BigUint<M>,
+    amount: &BigUint<M>,
+) -> BigUint<M> {
+    let intermediate = mul_div(sqrt_lower, sqrt_upper, &q96());
+    mul_div(amount, &intermediate, &(sqrt_upper - sqrt_lower))
+}
+
+/// liquidity = amount / (sqrt_upper - sqrt_lower)
+pub fn get_liquidity_for_amount_second<M: ManagedTypeApi>(
+    sqrt_lower: &BigUint<M>,
+    sqrt_upper: &BigUint<M>,
+    amount: &BigUint<M>,
+) -> BigUint<M> {
+    mul_div(amount, &q96(), &(sqrt_upper - sqrt_lower))
+}
+
+/// Maximum liquidity that can be minted in the [sqrt_lower, sqrt_upper] range
+/// with the given amounts, at the current price
+pub fn get_liquidity_for_amounts<M: ManagedTypeApi>(
+    sqrt_price: &BigUint<M>,
+    sqrt_lower: &BigUint<M>,
+    sqrt_upper: &BigUint<M>,
+    first_token_amount: &BigUint<M>,
+    second_token_amount: &BigUint<M>,
+) -> BigUint<M> {
+    if sqrt_price <= sqrt_lower {
+        get_liquidity_for_amount_first(sqrt_lower, sqrt_upper, first_token_amount)
+    } else if sqrt_price < sqrt_upper {
+        let first_liquidity =
+            get_liquidity_for_amount_first(sqrt_price, sqrt_upper, first_token_amount);
+        let second_liquidity =
+            get_liquidity_for_amount_second(sqrt_lower, sqrt_price, second_token_amount);
+
+        core::cmp::min(first_liquidity, second_liquidity)
+    } else {
+        get_liquidity_for_amount_second(sqrt_lower, sqrt_upper, second_token_amount)
+    }
+}
+
+/// Token amounts backing `liquidity` in the [sqrt_lower, sqrt_upper] range, at the current price
+pub fn get_amounts_for_liquidity<M: ManagedTypeApi>(
+    sqrt_price: &BigUint<M>,
+    sqrt_lower: &BigUint<M>,
+    sqrt_upper: &BigUint<M>,
+    liquidity: &BigUint<M>,
+    round_up: bool,
+) -> (BigUint<M>, BigUint<M>) {
+    if sqrt_price <= sqrt_lower {
+        let first_amount = get_amount_first_delta(sqrt_lower, sqrt_upper, liquidity, round_up);
+        (first_amount, BigUint::zero())
+    } else if sqrt_price < sqrt_upper {
+        let first_amount = get_amount_first_delta(sqrt_price, sqrt_upper, liquidity, round_up);
+        let second_amount = get_amount_second_delta(sqrt_lower, sqrt_price, liquidity, round_up);
+        (first_amount, second_amount)
+    } else {
+        let second_amount = get_amount_second_delta(sqrt_lower, sqrt_upper, liquidity, round_up);
+        (BigUint::zero(), second_amount)
+    }
+}
+
+/// Applies a signed liquidity delta, failing if the result would be negative
+pub fn add_liquidity_delta<M: ManagedTypeApi>(
+    liquidity: &BigUint<M>,
+    liquidity_delta: &BigInt<M>,
+) -> BigUint<M> {
+    match (BigInt::from(liquidity.clone()) + liquidity_delta.clone())
+        .into_big_uint()
+        .into_option()
+    {
+        Some(result) => result,
+        None => M::error_api_impl().signal_error(ERROR_NOT_ENOUGH_LIQUIDITY),
+    }
+}
@@ -0,0 +1,4 @@
+pub mod liquidity_amounts;
+pub mod sqrt_price_math;
+pub mod swap_math;
+pub mod tick_math;
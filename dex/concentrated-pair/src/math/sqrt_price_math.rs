@@ -0,0 +1,170 @@
+multiversx_sc::imports!();
+
+use crate::errors::{ERROR_NOT_ENOUGH_LIQUIDITY, ERROR_SQRT_PRICE_OUT_OF_RANGE};
+
+use super::tick_math::Q96_RESOLUTION;
+
+#[inline]
+pub fn q96<M: ManagedTypeApi>() -> BigUint<M> {
+    BigUint::from(1u32) << Q96_RESOLUTION
+}
+
+pub fn div_rounding_up<M: ManagedTypeApi>(
+    numerator: &BigUint<M>,
+    denominator: &BigUint<M>,
+) -> BigUint<M> {
+    let mut result = numerator / denominator;
+    if numerator % denominator != 0u32 {
+        result += 1u32;
+    }
+
+    result
+}
+
+#[inline]
+pub fn mul_div<M: ManagedTypeApi>(
+    first: &BigUint<M>,
+    second: &BigUint<M>,
+    denominator: &BigUint<M>,
+) -> BigUint<M> {
+    first * second / denominator
+}
+
+#[inline]
+pub fn mul_div_rounding_up<M: ManagedTypeApi>(
+    first: &BigUint<M>,
+    second: &BigUint<M>,
+    denominator: &BigUint<M>,
+) -> BigUint<M> {
+    div_rounding_up(&(first * second), denominator)
+}
+
+/// Amount of first token between two prices, for the given liquidity:
+/// liquidity * (sqrt_upper - sqrt_lower) / (sqrt_upper * sqrt_lower)
+pub fn get_amount_first_delta<M: ManagedTypeApi>(
+    sqrt_price_a: &BigUint<M>,
+    sqrt_price_b: &BigUint<M>,
+    liquidity: &BigUint<M>,
+    round_up: bool,
+) -> BigUint<M> {
+    let (sqrt_lower, sqrt_upper) = sort_prices(sqrt_price_a, sqrt_price_b);
+    if sqrt_lower == &0u32 {
+        M::error_api_impl().signal_error(ERROR_SQRT_PRICE_OUT_OF_RANGE);
+    }
+
+    let numerator_first = liquidity << Q96_RESOLUTION;
+    let numerator_second = sqrt_upper - sqrt_lower;
+    if round_up {
+        div_rounding_up(
+            &mul_div_rounding_up(&numerator_first, &numerator_second, sqrt_upper),
+            sqrt_lower,
+        )
+    } else {
+        mul_div(&numerator_first, &numerator_second, sqrt_upper) / sqrt_lower
+    }
+}
+
+/// Amount of second token between two prices, for the given liquidity:
+/// liquidity * (sqrt_upper - sqrt_lower)
+pub fn get_amount_second_delta<M: ManagedTypeApi>(
+    sqrt_price_a: &BigUint<M>,
+    sqrt_price_b: &BigUint<M>,
+    liquidity: &BigUint<M>,
+    round_up: bool,
+) -> BigUint<M> {
+    let (sqrt_lower, sqrt_upper) = sort_prices(sqrt_price_a, sqrt_price_b);
+    let price_diff = sqrt_upper - sqrt_lower;
+    if round_up {
+        mul_div_rounding_up(liquidity, &price_diff, &q96())
+    } else {
+        mul_div(liquidity, &price_diff, &q96())
+    }
+}
+
+/// Next price after adding or removing `amount` of the first token.
+/// Always rounds up, so the price moves less in the direction that favors the pool.
+pub fn get_next_sqrt_price_from_amount_first_rounding_up<M: ManagedTypeApi>(
+    sqrt_price: &BigUint<M>,
+    liquidity: &BigUint<M>,
+    amount: &BigUint<M>,
+    add: bool,
+) -> BigUint<M> {
+    if amount == &0u32 {
+        return sqrt_price.clone();
+    }
+
+    let numerator = liquidity << Q96_RESOLUTION;
+    let product = amount * sqrt_price;
+    if add {
+        let denominator = &numerator + &product;
+        mul_div_rounding_up(&numerator, sqrt_price, &denominator)
+    } else {
+        if product >= numerator {
+            M::error_api_impl().signal_error(ERROR_NOT_ENOUGH_LIQUIDITY);
+        }
+
+        let denominator = &numerator - &product;
+        mul_div_rounding_up(&numerator, sqrt_price, &denominator)
+    }
+}
+
+/// Next price after adding or removing `amount` of the second token.
+/// Always rounds down, so the price moves less in the direction that favors the pool.
+pub fn get_next_sqrt_price_from_amount_second_rounding_down<M: ManagedTypeApi>(
+    sqrt_price: &BigUint<M>,
+    liquidity: &BigUint<M>,
+    amount: &BigUint<M>,
+    add: bool,
+) -> BigUint<M> {
+    let shifted_amount = amount << Q96_RESOLUTION;
+    if add {
+        sqrt_price + &(shifted_amount / liquidity)
+    } else {
+        let quotient = div_rounding_up(&shifted_amount, liquidity);
+        if sqrt_price <= &quotient {
+            M::error_api_impl().signal_error(ERROR_NOT_ENOUGH_LIQUIDITY);
+        }
+
+        sqrt_price - &quotient
+    }
+}
+
+pub fn get_next_sqrt_price_from_input<M: ManagedTypeApi>(
+    sqrt_price: &BigUint<M>,
+    liquidity: &BigUint<M>,
+    amount_in: &BigUint<M>,
+    first_for_second: bool,
+) -> BigUint<M> {
+    if first_for_second {
+        get_next_sqrt_price_from_amount_first_rounding_up(sqrt_price, liquidity, amount_in, true)
+    } else {
+        get_next_sqrt_price_from_amount_second_rounding_down(sqrt_price, liquidity, amount_in, true)
+    }
+}
+
+pub fn get_next_sqrt_price_from_output<M: ManagedTypeApi>(
+    sqrt_price: &BigUint<M>,
+    liquidity: &BigUint<M>,
+    amount_out: &BigUint<M>,
+    first_for_second: bool,
+) -> BigUint<M> {
+    if first_for_second {
+        get_next_sqrt_price_from_amount_second_rounding_down(
+            sqrt_price, liquidity, amount_out, false,
+        )
+    } else {
+        get_next_sqrt_price_from_amount_first_rounding_up(sqrt_price, liquidity, amount_out, false)
+    }
+}
+
+#[inline]
+fn sort_prices<'a, M: ManagedTypeApi>(
+    sqrt_price_a: &'a BigUint<M>,
+    sqrt_price_b: &'a BigUint<M>,
+) -> (&'a BigUint<M>, &'a BigUint<M>) {
+    if sqrt_price_a <= sqrt_price_b {
+        (sqrt_price_a, sqrt_price_b)
+    } else {
+        (sqrt_price_b, sqrt_price_a)
+    }
+}
@@ -0,0 +1,114 @@
+multiversx_sc::imports!();
+
+use crate::config::MAX_PERCENTAGE;
+
+use super::sqrt_price_math::{
+    get_amount_first_delta, get_amount_second_delta, get_next_sqrt_price_from_input,
+    get_next_sqrt_price_from_output, mul_div_rounding_up,
+};
+
+pub struct SwapStep<M: ManagedTypeApi> {
+    pub sqrt_price_next: BigUint<M>,
+    pub amount_in: BigUint<M>,
+    pub amount_out: BigUint<M>,
+    pub fee_amount: BigUint<M>,
+}
+
+/// Computes the result of swapping inside a single tick range, i.e. with constant liquidity.
+/// The price moves from `sqrt_price_current` towards `sqrt_price_target`,
+/// stopping earlier if `amount_remaining` is consumed.
+/// `fee_amount` is always charged on top of `amount_in`.
+pub fn compute_swap_step<M: ManagedTypeApi>(
+    sqrt_price_current: &BigUint<M>,
+    sqrt_price_target: &BigUint<M>,
+    liquidity: &BigUint<M>,
+    amount_remaining: &BigUint<M>,
+    exact_input: bool,
+    fee_percent: u64,
+) -> SwapStep<M> {
+    let first_for_second = sqrt_price_current >= sqrt_price_target;
+
+    let mut amount_in = BigUint::zero();
+    let mut amount_out = BigUint::zero();
+    let sqrt_price_next = if exact_input {
+        let amount_remaining_less_fee =
+            amount_remaining * (MAX_PERCENTAGE - fee_percent) / MAX_PERCENTAGE;
+        amount_in = if first_for_second {
+            get_amount_first_delta(sqrt_price_target, sqrt_price_current, liquidity, true)
+        } else {
+            get_amount_second_delta(sqrt_price_current, sqrt_price_target, liquidity, true)
+        };
+
+        if amount_remaining_less_fee >= amount_in {
+            sqrt_price_target.clone()
+        } else {
+            get_next_sqrt_price_from_input(
+                sqrt_price_current,
+                liquidity,
+                &amount_remaining_less_fee,
+                first_for_second,
+            )
+        }
+    } else {
+        amount_out = if first_for_second {
+            get_amount_second_delta(sqrt_price_target, sqrt_price_current, liquidity, false)
+        } else {
+            get_amount_first_delta(sqrt_price_current, sqrt_price_target, liquidity, false)
+        };
+
+        if amount_remaining >= &amount_out {
+            sqrt_price_target.clone()
+        } else {
+            get_next_sqrt_price_from_output(
+                sqrt_price_current,
+                liquidity,
+                amount_remaining,
+                first_for_second,
+            )
+        }
+    };
+
+    let target_reached = &sqrt_price_next == sqrt_price_target;
+    if first_for_second {
+        if !(target_reached && exact_input) {
+            amount_in =
+                get_amount_first_delta(&sqrt_price_next, sqrt_price_current, liquidity, true);
+        }
+        if !target_reached || exact_input {
+            amount_out =
+                get_amount_second_delta(&sqrt_price_next, sqrt_price_current, liquidity, false);
+        }
+    } else {
+        if !(target_reached && exact_input) {
+            amount_in =
+                get_amount_second_delta(sqrt_price_current, &sqrt_price_next, liquidity, true);
+        }
+        if !target_reached || exact_input {
+            amount_out =
+                get_amount_first_delta(sqrt_price_current, &sqrt_price_next, liquidity, false);
+        }
+    }
+
+    // the output can never exceed the requested amount
+    if !exact_input && &amount_out > amount_remaining {
+        amount_out = amount_remaining.clone();
+    }
+
+    let fee_amount = if exact_input && !target_reached {
+        // the remainder of the input is taken as fee, as the price did not reach the target
+        amount_remaining - &amount_in
+    } else {
+        mul_div_rounding_up(
+            &amount_in,
+            &BigUint::from(fee_percent),
+            &BigUint::from(MAX_PERCENTAGE - fee_percent),
+        )
+    };
+
+    SwapStep {
+        sqrt_price_next,
+        amount_in,
+        amount_out,
+        fee_amount,
+    }
+}
@@ -0,0 +1,116 @@
+multiversx_sc::imports!();
+
+use crate::errors::{ERROR_SQRT_PRICE_OUT_OF_RANGE, ERROR_TICK_OUT_OF_RANGE};
+
+pub type Tick = i32;
+
+pub const MIN_TICK: Tick = -887_272;
+pub const MAX_TICK: Tick = -MIN_TICK;
+
+pub const Q96_RESOLUTION: usize = 96;
+pub const Q128_RESOLUTION: usize = 128;
+
+/// 2^128 / sqrt(1.0001)^(2^i), for i in 1..20. The entry for i = 0 is handled separately.
+const SQRT_RATIO_FACTORS: [u128; 19] = [
+    0xfff97272373d413259a46990580e213a,
+    0xfff2e50f5f656932ef12357cf3c7fdcc,
+    0xffe5caca7e10e4e61c3624eaa0941cd0,
+    0xffcb9843d60f6159c9db58835c926644,
+    0xff973b41fa98c081472e6896dfb254c0,
+    0xff2ea16466c96a3843ec78b326b52861,
+    0xfe5dee046a99a2a811c461f1969c3053,
+    0xfcbe86c7900a88aedcffc83b479aa3a4,
+    0xf987a7253ac413176f2b074cf7815e54,
+    0xf3392b0822b70005940c7a398e4b70f3,
+    0xe7159475a2c29b7443b29c7fa6e889d9,
+    0xd097f3bdfd2022b8845ad8f792aa5825,
+    0xa9f746462d870fdf8a65dc1f90e061e5,
+    0x70d869a156d2a1b890bb3df62baf32f7,
+    0x31be135f97d08fd981231505542fcfa6,
+    0x9aa508b5b7a84e1c677de54f3e99bc9,
+    0x5d6af8dedb81196699c329225ee604,
+    0x2216e584f5fa1ea926041bedfe98,
+    0x48a170391f7dc42444e8fa2,
+];
+const SQRT_RATIO_FIRST_BIT_FACTOR: u128 = 0xfffcb933bd6fad37aa2d162d1a594001;
+
+/// Number of ticks covered by one power of two of the Q64.96 square root price,
+/// i.e. 2 / log2(1.0001), rounded down and up respectively.
+const TICKS_PER_SQRT_PRICE_BIT_LOW: i64 = 13_863;
+const TICKS_PER_SQRT_PRICE_BIT_HIGH: i64 = 13_864;
+
+/// Returns sqrt(1.0001^tick) as a Q64.96 fixed point number
+pub fn get_sqrt_ratio_at_tick<M: ManagedTypeApi>(tick: Tick) -> BigUint<M> {
+    let abs_tick = tick.unsigned_abs();
+    if abs_tick > MAX_TICK as u32 {
+        M::error_api_impl().signal_error(ERROR_TICK_OUT_OF_RANGE);
+    }
+
+    let mut ratio = if abs_tick & 1 != 0 {
+        BigUint::from(SQRT_RATIO_FIRST_BIT_FACTOR)
+    } else {
+        BigUint::from(1u32) << Q128_RESOLUTION
+    };
+    for (i, factor) in SQRT_RATIO_FACTORS.iter().enumerate() {
+        if abs_tick & (1 << (i + 1)) != 0 {
+            ratio = (ratio * BigUint::from(*factor)) >> Q128_RESOLUTION;
+        }
+    }
+
+    if tick > 0 {
+        let max_u256 = (BigUint::from(1u32) << 256) - 1u32;
+        ratio = max_u256 / ratio;
+    }
+
+    // Q128.128 to Q64.96, rounding up
+    let has_remainder = (&ratio % &(BigUint::from(1u32) << 32)) != 0u32;
+    let mut sqrt_price = ratio >> 32;
+    if has_remainder {
+        sqrt_price += 1u32;
+    }
+
+    sqrt_price
+}
+
+#[inline]
+pub fn min_sqrt_ratio<M: ManagedTypeApi>() -> BigUint<M> {
+    get_sqrt_ratio_at_tick(MIN_TICK)
+}
+
+#[inline]
+pub fn max_sqrt_ratio<M: ManagedTypeApi>() -> BigUint<M> {
+    get_sqrt_ratio_at_tick(MAX_TICK)
+}
+
+/// Returns the greatest tick for which get_sqrt_ratio_at_tick(tick) <= sqrt_price
+pub fn get_tick_at_sqrt_ratio<M: ManagedTypeApi>(sqrt_price: &BigUint<M>) -> Tick {
+    if sqrt_price < &min_sqrt_ratio::<M>() || sqrt_price >= &max_sqrt_ratio::<M>() {
+        M::error_api_impl().signal_error(ERROR_SQRT_PRICE_OUT_OF_RANGE);
+    }
+
+    // narrow the search interval using the position of the most significant bit
+    let msb = sqrt_price.log2_floor().unwrap_or_default() as i64;
+    let exponent = msb - Q96_RESOLUTION as i64;
+    let low_bound = core::cmp::min(
+        exponent * TICKS_PER_SQRT_PRICE_BIT_LOW,
+        exponent * TICKS_PER_SQRT_PRICE_BIT_HIGH,
+    ) - 1;
+    let high_bound = core::cmp::max(
+        (exponent + 1) * TICKS_PER_SQRT_PRICE_BIT_LOW,
+        (exponent + 1) * TICKS_PER_SQRT_PRICE_BIT_HIGH,
+    ) + 1;
+
+    let mut low = core::cmp::max(low_bound, MIN_TICK as i64) as Tick;
+    let mut high = core::cmp::min(high_bound, MAX_TICK as i64) as Tick;
+    while low < high {
+        // round towards the upper bound, so the interval always shrinks
+        let middle = low + (high - low + 1) / 2;
+        if &get_sqrt_ratio_at_tick::<M>(middle) <= sqrt_price {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+
+    low
+}
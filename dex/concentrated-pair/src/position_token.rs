@@ -0,0 +1,56 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::ERROR_POSITION_TOKEN_NOT_ISSUED;
+use crate::math::tick_math::Tick;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct PositionTokenAttributes {
+    pub tick_lower: Tick,
+    pub tick_upper: Tick,
+}
+
+#[multiversx_sc::module]
+pub trait PositionTokenModule:
+    permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[payable("EGLD")]
+    #[endpoint(registerPositionToken)]
+    fn register_position_token(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+    ) {
+        self.require_caller_has_owner_permissions();
+
+        let payment_amount = self.call_value().egld().clone_value();
+        self.position_token().issue_and_set_all_roles(
+            EsdtTokenType::NonFungible,
+            payment_amount,
+            token_display_name,
+            token_ticker,
+            0,
+            None,
+        );
+    }
+
+    fn mint_position_token(
+        &self,
+        to: &ManagedAddress,
+        attributes: &PositionTokenAttributes,
+    ) -> EsdtTokenPayment {
+        require!(
+            !self.position_token().is_empty(),
+            ERROR_POSITION_TOKEN_NOT_ISSUED
+        );
+
+        self.position_token()
+            .nft_create_and_send(to, BigUint::from(1u32), attributes)
+    }
+
+    #[view(getPositionTokenId)]
+    #[storage_mapper("positionTokenId")]
+    fn position_token(&self) -> NonFungibleTokenMapper;
+}
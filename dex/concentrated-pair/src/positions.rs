@@ -0,0 +1,619 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use pausable::State;
+
+use crate::errors::*;
+use crate::events::PositionEventData;
+use crate::math::liquidity_amounts::{
+    add_liquidity_delta, get_amounts_for_liquidity, get_liquidity_for_amounts,
+};
+use crate::math::tick_math::{get_sqrt_ratio_at_tick, Tick, Q128_RESOLUTION};
+use crate::position_token::PositionTokenAttributes;
+use crate::storage_cache::StorageCache;
+
+pub type OpenPositionResultType<M> =
+    MultiValue3<EsdtTokenPayment<M>, EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
+pub type PositionAmountsResultType<M> = MultiValue2<EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug)]
+pub struct Position<M: ManagedTypeApi> {
+    pub tick_lower: Tick,
+    pub tick_upper: Tick,
+    pub liquidity: BigUint<M>,
+    /// Fee growth inside the range, as of the last update of the position
+    pub fee_growth_inside_first_last: BigInt<M>,
+    pub fee_growth_inside_second_last: BigInt<M>,
+    pub tokens_owed_first: BigUint<M>,
+    pub tokens_owed_second: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> Position<M> {
+    pub fn new(tick_lower: Tick, tick_upper: Tick) -> Self {
+        Position {
+            tick_lower,
+            tick_upper,
+            liquidity: BigUint::zero(),
+            fee_growth_inside_first_last: BigInt::zero(),
+            fee_growth_inside_second_last: BigInt::zero(),
+            tokens_owed_first: BigUint::zero(),
+            tokens_owed_second: BigUint::zero(),
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait PositionsModule:
+    crate::config::ConfigModule
+    + crate::ticks::TicksModule
+    + crate::position_token::PositionTokenModule
+    + crate::events::EventsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// Opens a new position in the [tick_lower, tick_upper) range.
+    /// Accepts any of the pool tokens as payment. Unused amounts are refunded.
+    #[payable("*")]
+    #[endpoint(openPosition)]
+    fn open_position(
+        &self,
+        tick_lower: Tick,
+        tick_upper: Tick,
+        first_token_amount_min: BigUint,
+        second_token_amount_min: BigUint,
+    ) -> OpenPositionResultType<Self::Api> {
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            self.is_state_active(storage_cache.contract_state),
+            ERROR_NOT_ACTIVE
+        );
+        self.require_valid_tick_range(tick_lower, tick_upper, storage_cache.tick_spacing);
+
+        let payments = self.call_value().all_esdt_transfers().clone_value();
+        let (first_token_amount, second_token_amount) =
+            self.get_pool_token_amounts(&storage_cache, &payments);
+
+        let mut position = Position::new(tick_lower, tick_upper);
+        let (liquidity, first_token_used, second_token_used) = self.add_position_liquidity(
+            &mut storage_cache,
+            &mut position,
+            &first_token_amount,
+            &second_token_amount,
+        );
+        require!(
+            first_token_used >= first_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+        require!(
+            second_token_used >= second_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+
+        let caller = self.blockchain().get_caller();
+        let position_payment = self.mint_position_token(
+            &caller,
+            &PositionTokenAttributes {
+                tick_lower,
+                tick_upper,
+            },
+        );
+        self.positions(position_payment.token_nonce).set(&position);
+
+        let first_token_payment = EsdtTokenPayment::new(
+            storage_cache.first_token_id.clone(),
+            0,
+            first_token_used.clone(),
+        );
+        let second_token_payment = EsdtTokenPayment::new(
+            storage_cache.second_token_id.clone(),
+            0,
+            second_token_used.clone(),
+        );
+        self.refund_unused_amounts(
+            &storage_cache,
+            &caller,
+            &(first_token_amount - &first_token_used),
+            &(second_token_amount - &second_token_used),
+        );
+
+        self.emit_update_position_event(
+            &storage_cache,
+            PositionEventData {
+                position_nonce: position_payment.token_nonce,
+                tick_lower,
+                tick_upper,
+                liquidity_delta: BigInt::from(liquidity),
+                position_liquidity: position.liquidity,
+                first_token_amount: first_token_used,
+                second_token_amount: second_token_used,
+            },
+        );
+
+        (position_payment, first_token_payment, second_token_payment).into()
+    }
+
+    /// Adds liquidity to an existing position.
+    /// The first payment must be the position token, followed by any of the pool tokens.
+    #[payable("*")]
+    #[endpoint(increaseLiquidity)]
+    fn increase_liquidity(
+        &self,
+        first_token_amount_min: BigUint,
+        second_token_amount_min: BigUint,
+    ) -> OpenPositionResultType<Self::Api> {
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            self.is_state_active(storage_cache.contract_state),
+            ERROR_NOT_ACTIVE
+        );
+
+        let mut payments = self.call_value().all_esdt_transfers().clone_value();
+        require!(!payments.is_empty(), ERROR_BAD_PAYMENT_TOKENS);
+        let position_payment = payments.get(0).clone();
+        payments.remove(0);
+        let mut position = self.get_position_from_payment(&position_payment);
+
+        let (first_token_amount, second_token_amount) =
+            self.get_pool_token_amounts(&storage_cache, &payments);
+        let (liquidity, first_token_used, second_token_used) = self.add_position_liquidity(
+            &mut storage_cache,
+            &mut position,
+            &first_token_amount,
+            &second_token_amount,
+        );
+        require!(
+            first_token_used >= first_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+        require!(
+            second_token_used >= second_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+        self.positions(position_payment.token_nonce).set(&position);
+
+        let caller = self.blockchain().get_caller();
+        self.send_payment_non_zero(&caller, &position_payment);
+        self.refund_unused_amounts(
+            &storage_cache,
+            &caller,
+            &(first_token_amount - &first_token_used),
+            &(second_token_amount - &second_token_used),
+        );
+
+        let first_token_payment = EsdtTokenPayment::new(
+            storage_cache.first_token_id.clone(),
+            0,
+            first_token_used.clone(),
+        );
+        let second_token_payment = EsdtTokenPayment::new(
+            storage_cache.second_token_id.clone(),
+            0,
+            second_token_used.clone(),
+        );
+
+        self.emit_update_position_event(
+            &storage_cache,
+            PositionEventData {
+                position_nonce: position_payment.token_nonce,
+                tick_lower: position.tick_lower,
+                tick_upper: position.tick_upper,
+                liquidity_delta: BigInt::from(liquidity),
+                position_liquidity: position.liquidity,
+                first_token_amount: first_token_used,
+                second_token_amount: second_token_used,
+            },
+        );
+
+        (position_payment, first_token_payment, second_token_payment).into()
+    }
+
+    /// Removes liquidity from a position and sends the underlying tokens back.
+    /// Accrued fees stay in the position until collected.
+    #[payable("*")]
+    #[endpoint(decreaseLiquidity)]
+    fn decrease_liquidity(
+        &self,
+        liquidity: BigUint,
+        first_token_amount_min: BigUint,
+        second_token_amount_min: BigUint,
+    ) -> PositionAmountsResultType<Self::Api> {
+        require!(liquidity > 0, ERROR_INVALID_ARGS);
+
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            self.is_state_active(storage_cache.contract_state),
+            ERROR_NOT_ACTIVE
+        );
+
+        let position_payment = self.call_value().single_esdt().clone();
+        let mut position = self.get_position_from_payment(&position_payment);
+        let (first_token_payment, second_token_payment) = self.remove_position_liquidity(
+            &mut storage_cache,
+            &mut position,
+            position_payment.token_nonce,
+            &liquidity,
+        );
+        require!(
+            first_token_payment.amount >= first_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+        require!(
+            second_token_payment.amount >= second_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+        self.positions(position_payment.token_nonce).set(&position);
+
+        let caller = self.blockchain().get_caller();
+        self.send_payment_non_zero(&caller, &position_payment);
+        self.send_payment_non_zero(&caller, &first_token_payment);
+        self.send_payment_non_zero(&caller, &second_token_payment);
+
+        (first_token_payment, second_token_payment).into()
+    }
+
+    /// Sends the fees accrued by a position, together with the position token
+    #[payable("*")]
+    #[endpoint(collectFees)]
+    fn collect_fees(&self) -> PositionAmountsResultType<Self::Api> {
+        let mut storage_cache = StorageCache::new(self);
+        let position_payment = self.call_value().single_esdt().clone();
+        let mut position = self.get_position_from_payment(&position_payment);
+
+        self.modify_position(&mut storage_cache, &mut position, &BigInt::zero());
+        let (first_token_payment, second_token_payment) = self.take_owed_tokens(
+            &mut storage_cache,
+            &mut position,
+            position_payment.token_nonce,
+        );
+        self.positions(position_payment.token_nonce).set(&position);
+
+        let caller = self.blockchain().get_caller();
+        self.send_payment_non_zero(&caller, &position_payment);
+        self.send_payment_non_zero(&caller, &first_token_payment);
+        self.send_payment_non_zero(&caller, &second_token_payment);
+
+        (first_token_payment, second_token_payment).into()
+    }
+
+    /// Removes all the liquidity of a position, collects its fees and burns the position token
+    #[payable("*")]
+    #[endpoint(closePosition)]
+    fn close_position(
+        &self,
+        first_token_amount_min: BigUint,
+        second_token_amount_min: BigUint,
+    ) -> PositionAmountsResultType<Self::Api> {
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            self.is_state_active(storage_cache.contract_state),
+            ERROR_NOT_ACTIVE
+        );
+
+        let position_payment = self.call_value().single_esdt().clone();
+        let mut position = self.get_position_from_payment(&position_payment);
+        let liquidity = position.liquidity.clone();
+        let (mut first_token_payment, mut second_token_payment) = if liquidity > 0 {
+            self.remove_position_liquidity(
+                &mut storage_cache,
+                &mut position,
+                position_payment.token_nonce,
+                &liquidity,
+            )
+        } else {
+            self.modify_position(&mut storage_cache, &mut position, &BigInt::zero());
+            (
+                EsdtTokenPayment::new(storage_cache.first_token_id.clone(), 0, BigUint::zero()),
+                EsdtTokenPayment::new(storage_cache.second_token_id.clone(), 0, BigUint::zero()),
+            )
+        };
+        require!(
+            first_token_payment.amount >= first_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+        require!(
+            second_token_payment.amount >= second_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+
+        let (first_fees, second_fees) = self.take_owed_tokens(
+            &mut storage_cache,
+            &mut position,
+            position_payment.token_nonce,
+        );
+        first_token_payment.amount += first_fees.amount;
+        second_token_payment.amount += second_fees.amount;
+
+        self.positions(position_payment.token_nonce).clear();
+        self.position_token()
+            .nft_burn(position_payment.token_nonce, &position_payment.amount);
+
+        let caller = self.blockchain().get_caller();
+        self.send_payment_non_zero(&caller, &first_token_payment);
+        self.send_payment_non_zero(&caller, &second_token_payment);
+
+        (first_token_payment, second_token_payment).into()
+    }
+
+    fn add_position_liquidity(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        position: &mut Position<Self::Api>,
+        first_token_amount: &BigUint,
+        second_token_amount: &BigUint,
+    ) -> (BigUint, BigUint, BigUint) {
+        let sqrt_price_lower = get_sqrt_ratio_at_tick::<Self::Api>(position.tick_lower);
+        let sqrt_price_upper = get_sqrt_ratio_at_tick::<Self::Api>(position.tick_upper);
+        let liquidity = get_liquidity_for_amounts(
+            &storage_cache.sqrt_price,
+            &sqrt_price_lower,
+            &sqrt_price_upper,
+            first_token_amount,
+            second_token_amount,
+        );
+        require!(liquidity > 0, ERROR_INSUFFICIENT_LIQUIDITY);
+
+        let (first_token_used, second_token_used) =
+            self.modify_position(storage_cache, position, &BigInt::from(liquidity.clone()));
+        require!(
+            &first_token_used <= first_token_amount,
+            ERROR_INSUFFICIENT_FIRST_TOKEN
+        );
+        require!(
+            &second_token_used <= second_token_amount,
+            ERROR_INSUFFICIENT_SECOND_TOKEN
+        );
+
+        storage_cache.first_token_reserve += &first_token_used;
+        storage_cache.second_token_reserve += &second_token_used;
+
+        (liquidity, first_token_used, second_token_used)
+    }
+
+    fn remove_position_liquidity(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        position: &mut Position<Self::Api>,
+        position_nonce: u64,
+        liquidity: &BigUint,
+    ) -> (EsdtTokenPayment, EsdtTokenPayment) {
+        require!(
+            &position.liquidity >= liquidity,
+            ERROR_NOT_ENOUGH_POSITION_LIQUIDITY
+        );
+
+        let liquidity_delta = -BigInt::from(liquidity.clone());
+        let (first_token_amount, second_token_amount) =
+            self.modify_position(storage_cache, position, &liquidity_delta);
+
+        storage_cache.first_token_reserve -= &first_token_amount;
+        storage_cache.second_token_reserve -= &second_token_amount;
+
+        self.emit_update_position_event(
+            storage_cache,
+            PositionEventData {
+                position_nonce,
+                tick_lower: position.tick_lower,
+                tick_upper: position.tick_upper,
+                liquidity_delta,
+                position_liquidity: position.liquidity.clone(),
+                first_token_amount: first_token_amount.clone(),
+                second_token_amount: second_token_amount.clone(),
+            },
+        );
+
+        (
+            EsdtTokenPayment::new(storage_cache.first_token_id.clone(), 0, first_token_amount),
+            EsdtTokenPayment::new(
+                storage_cache.second_token_id.clone(),
+                0,
+                second_token_amount,
+            ),
+        )
+    }
+
+    /// Applies a liquidity change to a position, updating the boundary ticks,
+    /// the active liquidity and the fees owed to the position.
+    /// Returns the token amounts that have to be deposited or withdrawn.
+    fn modify_position(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        position: &mut Position<Self::Api>,
+        liquidity_delta: &BigInt,
+    ) -> (BigUint, BigUint) {
+        let mut flipped_lower = false;
+        let mut flipped_upper = false;
+        if liquidity_delta != &0 {
+            flipped_lower = self.update_tick(
+                position.tick_lower,
+                storage_cache.current_tick,
+                liquidity_delta,
+                &storage_cache.fee_growth_global_first,
+                &storage_cache.fee_growth_global_second,
+                false,
+            );
+            flipped_upper = self.update_tick(
+                position.tick_upper,
+                storage_cache.current_tick,
+                liquidity_delta,
+                &storage_cache.fee_growth_global_first,
+                &storage_cache.fee_growth_global_second,
+                true,
+            );
+
+            if flipped_lower {
+                self.flip_tick(position.tick_lower, storage_cache.tick_spacing);
+            }
+            if flipped_upper {
+                self.flip_tick(position.tick_upper, storage_cache.tick_spacing);
+            }
+        }
+
+        let (fee_growth_inside_first, fee_growth_inside_second) = self.get_fee_growth_inside(
+            position.tick_lower,
+            position.tick_upper,
+            storage_cache.current_tick,
+            &storage_cache.fee_growth_global_first,
+            &storage_cache.fee_growth_global_second,
+        );
+        self.accrue_position_fees(position, fee_growth_inside_first, fee_growth_inside_second);
+        position.liquidity = add_liquidity_delta(&position.liquidity, liquidity_delta);
+
+        // ticks left without liquidity are only cleared after the fee growth inside was computed
+        if liquidity_delta < &0 {
+            if flipped_lower {
+                self.ticks(position.tick_lower).clear();
+            }
+            if flipped_upper {
+                self.ticks(position.tick_upper).clear();
+            }
+        }
+
+        if liquidity_delta == &0 {
+            return (BigUint::zero(), BigUint::zero());
+        }
+
+        if position.tick_lower <= storage_cache.current_tick
+            && storage_cache.current_tick < position.tick_upper
+        {
+            storage_cache.active_liquidity =
+                add_liquidity_delta(&storage_cache.active_liquidity, liquidity_delta);
+        }
+
+        let adding_liquidity = liquidity_delta > &0;
+        get_amounts_for_liquidity(
+            &storage_cache.sqrt_price,
+            &get_sqrt_ratio_at_tick(position.tick_lower),
+            &get_sqrt_ratio_at_tick(position.tick_upper),
+            &liquidity_delta.magnitude(),
+            adding_liquidity,
+        )
+    }
+
+    fn accrue_position_fees(
+        &self,
+        position: &mut Position<Self::Api>,
+        fee_growth_inside_first: BigInt,
+        fee_growth_inside_second: BigInt,
+    ) {
+        let (first_fees, second_fees) = self.compute_position_fees(
+            position,
+            &fee_growth_inside_first,
+            &fee_growth_inside_second,
+        );
+        position.tokens_owed_first += first_fees;
+        position.tokens_owed_second += second_fees;
+        position.fee_growth_inside_first_last = fee_growth_inside_first;
+        position.fee_growth_inside_second_last = fee_growth_inside_second;
+    }
+
+    /// Fees earned by a position since its last update
+    fn compute_position_fees(
+        &self,
+        position: &Position<Self::Api>,
+        fee_growth_inside_first: &BigInt,
+        fee_growth_inside_second: &BigInt,
+    ) -> (BigUint, BigUint) {
+        let first_delta =
+            fee_growth_inside_first.clone() - position.fee_growth_inside_first_last.clone();
+        let second_delta =
+            fee_growth_inside_second.clone() - position.fee_growth_inside_second_last.clone();
+
+        let first_fees = (&position.liquidity
+            * &first_delta
+                .into_big_uint()
+                .into_option()
+                .unwrap_or_default())
+            >> Q128_RESOLUTION;
+        let second_fees = (&position.liquidity
+            * &second_delta
+                .into_big_uint()
+                .into_option()
+                .unwrap_or_default())
+            >> Q128_RESOLUTION;
+
+        (first_fees, second_fees)
+    }
+
+    fn take_owed_tokens(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        position: &mut Position<Self::Api>,
+        position_nonce: u64,
+    ) -> (EsdtTokenPayment, EsdtTokenPayment) {
+        let first_token_amount = core::mem::take(&mut position.tokens_owed_first);
+        let second_token_amount = core::mem::take(&mut position.tokens_owed_second);
+
+        storage_cache.first_token_reserve -= &first_token_amount;
+        storage_cache.second_token_reserve -= &second_token_amount;
+
+        self.emit_collect_fees_event(
+            storage_cache,
+            position_nonce,
+            first_token_amount.clone(),
+            second_token_amount.clone(),
+        );
+
+        (
+            EsdtTokenPayment::new(storage_cache.first_token_id.clone(), 0, first_token_amount),
+            EsdtTokenPayment::new(
+                storage_cache.second_token_id.clone(),
+                0,
+                second_token_amount,
+            ),
+        )
+    }
+
+    fn get_pool_token_amounts(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        payments: &ManagedVec<EsdtTokenPayment>,
+    ) -> (BigUint, BigUint) {
+        let mut first_token_amount = BigUint::zero();
+        let mut second_token_amount = BigUint::zero();
+        for payment in payments.iter() {
+            if payment.token_identifier == storage_cache.first_token_id {
+                first_token_amount += &payment.amount;
+            } else if payment.token_identifier == storage_cache.second_token_id {
+                second_token_amount += &payment.amount;
+            } else {
+                sc_panic!(ERROR_BAD_PAYMENT_TOKENS);
+            }
+        }
+
+        (first_token_amount, second_token_amount)
+    }
+
+    fn get_position_from_payment(&self, payment: &EsdtTokenPayment) -> Position<Self::Api> {
+        require!(
+            payment.token_identifier == self.position_token().get_token_id(),
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+
+        let mapper = self.positions(payment.token_nonce);
+        require!(!mapper.is_empty(), ERROR_INVALID_POSITION);
+
+        mapper.get()
+    }
+
+    fn refund_unused_amounts(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        to: &ManagedAddress,
+        first_token_amount: &BigUint,
+        second_token_amount: &BigUint,
+    ) {
+        self.send_tokens_non_zero(to, &storage_cache.first_token_id, 0, first_token_amount);
+        self.send_tokens_non_zero(to, &storage_cache.second_token_id, 0, second_token_amount);
+    }
+
+    fn is_state_active(&self, state: State) -> bool {
+        state == State::Active || state == State::PartialActive
+    }
+
+    #[view(getPosition)]
+    #[storage_mapper("positions")]
+    fn positions(&self, position_nonce: u64) -> SingleValueMapper<Position<Self::Api>>;
+}
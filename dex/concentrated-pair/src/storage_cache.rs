@@ -0,0 +1,97 @@
+multiversx_sc::imports!();
+
+use pausable::State;
+
+use crate::math::tick_math::Tick;
+
+pub struct StorageCache<'a, C>
+where
+    C: crate::config::ConfigModule,
+{
+    sc_ref: &'a C,
+    pub contract_state: State,
+    pub first_token_id: TokenIdentifier<C::Api>,
+    pub second_token_id: TokenIdentifier<C::Api>,
+    pub first_token_reserve: BigUint<C::Api>,
+    pub second_token_reserve: BigUint<C::Api>,
+    pub tick_spacing: Tick,
+    pub sqrt_price: BigUint<C::Api>,
+    pub current_tick: Tick,
+    pub active_liquidity: BigUint<C::Api>,
+    pub fee_growth_global_first: BigUint<C::Api>,
+    pub fee_growth_global_second: BigUint<C::Api>,
+}
+
+impl<'a, C> StorageCache<'a, C>
+where
+    C: crate::config::ConfigModule,
+{
+    pub fn new(sc_ref: &'a C) -> Self {
+        let first_token_id = sc_ref.first_token_id().get();
+        let second_token_id = sc_ref.second_token_id().get();
+
+        StorageCache {
+            contract_state: sc_ref.state().get(),
+            first_token_reserve: sc_ref.pair_reserve(&first_token_id).get(),
+            second_token_reserve: sc_ref.pair_reserve(&second_token_id).get(),
+            tick_spacing: sc_ref.tick_spacing().get(),
+            sqrt_price: sc_ref.sqrt_price().get(),
+            current_tick: sc_ref.current_tick().get(),
+            active_liquidity: sc_ref.active_liquidity().get(),
+            fee_growth_global_first: sc_ref.fee_growth_global(&first_token_id).get(),
+            fee_growth_global_second: sc_ref.fee_growth_global(&second_token_id).get(),
+            first_token_id,
+            second_token_id,
+            sc_ref,
+        }
+    }
+
+    /// Returns true if the swap goes from the first token to the second one
+    pub fn is_first_for_second(
+        &self,
+        token_in: &TokenIdentifier<C::Api>,
+        token_out: &TokenIdentifier<C::Api>,
+    ) -> bool {
+        if token_in == &self.first_token_id && token_out == &self.second_token_id {
+            true
+        } else if token_in == &self.second_token_id && token_out == &self.first_token_id {
+            false
+        } else {
+            multiversx_sc::contract_base::ErrorHelper::<C::Api>::signal_error_with_message(
+                &b"Invalid tokens"[..],
+            );
+        }
+    }
+
+    pub fn get_mut_reserve(&mut self, first_token: bool) -> &mut BigUint<C::Api> {
+        if first_token {
+            &mut self.first_token_reserve
+        } else {
+            &mut self.second_token_reserve
+        }
+    }
+}
+
+impl<'a, C> Drop for StorageCache<'a, C>
+where
+    C: crate::config::ConfigModule,
+{
+    fn drop(&mut self) {
+        // commit changes to storage for the mutable fields
+        self.sc_ref
+            .pair_reserve(&self.first_token_id)
+            .set(&self.first_token_reserve);
+        self.sc_ref
+            .pair_reserve(&self.second_token_id)
+            .set(&self.second_token_reserve);
+        self.sc_ref.sqrt_price().set(&self.sqrt_price);
+        self.sc_ref.current_tick().set(self.current_tick);
+        self.sc_ref.active_liquidity().set(&self.active_liquidity);
+        self.sc_ref
+            .fee_growth_global(&self.first_token_id)
+            .set(&self.fee_growth_global_first);
+        self.sc_ref
+            .fee_growth_global(&self.second_token_id)
+            .set(&self.fee_growth_global_second);
+    }
+}
@@ -0,0 +1,316 @@
+multiversx_sc::imports!();
+
+use pausable::State;
+
+use crate::errors::*;
+use crate::events::SwapEventData;
+use crate::math::liquidity_amounts::add_liquidity_delta;
+use crate::math::swap_math::compute_swap_step;
+use crate::math::tick_math::{
+    get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, max_sqrt_ratio, min_sqrt_ratio, Tick, MAX_TICK,
+    MIN_TICK, Q128_RESOLUTION,
+};
+use crate::storage_cache::StorageCache;
+
+pub type SwapTokensFixedOutputResultType<M> = MultiValue2<EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
+
+/// State of a swap that moves the price through one or more tick ranges
+pub struct SwapState<M: ManagedTypeApi> {
+    pub amount_remaining: BigUint<M>,
+    pub amount_calculated: BigUint<M>,
+    pub sqrt_price: BigUint<M>,
+    pub tick: Tick,
+    pub liquidity: BigUint<M>,
+    pub fee_growth_global_in: BigUint<M>,
+    pub fee_amount: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> SwapState<M> {
+    pub fn new(
+        amount: BigUint<M>,
+        sqrt_price: BigUint<M>,
+        tick: Tick,
+        liquidity: BigUint<M>,
+        fee_growth_global_in: BigUint<M>,
+    ) -> Self {
+        SwapState {
+            amount_remaining: amount,
+            amount_calculated: BigUint::zero(),
+            sqrt_price,
+            tick,
+            liquidity,
+            fee_growth_global_in,
+            fee_amount: BigUint::zero(),
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait SwapModule:
+    crate::config::ConfigModule
+    + crate::ticks::TicksModule
+    + crate::events::EventsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInput)]
+    fn swap_tokens_fixed_input(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment {
+        require!(amount_out_min > 0, ERROR_INVALID_ARGS);
+
+        let mut storage_cache = StorageCache::new(self);
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+        require!(payment.amount > 0, ERROR_ZERO_AMOUNT);
+
+        let first_for_second =
+            storage_cache.is_first_for_second(&payment.token_identifier, &token_out);
+        let swap_state = self.perform_swap(
+            &mut storage_cache,
+            first_for_second,
+            payment.amount.clone(),
+            true,
+        );
+        require!(swap_state.amount_remaining == 0, ERROR_NOT_ENOUGH_RESERVE);
+
+        let amount_out = swap_state.amount_calculated;
+        require!(amount_out >= amount_out_min, ERROR_SLIPPAGE_EXCEEDED);
+
+        *storage_cache.get_mut_reserve(first_for_second) += &payment.amount;
+        *storage_cache.get_mut_reserve(!first_for_second) -= &amount_out;
+
+        let caller = self.blockchain().get_caller();
+        let output_payment = EsdtTokenPayment::new(token_out.clone(), 0, amount_out.clone());
+        self.send_payment_non_zero(&caller, &output_payment);
+
+        self.emit_swap_event(
+            &storage_cache,
+            SwapEventData {
+                token_id_in: payment.token_identifier,
+                token_amount_in: payment.amount,
+                token_id_out: token_out,
+                token_amount_out: amount_out,
+                fee_amount: swap_state.fee_amount,
+            },
+        );
+
+        output_payment
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedOutput)]
+    fn swap_tokens_fixed_output(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out: BigUint,
+    ) -> SwapTokensFixedOutputResultType<Self::Api> {
+        require!(amount_out > 0, ERROR_INVALID_ARGS);
+
+        let mut storage_cache = StorageCache::new(self);
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+        require!(payment.amount > 0, ERROR_ZERO_AMOUNT);
+
+        let first_for_second =
+            storage_cache.is_first_for_second(&payment.token_identifier, &token_out);
+        let swap_state = self.perform_swap(
+            &mut storage_cache,
+            first_for_second,
+            amount_out.clone(),
+            false,
+        );
+        require!(swap_state.amount_remaining == 0, ERROR_NOT_ENOUGH_RESERVE);
+
+        let amount_in = swap_state.amount_calculated;
+        require!(amount_in <= payment.amount, ERROR_SLIPPAGE_EXCEEDED);
+
+        *storage_cache.get_mut_reserve(first_for_second) += &amount_in;
+        *storage_cache.get_mut_reserve(!first_for_second) -= &amount_out;
+
+        let caller = self.blockchain().get_caller();
+        let output_payment = EsdtTokenPayment::new(token_out.clone(), 0, amount_out.clone());
+        let residuum = EsdtTokenPayment::new(
+            payment.token_identifier.clone(),
+            0,
+            &payment.amount - &amount_in,
+        );
+        self.send_payment_non_zero(&caller, &output_payment);
+        self.send_payment_non_zero(&caller, &residuum);
+
+        self.emit_swap_event(
+            &storage_cache,
+            SwapEventData {
+                token_id_in: payment.token_identifier,
+                token_amount_in: amount_in,
+                token_id_out: token_out,
+                token_amount_out: amount_out,
+                fee_amount: swap_state.fee_amount,
+            },
+        );
+
+        (output_payment, residuum).into()
+    }
+
+    /// Runs the swap against the pool state and commits the new price,
+    /// tick, active liquidity and fee growth to the storage cache
+    fn perform_swap(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        first_for_second: bool,
+        amount: BigUint,
+        exact_input: bool,
+    ) -> SwapState<Self::Api> {
+        let (fee_growth_global_in, fee_growth_global_out) = if first_for_second {
+            (
+                storage_cache.fee_growth_global_first.clone(),
+                storage_cache.fee_growth_global_second.clone(),
+            )
+        } else {
+            (
+                storage_cache.fee_growth_global_second.clone(),
+                storage_cache.fee_growth_global_first.clone(),
+            )
+        };
+
+        let mut swap_state = SwapState::new(
+            amount,
+            storage_cache.sqrt_price.clone(),
+            storage_cache.current_tick,
+            storage_cache.active_liquidity.clone(),
+            fee_growth_global_in,
+        );
+        self.compute_swap(
+            &mut swap_state,
+            storage_cache.tick_spacing,
+            first_for_second,
+            exact_input,
+            &fee_growth_global_out,
+            true,
+        );
+
+        storage_cache.sqrt_price = swap_state.sqrt_price.clone();
+        storage_cache.current_tick = swap_state.tick;
+        storage_cache.active_liquidity = swap_state.liquidity.clone();
+        if first_for_second {
+            storage_cache.fee_growth_global_first = swap_state.fee_growth_global_in.clone();
+        } else {
+            storage_cache.fee_growth_global_second = swap_state.fee_growth_global_in.clone();
+        }
+
+        swap_state
+    }
+
+    /// Moves the price one step at a time, where a step ends either at the next
+    /// initialized tick or when the amount is filled.
+    /// Ticks are only written when `cross_ticks` is set, which allows views to simulate swaps.
+    /// Stops early if there is no liquidity left in the swap direction.
+    fn compute_swap(
+        &self,
+        swap_state: &mut SwapState<Self::Api>,
+        tick_spacing: Tick,
+        first_for_second: bool,
+        exact_input: bool,
+        fee_growth_global_out: &BigUint,
+        cross_ticks: bool,
+    ) {
+        let fee_percent = self.total_fee_percent().get();
+        let sqrt_price_limit = if first_for_second {
+            min_sqrt_ratio::<Self::Api>() + 1u32
+        } else {
+            max_sqrt_ratio::<Self::Api>() - 1u32
+        };
+        let lowest_initialized_tick = self.lowest_initialized_tick().get();
+        let highest_initialized_tick = self.highest_initialized_tick().get();
+
+        while swap_state.amount_remaining > 0 && swap_state.sqrt_price != sqrt_price_limit {
+            let no_liquidity_left = if first_for_second {
+                swap_state.tick < lowest_initialized_tick
+            } else {
+                swap_state.tick >= highest_initialized_tick
+            };
+            if swap_state.liquidity == 0 && no_liquidity_left {
+                break;
+            }
+
+            let sqrt_price_start = swap_state.sqrt_price.clone();
+            let (tick_next, initialized) = self.next_initialized_tick_within_one_word(
+                swap_state.tick,
+                tick_spacing,
+                first_for_second,
+            );
+            let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+            let sqrt_price_next = get_sqrt_ratio_at_tick::<Self::Api>(tick_next);
+            let sqrt_price_target = if first_for_second {
+                core::cmp::max(&sqrt_price_next, &sqrt_price_limit)
+            } else {
+                core::cmp::min(&sqrt_price_next, &sqrt_price_limit)
+            };
+
+            let step = compute_swap_step(
+                &swap_state.sqrt_price,
+                sqrt_price_target,
+                &swap_state.liquidity,
+                &swap_state.amount_remaining,
+                exact_input,
+                fee_percent,
+            );
+            swap_state.sqrt_price = step.sqrt_price_next;
+            if exact_input {
+                swap_state.amount_remaining -= &(&step.amount_in + &step.fee_amount);
+                swap_state.amount_calculated += &step.amount_out;
+            } else {
+                swap_state.amount_remaining -= &step.amount_out;
+                swap_state.amount_calculated += &(&step.amount_in + &step.fee_amount);
+            }
+
+            if swap_state.liquidity > 0 {
+                swap_state.fee_growth_global_in +=
+                    (&step.fee_amount << Q128_RESOLUTION) / &swap_state.liquidity;
+            }
+            swap_state.fee_amount += step.fee_amount;
+
+            if swap_state.sqrt_price == sqrt_price_next {
+                if initialized {
+                    let mut liquidity_net = if cross_ticks {
+                        let (fee_growth_first, fee_growth_second) = if first_for_second {
+                            (&swap_state.fee_growth_global_in, fee_growth_global_out)
+                        } else {
+                            (fee_growth_global_out, &swap_state.fee_growth_global_in)
+                        };
+                        self.cross_tick(tick_next, fee_growth_first, fee_growth_second)
+                    } else {
+                        self.get_tick_info(tick_next).liquidity_net
+                    };
+                    if first_for_second {
+                        liquidity_net = -liquidity_net;
+                    }
+                    swap_state.liquidity =
+                        add_liquidity_delta(&swap_state.liquidity, &liquidity_net);
+                }
+
+                swap_state.tick = if first_for_second {
+                    tick_next - 1
+                } else {
+                    tick_next
+                };
+            } else if swap_state.sqrt_price != sqrt_price_start {
+                swap_state.tick = get_tick_at_sqrt_ratio(&swap_state.sqrt_price);
+            }
+        }
+    }
+
+    fn can_swap(&self, state: State) -> bool {
+        state == State::Active
+    }
+}
@@ -0,0 +1,250 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::errors::{ERROR_INVALID_TICK_RANGE, ERROR_TICK_NOT_SPACED};
+use crate::math::liquidity_amounts::add_liquidity_delta;
+use crate::math::tick_math::{Tick, MAX_TICK, MIN_TICK};
+
+const TICKS_PER_WORD: Tick = 64;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug)]
+pub struct TickInfo<M: ManagedTypeApi> {
+    /// Total liquidity of the positions that use this tick as a boundary
+    pub liquidity_gross: BigUint<M>,
+    /// Liquidity added to the active liquidity when the tick is crossed left to right
+    pub liquidity_net: BigInt<M>,
+    /// Fee growth on the other side of this tick, relative to the current tick
+    pub fee_growth_outside_first: BigUint<M>,
+    pub fee_growth_outside_second: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> Default for TickInfo<M> {
+    fn default() -> Self {
+        TickInfo {
+            liquidity_gross: BigUint::zero(),
+            liquidity_net: BigInt::zero(),
+            fee_growth_outside_first: BigUint::zero(),
+            fee_growth_outside_second: BigUint::zero(),
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait TicksModule {
+    fn require_valid_tick_range(&self, tick_lower: Tick, tick_upper: Tick, tick_spacing: Tick) {
+        require!(
+            tick_lower < tick_upper && tick_lower >= MIN_TICK && tick_upper <= MAX_TICK,
+            ERROR_INVALID_TICK_RANGE
+        );
+        require!(
+            tick_lower % tick_spacing == 0 && tick_upper % tick_spacing == 0,
+            ERROR_TICK_NOT_SPACED
+        );
+    }
+
+    fn get_tick_info(&self, tick: Tick) -> TickInfo<Self::Api> {
+        let mapper = self.ticks(tick);
+        if mapper.is_empty() {
+            TickInfo::default()
+        } else {
+            mapper.get()
+        }
+    }
+
+    /// Updates the tick used as a range boundary, after the liquidity of a position changed.
+    /// Returns true if the tick was flipped from initialized to uninitialized, or vice versa.
+    /// Ticks left without liquidity must be cleared by the caller.
+    #[allow(clippy::too_many_arguments)]
+    fn update_tick(
+        &self,
+        tick: Tick,
+        current_tick: Tick,
+        liquidity_delta: &BigInt,
+        fee_growth_global_first: &BigUint,
+        fee_growth_global_second: &BigUint,
+        is_upper: bool,
+    ) -> bool {
+        let mut tick_info = self.get_tick_info(tick);
+
+        let liquidity_gross_before = tick_info.liquidity_gross.clone();
+        let liquidity_gross_after = add_liquidity_delta(&liquidity_gross_before, liquidity_delta);
+
+        if liquidity_gross_before == 0 && tick <= current_tick {
+            // by convention, all the fee growth before initialization happened below the tick
+            tick_info.fee_growth_outside_first = fee_growth_global_first.clone();
+            tick_info.fee_growth_outside_second = fee_growth_global_second.clone();
+        }
+
+        tick_info.liquidity_net = if is_upper {
+            tick_info.liquidity_net - liquidity_delta.clone()
+        } else {
+            tick_info.liquidity_net + liquidity_delta.clone()
+        };
+
+        let flipped = (liquidity_gross_after == 0) != (liquidity_gross_before == 0);
+        tick_info.liquidity_gross = liquidity_gross_after;
+        self.ticks(tick).set(&tick_info);
+
+        flipped
+    }
+
+    /// Called when the price crosses an initialized tick during a swap.
+    /// Returns the liquidity net of the tick.
+    fn cross_tick(
+        &self,
+        tick: Tick,
+        fee_growth_global_first: &BigUint,
+        fee_growth_global_second: &BigUint,
+    ) -> BigInt {
+        let mapper = self.ticks(tick);
+        if mapper.is_empty() {
+            return BigInt::zero();
+        }
+
+        let mut tick_info = mapper.get();
+        tick_info.fee_growth_outside_first =
+            fee_growth_global_first - &tick_info.fee_growth_outside_first;
+        tick_info.fee_growth_outside_second =
+            fee_growth_global_second - &tick_info.fee_growth_outside_second;
+        mapper.set(&tick_info);
+
+        tick_info.liquidity_net
+    }
+
+    /// Fee growth per unit of liquidity inside the [tick_lower, tick_upper) range.
+    /// The value is only meaningful when compared to a previous value for the same range.
+    fn get_fee_growth_inside(
+        &self,
+        tick_lower: Tick,
+        tick_upper: Tick,
+        current_tick: Tick,
+        fee_growth_global_first: &BigUint,
+        fee_growth_global_second: &BigUint,
+    ) -> (BigInt, BigInt) {
+        let lower_info = self.get_tick_info(tick_lower);
+        let upper_info = self.get_tick_info(tick_upper);
+
+        let (below_first, below_second) = if current_tick >= tick_lower {
+            (
+                lower_info.fee_growth_outside_first,
+                lower_info.fee_growth_outside_second,
+            )
+        } else {
+            (
+                fee_growth_global_first - &lower_info.fee_growth_outside_first,
+                fee_growth_global_second - &lower_info.fee_growth_outside_second,
+            )
+        };
+        let (above_first, above_second) = if current_tick < tick_upper {
+            (
+                upper_info.fee_growth_outside_first,
+                upper_info.fee_growth_outside_second,
+            )
+        } else {
+            (
+                fee_growth_global_first - &upper_info.fee_growth_outside_first,
+                fee_growth_global_second - &upper_info.fee_growth_outside_second,
+            )
+        };
+
+        let inside_first = BigInt::from(fee_growth_global_first.clone())
+            - BigInt::from(below_first)
+            - BigInt::from(above_first);
+        let inside_second = BigInt::from(fee_growth_global_second.clone())
+            - BigInt::from(below_second)
+            - BigInt::from(above_second);
+
+        (inside_first, inside_second)
+    }
+
+    fn flip_tick(&self, tick: Tick, tick_spacing: Tick) {
+        require!(tick % tick_spacing == 0, ERROR_TICK_NOT_SPACED);
+
+        let (word_pos, bit_pos) = self.get_bitmap_position(tick / tick_spacing);
+        self.tick_bitmap(word_pos)
+            .update(|word| *word ^= 1u64 << bit_pos);
+
+        self.lowest_initialized_tick().update(|lowest| {
+            if tick < *lowest {
+                *lowest = tick;
+            }
+        });
+        self.highest_initialized_tick().update(|highest| {
+            if tick > *highest {
+                *highest = tick;
+            }
+        });
+    }
+
+    /// Returns the next initialized tick contained in the same word as the given tick,
+    /// either to the left (less than or equal) or to the right (greater than).
+    /// If there is no initialized tick in the word, the word boundary is returned instead.
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: Tick,
+        tick_spacing: Tick,
+        less_than_or_equal: bool,
+    ) -> (Tick, bool) {
+        let compressed = tick.div_euclid(tick_spacing);
+
+        if less_than_or_equal {
+            let (word_pos, bit_pos) = self.get_bitmap_position(compressed);
+            // all the bits at or to the right of the current bit
+            let mask = if bit_pos == (TICKS_PER_WORD - 1) as u32 {
+                u64::MAX
+            } else {
+                (1u64 << (bit_pos + 1)) - 1
+            };
+            let masked = self.tick_bitmap(word_pos).get() & mask;
+
+            if masked != 0 {
+                let most_significant_bit = (TICKS_PER_WORD - 1) as u32 - masked.leading_zeros();
+                let next_compressed = compressed - (bit_pos - most_significant_bit) as Tick;
+                (next_compressed * tick_spacing, true)
+            } else {
+                ((compressed - bit_pos as Tick) * tick_spacing, false)
+            }
+        } else {
+            let (word_pos, bit_pos) = self.get_bitmap_position(compressed + 1);
+            // all the bits at or to the left of the current bit
+            let mask = !((1u64 << bit_pos) - 1);
+            let masked = self.tick_bitmap(word_pos).get() & mask;
+
+            if masked != 0 {
+                let least_significant_bit = masked.trailing_zeros();
+                let next_compressed = compressed + 1 + (least_significant_bit - bit_pos) as Tick;
+                (next_compressed * tick_spacing, true)
+            } else {
+                let next_compressed =
+                    compressed + 1 + ((TICKS_PER_WORD - 1) as u32 - bit_pos) as Tick;
+                (next_compressed * tick_spacing, false)
+            }
+        }
+    }
+
+    #[inline]
+    fn get_bitmap_position(&self, compressed_tick: Tick) -> (Tick, u32) {
+        (
+            compressed_tick.div_euclid(TICKS_PER_WORD),
+            compressed_tick.rem_euclid(TICKS_PER_WORD) as u32,
+        )
+    }
+
+    #[view(getTickInfo)]
+    #[storage_mapper("ticks")]
+    fn ticks(&self, tick: Tick) -> SingleValueMapper<TickInfo<Self::Api>>;
+
+    #[storage_mapper("tick_bitmap")]
+    fn tick_bitmap(&self, word_pos: Tick) -> SingleValueMapper<u64>;
+
+    /// Lower bound for all the initialized ticks. Not updated when ticks are cleared.
+    #[view(getLowestInitializedTick)]
+    #[storage_mapper("lowest_initialized_tick")]
+    fn lowest_initialized_tick(&self) -> SingleValueMapper<Tick>;
+
+    /// Upper bound for all the initialized ticks. Not updated when ticks are cleared.
+    #[view(getHighestInitializedTick)]
+    #[storage_mapper("highest_initialized_tick")]
+    fn highest_initialized_tick(&self) -> SingleValueMapper<Tick>;
+}
@@ -0,0 +1,112 @@
+multiversx_sc::imports!();
+
+use crate::errors::*;
+use crate::swap::SwapState;
+
+#[multiversx_sc::module]
+pub trait ViewsModule:
+    crate::config::ConfigModule
+    + crate::ticks::TicksModule
+    + crate::positions::PositionsModule
+    + crate::position_token::PositionTokenModule
+    + crate::swap::SwapModule
+    + crate::events::EventsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[view(getAmountOut)]
+    fn get_amount_out_view(&self, token_in: TokenIdentifier, amount_in: BigUint) -> BigUint {
+        require!(amount_in > 0u64, ERROR_ZERO_AMOUNT);
+
+        let first_for_second = self.is_first_token(&token_in);
+        let swap_state = self.simulate_swap(first_for_second, amount_in, true);
+        require!(swap_state.amount_remaining == 0, ERROR_NOT_ENOUGH_RESERVE);
+
+        swap_state.amount_calculated
+    }
+
+    #[view(getAmountIn)]
+    fn get_amount_in_view(&self, token_wanted: TokenIdentifier, amount_wanted: BigUint) -> BigUint {
+        require!(amount_wanted > 0u64, ERROR_ZERO_AMOUNT);
+
+        let first_for_second = !self.is_first_token(&token_wanted);
+        let swap_state = self.simulate_swap(first_for_second, amount_wanted, false);
+        require!(swap_state.amount_remaining == 0, ERROR_NOT_ENOUGH_RESERVE);
+
+        swap_state.amount_calculated
+    }
+
+    /// Fees that would be sent to the owner of the position if collected now
+    #[view(getPositionFees)]
+    fn get_position_fees(&self, position_nonce: u64) -> MultiValue2<BigUint, BigUint> {
+        let mapper = self.positions(position_nonce);
+        require!(!mapper.is_empty(), ERROR_INVALID_POSITION);
+
+        let position = mapper.get();
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let (fee_growth_inside_first, fee_growth_inside_second) = self.get_fee_growth_inside(
+            position.tick_lower,
+            position.tick_upper,
+            self.current_tick().get(),
+            &self.fee_growth_global(&first_token_id).get(),
+            &self.fee_growth_global(&second_token_id).get(),
+        );
+        let (first_fees, second_fees) = self.compute_position_fees(
+            &position,
+            &fee_growth_inside_first,
+            &fee_growth_inside_second,
+        );
+
+        (
+            position.tokens_owed_first + first_fees,
+            position.tokens_owed_second + second_fees,
+        )
+            .into()
+    }
+
+    fn simulate_swap(
+        &self,
+        first_for_second: bool,
+        amount: BigUint,
+        exact_input: bool,
+    ) -> SwapState<Self::Api> {
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let (token_in, token_out) = if first_for_second {
+            (first_token_id, second_token_id)
+        } else {
+            (second_token_id, first_token_id)
+        };
+
+        let mut swap_state = SwapState::new(
+            amount,
+            self.sqrt_price().get(),
+            self.current_tick().get(),
+            self.active_liquidity().get(),
+            self.fee_growth_global(&token_in).get(),
+        );
+        self.compute_swap(
+            &mut swap_state,
+            self.tick_spacing().get(),
+            first_for_second,
+            exact_input,
+            &self.fee_growth_global(&token_out).get(),
+            false,
+        );
+
+        swap_state
+    }
+
+    fn is_first_token(&self, token_id: &TokenIdentifier) -> bool {
+        if token_id == &self.first_token_id().get() {
+            true
+        } else if token_id == &self.second_token_id().get() {
+            false
+        } else {
+            sc_panic!(ERROR_UNKNOWN_TOKEN);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use multiversx_sc::types::{
+    Address, BigUint, EsdtLocalRole, ManagedAddress, MultiValueEncoded, TokenIdentifier,
+};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+
+pub const CONCENTRATED_PAIR_WASM_PATH: &str = "concentrated-pair/output/concentrated-pair.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const POSITION_TOKEN_ID: &[u8] = b"CLPOS-abcdef";
+
+pub const TOTAL_FEE_PERCENT: u64 = 300;
+pub const TICK_SPACING: i32 = 10;
+pub const USER_TOTAL_TOKENS: u64 = 1_000_000_000_000;
+
+use concentrated_pair::config::ConfigModule;
+use concentrated_pair::math::tick_math::get_sqrt_ratio_at_tick;
+use concentrated_pair::position_token::PositionTokenModule;
+use concentrated_pair::positions::PositionsModule;
+use concentrated_pair::swap::SwapModule;
+use concentrated_pair::*;
+use pausable::{PausableModule, State};
+
+#[allow(dead_code)]
+pub struct ConcentratedPairSetup<PairObjBuilder>
+where
+    PairObjBuilder: 'static + Copy + Fn() -> concentrated_pair::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub user_address: Address,
+    pub pair_wrapper: ContractObjWrapper<concentrated_pair::ContractObj<DebugApi>, PairObjBuilder>,
+}
+
+#[allow(dead_code)]
+impl<PairObjBuilder> ConcentratedPairSetup<PairObjBuilder>
+where
+    PairObjBuilder: 'static + Copy + Fn() -> concentrated_pair::ContractObj<DebugApi>,
+{
+    /// Creates a pool with the initial price set to 1
+    pub fn new(pair_builder: PairObjBuilder) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let pair_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            pair_builder,
+            CONCENTRATED_PAIR_WASM_PATH,
+        );
+
+        b_mock
+            .execute_tx(&owner_addr, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    TOTAL_FEE_PERCENT,
+                    TICK_SPACING,
+                    get_sqrt_ratio_at_tick(0),
+                    MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+                );
+
+                sc.position_token()
+                    .set_token_id(managed_token_id!(POSITION_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        let position_token_roles = [EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn];
+        b_mock.set_esdt_local_roles(
+            pair_wrapper.address_ref(),
+            POSITION_TOKEN_ID,
+            &position_token_roles[..],
+        );
+
+        let user_addr = b_mock.create_user_account(&rust_zero);
+        b_mock.set_esdt_balance(
+            &user_addr,
+            WEGLD_TOKEN_ID,
+            &rust_biguint!(USER_TOTAL_TOKENS),
+        );
+        b_mock.set_esdt_balance(&user_addr, MEX_TOKEN_ID, &rust_biguint!(USER_TOTAL_TOKENS));
+
+        ConcentratedPairSetup {
+            b_mock,
+            owner_address: owner_addr,
+            user_address: user_addr,
+            pair_wrapper,
+        }
+    }
+
+    /// Returns the nonce of the new position and the amounts used
+    pub fn open_position(
+        &mut self,
+        tick_lower: i32,
+        tick_upper: i32,
+        first_token_amount: u64,
+        second_token_amount: u64,
+    ) -> (u64, u64, u64) {
+        let mut payments = Vec::new();
+        if first_token_amount > 0 {
+            payments.push(TxTokenTransfer {
+                token_identifier: WEGLD_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(first_token_amount),
+            });
+        }
+        if second_token_amount > 0 {
+            payments.push(TxTokenTransfer {
+                token_identifier: MEX_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(second_token_amount),
+            });
+        }
+
+        let mut result = (0, 0, 0);
+        self.b_mock
+            .execute_esdt_multi_transfer(&self.user_address, &self.pair_wrapper, &payments, |sc| {
+                let (position, first_used, second_used) = sc
+                    .open_position(
+                        tick_lower,
+                        tick_upper,
+                        managed_biguint!(0),
+                        managed_biguint!(0),
+                    )
+                    .into_tuple();
+
+                assert_eq!(
+                    position.token_identifier,
+                    managed_token_id!(POSITION_TOKEN_ID)
+                );
+                result = (
+                    position.token_nonce,
+                    first_used.amount.to_u64().unwrap(),
+                    second_used.amount.to_u64().unwrap(),
+                );
+            })
+            .assert_ok();
+
+        result
+    }
+
+    pub fn swap_fixed_input(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        desired_token_id: &[u8],
+        desired_amount_min: u64,
+    ) -> u64 {
+        let mut amount_out = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let ret = sc.swap_tokens_fixed_input(
+                        managed_token_id!(desired_token_id),
+                        managed_biguint!(desired_amount_min),
+                    );
+
+                    assert_eq!(ret.token_identifier, managed_token_id!(desired_token_id));
+                    amount_out = ret.amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        amount_out
+    }
+
+    /// Returns the amount of the payment token that was sent back
+    pub fn swap_fixed_output(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount_max: u64,
+        desired_token_id: &[u8],
+        desired_amount: u64,
+    ) -> u64 {
+        let mut residuum = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount_max),
+                |sc| {
+                    let (output, payment_residuum) = sc
+                        .swap_tokens_fixed_output(
+                            managed_token_id!(desired_token_id),
+                            managed_biguint!(desired_amount),
+                        )
+                        .into_tuple();
+
+                    assert_eq!(output.amount, managed_biguint!(desired_amount));
+                    residuum = payment_residuum.amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        residuum
+    }
+
+    pub fn collect_fees(&mut self, position_nonce: u64) -> (u64, u64) {
+        let mut fees = (0, 0);
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                POSITION_TOKEN_ID,
+                position_nonce,
+                &rust_biguint!(1),
+                |sc| {
+                    let (first_fees, second_fees) = sc.collect_fees().into_tuple();
+                    fees = (
+                        first_fees.amount.to_u64().unwrap(),
+                        second_fees.amount.to_u64().unwrap(),
+                    );
+                },
+            )
+            .assert_ok();
+
+        fees
+    }
+
+    pub fn close_position(&mut self, position_nonce: u64) -> (u64, u64) {
+        let mut amounts = (0, 0);
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pair_wrapper,
+                POSITION_TOKEN_ID,
+                position_nonce,
+                &rust_biguint!(1),
+                |sc| {
+                    let (first_amount, second_amount) = sc
+                        .close_position(managed_biguint!(0), managed_biguint!(0))
+                        .into_tuple();
+                    amounts = (
+                        first_amount.amount.to_u64().unwrap(),
+                        second_amount.amount.to_u64().unwrap(),
+                    );
+                },
+            )
+            .assert_ok();
+
+        amounts
+    }
+
+    pub fn get_reserve(&mut self, token_id: &[u8]) -> u64 {
+        let mut reserve = 0;
+        self.b_mock
+            .execute_query(&self.pair_wrapper, |sc| {
+                reserve = sc
+                    .pair_reserve(&TokenIdentifier::from(token_id))
+                    .get()
+                    .to_u64()
+                    .unwrap();
+            })
+            .assert_ok();
+
+        reserve
+    }
+
+    pub fn get_current_tick(&mut self) -> i32 {
+        let mut tick = 0;
+        self.b_mock
+            .execute_query(&self.pair_wrapper, |sc| {
+                tick = sc.current_tick().get();
+            })
+            .assert_ok();
+
+        tick
+    }
+
+    pub fn get_active_liquidity(&mut self) -> num_bigint::BigUint {
+        let mut liquidity = rust_biguint!(0);
+        self.b_mock
+            .execute_query(&self.pair_wrapper, |sc| {
+                liquidity = to_rust_biguint(sc.active_liquidity().get());
+            })
+            .assert_ok();
+
+        liquidity
+    }
+}
+
+pub fn to_rust_biguint(value: BigUint<DebugApi>) -> num_bigint::BigUint {
+    num_bigint::BigUint::from_bytes_be(value.to_bytes_be().as_slice())
+}
@@ -0,0 +1,297 @@
+#![allow(deprecated)]
+
+mod concentrated_pair_setup;
+use concentrated_pair::{
+    math::tick_math::{
+        get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, max_sqrt_ratio, min_sqrt_ratio, MAX_TICK,
+        MIN_TICK,
+    },
+    positions::PositionsModule,
+    swap::SwapModule,
+    views::ViewsModule,
+};
+use concentrated_pair_setup::*;
+use multiversx_sc::codec::Empty;
+use multiversx_sc::types::BigUint;
+use multiversx_sc_scenario::{managed_biguint, managed_token_id, rust_biguint, DebugApi};
+
+#[test]
+fn test_concentrated_pair_setup() {
+    let _ = ConcentratedPairSetup::new(concentrated_pair::contract_obj);
+}
+
+#[test]
+fn test_tick_math() {
+    DebugApi::dummy();
+
+    assert_eq!(
+        min_sqrt_ratio::<DebugApi>(),
+        BigUint::<DebugApi>::from(4_295_128_739u64)
+    );
+    assert_eq!(
+        to_rust_biguint(max_sqrt_ratio::<DebugApi>()).to_string(),
+        "1461446703485210103287273052203988822378723970342"
+    );
+    assert_eq!(
+        get_sqrt_ratio_at_tick::<DebugApi>(0),
+        BigUint::<DebugApi>::from(1u128 << 96)
+    );
+
+    for tick in [MIN_TICK, -100_000, -1, 0, 1, 50, 100_000, MAX_TICK - 1] {
+        let sqrt_price = get_sqrt_ratio_at_tick::<DebugApi>(tick);
+        assert_eq!(get_tick_at_sqrt_ratio(&sqrt_price), tick);
+        assert_eq!(get_tick_at_sqrt_ratio(&(sqrt_price + 1u32)), tick);
+    }
+}
+
+#[test]
+fn test_open_position() {
+    let mut pair_setup = ConcentratedPairSetup::new(concentrated_pair::contract_obj);
+
+    // symmetric range around the current price uses equal amounts
+    let (nonce, first_used, second_used) =
+        pair_setup.open_position(-1_000, 1_000, 1_000_000_000, 2_000_000_000);
+    assert_eq!(nonce, 1);
+    assert_eq!(first_used, 1_000_000_000);
+    assert_eq!(first_used, second_used);
+    assert_eq!(pair_setup.get_reserve(WEGLD_TOKEN_ID), first_used);
+    assert_eq!(pair_setup.get_reserve(MEX_TOKEN_ID), second_used);
+    assert_eq!(
+        pair_setup
+            .b_mock
+            .get_esdt_balance(&pair_setup.user_address, MEX_TOKEN_ID, 0),
+        rust_biguint!(USER_TOTAL_TOKENS - second_used)
+    );
+
+    // a range above the current price only holds the first token
+    let (nonce, first_used, second_used) = pair_setup.open_position(100, 200, 1_000_000, 1_000_000);
+    assert_eq!(nonce, 2);
+    assert_eq!(first_used, 1_000_000);
+    assert_eq!(second_used, 0);
+
+    // ticks must be multiples of the tick spacing
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.open_position(-15, 15, managed_biguint!(0), managed_biguint!(0));
+            },
+        )
+        .assert_user_error("Tick is not a multiple of the tick spacing");
+}
+
+#[test]
+fn test_swap_fixed_input() {
+    let mut pair_setup = ConcentratedPairSetup::new(concentrated_pair::contract_obj);
+    let _ = pair_setup.open_position(-1_000, 1_000, 1_000_000_000, 1_000_000_000);
+
+    let amount_in = 1_000_000;
+    let mut expected_amount_out = 0;
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            expected_amount_out = sc
+                .get_amount_out_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(amount_in),
+                )
+                .to_u64()
+                .unwrap();
+        })
+        .assert_ok();
+
+    let amount_out = pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, amount_in, MEX_TOKEN_ID, 1);
+    assert_eq!(amount_out, expected_amount_out);
+    assert!(amount_out < amount_in * 997 / 1_000);
+    assert!(amount_out > amount_in * 990 / 1_000);
+    assert!(pair_setup.get_current_tick() < 0);
+
+    assert_eq!(
+        pair_setup.get_reserve(WEGLD_TOKEN_ID),
+        1_000_000_000 + amount_in
+    );
+    assert_eq!(
+        pair_setup.get_reserve(MEX_TOKEN_ID),
+        1_000_000_000 - amount_out
+    );
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(amount_in),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(amount_in),
+                );
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+}
+
+#[test]
+fn test_swap_fixed_output() {
+    let mut pair_setup = ConcentratedPairSetup::new(concentrated_pair::contract_obj);
+    let _ = pair_setup.open_position(-1_000, 1_000, 1_000_000_000, 1_000_000_000);
+
+    let amount_wanted = 10_000_000;
+    let mut expected_amount_in = 0;
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            expected_amount_in = sc
+                .get_amount_in_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(amount_wanted),
+                )
+                .to_u64()
+                .unwrap();
+        })
+        .assert_ok();
+    assert!(expected_amount_in > amount_wanted * 1_003 / 1_000);
+
+    let payment_amount_max = 20_000_000;
+    let residuum = pair_setup.swap_fixed_output(
+        MEX_TOKEN_ID,
+        payment_amount_max,
+        WEGLD_TOKEN_ID,
+        amount_wanted,
+    );
+    assert_eq!(residuum, payment_amount_max - expected_amount_in);
+    assert!(pair_setup.get_current_tick() > 0);
+    assert_eq!(
+        pair_setup.get_reserve(MEX_TOKEN_ID),
+        1_000_000_000 + expected_amount_in
+    );
+}
+
+#[test]
+fn test_swap_crossing_ticks() {
+    let mut pair_setup = ConcentratedPairSetup::new(concentrated_pair::contract_obj);
+    let _ = pair_setup.open_position(-1_000, 1_000, 1_000_000_000, 1_000_000_000);
+    let wide_liquidity = pair_setup.get_active_liquidity();
+    let _ = pair_setup.open_position(-100, 100, 1_000_000_000, 1_000_000_000);
+    assert!(pair_setup.get_active_liquidity() > wide_liquidity);
+
+    // push the price below the narrow range
+    let _ = pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_500_000_000, MEX_TOKEN_ID, 1);
+    let current_tick = pair_setup.get_current_tick();
+    assert!((-1_000..-100).contains(&current_tick));
+    assert_eq!(pair_setup.get_active_liquidity(), wide_liquidity);
+
+    // and back above it
+    let _ = pair_setup.swap_fixed_input(MEX_TOKEN_ID, 3_000_000_000, WEGLD_TOKEN_ID, 1);
+    let current_tick = pair_setup.get_current_tick();
+    assert!((100..1_000).contains(&current_tick));
+    assert_eq!(pair_setup.get_active_liquidity(), wide_liquidity);
+
+    // the wide range is exhausted
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000_000_000u64),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(1),
+                );
+            },
+        )
+        .assert_user_error("Not enough reserve");
+}
+
+#[test]
+fn test_collect_fees() {
+    let mut pair_setup = ConcentratedPairSetup::new(concentrated_pair::contract_obj);
+    let (in_range_nonce, _, _) =
+        pair_setup.open_position(-1_000, 1_000, 1_000_000_000, 1_000_000_000);
+    let (out_of_range_nonce, _, _) = pair_setup.open_position(1_000, 2_000, 1_000_000_000, 0);
+
+    let amount_in = 10_000_000;
+    let _ = pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, amount_in, MEX_TOKEN_ID, 1);
+
+    let mut pending_fees = (0, 0);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let (first_fees, second_fees) = sc.get_position_fees(in_range_nonce).into_tuple();
+            pending_fees = (first_fees.to_u64().unwrap(), second_fees.to_u64().unwrap());
+        })
+        .assert_ok();
+
+    let expected_fee = amount_in * TOTAL_FEE_PERCENT / 100_000;
+    let (first_fees, second_fees) = pair_setup.collect_fees(in_range_nonce);
+    assert_eq!((first_fees, second_fees), pending_fees);
+    assert!(first_fees <= expected_fee && first_fees + 1 >= expected_fee);
+    assert_eq!(second_fees, 0);
+
+    // fees are only collected once
+    assert_eq!(pair_setup.collect_fees(in_range_nonce), (0, 0));
+    // liquidity outside of the active range earns nothing
+    assert_eq!(pair_setup.collect_fees(out_of_range_nonce), (0, 0));
+}
+
+#[test]
+fn test_close_position() {
+    let mut pair_setup = ConcentratedPairSetup::new(concentrated_pair::contract_obj);
+    let (nonce, first_used, second_used) =
+        pair_setup.open_position(-1_000, 1_000, 1_000_000_000, 1_000_000_000);
+
+    let amount_in = 10_000_000;
+    let amount_out = pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, amount_in, MEX_TOKEN_ID, 1);
+
+    // the only position gets back the whole pool, minus rounding
+    let (first_amount, second_amount) = pair_setup.close_position(nonce);
+    assert!(first_amount <= first_used + amount_in && first_amount + 2 >= first_used + amount_in);
+    assert!(
+        second_amount <= second_used - amount_out && second_amount + 2 >= second_used - amount_out
+    );
+    assert_eq!(pair_setup.get_active_liquidity(), rust_biguint!(0));
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert!(sc.positions(nonce).is_empty());
+        })
+        .assert_ok();
+    pair_setup.b_mock.check_nft_balance::<Empty>(
+        &pair_setup.user_address,
+        POSITION_TOKEN_ID,
+        nonce,
+        &rust_biguint!(0),
+        None,
+    );
+}
+
+#[test]
+fn test_swap_without_liquidity() {
+    let mut pair_setup = ConcentratedPairSetup::new(concentrated_pair::contract_obj);
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc
+                    .swap_tokens_fixed_input(managed_token_id!(MEX_TOKEN_ID), managed_biguint!(1));
+            },
+        )
+        .assert_user_error("Not enough reserve");
+}
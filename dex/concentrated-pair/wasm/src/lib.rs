@@ -0,0 +1,63 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Upgrade:                              1
+// Endpoints:                           37
+// Async Callback:                       1
+// Total number of exported functions:  40
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    concentrated_pair
+    (
+        init => init
+        upgrade => upgrade
+        setStateActiveNoSwaps => set_state_active_no_swaps
+        setFeePercent => set_fee_percent_endpoint
+        getTotalFeePercent => total_fee_percent
+        getRouterManagedAddress => router_address
+        getFirstTokenId => first_token_id
+        getSecondTokenId => second_token_id
+        getTickSpacing => tick_spacing
+        getSqrtPrice => sqrt_price
+        getCurrentTick => current_tick
+        getActiveLiquidity => active_liquidity
+        getFeeGrowthGlobal => fee_growth_global
+        getReserve => pair_reserve
+        getTickInfo => ticks
+        getLowestInitializedTick => lowest_initialized_tick
+        getHighestInitializedTick => highest_initialized_tick
+        registerPositionToken => register_position_token
+        getPositionTokenId => position_token
+        openPosition => open_position
+        increaseLiquidity => increase_liquidity
+        decreaseLiquidity => decrease_liquidity
+        collectFees => collect_fees
+        closePosition => close_position
+        getPosition => positions
+        swapTokensFixedInput => swap_tokens_fixed_input
+        swapTokensFixedOutput => swap_tokens_fixed_output
+        getAmountOut => get_amount_out_view
+        getAmountIn => get_amount_in_view
+        getPositionFees => get_position_fees
+        addAdmin => add_admin_endpoint
+        removeAdmin => remove_admin_endpoint
+        updateOwnerOrAdmin => update_owner_or_admin_endpoint
+        getPermissions => permissions
+        addToPauseWhitelist => add_to_pause_whitelist
+        removeFromPauseWhitelist => remove_from_pause_whitelist
+        pause => pause
+        resume => resume
+        getState => state
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback! { concentrated_pair }
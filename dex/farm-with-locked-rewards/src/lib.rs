@@ -3,7 +3,7 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use common_structs::FarmTokenAttributes;
+use common_structs::{FarmTokenAttributes, Nonce};
 use contexts::storage_cache::StorageCache;
 use core::marker::PhantomData;
 use fixed_supply_token::FixedSupplyToken;
@@ -16,6 +16,7 @@ use farm::{
     EnterFarmResultType, ExitFarmWithPartialPosResultType, MAX_PERCENT,
 };
 use farm_base_impl::base_traits_impl::FarmContract;
+use rewards::{EmissionDecayCurve, EmissionSchedule, EmissionSegment};
 
 #[multiversx_sc::contract]
 pub trait Farm:
@@ -265,6 +266,54 @@ pub trait Farm:
         self.set_per_block_rewards::<NoMintWrapper<Self>>(per_block_amount);
     }
 
+    /// Pairs of (start block, per block amount), sorted by start block.
+    #[endpoint(setEmissionSegments)]
+    fn set_emission_segments_endpoint(
+        &self,
+        segments: MultiValueEncoded<MultiValue2<Nonce, BigUint>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+
+        let mut emission_segments = ManagedVec::new();
+        for segment in segments {
+            let (start_block, per_block_amount) = segment.into_tuple();
+            emission_segments.push(EmissionSegment {
+                start_block,
+                per_block_amount,
+            });
+        }
+        self.update_emission_schedule::<NoMintWrapper<Self>>(Some(EmissionSchedule::Segments(
+            emission_segments,
+        )));
+    }
+
+    #[endpoint(setEmissionDecayCurve)]
+    fn set_emission_decay_curve_endpoint(
+        &self,
+        start_block: Nonce,
+        initial_per_block_amount: BigUint,
+        period_blocks: Nonce,
+        decay_percent: u64,
+    ) {
+        self.require_caller_has_admin_permissions();
+
+        let curve = EmissionDecayCurve {
+            start_block,
+            initial_per_block_amount,
+            period_blocks,
+            decay_percent,
+        };
+        self.update_emission_schedule::<NoMintWrapper<Self>>(Some(EmissionSchedule::DecayCurve(
+            curve,
+        )));
+    }
+
+    #[endpoint(clearEmissionSchedule)]
+    fn clear_emission_schedule_endpoint(&self) {
+        self.require_caller_has_admin_permissions();
+        self.update_emission_schedule::<NoMintWrapper<Self>>(None);
+    }
+
     #[endpoint(setBoostedYieldsRewardsPercentage)]
     fn set_boosted_yields_rewards_percentage(&self, percentage: u64) {
         self.require_caller_has_admin_permissions();
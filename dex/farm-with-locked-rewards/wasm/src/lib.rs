@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           66
+// Endpoints:                           76
 // Async Callback:                       1
-// Total number of exported functions:  69
+// Total number of exported functions:  79
 
 #![no_std]
 
@@ -28,8 +28,13 @@ multiversx_sc_wasm_adapter::endpoints! {
         startProduceRewards => start_produce_rewards_endpoint
         endProduceRewards => end_produce_rewards_endpoint
         setPerBlockRewardAmount => set_per_block_rewards_endpoint
+        setEmissionSegments => set_emission_segments_endpoint
+        setEmissionDecayCurve => set_emission_decay_curve_endpoint
+        clearEmissionSchedule => clear_emission_schedule_endpoint
         setBoostedYieldsRewardsPercentage => set_boosted_yields_rewards_percentage
         calculateRewardsForGivenPosition => calculate_rewards_for_given_position
+        getEmissionForBlocks => calculate_emission
+        getEmissionSchedule => emission_schedule
         getRewardPerShare => reward_per_share
         getRewardReserve => reward_reserve
         getFarmingTokenId => farming_token_id
@@ -62,8 +67,13 @@ multiversx_sc_wasm_adapter::endpoints! {
         set_penalty_percent => set_penalty_percent
         set_minimum_farming_epochs => set_minimum_farming_epochs
         set_burn_gas_limit => set_burn_gas_limit
+        setPenaltyCurve => set_penalty_curve
+        getExitPenaltyForGivenPosition => get_exit_penalty_for_given_position
         getPenaltyPercent => penalty_percent
         getMinimumFarmingEpoch => minimum_farming_epochs
+        getPenaltyCurve => penalty_curve
+        isPenaltyRedistributed => redistribute_penalty
+        getUndistributedPenalty => undistributed_penalty
         getBurnGasLimit => burn_gas_limit
         getPairContractManagedAddress => pair_contract_address
         collectUndistributedBoostedRewards => collect_undistributed_boosted_rewards
@@ -11,8 +11,9 @@ use common_structs::FarmTokenAttributes;
 use contexts::storage_cache::StorageCache;
 
 use farm_base_impl::base_traits_impl::{DefaultFarmWrapper, FarmContract};
+use rewards::EmissionSchedule;
 
-use crate::{exit_penalty, MAX_PERCENT};
+use crate::exit_penalty;
 
 pub type DoubleMultiPayment<M> = MultiValue2<EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
 pub type ClaimRewardsResultType<M> = DoubleMultiPayment<M>;
@@ -257,6 +258,19 @@ pub trait BaseFunctionsModule:
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
+    fn update_emission_schedule<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        opt_schedule: Option<EmissionSchedule<Self::Api>>,
+    ) {
+        let mut storage = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage);
+
+        match opt_schedule {
+            Some(schedule) => self.set_emission_schedule(schedule),
+            None => self.emission_schedule().clear(),
+        }
+    }
+
     fn require_queried(&self) {
         let caller = self.blockchain().get_caller();
         let sc_address = self.blockchain().get_sc_address();
@@ -342,13 +356,11 @@ where
         token_attributes: &Self::AttributesType,
     ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
         let current_epoch = sc.blockchain().get_block_epoch();
-        let user_farming_epochs = current_epoch - token_attributes.entering_epoch;
-        let min_farming_epochs = sc.minimum_farming_epochs().get();
-        if user_farming_epochs >= min_farming_epochs {
-            BigUint::zero()
-        } else {
-            total_exit_amount * sc.penalty_percent().get() / MAX_PERCENT
-        }
+        sc.calculate_exit_penalty(
+            total_exit_amount,
+            token_attributes.entering_epoch,
+            current_epoch,
+        )
     }
 
     fn apply_penalty(
@@ -361,6 +373,12 @@ where
         if penalty_amount > 0 {
             *total_exit_amount -= &penalty_amount;
 
+            if sc.redistribute_penalty().get() && storage_cache.farm_token_supply > 0 {
+                sc.undistributed_penalty()
+                    .update(|undistributed_penalty| *undistributed_penalty += &penalty_amount);
+                return;
+            }
+
             sc.burn_farming_tokens(
                 &penalty_amount,
                 &storage_cache.farming_token_id,
@@ -1,7 +1,9 @@
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
 
 use common_errors::ERROR_PARAMETERS;
-use common_structs::Epoch;
+use common_structs::{Epoch, FarmTokenAttributes};
+use math::linear_interpolation;
 use pair::pair_actions::remove_liq::ProxyTrait as _;
 
 use crate::MAX_PERCENT;
@@ -11,6 +13,16 @@ pub const DEFAULT_MINUMUM_FARMING_EPOCHS: u64 = 3;
 pub const DEFAULT_BURN_GAS_LIMIT: u64 = 50_000_000;
 pub const DEFAULT_NFT_DEPOSIT_MAX_LEN: usize = 10;
 pub const MAX_MINIMUM_FARMING_EPOCHS: u64 = 30;
+pub const MAX_PENALTY_CURVE_POINTS: usize = 10;
+
+#[type_abi]
+#[derive(
+    ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Debug,
+)]
+pub struct PenaltyCurvePoint {
+    pub farming_epochs: Epoch,
+    pub penalty_percent: u64,
+}
 
 #[multiversx_sc::module]
 pub trait ExitPenaltyModule: permissions_module::PermissionsModule {
@@ -35,6 +47,116 @@ pub trait ExitPenaltyModule: permissions_module::PermissionsModule {
         self.burn_gas_limit().set(gas_limit);
     }
 
+    /// Pairs of (farming epochs, penalty percent). The penalty is interpolated linearly
+    /// between the points, and is zero after the last one. Without any points, the flat
+    /// penalty percent is applied until the minimum farming epochs pass.
+    #[endpoint(setPenaltyCurve)]
+    fn set_penalty_curve(&self, points: MultiValueEncoded<MultiValue2<Epoch, u64>>) {
+        self.require_caller_has_admin_permissions();
+
+        let mut penalty_curve = ManagedVec::new();
+        let mut opt_prev_point: Option<PenaltyCurvePoint> = None;
+        for point in points {
+            let (farming_epochs, penalty_percent) = point.into_tuple();
+            require!(penalty_percent < MAX_PERCENT, ERROR_PARAMETERS);
+            if let Some(prev_point) = opt_prev_point {
+                require!(
+                    farming_epochs > prev_point.farming_epochs
+                        && penalty_percent <= prev_point.penalty_percent,
+                    "Invalid penalty curve"
+                );
+            }
+
+            let current_point = PenaltyCurvePoint {
+                farming_epochs,
+                penalty_percent,
+            };
+            penalty_curve.push(current_point);
+            opt_prev_point = Some(current_point);
+        }
+
+        require!(
+            penalty_curve.len() <= MAX_PENALTY_CURVE_POINTS,
+            ERROR_PARAMETERS
+        );
+        if let Some(last_point) = opt_prev_point {
+            require!(
+                last_point.penalty_percent == 0,
+                "Penalty curve must end at zero"
+            );
+        }
+
+        self.penalty_curve().set(penalty_curve);
+    }
+
+    fn get_penalty_percent_for_farming_epochs(&self, farming_epochs: Epoch) -> u64 {
+        let penalty_curve = self.penalty_curve().get();
+        if penalty_curve.is_empty() {
+            if farming_epochs >= self.minimum_farming_epochs().get() {
+                return 0;
+            }
+
+            return self.penalty_percent().get();
+        }
+
+        let first_point = penalty_curve.get(0);
+        if farming_epochs <= first_point.farming_epochs {
+            return first_point.penalty_percent;
+        }
+
+        for i in 1..penalty_curve.len() {
+            let prev_point = penalty_curve.get(i - 1);
+            let next_point = penalty_curve.get(i);
+            if farming_epochs <= next_point.farming_epochs {
+                return linear_interpolation::<Self::Api, _>(
+                    prev_point.farming_epochs,
+                    next_point.farming_epochs,
+                    farming_epochs,
+                    prev_point.penalty_percent,
+                    next_point.penalty_percent,
+                );
+            }
+        }
+
+        0
+    }
+
+    fn calculate_exit_penalty(
+        &self,
+        exit_amount: &BigUint,
+        entering_epoch: Epoch,
+        exit_epoch: Epoch,
+    ) -> BigUint {
+        let farming_epochs = exit_epoch.saturating_sub(entering_epoch);
+        let penalty_percent = self.get_penalty_percent_for_farming_epochs(farming_epochs);
+
+        exit_amount * penalty_percent / MAX_PERCENT
+    }
+
+    /// Returns the penalty for exiting with the given position now,
+    /// and the penalty for exiting after `epochs_from_now` epochs.
+    #[view(getExitPenaltyForGivenPosition)]
+    fn get_exit_penalty_for_given_position(
+        &self,
+        farm_token_amount: BigUint,
+        attributes: FarmTokenAttributes<Self::Api>,
+        epochs_from_now: Epoch,
+    ) -> MultiValue2<BigUint, BigUint> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let current_penalty = self.calculate_exit_penalty(
+            &farm_token_amount,
+            attributes.entering_epoch,
+            current_epoch,
+        );
+        let future_penalty = self.calculate_exit_penalty(
+            &farm_token_amount,
+            attributes.entering_epoch,
+            current_epoch + epochs_from_now,
+        );
+
+        (current_penalty, future_penalty).into()
+    }
+
     fn burn_farming_tokens(
         &self,
         farming_amount: &BigUint,
@@ -66,6 +188,19 @@ pub trait ExitPenaltyModule: permissions_module::PermissionsModule {
     #[storage_mapper("minimum_farming_epochs")]
     fn minimum_farming_epochs(&self) -> SingleValueMapper<Epoch>;
 
+    #[view(getPenaltyCurve)]
+    #[storage_mapper("penalty_curve")]
+    fn penalty_curve(&self) -> SingleValueMapper<ManagedVec<PenaltyCurvePoint>>;
+
+    #[view(isPenaltyRedistributed)]
+    #[storage_mapper("redistribute_penalty")]
+    fn redistribute_penalty(&self) -> SingleValueMapper<bool>;
+
+    /// Redistributed penalties which were not yet added to the remaining positions.
+    #[view(getUndistributedPenalty)]
+    #[storage_mapper("undistributed_penalty")]
+    fn undistributed_penalty(&self) -> SingleValueMapper<BigUint>;
+
     #[view(getBurnGasLimit)]
     #[storage_mapper("burn_gas_limit")]
     fn burn_gas_limit(&self) -> SingleValueMapper<u64>;
@@ -5,22 +5,49 @@ multiversx_sc::derive_imports!();
 
 pub mod base_functions;
 pub mod exit_penalty;
+pub mod reward_streams;
 
-use base_functions::{ClaimRewardsResultType, DoubleMultiPayment, Wrapper};
-use common_structs::FarmTokenAttributes;
+use base_functions::{DoubleMultiPayment, Wrapper};
+use common_structs::{Epoch, FarmTokenAttributes, Nonce, PaymentsVec};
 use contexts::storage_cache::StorageCache;
 
 use exit_penalty::{
     DEFAULT_BURN_GAS_LIMIT, DEFAULT_MINUMUM_FARMING_EPOCHS, DEFAULT_PENALTY_PERCENT,
 };
-use farm_base_impl::base_traits_impl::FarmContract;
+use farm_base_impl::base_traits_impl::{DefaultFarmWrapper, FarmContract};
+use farm_boosted_yields::UserBoostedYieldsInfo;
 use fixed_supply_token::FixedSupplyToken;
+use rewards::{EmissionDecayCurve, EmissionSchedule, EmissionSegment};
 
 pub type EnterFarmResultType<M> = DoubleMultiPayment<M>;
 pub type ExitFarmWithPartialPosResultType<M> = DoubleMultiPayment<M>;
+pub type ClaimRewardsWithStreamsResultType<M> = MultiValue3<
+    EsdtTokenPayment<M>,
+    EsdtTokenPayment<M>,
+    MultiValueEncoded<M, EsdtTokenPayment<M>>,
+>;
+pub type ExitFarmWithStreamsResultType<M> = MultiValue3<
+    EsdtTokenPayment<M>,
+    EsdtTokenPayment<M>,
+    MultiValueEncoded<M, EsdtTokenPayment<M>>,
+>;
 
 pub const MAX_PERCENT: u64 = 10_000;
 
+/// The rewards of a farm position, as of the current block,
+/// and what the position owner would get by exiting now.
+#[type_abi]
+#[derive(TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct FarmPositionInfo<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub entering_epoch: Epoch,
+    pub farming_token_amount: BigUint<M>,
+    pub base_rewards: BigUint<M>,
+    pub reward_stream_rewards: PaymentsVec<M>,
+    pub exit_penalty: BigUint<M>,
+    pub owner_boosted_yields: UserBoostedYieldsInfo<M>,
+}
+
 #[multiversx_sc::contract]
 pub trait Farm:
     rewards::RewardsModule
@@ -34,6 +61,7 @@ pub trait Farm:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + base_functions::BaseFunctionsModule
     + exit_penalty::ExitPenaltyModule
+    + reward_streams::RewardStreamsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -107,7 +135,15 @@ pub trait Farm:
         let boosted_rewards_payment =
             EsdtTokenPayment::new(self.reward_token_id().get(), 0, boosted_rewards);
 
+        self.accrue_reward_streams();
+        let stream_rewards = self.claim_reward_streams(&self.call_value().all_esdt_transfers());
+
         let new_farm_token = self.enter_farm::<Wrapper<Self>>(orig_caller.clone());
+        self.set_reward_stream_position(
+            new_farm_token.token_nonce,
+            &new_farm_token.amount,
+            &stream_rewards,
+        );
         self.send_payment_non_zero(&caller, &new_farm_token);
         self.send_payment_non_zero(&caller, &boosted_rewards_payment);
 
@@ -121,18 +157,32 @@ pub trait Farm:
     fn claim_rewards_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ClaimRewardsResultType<Self::Api> {
+    ) -> ClaimRewardsWithStreamsResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
         self.migrate_old_farm_positions(&orig_caller);
 
-        let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(orig_caller);
+        self.accrue_reward_streams();
+        let stream_rewards = self.claim_reward_streams(&self.call_value().all_esdt_transfers());
+
+        let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(orig_caller.clone());
+        self.set_reward_stream_position(
+            claim_rewards_result.new_farm_token.token_nonce,
+            &claim_rewards_result.new_farm_token.amount,
+            &ManagedVec::new(),
+        );
 
         self.send_payment_non_zero(&caller, &claim_rewards_result.new_farm_token);
         self.send_payment_non_zero(&caller, &claim_rewards_result.rewards);
+        self.send_multiple_tokens_if_not_zero(&caller, &stream_rewards);
 
-        claim_rewards_result.into()
+        (
+            claim_rewards_result.new_farm_token,
+            claim_rewards_result.rewards,
+            stream_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -146,7 +196,15 @@ pub trait Farm:
 
         self.migrate_old_farm_positions(&orig_caller);
 
+        self.accrue_reward_streams();
+        let stream_rewards = self.claim_reward_streams(&self.call_value().all_esdt_transfers());
+
         let output_farm_token_payment = self.compound_rewards::<Wrapper<Self>>(orig_caller.clone());
+        self.set_reward_stream_position(
+            output_farm_token_payment.token_nonce,
+            &output_farm_token_payment.amount,
+            &stream_rewards,
+        );
 
         self.send_payment_non_zero(&caller, &output_farm_token_payment);
 
@@ -160,7 +218,7 @@ pub trait Farm:
     fn exit_farm_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+    ) -> ExitFarmWithStreamsResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
@@ -168,16 +226,27 @@ pub trait Farm:
 
         let migrated_amount = self.migrate_old_farm_positions(&orig_caller);
 
+        self.accrue_reward_streams();
+        let stream_rewards =
+            self.claim_reward_streams(&ManagedVec::from_single_item(payment.clone()));
+
         let exit_farm_result = self.exit_farm::<Wrapper<Self>>(orig_caller.clone(), &payment);
+        self.distribute_exit_penalty();
 
         self.decrease_old_farm_positions(migrated_amount, &orig_caller);
 
         self.send_payment_non_zero(&caller, &exit_farm_result.farming_tokens);
         self.send_payment_non_zero(&caller, &exit_farm_result.rewards);
+        self.send_multiple_tokens_if_not_zero(&caller, &stream_rewards);
 
         self.clear_user_energy_if_needed(&orig_caller);
 
-        (exit_farm_result.farming_tokens, exit_farm_result.rewards).into()
+        (
+            exit_farm_result.farming_tokens,
+            exit_farm_result.rewards,
+            stream_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -194,7 +263,15 @@ pub trait Farm:
         let boosted_rewards_payment =
             EsdtTokenPayment::new(self.reward_token_id().get(), 0, boosted_rewards);
 
-        let merged_farm_token = self.merge_and_update_farm_tokens(orig_caller);
+        self.accrue_reward_streams();
+        let stream_rewards = self.claim_reward_streams(&self.call_value().all_esdt_transfers());
+
+        let merged_farm_token = self.merge_and_update_farm_tokens(orig_caller.clone());
+        self.set_reward_stream_position(
+            merged_farm_token.token_nonce,
+            &merged_farm_token.amount,
+            &stream_rewards,
+        );
 
         self.send_payment_non_zero(&caller, &merged_farm_token);
         self.send_payment_non_zero(&caller, &boosted_rewards_payment);
@@ -266,6 +343,61 @@ pub trait Farm:
         self.set_per_block_rewards::<Wrapper<Self>>(per_block_amount);
     }
 
+    /// Pairs of (start block, per block amount), sorted by start block.
+    #[endpoint(setEmissionSegments)]
+    fn set_emission_segments_endpoint(
+        &self,
+        segments: MultiValueEncoded<MultiValue2<Nonce, BigUint>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+
+        let mut emission_segments = ManagedVec::new();
+        for segment in segments {
+            let (start_block, per_block_amount) = segment.into_tuple();
+            emission_segments.push(EmissionSegment {
+                start_block,
+                per_block_amount,
+            });
+        }
+        self.update_emission_schedule::<Wrapper<Self>>(Some(EmissionSchedule::Segments(
+            emission_segments,
+        )));
+    }
+
+    #[endpoint(setEmissionDecayCurve)]
+    fn set_emission_decay_curve_endpoint(
+        &self,
+        start_block: Nonce,
+        initial_per_block_amount: BigUint,
+        period_blocks: Nonce,
+        decay_percent: u64,
+    ) {
+        self.require_caller_has_admin_permissions();
+
+        let curve = EmissionDecayCurve {
+            start_block,
+            initial_per_block_amount,
+            period_blocks,
+            decay_percent,
+        };
+        self.update_emission_schedule::<Wrapper<Self>>(Some(EmissionSchedule::DecayCurve(curve)));
+    }
+
+    #[endpoint(clearEmissionSchedule)]
+    fn clear_emission_schedule_endpoint(&self) {
+        self.require_caller_has_admin_permissions();
+        self.update_emission_schedule::<Wrapper<Self>>(None);
+    }
+
+    /// Instead of being burned, the exit penalties are paid out
+    /// to the remaining positions, as farming tokens.
+    /// The penalty of the last position leaving the farm is still burned.
+    #[endpoint(setRedistributePenalty)]
+    fn set_redistribute_penalty(&self, redistribute: bool) {
+        self.require_caller_has_admin_permissions();
+        self.redistribute_penalty().set(redistribute);
+    }
+
     #[endpoint(setBoostedYieldsRewardsPercentage)]
     fn set_boosted_yields_rewards_percentage(&self, percentage: u64) {
         self.require_caller_has_admin_permissions();
@@ -297,4 +429,79 @@ pub trait Farm:
             &storage_cache,
         )
     }
+
+    /// The base rewards and the stream rewards of the given farm token,
+    /// as returned by `claimRewards` and `exitFarm`.
+    #[view(calculateRewardsForGivenNonce)]
+    fn calculate_rewards_for_given_nonce(
+        &self,
+        farm_token_nonce: Nonce,
+        farm_token_amount: BigUint,
+    ) -> MultiValue2<BigUint, MultiValueEncoded<EsdtTokenPayment>> {
+        self.require_queried();
+
+        let mut storage_cache = StorageCache::new(self);
+        Wrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let token_attributes: FarmTokenAttributes<Self::Api> =
+            self.farm_token().get_token_attributes(farm_token_nonce);
+        let attributes = token_attributes.into_part(&farm_token_amount);
+        let base_rewards = Wrapper::<Self>::calculate_rewards(
+            self,
+            &attributes.original_owner,
+            &farm_token_amount,
+            &attributes,
+            &storage_cache,
+        );
+        let stream_rewards =
+            self.calculate_reward_streams_for_position(farm_token_nonce, &farm_token_amount);
+
+        (base_rewards, stream_rewards.into()).into()
+    }
+
+    /// The attributes are read from the farm token. The boosted rewards are the ones
+    /// of the position owner, which are not tied to any single position.
+    #[view(getFarmPositionInfo)]
+    fn get_farm_position_info(
+        &self,
+        farm_token_nonce: Nonce,
+        farm_token_amount: BigUint,
+    ) -> FarmPositionInfo<Self::Api> {
+        self.require_queried();
+
+        let mut storage_cache = StorageCache::new(self);
+        Wrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let token_attributes: FarmTokenAttributes<Self::Api> =
+            self.farm_token().get_token_attributes(farm_token_nonce);
+        let attributes = token_attributes.into_part(&farm_token_amount);
+        let owner = attributes.original_owner.clone();
+
+        let base_rewards = DefaultFarmWrapper::<Self>::calculate_rewards(
+            self,
+            &owner,
+            &farm_token_amount,
+            &attributes,
+            &storage_cache,
+        );
+        let reward_stream_rewards =
+            self.calculate_reward_streams_for_position(farm_token_nonce, &farm_token_amount);
+        let farming_token_amount = attributes.get_total_supply();
+        let exit_penalty = self.calculate_exit_penalty(
+            &farming_token_amount,
+            attributes.entering_epoch,
+            self.blockchain().get_block_epoch(),
+        );
+        let owner_boosted_yields = self.get_user_boosted_yields_info(&owner);
+
+        FarmPositionInfo {
+            owner,
+            entering_epoch: attributes.entering_epoch,
+            farming_token_amount,
+            base_rewards,
+            reward_stream_rewards,
+            exit_penalty,
+            owner_boosted_yields,
+        }
+    }
 }
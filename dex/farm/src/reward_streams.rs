@@ -0,0 +1,488 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_errors::{ERROR_PARAMETERS, ERROR_ZERO_AMOUNT};
+use common_structs::{Nonce, PaymentsVec};
+
+pub const MAX_REWARD_STREAMS: usize = 5;
+pub const MAX_ENDED_REWARD_STREAMS: usize = 5;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct RewardStream<M: ManagedTypeApi> {
+    pub id: u64,
+    pub per_block_amount: BigUint<M>,
+    pub start_block: Nonce,
+    pub end_block: Nonce,
+    pub last_update_block: Nonce,
+    pub reserve: BigUint<M>,
+    pub reward_per_share: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(
+    ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug,
+)]
+pub struct RewardStreamCheckpoint<M: ManagedTypeApi> {
+    pub stream_id: u64,
+    pub reward_per_share: BigUint<M>,
+}
+
+/// The reward per share of each stream when the farm token nonce was created,
+/// and the amount of the nonce which was not yet burned.
+#[type_abi]
+#[derive(TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct RewardStreamPosition<M: ManagedTypeApi> {
+    pub remaining_amount: BigUint<M>,
+    pub checkpoints: ManagedVec<M, RewardStreamCheckpoint<M>>,
+}
+
+/// Reward tokens distributed on top of the farm's own reward token, usually funded by
+/// partner projects. Each stream pays its per block amount between its start and end
+/// blocks, for as long as its reserve lasts.
+///
+/// The farm token attributes only hold the reward per share of the main reward token,
+/// so the reward per share of each stream is kept per farm token nonce instead, until the
+/// whole nonce is burned. `claimRewards` and `exitFarm` send the stream rewards of the farm
+/// tokens to the caller and return them after the farm tokens and the base rewards. The other
+/// endpoints carry them over to the farm tokens they create, by lowering their reward per share.
+///
+/// A removed stream is kept as an ended stream, without any per block amount or reserve,
+/// so that the positions created before can still claim what the stream paid them.
+/// Adding a stream for its token again resumes it.
+///
+/// The redistributed exit penalties are paid out as a stream of the farming token,
+/// without any schedule, which does not count towards `MAX_REWARD_STREAMS`.
+#[multiversx_sc::module]
+pub trait RewardStreamsModule:
+    config::ConfigModule
+    + farm_token::FarmTokenModule
+    + token_send::TokenSendModule
+    + pausable::PausableModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + crate::exit_penalty::ExitPenaltyModule
+{
+    #[endpoint(addRewardStream)]
+    fn add_reward_stream(
+        &self,
+        token_id: TokenIdentifier,
+        per_block_amount: BigUint,
+        start_block: Nonce,
+        end_block: Nonce,
+    ) {
+        self.require_caller_has_admin_permissions();
+        require!(token_id.is_valid_esdt_identifier(), ERROR_PARAMETERS);
+        require!(
+            token_id != self.reward_token_id().get()
+                && token_id != self.farming_token_id().get()
+                && token_id != self.farm_token().get_token_id(),
+            "Invalid reward stream token"
+        );
+        self.require_valid_schedule(&per_block_amount, start_block, end_block);
+
+        let mut reward_streams = self.reward_streams();
+        let current_block = self.blockchain().get_block_nonce();
+        if let Some(mut reward_stream) = reward_streams.get(&token_id) {
+            require!(
+                self.is_ended_reward_stream(&reward_stream),
+                "Reward stream already exists"
+            );
+            require!(
+                self.count_active_reward_streams() < MAX_REWARD_STREAMS,
+                "Too many reward streams"
+            );
+
+            self.accrue_reward_streams();
+            reward_stream.per_block_amount = per_block_amount;
+            reward_stream.start_block = start_block;
+            reward_stream.end_block = end_block;
+            reward_stream.last_update_block = current_block;
+            let _ = reward_streams.insert(token_id, reward_stream);
+            return;
+        }
+
+        let mut reward_streams_count = reward_streams.len();
+        if reward_streams.contains_key(&self.farming_token_id().get()) {
+            reward_streams_count -= 1;
+        }
+        require!(
+            self.count_active_reward_streams() < MAX_REWARD_STREAMS
+                && reward_streams_count < MAX_REWARD_STREAMS + MAX_ENDED_REWARD_STREAMS,
+            "Too many reward streams"
+        );
+
+        let reward_stream = RewardStream {
+            id: self.new_reward_stream_id(),
+            per_block_amount,
+            start_block,
+            end_block,
+            last_update_block: current_block,
+            reserve: BigUint::zero(),
+            reward_per_share: BigUint::zero(),
+        };
+        let _ = reward_streams.insert(token_id, reward_stream);
+    }
+
+    #[endpoint(setRewardStreamSchedule)]
+    fn set_reward_stream_schedule(
+        &self,
+        token_id: TokenIdentifier,
+        per_block_amount: BigUint,
+        start_block: Nonce,
+        end_block: Nonce,
+    ) {
+        self.require_caller_has_admin_permissions();
+        self.require_valid_schedule(&per_block_amount, start_block, end_block);
+        self.require_not_penalty_stream(&token_id);
+
+        self.accrue_reward_streams();
+        let mut reward_stream = self.get_active_reward_stream(&token_id);
+        reward_stream.per_block_amount = per_block_amount;
+        reward_stream.start_block = start_block;
+        reward_stream.end_block = end_block;
+        let _ = self.reward_streams().insert(token_id, reward_stream);
+    }
+
+    /// Sends the remaining reserve of the stream back to the caller and ends the stream.
+    /// The rewards the stream already paid stay in the farm for the positions to claim.
+    #[endpoint(removeRewardStream)]
+    fn remove_reward_stream(&self, token_id: TokenIdentifier) {
+        self.require_caller_has_admin_permissions();
+        self.require_not_penalty_stream(&token_id);
+
+        self.accrue_reward_streams();
+        let mut reward_stream = self.get_active_reward_stream(&token_id);
+        let reserve = core::mem::take(&mut reward_stream.reserve);
+        reward_stream.per_block_amount = BigUint::zero();
+        reward_stream.end_block = self.blockchain().get_block_nonce();
+        let _ = self
+            .reward_streams()
+            .insert(token_id.clone(), reward_stream);
+
+        let caller = self.blockchain().get_caller();
+        self.send_payment_non_zero(&caller, &EsdtTokenPayment::new(token_id, 0, reserve));
+    }
+
+    /// Anyone can add to the reserve of a reward stream.
+    #[payable("*")]
+    #[endpoint(fundRewardStream)]
+    fn fund_reward_stream(&self) {
+        let payment = self.call_value().single_esdt();
+        require!(payment.amount > 0, ERROR_ZERO_AMOUNT);
+        self.require_not_penalty_stream(&payment.token_identifier);
+
+        self.accrue_reward_streams();
+        let mut reward_stream = self.get_active_reward_stream(&payment.token_identifier);
+        reward_stream.reserve += &payment.amount;
+        let _ = self
+            .reward_streams()
+            .insert(payment.token_identifier.clone(), reward_stream);
+    }
+
+    fn require_valid_schedule(
+        &self,
+        per_block_amount: &BigUint,
+        start_block: Nonce,
+        end_block: Nonce,
+    ) {
+        require!(per_block_amount > &0, ERROR_ZERO_AMOUNT);
+        require!(start_block < end_block, ERROR_PARAMETERS);
+    }
+
+    fn require_not_penalty_stream(&self, token_id: &TokenIdentifier) {
+        require!(
+            token_id != &self.farming_token_id().get(),
+            "Invalid reward stream token"
+        );
+    }
+
+    fn get_reward_stream(&self, token_id: &TokenIdentifier) -> RewardStream<Self::Api> {
+        self.reward_streams()
+            .get(token_id)
+            .unwrap_or_else(|| sc_panic!("Unknown reward stream"))
+    }
+
+    fn get_active_reward_stream(&self, token_id: &TokenIdentifier) -> RewardStream<Self::Api> {
+        let reward_stream = self.get_reward_stream(token_id);
+        require!(
+            !self.is_ended_reward_stream(&reward_stream),
+            "Reward stream ended"
+        );
+
+        reward_stream
+    }
+
+    /// Only the penalty stream and the ended streams have no per block amount.
+    fn is_ended_reward_stream(&self, reward_stream: &RewardStream<Self::Api>) -> bool {
+        reward_stream.per_block_amount == 0
+    }
+
+    fn count_active_reward_streams(&self) -> usize {
+        self.reward_streams()
+            .values()
+            .filter(|reward_stream| !self.is_ended_reward_stream(reward_stream))
+            .count()
+    }
+
+    /// Has to be called before any change of the farm token supply.
+    fn accrue_reward_streams(&self) {
+        let mut reward_streams = self.reward_streams();
+        if reward_streams.is_empty() {
+            return;
+        }
+
+        let farm_token_supply = self.farm_token_supply().get();
+        let division_safety_constant = self.division_safety_constant().get();
+        let current_block = self.blockchain().get_block_nonce();
+        let token_ids: ManagedVec<TokenIdentifier> = reward_streams.keys().collect();
+        for token_id in token_ids.into_iter() {
+            let mut reward_stream = self.get_reward_stream(&token_id);
+            self.accrue_reward_stream(
+                &mut reward_stream,
+                &farm_token_supply,
+                &division_safety_constant,
+                current_block,
+            );
+            let _ = reward_streams.insert(token_id, reward_stream);
+        }
+    }
+
+    /// The blocks without any farm token supply are skipped, their rewards stay in the reserve.
+    fn accrue_reward_stream(
+        &self,
+        reward_stream: &mut RewardStream<Self::Api>,
+        farm_token_supply: &BigUint,
+        division_safety_constant: &BigUint,
+        current_block: Nonce,
+    ) {
+        let from_block = core::cmp::max(reward_stream.last_update_block, reward_stream.start_block);
+        let to_block = core::cmp::min(current_block, reward_stream.end_block);
+        if current_block > reward_stream.last_update_block {
+            reward_stream.last_update_block = current_block;
+        }
+        if to_block <= from_block || farm_token_supply == &0 {
+            return;
+        }
+
+        let mut amount = &reward_stream.per_block_amount * (to_block - from_block);
+        if amount > reward_stream.reserve {
+            amount = reward_stream.reserve.clone();
+        }
+        if amount == 0 {
+            return;
+        }
+
+        reward_stream.reserve -= &amount;
+        reward_stream.reward_per_share += amount * division_safety_constant / farm_token_supply;
+    }
+
+    /// Has to be called after the exiting positions are removed from the farm token supply.
+    /// The penalty is only kept for redistribution while there is some farm token supply left.
+    fn distribute_exit_penalty(&self) {
+        let undistributed_penalty_mapper = self.undistributed_penalty();
+        let undistributed_penalty = undistributed_penalty_mapper.get();
+        let farm_token_supply = self.farm_token_supply().get();
+        if undistributed_penalty == 0 || farm_token_supply == 0 {
+            return;
+        }
+
+        let farming_token_id = self.farming_token_id().get();
+        let mut reward_streams = self.reward_streams();
+        let mut penalty_stream = match reward_streams.get(&farming_token_id) {
+            Some(penalty_stream) => penalty_stream,
+            None => RewardStream {
+                id: self.new_reward_stream_id(),
+                per_block_amount: BigUint::zero(),
+                start_block: 0,
+                end_block: 0,
+                last_update_block: self.blockchain().get_block_nonce(),
+                reserve: BigUint::zero(),
+                reward_per_share: BigUint::zero(),
+            },
+        };
+        let division_safety_constant = self.division_safety_constant().get();
+        penalty_stream.reward_per_share +=
+            undistributed_penalty * division_safety_constant / farm_token_supply;
+        let _ = reward_streams.insert(farming_token_id, penalty_stream);
+
+        undistributed_penalty_mapper.clear();
+    }
+
+    /// Computes the stream rewards of the farm tokens among the payments, which are burned.
+    /// The streams have to be accrued beforehand.
+    fn claim_reward_streams(&self, payments: &PaymentsVec<Self::Api>) -> PaymentsVec<Self::Api> {
+        let mut rewards = ManagedVec::new();
+        let farm_token_id = self.farm_token().get_token_id();
+        for payment in payments.iter() {
+            if payment.token_identifier != farm_token_id {
+                continue;
+            }
+
+            let position_rewards =
+                self.calculate_reward_streams_for_position(payment.token_nonce, &payment.amount);
+            for reward in position_rewards.iter() {
+                add_reward(&mut rewards, reward.clone());
+            }
+
+            self.burn_reward_stream_position(payment.token_nonce, &payment.amount);
+        }
+
+        rewards
+    }
+
+    fn burn_reward_stream_position(&self, farm_token_nonce: Nonce, farm_token_amount: &BigUint) {
+        let position_mapper = self.reward_stream_position(farm_token_nonce);
+        if position_mapper.is_empty() {
+            return;
+        }
+
+        let mut position = position_mapper.get();
+        if farm_token_amount >= &position.remaining_amount {
+            position_mapper.clear();
+            return;
+        }
+
+        position.remaining_amount -= farm_token_amount;
+        position_mapper.set(position);
+    }
+
+    /// The carried rewards, claimed from the burned farm tokens, stay claimable
+    /// by the new farm token.
+    fn set_reward_stream_position(
+        &self,
+        farm_token_nonce: Nonce,
+        farm_token_amount: &BigUint,
+        carried_rewards: &PaymentsVec<Self::Api>,
+    ) {
+        let reward_streams = self.reward_streams();
+        if reward_streams.is_empty() {
+            return;
+        }
+
+        let division_safety_constant = self.division_safety_constant().get();
+        let mut checkpoints = ManagedVec::new();
+        for (token_id, reward_stream) in reward_streams.iter() {
+            let mut reward_per_share = reward_stream.reward_per_share;
+            if let Some(carried_reward) = carried_rewards
+                .iter()
+                .find(|reward| reward.token_identifier == token_id)
+            {
+                reward_per_share -=
+                    &carried_reward.amount * &division_safety_constant / farm_token_amount;
+            }
+
+            checkpoints.push(RewardStreamCheckpoint {
+                stream_id: reward_stream.id,
+                reward_per_share,
+            });
+        }
+        self.reward_stream_position(farm_token_nonce)
+            .set(RewardStreamPosition {
+                remaining_amount: farm_token_amount.clone(),
+                checkpoints,
+            });
+    }
+
+    /// Stream rewards for the given farm token, as of the current block.
+    /// The streams added after the nonce was created are counted from their start.
+    fn calculate_reward_streams_for_position(
+        &self,
+        farm_token_nonce: Nonce,
+        farm_token_amount: &BigUint,
+    ) -> PaymentsVec<Self::Api> {
+        let mut rewards = ManagedVec::new();
+        let reward_streams = self.reward_streams();
+        if reward_streams.is_empty() {
+            return rewards;
+        }
+
+        let farm_token_supply = self.farm_token_supply().get();
+        let division_safety_constant = self.division_safety_constant().get();
+        let current_block = self.blockchain().get_block_nonce();
+        let position_mapper = self.reward_stream_position(farm_token_nonce);
+        let checkpoints = if position_mapper.is_empty() {
+            ManagedVec::new()
+        } else {
+            position_mapper.get().checkpoints
+        };
+
+        for (token_id, mut reward_stream) in reward_streams.iter() {
+            self.accrue_reward_stream(
+                &mut reward_stream,
+                &farm_token_supply,
+                &division_safety_constant,
+                current_block,
+            );
+
+            let position_reward_per_share = checkpoints
+                .iter()
+                .find(|checkpoint| checkpoint.stream_id == reward_stream.id)
+                .map(|checkpoint| checkpoint.reward_per_share.clone())
+                .unwrap_or_default();
+            let amount = farm_token_amount
+                * &(&reward_stream.reward_per_share - &position_reward_per_share)
+                / &division_safety_constant;
+            if amount > 0 {
+                rewards.push(EsdtTokenPayment::new(token_id, 0, amount));
+            }
+        }
+
+        rewards
+    }
+
+    fn new_reward_stream_id(&self) -> u64 {
+        self.last_reward_stream_id().update(|last_id| {
+            *last_id += 1;
+            *last_id
+        })
+    }
+
+    #[view(getRewardStreamRewardsForPosition)]
+    fn get_reward_stream_rewards_for_position(
+        &self,
+        farm_token_nonce: Nonce,
+        farm_token_amount: BigUint,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        self.calculate_reward_streams_for_position(farm_token_nonce, &farm_token_amount)
+            .into()
+    }
+
+    #[view(getRewardStreams)]
+    fn get_reward_streams(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<TokenIdentifier, RewardStream<Self::Api>>> {
+        let mut result = MultiValueEncoded::new();
+        for (token_id, reward_stream) in self.reward_streams().iter() {
+            result.push((token_id, reward_stream).into());
+        }
+
+        result
+    }
+
+    #[storage_mapper("rewardStreams")]
+    fn reward_streams(&self) -> MapMapper<TokenIdentifier, RewardStream<Self::Api>>;
+
+    #[view(getRewardStreamPosition)]
+    #[storage_mapper("rewardStreamPosition")]
+    fn reward_stream_position(
+        &self,
+        farm_token_nonce: Nonce,
+    ) -> SingleValueMapper<RewardStreamPosition<Self::Api>>;
+
+    #[storage_mapper("lastRewardStreamId")]
+    fn last_reward_stream_id(&self) -> SingleValueMapper<u64>;
+}
+
+fn add_reward<M: ManagedTypeApi>(rewards: &mut PaymentsVec<M>, reward: EsdtTokenPayment<M>) {
+    for i in 0..rewards.len() {
+        let mut existing_reward = rewards.get(i).clone();
+        if existing_reward.token_identifier == reward.token_identifier {
+            existing_reward.amount += reward.amount;
+            let _ = rewards.set(i, existing_reward);
+            return;
+        }
+    }
+
+    rewards.push(reward);
+}
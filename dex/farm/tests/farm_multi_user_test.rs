@@ -1,9 +1,11 @@
 #![allow(deprecated)]
 
 use common_structs::FarmTokenAttributes;
+use multiversx_sc::types::MultiValueEncoded;
 use multiversx_sc_scenario::{managed_address, managed_biguint, rust_biguint, DebugApi};
 
 pub mod farm_setup;
+use farm::{exit_penalty::ExitPenaltyModule, Farm};
 use farm_boosted_yields::boosted_yields_factors::BoostedYieldsFactorsModule;
 use farm_boosted_yields::boosted_yields_factors::{BoostedYieldsConfig, BoostedYieldsFactors};
 use farm_setup::multi_user_farm_setup::*;
@@ -972,3 +974,185 @@ fn farm_claim_with_minimum_tokens() {
     farm_setup.check_remaining_boosted_rewards_to_distribute(2, 0);
     farm_setup.check_remaining_boosted_rewards_to_distribute(3, 0);
 }
+
+#[test]
+fn farm_exit_penalty_curve_redistribution_test() {
+    DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    // 10% penalty on entering, decreasing linearly to 0 after 10 epochs
+    let owner = farm_setup.owner.clone();
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            let mut points = MultiValueEncoded::new();
+            points.push((0u64, 1_000u64).into());
+            points.push((10u64, 0u64).into());
+            sc.set_penalty_curve(points);
+            sc.set_redistribute_penalty(true);
+        })
+        .assert_ok();
+    farm_setup
+        .b_mock
+        .execute_tx(&owner, &farm_setup.farm_wrapper, &rust_biguint!(0), |sc| {
+            let mut points = MultiValueEncoded::new();
+            points.push((0u64, 1_000u64).into());
+            points.push((5u64, 2_000u64).into());
+            points.push((10u64, 0u64).into());
+            sc.set_penalty_curve(points);
+        })
+        .assert_user_error("Invalid penalty curve");
+
+    let farm_token_amount = 100_000_000;
+    let first_user = farm_setup.first_user.clone();
+    let second_user = farm_setup.second_user.clone();
+    farm_setup.enter_farm(&first_user, farm_token_amount);
+    farm_setup.enter_farm(&second_user, farm_token_amount);
+
+    // 6% penalty now, 3% in 3 epochs
+    farm_setup.b_mock.set_block_epoch(4);
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let attributes = FarmTokenAttributes {
+                reward_per_share: managed_biguint!(0),
+                entering_epoch: 0,
+                compounded_reward: managed_biguint!(0),
+                current_farm_amount: managed_biguint!(farm_token_amount),
+                original_owner: managed_address!(&first_user),
+            };
+            let (current_penalty, future_penalty) = sc
+                .get_exit_penalty_for_given_position(
+                    managed_biguint!(farm_token_amount),
+                    attributes,
+                    3,
+                )
+                .into_tuple();
+            assert_eq!(current_penalty, managed_biguint!(6_000_000));
+            assert_eq!(future_penalty, managed_biguint!(3_000_000));
+        })
+        .assert_ok();
+
+    // the penalty of the first user goes to the second user
+    farm_setup.exit_farm(&first_user, 1, farm_token_amount);
+    farm_setup.b_mock.check_esdt_balance(
+        &first_user,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(FARMING_TOKEN_BALANCE - 6_000_000),
+    );
+
+    let _ = farm_setup.claim_rewards(&second_user, 2, farm_token_amount);
+    farm_setup.b_mock.check_esdt_balance(
+        &second_user,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(FARMING_TOKEN_BALANCE - farm_token_amount + 6_000_000),
+    );
+
+    // no penalty left after the last curve point
+    farm_setup.b_mock.set_block_epoch(10);
+    farm_setup.exit_farm(&second_user, 3, farm_token_amount);
+    farm_setup.b_mock.check_esdt_balance(
+        &second_user,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(FARMING_TOKEN_BALANCE + 6_000_000),
+    );
+
+    // the penalty of the last position is burned instead of waiting for the next one
+    let third_user = farm_setup.third_user.clone();
+    farm_setup.enter_farm(&third_user, farm_token_amount);
+    farm_setup.exit_farm(&third_user, 4, farm_token_amount);
+    farm_setup.b_mock.check_esdt_balance(
+        &third_user,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(FARMING_TOKEN_BALANCE - 10_000_000),
+    );
+    farm_setup.b_mock.check_esdt_balance(
+        farm_setup.farm_wrapper.address_ref(),
+        FARMING_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(sc.undistributed_penalty().get(), managed_biguint!(0));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn farm_position_info_test() {
+    DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory_mock::contract_obj,
+        energy_update::contract_obj,
+    );
+
+    farm_setup.set_boosted_yields_rewards_percentage(BOOSTED_YIELDS_PERCENTAGE);
+    farm_setup.set_boosted_yields_factors();
+    farm_setup.b_mock.set_block_epoch(2);
+
+    let first_farm_token_amount = 100_000_000;
+    let second_farm_token_amount = 50_000_000;
+    let first_user = farm_setup.first_user.clone();
+    let second_user = farm_setup.second_user.clone();
+    let third_user = farm_setup.third_user.clone();
+    farm_setup.set_user_energy(&first_user, 1_000, 2, 1);
+    farm_setup.enter_farm(&first_user, first_farm_token_amount);
+    farm_setup.set_user_energy(&second_user, 4_000, 2, 1);
+    farm_setup.enter_farm(&second_user, second_farm_token_amount);
+
+    let _ = farm_setup.claim_rewards(&first_user, 1, first_farm_token_amount);
+    let _ = farm_setup.claim_rewards(&second_user, 2, second_farm_token_amount);
+
+    // 7_500 base farm rewards, 2_500 boosted yields rewards
+    farm_setup.b_mock.set_block_nonce(10);
+    farm_setup.b_mock.set_block_epoch(6);
+    farm_setup.set_user_energy(&first_user, 1_000, 6, 1);
+    farm_setup.set_user_energy(&second_user, 4_000, 6, 1);
+    farm_setup.set_user_energy(&third_user, 1, 6, 1);
+    farm_setup.enter_farm(&third_user, 1);
+    farm_setup.exit_farm(&third_user, 5, 1);
+
+    farm_setup.b_mock.set_block_epoch(10);
+    farm_setup.set_user_energy(&first_user, 1_000, 10, 1);
+
+    farm_setup
+        .b_mock
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let position_info =
+                sc.get_farm_position_info(3, managed_biguint!(first_farm_token_amount));
+            assert_eq!(position_info.owner, managed_address!(&first_user));
+            assert_eq!(position_info.entering_epoch, 2);
+            assert_eq!(
+                position_info.farming_token_amount,
+                managed_biguint!(first_farm_token_amount)
+            );
+            assert_eq!(position_info.base_rewards, managed_biguint!(5_000));
+            assert_eq!(position_info.exit_penalty, managed_biguint!(0));
+
+            let boosted_yields = position_info.owner_boosted_yields;
+            assert_eq!(boosted_yields.energy, managed_biguint!(1_000));
+            assert_eq!(
+                boosted_yields.total_farm_position,
+                managed_biguint!(first_farm_token_amount)
+            );
+            assert_eq!(boosted_yields.rewards_per_week.len(), 1);
+            let week_rewards = boosted_yields.rewards_per_week.get(0);
+            assert_eq!(week_rewards.week, 1);
+            assert_eq!(week_rewards.amount, managed_biguint!(966));
+            assert_eq!(
+                boosted_yields.factors.unwrap().max_rewards_factor,
+                managed_biguint!(MAX_REWARDS_FACTOR)
+            );
+        })
+        .assert_ok();
+
+    // the view matches the claim
+    let received_reward_amt = farm_setup.claim_rewards(&first_user, 3, first_farm_token_amount);
+    assert_eq!(received_reward_amt, 5_000 + 966);
+}
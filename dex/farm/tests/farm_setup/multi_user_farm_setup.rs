@@ -424,7 +424,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (out_farm_token, out_reward_token) =
+                    let (out_farm_token, out_reward_token, _) =
                         sc.claim_rewards_endpoint(OptionalValue::None).into_tuple();
                     assert_eq!(
                         out_farm_token.token_identifier,
@@ -470,7 +470,7 @@ where
         let mut result = 0;
         self.b_mock
             .execute_esdt_multi_transfer(user, &self.farm_wrapper, &payments, |sc| {
-                let (out_farm_token, out_reward_token) =
+                let (out_farm_token, out_reward_token, _) =
                     sc.claim_rewards_endpoint(OptionalValue::None).into_tuple();
                 assert_eq!(
                     out_farm_token.token_identifier,
@@ -547,7 +547,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (out_farm_token, out_reward_token) = sc
+                    let (out_farm_token, out_reward_token, _) = sc
                         .claim_rewards_endpoint(OptionalValue::Some(managed_address!(user)))
                         .into_tuple();
                     assert_eq!(
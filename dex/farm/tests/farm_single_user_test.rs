@@ -2,13 +2,19 @@
 
 mod farm_setup;
 
+use common_structs::FarmTokenAttributes;
 use config::ConfigModule;
+use farm::{reward_streams::RewardStreamsModule, Farm};
 use farm_setup::single_user_farm_setup::*;
-use multiversx_sc::types::EsdtLocalRole;
+use multiversx_sc::{
+    codec::multi_types::OptionalValue,
+    types::{EsdtLocalRole, MultiValueEncoded},
+};
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, rust_biguint,
     whitebox_legacy::TxTokenTransfer, DebugApi,
 };
+use rewards::RewardsModule;
 use sc_whitelist_module::SCWhitelistModule;
 
 #[test]
@@ -209,6 +215,7 @@ fn test_farm_through_simple_lock() {
     const LOCKED_TOKEN_ID: &[u8] = b"NOOOO-123456";
     const LOCKED_LP_TOKEN_ID: &[u8] = b"LKLP-123456";
     const FARM_PROXY_TOKEN_ID: &[u8] = b"PROXY-123456";
+    const PARTNER_TOKEN_ID: &[u8] = b"PARTNER-abcdef";
 
     DebugApi::dummy();
     let rust_zero = rust_biguint!(0);
@@ -254,6 +261,40 @@ fn test_farm_through_simple_lock() {
         )
         .assert_ok();
 
+    // the stream rewards are forwarded by the simple lock contract
+    b_mock.set_esdt_balance(
+        &farm_setup.owner_address,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(100_000),
+    );
+    b_mock
+        .execute_tx(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.add_reward_stream(
+                    managed_token_id!(PARTNER_TOKEN_ID),
+                    managed_biguint!(1_000),
+                    0,
+                    1_000,
+                );
+            },
+        )
+        .assert_ok();
+    b_mock
+        .execute_esdt_transfer(
+            &farm_setup.owner_address,
+            &farm_setup.farm_wrapper,
+            PARTNER_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                sc.fund_reward_stream();
+            },
+        )
+        .assert_ok();
+
     b_mock.set_esdt_local_roles(
         lock_wrapper.address_ref(),
         LOCKED_LP_TOKEN_ID,
@@ -385,6 +426,7 @@ fn test_farm_through_simple_lock() {
         MEX_TOKEN_ID,
         &rust_biguint!(10 * PER_BLOCK_REWARD_AMOUNT),
     );
+    b_mock.check_esdt_balance(&user_addr, PARTNER_TOKEN_ID, &rust_biguint!(10_000));
 
     // user exit farm
     b_mock.set_block_nonce(25);
@@ -432,6 +474,7 @@ fn test_farm_through_simple_lock() {
         MEX_TOKEN_ID,
         &rust_biguint!(25 * PER_BLOCK_REWARD_AMOUNT),
     );
+    b_mock.check_esdt_balance(&user_addr, PARTNER_TOKEN_ID, &rust_biguint!(25_000));
 
     // user enter farm again
     b_mock.set_block_epoch(0);
@@ -616,3 +659,366 @@ fn test_farm_through_simple_lock() {
         Some(&lp_proxy_token_attributes),
     );
 }
+
+#[test]
+fn test_reward_streams() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj, pair::contract_obj);
+    let partner_token_id = b"PARTNER-abcdef";
+    let owner_address = farm_setup.owner_address.clone();
+    let user_address = farm_setup.user_address.clone();
+
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        &owner_address,
+        partner_token_id,
+        &rust_biguint!(1_000_000),
+    );
+
+    // 1_000 tokens per block between the blocks 5 and 105, but only 50_000 funded
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_reward_stream(
+                    managed_token_id!(partner_token_id),
+                    managed_biguint!(1_000),
+                    5,
+                    105,
+                );
+            },
+        )
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            partner_token_id,
+            0,
+            &rust_biguint!(50_000),
+            |sc| {
+                sc.fund_reward_stream();
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    farm_setup.enter_farm(farm_in_amount, &[], 1, 0, 0, 0);
+
+    farm_setup.set_block_nonce(10);
+    let expected_mex_out = 10 * PER_BLOCK_REWARD_AMOUNT;
+    farm_setup.claim_rewards(
+        farm_in_amount,
+        1,
+        expected_mex_out,
+        &rust_biguint!(expected_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - farm_in_amount),
+        2,
+        500_000_000,
+    );
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &user_address,
+        partner_token_id,
+        &rust_biguint!(5_000),
+    );
+
+    // the stream ends when its reserve runs out
+    farm_setup.set_block_epoch(5);
+    farm_setup.set_block_nonce(200);
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let attributes = FarmTokenAttributes {
+                reward_per_share: managed_biguint!(500_000_000),
+                entering_epoch: 0,
+                compounded_reward: managed_biguint!(0),
+                current_farm_amount: managed_biguint!(farm_in_amount),
+                original_owner: managed_address!(&user_address),
+            };
+            let rewards = sc.calculate_rewards_for_given_position(
+                managed_address!(&user_address),
+                managed_biguint!(farm_in_amount),
+                attributes,
+            );
+            assert_eq!(rewards, managed_biguint!(190 * PER_BLOCK_REWARD_AMOUNT));
+
+            let (rewards, stream_rewards) = sc
+                .calculate_rewards_for_given_nonce(2, managed_biguint!(farm_in_amount))
+                .into_tuple();
+            assert_eq!(rewards, managed_biguint!(190 * PER_BLOCK_REWARD_AMOUNT));
+            let stream_rewards = stream_rewards.to_vec();
+            assert_eq!(stream_rewards.len(), 1);
+            let stream_reward = stream_rewards.get(0);
+            assert_eq!(
+                stream_reward.token_identifier,
+                managed_token_id!(partner_token_id)
+            );
+            assert_eq!(stream_reward.amount, managed_biguint!(45_000));
+        })
+        .assert_ok();
+
+    // entering with the farm token carries its stream rewards over to the new farm token
+    let payments = [
+        TxTokenTransfer {
+            token_identifier: LP_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(farm_in_amount),
+        },
+        TxTokenTransfer {
+            token_identifier: FARM_TOKEN_ID.to_vec(),
+            nonce: 2,
+            value: rust_biguint!(farm_in_amount),
+        },
+    ];
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_multi_transfer(&user_address, &farm_setup.farm_wrapper, &payments, |sc| {
+            let (out_farm_token, _) = sc.enter_farm_endpoint(OptionalValue::None).into_tuple();
+            assert_eq!(out_farm_token.token_nonce, 3);
+        })
+        .assert_ok();
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &user_address,
+        partner_token_id,
+        &rust_biguint!(5_000),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let (_, stream_rewards) = sc
+                .calculate_rewards_for_given_nonce(3, managed_biguint!(2 * farm_in_amount))
+                .into_tuple();
+            let stream_rewards = stream_rewards.to_vec();
+            assert_eq!(stream_rewards.len(), 1);
+            assert_eq!(stream_rewards.get(0).amount, managed_biguint!(45_000));
+        })
+        .assert_ok();
+
+    // removing the stream only returns its reserve, the rewards it already paid stay claimable
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            partner_token_id,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                sc.fund_reward_stream();
+            },
+        )
+        .assert_ok();
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.remove_reward_stream(managed_token_id!(partner_token_id));
+            },
+        )
+        .assert_ok();
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &owner_address,
+        partner_token_id,
+        &rust_biguint!(1_000_000 - 50_000),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            partner_token_id,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                sc.fund_reward_stream();
+            },
+        )
+        .assert_user_error("Reward stream ended");
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            3,
+            &rust_biguint!(2 * farm_in_amount),
+            |sc| {
+                let (_, _, stream_rewards) =
+                    sc.exit_farm_endpoint(OptionalValue::None).into_tuple();
+                let stream_rewards = stream_rewards.to_vec();
+                assert_eq!(stream_rewards.len(), 1);
+                let stream_reward = stream_rewards.get(0);
+                assert_eq!(
+                    stream_reward.token_identifier,
+                    managed_token_id!(partner_token_id)
+                );
+                assert_eq!(stream_reward.amount, managed_biguint!(45_000));
+            },
+        )
+        .assert_ok();
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &user_address,
+        partner_token_id,
+        &rust_biguint!(50_000),
+    );
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let (_, reward_stream) = sc
+                .get_reward_streams()
+                .into_iter()
+                .next()
+                .unwrap()
+                .into_tuple();
+            assert_eq!(reward_stream.per_block_amount, managed_biguint!(0));
+            assert_eq!(reward_stream.reserve, managed_biguint!(0));
+            assert_eq!(reward_stream.last_update_block, 200);
+
+            // all the farm token nonces were burned
+            assert!(sc.reward_stream_position(1).is_empty());
+            assert!(sc.reward_stream_position(2).is_empty());
+            assert!(sc.reward_stream_position(3).is_empty());
+        })
+        .assert_ok();
+
+    // adding the stream again resumes it
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_reward_stream(
+                    managed_token_id!(partner_token_id),
+                    managed_biguint!(1_000),
+                    300,
+                    400,
+                );
+                assert_eq!(sc.reward_streams().len(), 1);
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn test_emission_schedule() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj, pair::contract_obj);
+    let owner_address = farm_setup.owner_address.clone();
+
+    // flat amount until block 4, then double until block 7, then triple
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut segments = MultiValueEncoded::new();
+                segments.push((4u64, managed_biguint!(2 * PER_BLOCK_REWARD_AMOUNT)).into());
+                segments.push((7u64, managed_biguint!(3 * PER_BLOCK_REWARD_AMOUNT)).into());
+                sc.set_emission_segments_endpoint(segments);
+            },
+        )
+        .assert_ok();
+
+    let farm_in_amount = 100_000_000;
+    farm_setup.enter_farm(farm_in_amount, &[], 1, 0, 0, 0);
+
+    farm_setup.set_block_nonce(10);
+    let first_mex_out = (4 + 3 * 2 + 3 * 3) * PER_BLOCK_REWARD_AMOUNT;
+    farm_setup.claim_rewards(
+        farm_in_amount,
+        1,
+        first_mex_out,
+        &rust_biguint!(first_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - farm_in_amount),
+        2,
+        950_000_000,
+    );
+
+    // halving every 600 blocks, starting with 20_000 per block from block 12
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_emission_decay_curve_endpoint(12, managed_biguint!(20_000), 600, 5_000);
+            },
+        )
+        .assert_ok();
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_emission_decay_curve_endpoint(5, managed_biguint!(20_000), 600, 5_000);
+            },
+        )
+        .assert_user_error("Curve starts in the past");
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_emission_decay_curve_endpoint(12, managed_biguint!(20_000), 3, 5_000);
+            },
+        )
+        .assert_user_error("Decay period too short");
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_emission_decay_curve_endpoint(12, managed_biguint!(20_000), 600, 1);
+            },
+        )
+        .assert_user_error("Invalid decay percent");
+
+    farm_setup.set_block_nonce(1_512);
+    let second_mex_out = 2 * PER_BLOCK_REWARD_AMOUNT + 600 * 20_000 + 600 * 10_000 + 300 * 5_000;
+    farm_setup.claim_rewards(
+        farm_in_amount,
+        2,
+        second_mex_out,
+        &rust_biguint!(first_mex_out + second_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - farm_in_amount),
+        3,
+        196_050_000_000,
+    );
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            // 20_000 * 600 * (1 + 1/2 + 1/4 + ...)
+            assert_eq!(
+                sc.calculate_emission(12, 12 + 100 * 600),
+                managed_biguint!(2 * 600 * 20_000)
+            );
+            assert_eq!(
+                sc.calculate_emission(12, 1_000_000_000_000),
+                managed_biguint!(2 * 600 * 20_000)
+            );
+            assert_eq!(
+                sc.calculate_emission(912, 1_512),
+                managed_biguint!(300 * 10_000 + 300 * 5_000)
+            );
+        })
+        .assert_ok();
+}
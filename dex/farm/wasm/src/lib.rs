@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           63
+// Endpoints:                           83
 // Async Callback:                       1
-// Total number of exported functions:  66
+// Total number of exported functions:  86
 
 #![no_std]
 
@@ -29,8 +29,16 @@ multiversx_sc_wasm_adapter::endpoints! {
         startProduceRewards => start_produce_rewards_endpoint
         endProduceRewards => end_produce_rewards_endpoint
         setPerBlockRewardAmount => set_per_block_rewards_endpoint
+        setEmissionSegments => set_emission_segments_endpoint
+        setEmissionDecayCurve => set_emission_decay_curve_endpoint
+        clearEmissionSchedule => clear_emission_schedule_endpoint
+        setRedistributePenalty => set_redistribute_penalty
         setBoostedYieldsRewardsPercentage => set_boosted_yields_rewards_percentage
         calculateRewardsForGivenPosition => calculate_rewards_for_given_position
+        calculateRewardsForGivenNonce => calculate_rewards_for_given_nonce
+        getFarmPositionInfo => get_farm_position_info
+        getEmissionForBlocks => calculate_emission
+        getEmissionSchedule => emission_schedule
         getRewardPerShare => reward_per_share
         getRewardReserve => reward_reserve
         getFarmingTokenId => farming_token_id
@@ -59,10 +67,22 @@ multiversx_sc_wasm_adapter::endpoints! {
         set_penalty_percent => set_penalty_percent
         set_minimum_farming_epochs => set_minimum_farming_epochs
         set_burn_gas_limit => set_burn_gas_limit
+        setPenaltyCurve => set_penalty_curve
+        getExitPenaltyForGivenPosition => get_exit_penalty_for_given_position
         getPenaltyPercent => penalty_percent
         getMinimumFarmingEpoch => minimum_farming_epochs
+        getPenaltyCurve => penalty_curve
+        isPenaltyRedistributed => redistribute_penalty
+        getUndistributedPenalty => undistributed_penalty
         getBurnGasLimit => burn_gas_limit
         getPairContractManagedAddress => pair_contract_address
+        addRewardStream => add_reward_stream
+        setRewardStreamSchedule => set_reward_stream_schedule
+        removeRewardStream => remove_reward_stream
+        fundRewardStream => fund_reward_stream
+        getRewardStreamRewardsForPosition => get_reward_stream_rewards_for_position
+        getRewardStreams => get_reward_streams
+        getRewardStreamPosition => reward_stream_position
         collectUndistributedBoostedRewards => collect_undistributed_boosted_rewards
         getBoostedYieldsRewardsPercentage => boosted_yields_rewards_percentage
         getAccumulatedRewardsForWeek => accumulated_rewards_for_week
@@ -0,0 +1,60 @@
+multiversx_sc::imports!();
+
+use crate::errors::*;
+
+pub const MAX_PERCENTAGE: u64 = 100_000;
+pub const MAX_EXECUTOR_REWARD_PERCENT: u64 = 1_000;
+
+#[multiversx_sc::module]
+pub trait ConfigModule {
+    /// The share of each fill output paid to the executor, out of 100_000.
+    #[only_owner]
+    #[endpoint(setExecutorRewardPercent)]
+    fn set_executor_reward_percent_endpoint(&self, executor_reward_percent: u64) {
+        self.set_executor_reward_percent(executor_reward_percent);
+    }
+
+    #[only_owner]
+    #[endpoint(addPairs)]
+    fn add_pairs_endpoint(&self, pairs: MultiValueEncoded<ManagedAddress>) {
+        self.add_pairs(pairs);
+    }
+
+    /// Orders already placed on the removed pairs can still be cancelled or expire,
+    /// but are no longer filled.
+    #[only_owner]
+    #[endpoint(removePairs)]
+    fn remove_pairs(&self, pairs: MultiValueEncoded<ManagedAddress>) {
+        let mut pairs_mapper = self.pairs();
+        for pair_address in pairs {
+            let _ = pairs_mapper.swap_remove(&pair_address);
+        }
+    }
+
+    fn set_executor_reward_percent(&self, executor_reward_percent: u64) {
+        require!(
+            executor_reward_percent <= MAX_EXECUTOR_REWARD_PERCENT,
+            ERROR_BAD_PERCENTS
+        );
+        self.executor_reward_percent().set(executor_reward_percent);
+    }
+
+    fn add_pairs(&self, pairs: MultiValueEncoded<ManagedAddress>) {
+        let mut pairs_mapper = self.pairs();
+        for pair_address in pairs {
+            require!(
+                self.blockchain().is_smart_contract(&pair_address),
+                ERROR_NOT_A_SC
+            );
+            let _ = pairs_mapper.insert(pair_address);
+        }
+    }
+
+    #[view(getExecutorRewardPercent)]
+    #[storage_mapper("executor_reward_percent")]
+    fn executor_reward_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getPairs)]
+    #[storage_mapper("pairs")]
+    fn pairs(&self) -> UnorderedSetMapper<ManagedAddress>;
+}
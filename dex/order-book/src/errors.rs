@@ -0,0 +1,10 @@
+pub static ERROR_BAD_PERCENTS: &[u8] = b"Bad percents";
+pub static ERROR_NOT_A_SC: &[u8] = b"Not a smart contract";
+pub static ERROR_UNKNOWN_PAIR: &[u8] = b"Unknown pair";
+pub static ERROR_BAD_PAYMENT_TOKENS: &[u8] = b"Bad payment tokens";
+pub static ERROR_BAD_OUTPUT_TOKEN: &[u8] = b"Output token is not the other pair token";
+pub static ERROR_ZERO_LIMIT_PRICE: &[u8] = b"Limit price cannot be zero";
+pub static ERROR_ORDER_EXPIRED: &[u8] = b"Order already expired";
+pub static ERROR_BAD_TWAP_ARGS: &[u8] = b"Bad TWAP order arguments";
+pub static ERROR_ORDER_NOT_FOUND: &[u8] = b"Order not found";
+pub static ERROR_NOT_ORDER_CREATOR: &[u8] = b"Only the order creator can cancel it";
@@ -0,0 +1,111 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::orders::{Order, OrderId};
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct FillOrderEvent<M: ManagedTypeApi> {
+    executor: ManagedAddress<M>,
+    order: Order<M>,
+    amount_in: BigUint<M>,
+    amount_out: BigUint<M>,
+    executor_reward: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_create_order_event(&self, order_id: OrderId, order: Order<Self::Api>) {
+        self.create_order_event(
+            &order.creator.clone(),
+            &order.pair_address.clone(),
+            order_id,
+            &order,
+        );
+    }
+
+    fn emit_cancel_order_event(&self, order_id: OrderId, order: Order<Self::Api>) {
+        self.cancel_order_event(
+            &order.creator.clone(),
+            &order.pair_address.clone(),
+            order_id,
+            &order,
+        );
+    }
+
+    fn emit_expire_order_event(&self, order_id: OrderId, order: Order<Self::Api>) {
+        self.expire_order_event(
+            &order.creator.clone(),
+            &order.pair_address.clone(),
+            order_id,
+            &order,
+        );
+    }
+
+    fn emit_fill_order_event(
+        &self,
+        order_id: OrderId,
+        order: Order<Self::Api>,
+        amount_in: BigUint,
+        amount_out: BigUint,
+        executor_reward: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.fill_order_event(
+            &order.creator.clone(),
+            &order.pair_address.clone(),
+            order_id,
+            epoch,
+            &FillOrderEvent {
+                executor: self.blockchain().get_caller(),
+                order,
+                amount_in,
+                amount_out,
+                executor_reward,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    #[event("create_order")]
+    fn create_order_event(
+        &self,
+        #[indexed] creator: &ManagedAddress,
+        #[indexed] pair_address: &ManagedAddress,
+        #[indexed] order_id: OrderId,
+        order: &Order<Self::Api>,
+    );
+
+    #[event("cancel_order")]
+    fn cancel_order_event(
+        &self,
+        #[indexed] creator: &ManagedAddress,
+        #[indexed] pair_address: &ManagedAddress,
+        #[indexed] order_id: OrderId,
+        order: &Order<Self::Api>,
+    );
+
+    #[event("expire_order")]
+    fn expire_order_event(
+        &self,
+        #[indexed] creator: &ManagedAddress,
+        #[indexed] pair_address: &ManagedAddress,
+        #[indexed] order_id: OrderId,
+        order: &Order<Self::Api>,
+    );
+
+    #[event("fill_order")]
+    fn fill_order_event(
+        &self,
+        #[indexed] creator: &ManagedAddress,
+        #[indexed] pair_address: &ManagedAddress,
+        #[indexed] order_id: OrderId,
+        #[indexed] epoch: u64,
+        fill_order_event: &FillOrderEvent<Self::Api>,
+    );
+}
@@ -0,0 +1,165 @@
+multiversx_sc::imports!();
+
+use crate::{
+    config::{self, MAX_PERCENTAGE},
+    events,
+    orders::{self, Order, OrderId, PriceSource},
+};
+use pair::{
+    pair_actions::{swap::ProxyTrait as _, views::ProxyTrait as _},
+    read_pair_storage, safe_price_reader,
+};
+
+#[multiversx_sc::module]
+pub trait ExecutionModule:
+    config::ConfigModule
+    + events::EventsModule
+    + orders::OrdersModule
+    + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
+{
+    /// Fills the given orders, or their next TWAP slice, where the price condition is met
+    /// and closes the expired ones. Orders that cannot be filled yet are skipped, so that a
+    /// batch does not fail because of a single one. The executor receives a share of every
+    /// fill output, on top of the minimum the creator gets at the limit price. The output
+    /// is sent as returned by the pair, which may be a locked token.
+    #[endpoint(executeOrders)]
+    fn execute_orders(
+        &self,
+        order_ids: MultiValueEncoded<OrderId>,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let executor_reward_percent = self.executor_reward_percent().get();
+
+        let mut executor_rewards = ManagedVec::new();
+        for order_id in order_ids {
+            let order_mapper = self.orders(order_id);
+            if order_mapper.is_empty() {
+                continue;
+            }
+
+            let mut order = order_mapper.get();
+            if current_timestamp >= order.expiry_timestamp {
+                self.close_order(order_id, &order);
+                self.emit_expire_order_event(order_id, order);
+                continue;
+            }
+
+            if order.last_fill_timestamp + order.slice_interval > current_timestamp
+                || !self.pairs().contains(&order.pair_address)
+                || !self.is_pair_active(&order.pair_address)
+            {
+                continue;
+            }
+
+            let amount_in = order.get_next_fill_amount();
+            let creator_amount_min = order.get_min_amount_out(&amount_in);
+            if creator_amount_min == 0 {
+                continue;
+            }
+
+            let amount_out_min =
+                self.get_amount_out_min_with_reward(&creator_amount_min, executor_reward_percent);
+            if !self.is_price_reached(&order, &amount_in, &amount_out_min) {
+                continue;
+            }
+
+            let payment_out: EsdtTokenPayment = self
+                .order_pair_proxy(order.pair_address.clone())
+                .swap_tokens_fixed_input(order.output_token_id.clone(), amount_out_min)
+                .with_esdt_transfer((order.input_token_id.clone(), 0, amount_in.clone()))
+                .execute_on_dest_context();
+
+            let reward_amount = &payment_out.amount * executor_reward_percent / MAX_PERCENTAGE;
+            let creator_amount = &payment_out.amount - &reward_amount;
+            self.send().direct_esdt(
+                &order.creator,
+                &payment_out.token_identifier,
+                payment_out.token_nonce,
+                &creator_amount,
+            );
+            if reward_amount > 0 {
+                executor_rewards.push(EsdtTokenPayment::new(
+                    payment_out.token_identifier.clone(),
+                    payment_out.token_nonce,
+                    reward_amount.clone(),
+                ));
+            }
+
+            order.input_amount_left -= &amount_in;
+            order.last_fill_timestamp = current_timestamp;
+            if order.input_amount_left == 0 {
+                order_mapper.clear();
+                let _ = self.pair_orders(&order.pair_address).swap_remove(&order_id);
+            } else {
+                order_mapper.set(&order);
+            }
+
+            self.emit_fill_order_event(order_id, order, amount_in, creator_amount, reward_amount);
+        }
+
+        if !executor_rewards.is_empty() {
+            self.send().direct_multi(&caller, &executor_rewards);
+        }
+
+        executor_rewards
+    }
+
+    /// The swap output for which the creator still gets `creator_amount_min` once the
+    /// executor reward is taken out, rounded up.
+    fn get_amount_out_min_with_reward(
+        &self,
+        creator_amount_min: &BigUint,
+        executor_reward_percent: u64,
+    ) -> BigUint {
+        let creator_percent = MAX_PERCENTAGE - executor_reward_percent;
+        (creator_amount_min * MAX_PERCENTAGE + creator_percent - 1u64) / creator_percent
+    }
+
+    /// Safe price orders also need the spot price past the limit, since the swap itself
+    /// is done at the spot price. The pair is asked whether the swap would go through,
+    /// so that a failing swap does not revert the whole batch.
+    fn is_price_reached(
+        &self,
+        order: &Order<Self::Api>,
+        amount_in: &BigUint,
+        amount_out_min: &BigUint,
+    ) -> bool {
+        let pair_address = order.pair_address.clone();
+        let can_swap: bool = self
+            .order_pair_proxy(pair_address.clone())
+            .can_swap_fixed_input(
+                order.input_token_id.clone(),
+                amount_in.clone(),
+                amount_out_min.clone(),
+            )
+            .execute_on_dest_context();
+        if !can_swap {
+            return false;
+        }
+
+        match order.price_source {
+            PriceSource::Spot => true,
+            PriceSource::Safe => {
+                if !self.has_safe_price_observations(&pair_address) {
+                    return false;
+                }
+
+                let safe_price_payment = self.compute_safe_price_by_default_offset(
+                    &pair_address,
+                    EsdtTokenPayment::new(order.input_token_id.clone(), 0, amount_in.clone()),
+                );
+
+                &safe_price_payment.amount >= amount_out_min
+            }
+        }
+    }
+
+    fn is_pair_active(&self, pair_address: &ManagedAddress) -> bool {
+        self.get_pair_state_mapper(pair_address.clone()).get() == pausable::State::Active
+    }
+
+    #[proxy]
+    fn order_pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+}
@@ -0,0 +1,31 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod execution;
+pub mod orders;
+
+/// Escrows the input token of resting limit orders and of time-sliced (TWAP) orders,
+/// and fills them against the liquidity of the whitelisted pairs. Anyone can execute
+/// the orders once their price condition is met, for a share of the output.
+#[multiversx_sc::contract]
+pub trait OrderBook:
+    config::ConfigModule
+    + events::EventsModule
+    + orders::OrdersModule
+    + execution::ExecutionModule
+    + pair::read_pair_storage::ReadPairStorageModule
+    + pair::safe_price_reader::SafePriceReaderModule
+{
+    #[init]
+    fn init(&self, executor_reward_percent: u64, pairs: MultiValueEncoded<ManagedAddress>) {
+        self.set_executor_reward_percent(executor_reward_percent);
+        self.add_pairs(pairs);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
@@ -0,0 +1,213 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{config, errors::*, events};
+use pair::read_pair_storage;
+
+pub type OrderId = u64;
+pub type Timestamp = u64;
+
+/// Limit prices are amounts of output token per PRICE_PRECISION units of input token.
+pub const PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PriceSource {
+    Spot,
+    Safe,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Debug)]
+pub struct Order<M: ManagedTypeApi> {
+    pub creator: ManagedAddress<M>,
+    pub pair_address: ManagedAddress<M>,
+    pub input_token_id: TokenIdentifier<M>,
+    pub input_amount_left: BigUint<M>,
+    pub output_token_id: TokenIdentifier<M>,
+    pub limit_price: BigUint<M>,
+    pub price_source: PriceSource,
+    pub slice_amount: BigUint<M>,
+    pub slice_interval: Timestamp,
+    pub last_fill_timestamp: Timestamp,
+    pub expiry_timestamp: Timestamp,
+}
+
+impl<M: ManagedTypeApi> Order<M> {
+    pub fn get_next_fill_amount(&self) -> BigUint<M> {
+        core::cmp::min(&self.slice_amount, &self.input_amount_left).clone()
+    }
+
+    pub fn get_min_amount_out(&self, amount_in: &BigUint<M>) -> BigUint<M> {
+        amount_in * &self.limit_price / PRICE_PRECISION
+    }
+}
+
+#[multiversx_sc::module]
+pub trait OrdersModule:
+    config::ConfigModule + events::EventsModule + read_pair_storage::ReadPairStorageModule
+{
+    /// Sells the whole payment for `output_token_id` once the price given by `price_source`
+    /// reaches `limit_price`.
+    #[payable("*")]
+    #[endpoint(createLimitOrder)]
+    fn create_limit_order(
+        &self,
+        pair_address: ManagedAddress,
+        output_token_id: TokenIdentifier,
+        limit_price: BigUint,
+        price_source: PriceSource,
+        expiry_timestamp: Timestamp,
+    ) -> OrderId {
+        let payment = self.call_value().single_esdt().clone();
+        self.create_order(
+            pair_address,
+            output_token_id,
+            limit_price,
+            price_source,
+            payment.amount.clone(),
+            0,
+            expiry_timestamp,
+        )
+    }
+
+    /// Splits the payment in `slices` equal parts, filled at least `slice_interval` seconds
+    /// apart, each only while the price given by `price_source` is at or above `limit_price`.
+    #[payable("*")]
+    #[endpoint(createTwapOrder)]
+    fn create_twap_order(
+        &self,
+        pair_address: ManagedAddress,
+        output_token_id: TokenIdentifier,
+        limit_price: BigUint,
+        price_source: PriceSource,
+        slices: u64,
+        slice_interval: Timestamp,
+        expiry_timestamp: Timestamp,
+    ) -> OrderId {
+        require!(slices > 1 && slice_interval > 0, ERROR_BAD_TWAP_ARGS);
+
+        let payment = self.call_value().single_esdt().clone();
+        let slice_amount = (&payment.amount + slices - 1u64) / slices;
+        self.create_order(
+            pair_address,
+            output_token_id,
+            limit_price,
+            price_source,
+            slice_amount,
+            slice_interval,
+            expiry_timestamp,
+        )
+    }
+
+    #[endpoint(cancelOrder)]
+    fn cancel_order(&self, order_id: OrderId) {
+        let order_mapper = self.orders(order_id);
+        require!(!order_mapper.is_empty(), ERROR_ORDER_NOT_FOUND);
+
+        let order = order_mapper.get();
+        let caller = self.blockchain().get_caller();
+        require!(caller == order.creator, ERROR_NOT_ORDER_CREATOR);
+
+        self.close_order(order_id, &order);
+        self.emit_cancel_order_event(order_id, order);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_order(
+        &self,
+        pair_address: ManagedAddress,
+        output_token_id: TokenIdentifier,
+        limit_price: BigUint,
+        price_source: PriceSource,
+        slice_amount: BigUint,
+        slice_interval: Timestamp,
+        expiry_timestamp: Timestamp,
+    ) -> OrderId {
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            payment.token_nonce == 0 && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+        require!(self.pairs().contains(&pair_address), ERROR_UNKNOWN_PAIR);
+        require!(limit_price > 0, ERROR_ZERO_LIMIT_PRICE);
+        require!(
+            expiry_timestamp > self.blockchain().get_block_timestamp(),
+            ERROR_ORDER_EXPIRED
+        );
+
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+        let expected_output_token_id = if payment.token_identifier == first_token_id {
+            second_token_id
+        } else if payment.token_identifier == second_token_id {
+            first_token_id
+        } else {
+            sc_panic!(ERROR_BAD_PAYMENT_TOKENS);
+        };
+        require!(
+            output_token_id == expected_output_token_id,
+            ERROR_BAD_OUTPUT_TOKEN
+        );
+
+        let order_id = self.last_order_id().update(|last_order_id| {
+            *last_order_id += 1;
+            *last_order_id
+        });
+        let order = Order {
+            creator: self.blockchain().get_caller(),
+            pair_address,
+            input_token_id: payment.token_identifier,
+            input_amount_left: payment.amount,
+            output_token_id,
+            limit_price,
+            price_source,
+            slice_amount,
+            slice_interval,
+            last_fill_timestamp: 0,
+            expiry_timestamp,
+        };
+        let _ = self.pair_orders(&order.pair_address).insert(order_id);
+        self.orders(order_id).set(&order);
+
+        self.emit_create_order_event(order_id, order);
+
+        order_id
+    }
+
+    /// Sends the unfilled input back to the creator and removes the order.
+    fn close_order(&self, order_id: OrderId, order: &Order<Self::Api>) {
+        self.orders(order_id).clear();
+        let _ = self.pair_orders(&order.pair_address).swap_remove(&order_id);
+
+        self.send().direct_esdt(
+            &order.creator,
+            &order.input_token_id,
+            0,
+            &order.input_amount_left,
+        );
+    }
+
+    #[view(getOrder)]
+    fn get_order(&self, order_id: OrderId) -> Order<Self::Api> {
+        let order_mapper = self.orders(order_id);
+        require!(!order_mapper.is_empty(), ERROR_ORDER_NOT_FOUND);
+
+        order_mapper.get()
+    }
+
+    #[view(getPairOrderIds)]
+    fn get_pair_order_ids(&self, pair_address: ManagedAddress) -> MultiValueEncoded<OrderId> {
+        self.pair_orders(&pair_address).iter().collect()
+    }
+
+    #[storage_mapper("orders")]
+    fn orders(&self, order_id: OrderId) -> SingleValueMapper<Order<Self::Api>>;
+
+    #[storage_mapper("pair_orders")]
+    fn pair_orders(&self, pair_address: &ManagedAddress) -> UnorderedSetMapper<OrderId>;
+
+    #[view(getLastOrderId)]
+    #[storage_mapper("last_order_id")]
+    fn last_order_id(&self) -> SingleValueMapper<OrderId>;
+}
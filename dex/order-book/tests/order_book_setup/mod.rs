@@ -0,0 +1,299 @@
+use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+
+pub const ORDER_BOOK_WASM_PATH: &str = "order-book/output/order-book.wasm";
+pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const LP_TOKEN_ID: &[u8] = b"LPTOK-abcdef";
+pub const LOCKED_TOKEN_ID: &[u8] = b"LOCKED-abcdef";
+
+pub const USER_TOTAL_TOKENS: u64 = 1_000_000_000;
+pub const INITIAL_LIQUIDITY: u64 = 1_000_000;
+pub const EXECUTOR_REWARD_PERCENT: u64 = 1_000;
+pub const START_TIMESTAMP: u64 = 1_000;
+
+use order_book::execution::ExecutionModule;
+use order_book::orders::{OrderId, OrdersModule, PriceSource};
+use order_book::*;
+use pair::circuit_breaker::{CircuitBreakerAction, CircuitBreakerModule};
+use pair::config::ConfigModule as PairConfigModule;
+use pair::locking_wrapper::LockingWrapperModule;
+use pair::pair_actions::add_liq::AddLiquidityModule;
+use pair::pair_actions::swap::SwapModule;
+use pair::Pair;
+use pausable::{PausableModule, State};
+use simple_lock::locked_token::LockedTokenModule;
+use simple_lock::SimpleLock;
+
+#[allow(dead_code)]
+pub struct OrderBookSetup<OrderBookObjBuilder, PairObjBuilder>
+where
+    OrderBookObjBuilder: 'static + Copy + Fn() -> order_book::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub user_address: Address,
+    pub executor_address: Address,
+    pub order_book_wrapper:
+        ContractObjWrapper<order_book::ContractObj<DebugApi>, OrderBookObjBuilder>,
+    pub pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+}
+
+#[allow(dead_code)]
+impl<OrderBookObjBuilder, PairObjBuilder> OrderBookSetup<OrderBookObjBuilder, PairObjBuilder>
+where
+    OrderBookObjBuilder: 'static + Copy + Fn() -> order_book::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub fn new(order_book_builder: OrderBookObjBuilder, pair_builder: PairObjBuilder) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        b_mock.set_block_timestamp(START_TIMESTAMP);
+
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let user_addr = b_mock.create_user_account(&rust_zero);
+        let executor_addr = b_mock.create_user_account(&rust_zero);
+        for token_id in [WEGLD_TOKEN_ID, MEX_TOKEN_ID] {
+            b_mock.set_esdt_balance(&owner_addr, token_id, &rust_biguint!(USER_TOTAL_TOKENS));
+            b_mock.set_esdt_balance(&user_addr, token_id, &rust_biguint!(USER_TOTAL_TOKENS));
+        }
+
+        let pair_wrapper =
+            b_mock.create_sc_account(&rust_zero, Some(&owner_addr), pair_builder, PAIR_WASM_PATH);
+        b_mock
+            .execute_tx(&owner_addr, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    300,
+                    50,
+                    ManagedAddress::<DebugApi>::zero(),
+                    MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+                );
+
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(LP_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(pair_wrapper.address_ref(), LP_TOKEN_ID, &lp_token_roles[..]);
+
+        let liquidity_payments = [WEGLD_TOKEN_ID, MEX_TOKEN_ID].map(|token_id| TxTokenTransfer {
+            token_identifier: token_id.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(INITIAL_LIQUIDITY),
+        });
+        b_mock
+            .execute_esdt_multi_transfer(&owner_addr, &pair_wrapper, &liquidity_payments, |sc| {
+                sc.add_liquidity(managed_biguint!(1), managed_biguint!(1));
+            })
+            .assert_ok();
+
+        let order_book_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            order_book_builder,
+            ORDER_BOOK_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_addr, &order_book_wrapper, &rust_zero, |sc| {
+                let mut pairs = MultiValueEncoded::new();
+                pairs.push(managed_address!(pair_wrapper.address_ref()));
+
+                sc.init(EXECUTOR_REWARD_PERCENT, pairs);
+            })
+            .assert_ok();
+
+        OrderBookSetup {
+            b_mock,
+            owner_address: owner_addr,
+            user_address: user_addr,
+            executor_address: executor_addr,
+            order_book_wrapper,
+            pair_wrapper,
+        }
+    }
+
+    pub fn create_limit_order(
+        &mut self,
+        input_token_id: &[u8],
+        input_amount: u64,
+        output_token_id: &[u8],
+        limit_price: u128,
+        expiry_timestamp: u64,
+    ) -> OrderId {
+        let mut order_id = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.order_book_wrapper,
+                input_token_id,
+                0,
+                &rust_biguint!(input_amount),
+                |sc| {
+                    order_id = sc.create_limit_order(
+                        managed_address!(self.pair_wrapper.address_ref()),
+                        managed_token_id!(output_token_id),
+                        managed_biguint!(limit_price),
+                        PriceSource::Spot,
+                        expiry_timestamp,
+                    );
+                },
+            )
+            .assert_ok();
+
+        order_id
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_twap_order(
+        &mut self,
+        input_token_id: &[u8],
+        input_amount: u64,
+        output_token_id: &[u8],
+        limit_price: u128,
+        slices: u64,
+        slice_interval: u64,
+        expiry_timestamp: u64,
+    ) -> OrderId {
+        let mut order_id = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.order_book_wrapper,
+                input_token_id,
+                0,
+                &rust_biguint!(input_amount),
+                |sc| {
+                    order_id = sc.create_twap_order(
+                        managed_address!(self.pair_wrapper.address_ref()),
+                        managed_token_id!(output_token_id),
+                        managed_biguint!(limit_price),
+                        PriceSource::Spot,
+                        slices,
+                        slice_interval,
+                        expiry_timestamp,
+                    );
+                },
+            )
+            .assert_ok();
+
+        order_id
+    }
+
+    /// Returns the executor reward amounts
+    pub fn execute_orders(&mut self, order_ids: &[OrderId]) -> Vec<u64> {
+        let mut rewards = Vec::new();
+        self.b_mock
+            .execute_tx(
+                &self.executor_address,
+                &self.order_book_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    let mut args = MultiValueEncoded::new();
+                    for order_id in order_ids {
+                        args.push(*order_id);
+                    }
+
+                    let executor_rewards = sc.execute_orders(args);
+                    for reward in executor_rewards.iter() {
+                        rewards.push(reward.amount.to_u64().unwrap());
+                    }
+                },
+            )
+            .assert_ok();
+
+        rewards
+    }
+
+    pub fn cancel_order(&mut self, caller: &Address, order_id: OrderId) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.order_book_wrapper, &rust_biguint!(0), |sc| {
+                sc.cancel_order(order_id);
+            })
+    }
+
+    pub fn check_order_exists(&mut self, order_id: OrderId, expected: bool) {
+        self.b_mock
+            .execute_query(&self.order_book_wrapper, |sc| {
+                assert_eq!(sc.orders(order_id).is_empty(), !expected);
+            })
+            .assert_ok();
+    }
+
+    pub fn swap_on_pair(&mut self, payment_token_id: &[u8], amount: u64, token_out: &[u8]) {
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.owner_address,
+                &self.pair_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(amount),
+                |sc| {
+                    sc.swap_tokens_fixed_input(managed_token_id!(token_out), managed_biguint!(1));
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn set_pair_circuit_breaker(&mut self, max_deviation_percent: u64, window_rounds: u64) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.set_circuit_breaker(
+                        max_deviation_percent,
+                        window_rounds,
+                        CircuitBreakerAction::Revert,
+                    );
+                },
+            )
+            .assert_ok();
+    }
+
+    /// Makes the pair lock the swap output until the given epoch
+    pub fn set_pair_locking(&mut self, locking_deadline_epoch: u64, unlock_epoch: u64) {
+        let rust_zero = rust_biguint!(0);
+        let locking_wrapper = self.b_mock.create_sc_account(
+            &rust_zero,
+            Some(&self.owner_address),
+            simple_lock::contract_obj,
+            "simple-lock.wasm",
+        );
+        self.b_mock
+            .execute_tx(&self.owner_address, &locking_wrapper, &rust_zero, |sc| {
+                sc.init();
+                sc.locked_token()
+                    .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
+            })
+            .assert_ok();
+        self.b_mock.set_esdt_local_roles(
+            locking_wrapper.address_ref(),
+            LOCKED_TOKEN_ID,
+            &[
+                EsdtLocalRole::NftCreate,
+                EsdtLocalRole::NftAddQuantity,
+                EsdtLocalRole::NftBurn,
+            ],
+        );
+
+        self.b_mock
+            .execute_tx(&self.owner_address, &self.pair_wrapper, &rust_zero, |sc| {
+                sc.set_locking_sc_address(managed_address!(locking_wrapper.address_ref()));
+                sc.set_locking_deadline_epoch(locking_deadline_epoch);
+                sc.set_unlock_epoch(unlock_epoch);
+            })
+            .assert_ok();
+    }
+}
@@ -0,0 +1,238 @@
+#![allow(deprecated)]
+
+mod order_book_setup;
+use multiversx_sc_scenario::{rust_biguint, DebugApi};
+use order_book_setup::*;
+use simple_lock::locked_token::LockedTokenAttributes;
+
+const PRICE_ONE: u128 = 1_000_000_000_000_000_000;
+
+#[test]
+fn test_order_book_setup() {
+    let _ = OrderBookSetup::new(order_book::contract_obj, pair::contract_obj);
+}
+
+#[test]
+fn test_limit_order() {
+    let mut setup = OrderBookSetup::new(order_book::contract_obj, pair::contract_obj);
+    let order_id = setup.create_limit_order(
+        WEGLD_TOKEN_ID,
+        10_000,
+        MEX_TOKEN_ID,
+        PRICE_ONE * 99 / 100,
+        START_TIMESTAMP + 100,
+    );
+
+    // spot price below the limit
+    let rewards = setup.execute_orders(&[order_id]);
+    assert!(rewards.is_empty());
+    setup.check_order_exists(order_id, true);
+
+    setup.swap_on_pair(MEX_TOKEN_ID, 100_000, WEGLD_TOKEN_ID);
+
+    let rewards = setup.execute_orders(&[order_id]);
+    assert_eq!(rewards, vec![119]);
+    setup.check_order_exists(order_id, false);
+
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS + 11_810),
+    );
+    setup
+        .b_mock
+        .check_esdt_balance(&setup.executor_address, MEX_TOKEN_ID, &rust_biguint!(119));
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS - 10_000),
+    );
+}
+
+#[test]
+fn test_skipped_orders() {
+    let mut setup = OrderBookSetup::new(order_book::contract_obj, pair::contract_obj);
+
+    // a safe price window for the circuit breaker
+    setup.b_mock.set_block_round(1);
+    setup.swap_on_pair(MEX_TOKEN_ID, 100, WEGLD_TOKEN_ID);
+    setup.b_mock.set_block_round(20);
+    setup.set_pair_circuit_breaker(5_000, 10);
+
+    // the spot output covers the limit, but not the executor reward on top of it
+    let reward_order_id = setup.create_limit_order(
+        WEGLD_TOKEN_ID,
+        10_000,
+        MEX_TOKEN_ID,
+        PRICE_ONE * 98 / 100,
+        START_TIMESTAMP + 100,
+    );
+    // the minimum output rounds down to zero
+    let dust_order_id = setup.create_limit_order(
+        WEGLD_TOKEN_ID,
+        1,
+        MEX_TOKEN_ID,
+        PRICE_ONE * 99 / 100,
+        START_TIMESTAMP + 100,
+    );
+    // the swap would be reverted by the circuit breaker
+    let large_order_id = setup.create_limit_order(
+        WEGLD_TOKEN_ID,
+        100_000,
+        MEX_TOKEN_ID,
+        PRICE_ONE / 2,
+        START_TIMESTAMP + 100,
+    );
+    let order_id = setup.create_limit_order(
+        WEGLD_TOKEN_ID,
+        10_000,
+        MEX_TOKEN_ID,
+        PRICE_ONE * 97 / 100,
+        START_TIMESTAMP + 100,
+    );
+
+    let rewards = setup.execute_orders(&[reward_order_id, dust_order_id, large_order_id, order_id]);
+    assert_eq!(rewards, vec![98]);
+    setup.check_order_exists(reward_order_id, true);
+    setup.check_order_exists(dust_order_id, true);
+    setup.check_order_exists(large_order_id, true);
+    setup.check_order_exists(order_id, false);
+
+    // the creator gets at least the limit price after the executor reward
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS + 9_775),
+    );
+}
+
+#[test]
+fn test_locked_output_order() {
+    let mut setup = OrderBookSetup::new(order_book::contract_obj, pair::contract_obj);
+    setup.swap_on_pair(MEX_TOKEN_ID, 100_000, WEGLD_TOKEN_ID);
+    setup.set_pair_locking(5, 10);
+
+    let order_id = setup.create_limit_order(
+        WEGLD_TOKEN_ID,
+        10_000,
+        MEX_TOKEN_ID,
+        PRICE_ONE * 99 / 100,
+        START_TIMESTAMP + 100,
+    );
+
+    // the creator and the executor get the locked swap output
+    let rewards = setup.execute_orders(&[order_id]);
+    assert_eq!(rewards, vec![119]);
+    setup.check_order_exists(order_id, false);
+
+    setup
+        .b_mock
+        .check_nft_balance::<LockedTokenAttributes<DebugApi>>(
+            &setup.user_address,
+            LOCKED_TOKEN_ID,
+            1,
+            &rust_biguint!(11_810),
+            None,
+        );
+    setup
+        .b_mock
+        .check_nft_balance::<LockedTokenAttributes<DebugApi>>(
+            &setup.executor_address,
+            LOCKED_TOKEN_ID,
+            1,
+            &rust_biguint!(119),
+            None,
+        );
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS),
+    );
+}
+
+#[test]
+fn test_twap_order() {
+    let mut setup = OrderBookSetup::new(order_book::contract_obj, pair::contract_obj);
+    let order_id = setup.create_twap_order(
+        WEGLD_TOKEN_ID,
+        9_000,
+        MEX_TOKEN_ID,
+        PRICE_ONE * 9 / 10,
+        3,
+        10,
+        START_TIMESTAMP + 100,
+    );
+
+    let rewards = setup.execute_orders(&[order_id]);
+    assert_eq!(rewards.len(), 1);
+
+    // the next slice is not due yet
+    let rewards = setup.execute_orders(&[order_id]);
+    assert!(rewards.is_empty());
+
+    setup.b_mock.set_block_timestamp(START_TIMESTAMP + 10);
+    let rewards = setup.execute_orders(&[order_id]);
+    assert_eq!(rewards.len(), 1);
+    setup.check_order_exists(order_id, true);
+
+    setup.b_mock.set_block_timestamp(START_TIMESTAMP + 20);
+    let rewards = setup.execute_orders(&[order_id]);
+    assert_eq!(rewards.len(), 1);
+    setup.check_order_exists(order_id, false);
+
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS - 9_000),
+    );
+}
+
+#[test]
+fn test_cancel_and_expire_order() {
+    let mut setup = OrderBookSetup::new(order_book::contract_obj, pair::contract_obj);
+    let first_order_id = setup.create_limit_order(
+        WEGLD_TOKEN_ID,
+        10_000,
+        MEX_TOKEN_ID,
+        PRICE_ONE * 2,
+        START_TIMESTAMP + 100,
+    );
+    let second_order_id = setup.create_limit_order(
+        MEX_TOKEN_ID,
+        20_000,
+        WEGLD_TOKEN_ID,
+        PRICE_ONE * 2,
+        START_TIMESTAMP + 100,
+    );
+
+    let executor_address = setup.executor_address.clone();
+    setup
+        .cancel_order(&executor_address, first_order_id)
+        .assert_user_error("Only the order creator can cancel it");
+
+    let user_address = setup.user_address.clone();
+    setup
+        .cancel_order(&user_address, first_order_id)
+        .assert_ok();
+    setup.check_order_exists(first_order_id, false);
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS),
+    );
+
+    setup
+        .cancel_order(&user_address, first_order_id)
+        .assert_user_error("Order not found");
+
+    // anyone can close an expired order, the input goes back to the creator
+    setup.b_mock.set_block_timestamp(START_TIMESTAMP + 100);
+    let rewards = setup.execute_orders(&[first_order_id, second_order_id]);
+    assert!(rewards.is_empty());
+    setup.check_order_exists(second_order_id, false);
+    setup.b_mock.check_esdt_balance(
+        &setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS),
+    );
+}
@@ -0,0 +1,38 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Upgrade:                              1
+// Endpoints:                           12
+// Async Callback (empty):               1
+// Total number of exported functions:  15
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    order_book
+    (
+        init => init
+        upgrade => upgrade
+        setExecutorRewardPercent => set_executor_reward_percent_endpoint
+        addPairs => add_pairs_endpoint
+        removePairs => remove_pairs
+        getExecutorRewardPercent => executor_reward_percent
+        getPairs => pairs
+        createLimitOrder => create_limit_order
+        createTwapOrder => create_twap_order
+        cancelOrder => cancel_order
+        getOrder => get_order
+        getPairOrderIds => get_pair_order_ids
+        getLastOrderId => last_order_id
+        executeOrders => execute_orders
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
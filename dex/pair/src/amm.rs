@@ -4,10 +4,20 @@ multiversx_sc::derive_imports!();
 use crate::config::MAX_PERCENTAGE;
 
 use super::config;
+use super::dynamic_fee;
+use super::dynamic_fee_reader;
+use super::read_pair_storage;
+use super::safe_price_reader;
+use super::stable_swap;
 
 #[multiversx_sc::module]
 pub trait AmmModule:
     config::ConfigModule
+    + stable_swap::StableSwapModule
+    + dynamic_fee::DynamicFeeModule
+    + safe_price_reader::SafePriceReaderModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + read_pair_storage::ReadPairStorageModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
@@ -17,6 +27,10 @@ pub trait AmmModule:
         first_token_amount: &BigUint,
         second_token_amount: &BigUint,
     ) -> BigUint {
+        if self.is_stable_swap() {
+            return self.compute_stable_invariant(first_token_amount, second_token_amount);
+        }
+
         first_token_amount * second_token_amount
     }
 
@@ -35,6 +49,10 @@ pub trait AmmModule:
         reserve_in: &BigUint,
         reserve_out: &BigUint,
     ) -> BigUint {
+        if self.is_stable_swap() {
+            return self.get_stable_amount_out(amount_in, reserve_in, reserve_out);
+        }
+
         let numerator = amount_in * reserve_out;
         let denominator = reserve_in + amount_in;
 
@@ -47,7 +65,23 @@ pub trait AmmModule:
         reserve_in: &BigUint,
         reserve_out: &BigUint,
     ) -> BigUint {
-        let amount_in_with_fee = amount_in * (MAX_PERCENTAGE - self.total_fee_percent().get());
+        let total_fee_percent = self.get_effective_total_fee_percent();
+        self.get_amount_out_with_fee(amount_in, reserve_in, reserve_out, total_fee_percent)
+    }
+
+    fn get_amount_out_with_fee(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        total_fee_percent: u64,
+    ) -> BigUint {
+        let amount_in_with_fee = amount_in * (MAX_PERCENTAGE - total_fee_percent);
+        if self.is_stable_swap() {
+            let amount_in_after_fee = amount_in_with_fee / MAX_PERCENTAGE;
+            return self.get_stable_amount_out(&amount_in_after_fee, reserve_in, reserve_out);
+        }
+
         let numerator = &amount_in_with_fee * reserve_out;
         let denominator = (reserve_in * MAX_PERCENTAGE) + amount_in_with_fee;
 
@@ -60,9 +94,26 @@ pub trait AmmModule:
         reserve_in: &BigUint,
         reserve_out: &BigUint,
     ) -> BigUint {
+        let total_fee_percent = self.get_effective_total_fee_percent();
+        self.get_amount_in_with_fee(amount_out, reserve_in, reserve_out, total_fee_percent)
+    }
+
+    fn get_amount_in_with_fee(
+        &self,
+        amount_out: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        total_fee_percent: u64,
+    ) -> BigUint {
+        if self.is_stable_swap() {
+            let amount_in_after_fee =
+                self.get_stable_amount_in(amount_out, reserve_in, reserve_out);
+            return (amount_in_after_fee * MAX_PERCENTAGE / (MAX_PERCENTAGE - total_fee_percent))
+                + 1u64;
+        }
+
         let numerator = reserve_in * amount_out * MAX_PERCENTAGE;
-        let denominator =
-            (reserve_out - amount_out) * (MAX_PERCENTAGE - self.total_fee_percent().get());
+        let denominator = (reserve_out - amount_out) * (MAX_PERCENTAGE - total_fee_percent);
 
         (numerator / denominator) + 1u64
     }
@@ -0,0 +1,201 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use pausable::State;
+
+use crate::{
+    amm,
+    config::{self, MAX_PERCENTAGE},
+    contexts::base::StorageCache,
+    dynamic_fee, dynamic_fee_reader,
+    errors::*,
+    events, read_pair_storage, safe_price,
+    safe_price::Round,
+    safe_price_reader, safe_price_view, stable_swap,
+};
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CircuitBreakerAction {
+    Revert,
+    SetPartialActive,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Debug)]
+pub struct CircuitBreakerConfig {
+    pub max_deviation_percent: u64,
+    pub window_rounds: Round,
+    pub action: CircuitBreakerAction,
+}
+
+#[multiversx_sc::module]
+pub trait CircuitBreakerModule:
+    safe_price_view::SafePriceViewModule
+    + safe_price::SafePriceModule
+    + safe_price_reader::SafePriceReaderModule
+    + read_pair_storage::ReadPairStorageModule
+    + events::EventsModule
+    + config::ConfigModule
+    + amm::AmmModule
+    + dynamic_fee::DynamicFeeModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + stable_swap::StableSwapModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    /// Bounds the deviation of the post-swap spot price from the safe price of the last
+    /// `window_rounds` rounds, as a percentage of MAX_PERCENTAGE. Past the bound, the swap
+    /// either reverts or goes through and switches the pair to PartialActive.
+    #[endpoint(setCircuitBreaker)]
+    fn set_circuit_breaker(
+        &self,
+        max_deviation_percent: u64,
+        window_rounds: Round,
+        action: CircuitBreakerAction,
+    ) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            max_deviation_percent > 0 && max_deviation_percent <= MAX_PERCENTAGE,
+            ERROR_BAD_MAX_DEVIATION
+        );
+        require!(window_rounds > 0, ERROR_BAD_DEVIATION_WINDOW);
+
+        self.circuit_breaker_config().set(CircuitBreakerConfig {
+            max_deviation_percent,
+            window_rounds,
+            action,
+        });
+    }
+
+    #[endpoint(disableCircuitBreaker)]
+    fn disable_circuit_breaker(&self) {
+        self.require_caller_has_owner_permissions();
+        self.circuit_breaker_config().clear();
+    }
+
+    fn check_price_deviation(&self, storage_cache: &StorageCache<Self>) {
+        let config_mapper = self.circuit_breaker_config();
+        if config_mapper.is_empty() {
+            return;
+        }
+
+        let config = config_mapper.get();
+        let (safe_price_first_token_reserve, safe_price_second_token_reserve, deviation) =
+            match self.get_price_deviation(
+                &config,
+                &storage_cache.first_token_reserve,
+                &storage_cache.second_token_reserve,
+            ) {
+                Some(price_deviation) => price_deviation,
+                None => return,
+            };
+        if deviation <= config.max_deviation_percent {
+            return;
+        }
+
+        match config.action {
+            CircuitBreakerAction::Revert => sc_panic!(ERROR_PRICE_DEVIATION_TOO_HIGH),
+            CircuitBreakerAction::SetPartialActive => {
+                self.state().set(State::PartialActive);
+                self.emit_price_deviation_event(
+                    storage_cache,
+                    safe_price_first_token_reserve,
+                    safe_price_second_token_reserve,
+                    deviation.to_u64().unwrap_or(u64::MAX),
+                    config.max_deviation_percent,
+                );
+            }
+        }
+    }
+
+    /// Whether a swap leaving the given reserves would be reverted by the circuit breaker.
+    fn is_price_deviation_reverted(
+        &self,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> bool {
+        let config_mapper = self.circuit_breaker_config();
+        if config_mapper.is_empty() {
+            return false;
+        }
+
+        let config = config_mapper.get();
+        if config.action != CircuitBreakerAction::Revert {
+            return false;
+        }
+
+        match self.get_price_deviation(&config, first_token_reserve, second_token_reserve) {
+            Some((_, _, deviation)) => deviation > config.max_deviation_percent,
+            None => false,
+        }
+    }
+
+    /// The safe price reserves of the window and the deviation of the spot price of the
+    /// given reserves from it. None while the observations do not cover the whole window.
+    fn get_price_deviation(
+        &self,
+        config: &CircuitBreakerConfig,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> Option<(BigUint, BigUint, BigUint)> {
+        let current_round = self.blockchain().get_block_round();
+        if current_round <= config.window_rounds {
+            return None;
+        }
+
+        let start_round = current_round - config.window_rounds;
+        let pair_address = self.blockchain().get_sc_address();
+        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+        if price_observations.is_empty() {
+            return None;
+        }
+
+        let oldest_price_observation = self.get_oldest_price_observation(
+            self.safe_price_current_index().get(),
+            &price_observations,
+        );
+        if oldest_price_observation.recording_round > start_round {
+            return None;
+        }
+
+        let (first_price_observation, last_price_observation) =
+            self.get_interval_price_observations(&pair_address, start_round, current_round);
+        let weight_diff =
+            last_price_observation.weight_accumulated - first_price_observation.weight_accumulated;
+        let safe_price_first_token_reserve = (&last_price_observation
+            .first_token_reserve_accumulated
+            - &first_price_observation.first_token_reserve_accumulated)
+            / weight_diff;
+        let safe_price_second_token_reserve = (&last_price_observation
+            .second_token_reserve_accumulated
+            - &first_price_observation.second_token_reserve_accumulated)
+            / weight_diff;
+        if safe_price_first_token_reserve == 0u64 || safe_price_second_token_reserve == 0u64 {
+            return None;
+        }
+
+        // spot price = second_reserve / first_reserve, compared without the division
+        let (spot_first_token_reserve, spot_second_token_reserve) =
+            self.get_spot_price_reserves(first_token_reserve, second_token_reserve);
+        let spot_cross = spot_second_token_reserve * &safe_price_first_token_reserve;
+        let safe_price_cross = &safe_price_second_token_reserve * &spot_first_token_reserve;
+        let cross_diff = if spot_cross > safe_price_cross {
+            &spot_cross - &safe_price_cross
+        } else {
+            &safe_price_cross - &spot_cross
+        };
+        let deviation = cross_diff * MAX_PERCENTAGE / safe_price_cross;
+
+        Some((
+            safe_price_first_token_reserve,
+            safe_price_second_token_reserve,
+            deviation,
+        ))
+    }
+
+    #[view(getCircuitBreakerConfig)]
+    #[storage_mapper("circuit_breaker_config")]
+    fn circuit_breaker_config(&self) -> SingleValueMapper<CircuitBreakerConfig>;
+}
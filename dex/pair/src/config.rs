@@ -72,4 +72,9 @@ pub trait ConfigModule:
     #[view(getReserve)]
     #[storage_mapper("reserve")]
     fn pair_reserve(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    /// Set while the pair hooks or a flash swap callback are called, blocks any
+    /// reentrant pair action.
+    #[storage_mapper("hook_call_in_progress")]
+    fn hook_call_in_progress(&self) -> SingleValueMapper<bool>;
 }
@@ -1,6 +1,8 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
 pub struct AddLiquidityContext<M: ManagedTypeApi> {
     pub first_payment: EsdtTokenPayment<M>,
     pub second_payment: EsdtTokenPayment<M>,
@@ -1,9 +1,10 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use crate::State;
+use crate::{State, ERROR_HOOK_CALL_IN_PROGRESS};
 
-#[derive(PartialEq, Copy, Clone)]
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Copy, Clone)]
 pub enum SwapTokensOrder {
     PoolOrder,
     ReverseOrder,
@@ -28,6 +29,12 @@ where
     C: crate::config::ConfigModule,
 {
     pub fn new(sc_ref: &'a C) -> Self {
+        if sc_ref.hook_call_in_progress().get() {
+            multiversx_sc::contract_base::ErrorHelper::<C::Api>::signal_error_with_message(
+                ERROR_HOOK_CALL_IN_PROGRESS,
+            );
+        }
+
         let first_token_id = sc_ref.first_token_id().get();
         let second_token_id = sc_ref.second_token_id().get();
         let first_token_reserve = sc_ref.pair_reserve(&first_token_id).get();
@@ -45,6 +52,20 @@ where
         }
     }
 
+    /// Commits the changes of the mutable fields to storage, which otherwise
+    /// only happens on drop.
+    pub fn commit(&self) {
+        self.sc_ref
+            .pair_reserve(&self.first_token_id)
+            .set(&self.first_token_reserve);
+
+        self.sc_ref
+            .pair_reserve(&self.second_token_id)
+            .set(&self.second_token_reserve);
+
+        self.sc_ref.lp_token_supply().set(&self.lp_token_supply);
+    }
+
     pub fn get_swap_tokens_order(
         &self,
         first_token_id: &TokenIdentifier<C::Api>,
@@ -102,15 +123,6 @@ where
     C: crate::config::ConfigModule,
 {
     fn drop(&mut self) {
-        // commit changes to storage for the mutable fields
-        self.sc_ref
-            .pair_reserve(&self.first_token_id)
-            .set(&self.first_token_reserve);
-
-        self.sc_ref
-            .pair_reserve(&self.second_token_id)
-            .set(&self.second_token_reserve);
-
-        self.sc_ref.lp_token_supply().set(&self.lp_token_supply);
+        self.commit();
     }
 }
@@ -1,6 +1,8 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
 pub struct RemoveLiquidityContext<M: ManagedTypeApi> {
     pub lp_token_payment_amount: BigUint<M>,
     pub first_token_amount_min: BigUint<M>,
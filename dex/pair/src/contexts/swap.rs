@@ -3,6 +3,8 @@ multiversx_sc::derive_imports!();
 
 use super::base::SwapTokensOrder;
 
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
 pub struct SwapContext<M: ManagedTypeApi> {
     pub input_token_id: TokenIdentifier<M>,
     pub input_token_amount: BigUint<M>,
@@ -14,6 +16,8 @@ pub struct SwapContext<M: ManagedTypeApi> {
     pub final_input_amount: BigUint<M>,
     pub final_output_amount: BigUint<M>,
     pub fee_amount: BigUint<M>,
+    pub total_fee_percent: u64,
+    pub lp_fee_growth: BigUint<M>,
 }
 
 impl<M: ManagedTypeApi> SwapContext<M> {
@@ -33,6 +37,8 @@ impl<M: ManagedTypeApi> SwapContext<M> {
             final_input_amount: BigUint::zero(),
             final_output_amount: BigUint::zero(),
             fee_amount: BigUint::zero(),
+            total_fee_percent: 0,
+            lp_fee_growth: BigUint::zero(),
         }
     }
 }
@@ -0,0 +1,84 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{
+    config::{self, MAX_FEE_PERCENTAGE},
+    dynamic_fee_reader,
+    errors::*,
+    read_pair_storage,
+    safe_price::Round,
+    safe_price_reader, stable_swap,
+};
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Debug)]
+pub struct DynamicFeeConfig {
+    pub min_fee_percent: u64,
+    pub max_fee_percent: u64,
+    pub window_rounds: Round,
+    pub max_volatility_percent: u64,
+}
+
+#[multiversx_sc::module]
+pub trait DynamicFeeModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + stable_swap::StableSwapModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    /// Replaces the static total fee with one that grows linearly with the price volatility
+    /// of the last `window_rounds` rounds. The max fee is charged from a volatility of
+    /// `max_volatility_percent` upwards. The special fee stays static.
+    #[endpoint(setDynamicFee)]
+    fn set_dynamic_fee(
+        &self,
+        min_fee_percent: u64,
+        max_fee_percent: u64,
+        window_rounds: Round,
+        max_volatility_percent: u64,
+    ) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(
+            min_fee_percent >= self.special_fee_percent().get()
+                && min_fee_percent <= max_fee_percent
+                && max_fee_percent <= MAX_FEE_PERCENTAGE,
+            ERROR_BAD_PERCENTS
+        );
+        require!(
+            window_rounds > 0 && max_volatility_percent > 0,
+            ERROR_BAD_DYNAMIC_FEE_ARGS
+        );
+
+        self.dynamic_fee_config().set(DynamicFeeConfig {
+            min_fee_percent,
+            max_fee_percent,
+            window_rounds,
+            max_volatility_percent,
+        });
+    }
+
+    #[endpoint(disableDynamicFee)]
+    fn disable_dynamic_fee(&self) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.dynamic_fee_config().clear();
+    }
+
+    #[view(isDynamicFeeEnabled)]
+    fn is_dynamic_fee_enabled(&self) -> bool {
+        !self.dynamic_fee_config().is_empty()
+    }
+
+    #[view(getEffectiveFeePercent)]
+    fn get_effective_total_fee_percent(&self) -> u64 {
+        let pair_address = self.blockchain().get_sc_address();
+        self.get_pair_effective_total_fee_percent(&pair_address)
+    }
+
+    #[view(getDynamicFeeConfig)]
+    #[storage_mapper("dynamic_fee_config")]
+    fn dynamic_fee_config(&self) -> SingleValueMapper<DynamicFeeConfig>;
+}
@@ -0,0 +1,125 @@
+multiversx_sc::imports!();
+
+use crate::{
+    config::MAX_PERCENTAGE,
+    read_pair_storage,
+    safe_price::{Round, MAX_OBSERVATIONS},
+    safe_price_reader,
+};
+
+pub const MAX_VOLATILITY_OBSERVATIONS: usize = 50;
+const VOLATILITY_PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+/// Dynamic fee computations that only read the pair storage, so they can be
+/// used both by the pair itself and by contracts on the same shard.
+#[multiversx_sc::module]
+pub trait DynamicFeeReaderModule:
+    read_pair_storage::ReadPairStorageModule + safe_price_reader::SafePriceReaderModule
+{
+    /// The total fee the pair currently charges on swaps, the dynamic one if it is set.
+    fn get_pair_effective_total_fee_percent(&self, pair_address: &ManagedAddress) -> u64 {
+        let config_mapper = self.get_dynamic_fee_config_mapper(pair_address.clone());
+        if config_mapper.is_empty() {
+            return self
+                .get_total_fee_percent_mapper(pair_address.clone())
+                .get();
+        }
+
+        let config = config_mapper.get();
+        let volatility = core::cmp::min(
+            self.compute_pair_price_volatility(pair_address, config.window_rounds),
+            config.max_volatility_percent,
+        );
+        let fee_percent = config.min_fee_percent
+            + (config.max_fee_percent - config.min_fee_percent) * volatility
+                / config.max_volatility_percent;
+
+        // the special fee may have been raised after the dynamic fee was set
+        let special_fee_percent = self
+            .get_special_fee_percent_mapper(pair_address.clone())
+            .get();
+        core::cmp::max(fee_percent, special_fee_percent)
+    }
+
+    /// The spread between the highest and the lowest price held in the window, relative
+    /// to the lowest one. The prices are read from the observations and the current reserves.
+    fn compute_pair_price_volatility(
+        &self,
+        pair_address: &ManagedAddress,
+        window_rounds: Round,
+    ) -> u64 {
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+        let first_token_reserve = self
+            .get_pair_reserve_mapper(pair_address.clone(), &first_token_id)
+            .get();
+        let second_token_reserve = self
+            .get_pair_reserve_mapper(pair_address.clone(), &second_token_id)
+            .get();
+        if first_token_reserve == 0u64 || second_token_reserve == 0u64 {
+            return 0;
+        }
+
+        let (first_token_reserve, second_token_reserve) = self.get_pair_spot_price_reserves(
+            pair_address,
+            &first_token_reserve,
+            &second_token_reserve,
+        );
+        let spot_price = second_token_reserve * VOLATILITY_PRICE_PRECISION / first_token_reserve;
+        let mut min_price = spot_price.clone();
+        let mut max_price = spot_price;
+
+        let start_round = self
+            .blockchain()
+            .get_block_round()
+            .saturating_sub(window_rounds);
+        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+        let observations_len = price_observations.len();
+        if observations_len >= 2 {
+            let mut index = self
+                .get_safe_price_current_index_mapper(pair_address.clone())
+                .get();
+            let mut observation = price_observations.get(index);
+            for _ in 0..core::cmp::min(observations_len - 1, MAX_VOLATILITY_OBSERVATIONS) {
+                if observation.recording_round <= start_round {
+                    break;
+                }
+
+                // VecMapper index starts at 1, the buffer only wraps around once full
+                let previous_index = if index > 1 {
+                    index - 1
+                } else if observations_len == MAX_OBSERVATIONS {
+                    MAX_OBSERVATIONS
+                } else {
+                    break;
+                };
+                let previous_observation = price_observations.get(previous_index);
+
+                let first_token_reserve_diff = &observation.first_token_reserve_accumulated
+                    - &previous_observation.first_token_reserve_accumulated;
+                let second_token_reserve_diff = &observation.second_token_reserve_accumulated
+                    - &previous_observation.second_token_reserve_accumulated;
+                if first_token_reserve_diff > 0 {
+                    let price = second_token_reserve_diff * VOLATILITY_PRICE_PRECISION
+                        / first_token_reserve_diff;
+                    if price < min_price {
+                        min_price = price;
+                    } else if price > max_price {
+                        max_price = price;
+                    }
+                }
+
+                index = previous_index;
+                observation = previous_observation;
+            }
+        }
+
+        if min_price == 0u64 {
+            return u64::MAX;
+        }
+
+        ((max_price - &min_price) * MAX_PERCENTAGE / min_price)
+            .to_u64()
+            .unwrap_or(u64::MAX)
+    }
+}
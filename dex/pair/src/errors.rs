@@ -52,3 +52,34 @@ pub static ERROR_SAFE_PRICE_CURRENT_INDEX: &[u8] =
     b"The current safe price index is greater than the maximum number of observations";
 pub static ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST: &[u8] =
     b"The price observation does not exist";
+pub static ERROR_SAFE_PRICE_LOG_PRICE_NOT_AVAILABLE: &[u8] =
+    b"Log price observations do not cover the whole interval";
+pub static ERROR_SAFE_PRICE_NOT_ENOUGH_OBSERVATIONS: &[u8] =
+    b"Not enough price observations in the interval";
+
+pub static ERROR_AMM_MODE_CHANGE_AFTER_LIQUIDITY: &[u8] =
+    b"The AMM mode can only be changed before adding liquidity";
+pub static ERROR_NOT_STABLE_SWAP: &[u8] = b"Pair is not in stable swap mode";
+pub static ERROR_BAD_AMPLIFICATION: &[u8] = b"Bad amplification";
+pub static ERROR_RAMP_TOO_SHORT: &[u8] = b"Amplification ramp too short";
+pub static ERROR_STABLE_SWAP_NOT_CONVERGING: &[u8] = b"Stable swap invariant does not converge";
+
+pub static ERROR_BAD_MAX_DEVIATION: &[u8] = b"Bad max price deviation";
+pub static ERROR_BAD_DEVIATION_WINDOW: &[u8] = b"Bad price deviation window";
+pub static ERROR_PRICE_DEVIATION_TOO_HIGH: &[u8] = b"Price deviation from safe price too high";
+pub static ERROR_BAD_DYNAMIC_FEE_ARGS: &[u8] = b"Bad dynamic fee arguments";
+pub static ERROR_ZAP_AMOUNT_TOO_LOW: &[u8] = b"Amount too low for a single token liquidity add";
+pub static ERROR_ZAP_OUTPUT_LOCKED: &[u8] =
+    b"Single token liquidity removal not available while swaps are locked";
+pub static ERROR_FLASH_SWAP_DISABLED: &[u8] = b"Flash swaps are not enabled";
+pub static ERROR_FLASH_SWAP_CALLER_NOT_SC: &[u8] = b"Flash swaps are only available to contracts";
+pub static ERROR_FEE_DESTINATIONS_SET: &[u8] = b"Fee destinations are set";
+pub static ERROR_PROTOCOL_FEE_ENABLED: &[u8] = b"Protocol fee is enabled";
+pub static ERROR_PROTOCOL_FEE_NOT_ENABLED: &[u8] = b"Protocol fee is not enabled";
+pub static ERROR_HOOK_NOT_A_CONTRACT: &[u8] = b"Hook address is not a contract";
+pub static ERROR_TOO_MANY_HOOKS: &[u8] = b"Too many hooks";
+pub static ERROR_HOOK_ALREADY_ADDED: &[u8] = b"Hook already added";
+pub static ERROR_HOOK_NOT_FOUND: &[u8] = b"Hook not found";
+pub static ERROR_ACTION_VETOED: &[u8] = b"Action vetoed by hook";
+pub static ERROR_HOOK_CALL_IN_PROGRESS: &[u8] =
+    b"Pair cannot be used from a hook or a flash swap callback";
@@ -1,3 +1,4 @@
+use crate::config::MAX_PERCENTAGE;
 use crate::contexts::add_liquidity::AddLiquidityContext;
 use crate::contexts::base::StorageCache;
 use crate::contexts::remove_liquidity::RemoveLiquidityContext;
@@ -20,6 +21,7 @@ pub struct SwapEvent<M: ManagedTypeApi> {
     block: u64,
     epoch: u64,
     timestamp: u64,
+    lp_fee_growth: BigUint<M>,
 }
 
 #[type_abi]
@@ -72,6 +74,68 @@ pub struct RemoveLiquidityEvent<M: ManagedTypeApi> {
     timestamp: u64,
 }
 
+#[type_abi]
+#[derive(TopEncode)]
+pub struct SwapFeeEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    token_id_in: TokenIdentifier<M>,
+    token_amount_in: BigUint<M>,
+    total_fee_percent: u64,
+    total_fee_amount: BigUint<M>,
+    special_fee_amount: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct PriceDeviationEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    first_token_id: TokenIdentifier<M>,
+    second_token_id: TokenIdentifier<M>,
+    first_token_reserves: BigUint<M>,
+    second_token_reserves: BigUint<M>,
+    safe_price_first_token_reserves: BigUint<M>,
+    safe_price_second_token_reserves: BigUint<M>,
+    deviation_percent: u64,
+    max_deviation_percent: u64,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct FlashSwapEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    token_id_out: TokenIdentifier<M>,
+    token_amount_out: BigUint<M>,
+    first_token_amount_in: BigUint<M>,
+    second_token_amount_in: BigUint<M>,
+    first_token_fee_amount: BigUint<M>,
+    second_token_fee_amount: BigUint<M>,
+    first_token_reserves: BigUint<M>,
+    second_token_reserves: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct ProtocolFeeEvent<M: ManagedTypeApi> {
+    treasury: ManagedAddress<M>,
+    lp_token_id: TokenIdentifier<M>,
+    lp_token_amount: BigUint<M>,
+    lp_supply: BigUint<M>,
+    first_token_reserves: BigUint<M>,
+    second_token_reserves: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[multiversx_sc::module]
 pub trait EventsModule:
     crate::config::ConfigModule
@@ -103,6 +167,29 @@ pub trait EventsModule:
                 block: self.blockchain().get_block_nonce(),
                 epoch,
                 timestamp: self.blockchain().get_block_timestamp(),
+                lp_fee_growth: context.lp_fee_growth,
+            },
+        )
+    }
+
+    fn emit_swap_fee_event(&self, context: &SwapContext<Self::Api>) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.swap_fee_event(
+            &context.input_token_id,
+            &caller,
+            epoch,
+            &SwapFeeEvent {
+                caller: caller.clone(),
+                token_id_in: context.input_token_id.clone(),
+                token_amount_in: context.final_input_amount.clone(),
+                total_fee_percent: context.total_fee_percent,
+                total_fee_amount: &context.final_input_amount * context.total_fee_percent
+                    / MAX_PERCENTAGE,
+                special_fee_amount: context.fee_amount.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
             },
         )
     }
@@ -192,6 +279,93 @@ pub trait EventsModule:
         )
     }
 
+    fn emit_price_deviation_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        safe_price_first_token_reserves: BigUint,
+        safe_price_second_token_reserves: BigUint,
+        deviation_percent: u64,
+        max_deviation_percent: u64,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.price_deviation_event(
+            &storage_cache.first_token_id,
+            &storage_cache.second_token_id,
+            epoch,
+            &PriceDeviationEvent {
+                caller,
+                first_token_id: storage_cache.first_token_id.clone(),
+                second_token_id: storage_cache.second_token_id.clone(),
+                first_token_reserves: storage_cache.first_token_reserve.clone(),
+                second_token_reserves: storage_cache.second_token_reserve.clone(),
+                safe_price_first_token_reserves,
+                safe_price_second_token_reserves,
+                deviation_percent,
+                max_deviation_percent,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_flash_swap_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        payment_out: EsdtTokenPayment,
+        first_token_amount_in: BigUint,
+        second_token_amount_in: BigUint,
+        first_token_fee_amount: BigUint,
+        second_token_fee_amount: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.flash_swap_event(
+            &payment_out.token_identifier,
+            &caller,
+            epoch,
+            &FlashSwapEvent {
+                caller: caller.clone(),
+                token_id_out: payment_out.token_identifier.clone(),
+                token_amount_out: payment_out.amount,
+                first_token_amount_in,
+                second_token_amount_in,
+                first_token_fee_amount,
+                second_token_fee_amount,
+                first_token_reserves: storage_cache.first_token_reserve.clone(),
+                second_token_reserves: storage_cache.second_token_reserve.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_protocol_fee_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        treasury: ManagedAddress,
+        lp_token_amount: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.protocol_fee_event(
+            &treasury,
+            epoch,
+            &ProtocolFeeEvent {
+                treasury: treasury.clone(),
+                lp_token_id: storage_cache.lp_token_id.clone(),
+                lp_token_amount,
+                lp_supply: storage_cache.lp_token_supply.clone(),
+                first_token_reserves: storage_cache.first_token_reserve.clone(),
+                second_token_reserves: storage_cache.second_token_reserve.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
     #[event("swap")]
     fn swap_event(
         &self,
@@ -202,6 +376,15 @@ pub trait EventsModule:
         swap_event: &SwapEvent<Self::Api>,
     );
 
+    #[event("swap_fee")]
+    fn swap_fee_event(
+        &self,
+        #[indexed] token_in: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        swap_fee_event: &SwapFeeEvent<Self::Api>,
+    );
+
     #[event("swap_no_fee_and_forward")]
     fn swap_no_fee_and_forward_event(
         &self,
@@ -230,4 +413,30 @@ pub trait EventsModule:
         #[indexed] epoch: u64,
         remove_liquidity_event: &RemoveLiquidityEvent<Self::Api>,
     );
+
+    #[event("price_deviation")]
+    fn price_deviation_event(
+        &self,
+        #[indexed] first_token: &TokenIdentifier,
+        #[indexed] second_token: &TokenIdentifier,
+        #[indexed] epoch: u64,
+        price_deviation_event: &PriceDeviationEvent<Self::Api>,
+    );
+
+    #[event("flash_swap")]
+    fn flash_swap_event(
+        &self,
+        #[indexed] token_out: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        flash_swap_event: &FlashSwapEvent<Self::Api>,
+    );
+
+    #[event("protocol_fee")]
+    fn protocol_fee_event(
+        &self,
+        #[indexed] treasury: &ManagedAddress,
+        #[indexed] epoch: u64,
+        protocol_fee_event: &ProtocolFeeEvent<Self::Api>,
+    );
 }
@@ -3,14 +3,21 @@ multiversx_sc::derive_imports!();
 
 use super::amm;
 use super::config;
+use super::dynamic_fee;
+use super::dynamic_fee_reader;
 use super::errors::*;
+use super::events;
 use super::liquidity_pool;
+use super::read_pair_storage;
+use super::safe_price_reader;
+use super::stable_swap;
 use crate::config::MAX_PERCENTAGE;
 use crate::contexts::base::StorageCache;
 use crate::contexts::base::SwapTokensOrder;
 
 use common_structs::TokenPair;
 use fees_collector::fees_accumulation::ProxyTrait as _;
+use pausable::State;
 
 mod self_proxy {
     multiversx_sc::imports!();
@@ -28,6 +35,12 @@ pub trait FeeModule:
     config::ConfigModule
     + liquidity_pool::LiquidityPoolModule
     + amm::AmmModule
+    + dynamic_fee::DynamicFeeModule
+    + safe_price_reader::SafePriceReaderModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + read_pair_storage::ReadPairStorageModule
+    + stable_swap::StableSwapModule
+    + events::EventsModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
@@ -108,6 +121,7 @@ pub trait FeeModule:
         fees_collector_cut_percentage: u64,
     ) {
         self.require_caller_has_owner_permissions();
+        require!(!self.is_protocol_fee_enabled(), ERROR_PROTOCOL_FEE_ENABLED);
         require!(
             self.blockchain().is_smart_contract(&fees_collector_address),
             "Invalid fees collector address"
@@ -117,11 +131,266 @@ pub trait FeeModule:
             "Invalid fees percentage"
         );
 
+        // the cut buffered so far goes to the previous fees collector
+        self.distribute_fees_collector_cut();
+
         self.fees_collector_address().set(&fees_collector_address);
         self.fees_collector_cut_percentage()
             .set(fees_collector_cut_percentage);
     }
 
+    /// Alternative to the fee destinations, in the style of the Uniswap v2 protocol fee:
+    /// the whole swap fee stays in the reserves, and `share_percent` of the liquidity growth
+    /// it brings is minted as LP tokens to `treasury_address` whenever liquidity is added
+    /// or removed. No fee is sent out during the swaps.
+    #[endpoint(setProtocolFee)]
+    fn set_protocol_fee(&self, treasury_address: ManagedAddress, share_percent: u64) {
+        self.require_caller_has_owner_permissions();
+        require!(!self.is_fee_enabled(), ERROR_FEE_DESTINATIONS_SET);
+        require!(
+            share_percent > 0 && share_percent < MAX_PERCENTAGE,
+            ERROR_BAD_PERCENTS
+        );
+
+        // the growth so far is settled with the previous settings
+        let mut storage_cache = StorageCache::new(self);
+        self.mint_protocol_fee(&mut storage_cache);
+
+        self.protocol_fee_treasury().set(&treasury_address);
+        self.protocol_fee_share_percent().set(share_percent);
+        self.update_protocol_fee_root_k(&storage_cache);
+    }
+
+    #[endpoint(disableProtocolFee)]
+    fn disable_protocol_fee(&self) {
+        self.require_caller_has_owner_permissions();
+        require!(self.is_protocol_fee_enabled(), ERROR_PROTOCOL_FEE_NOT_ENABLED);
+
+        let mut storage_cache = StorageCache::new(self);
+        self.mint_protocol_fee(&mut storage_cache);
+
+        self.protocol_fee_treasury().clear();
+        self.protocol_fee_share_percent().clear();
+        self.protocol_fee_root_k_last().clear();
+    }
+
+    #[view(isProtocolFeeEnabled)]
+    fn is_protocol_fee_enabled(&self) -> bool {
+        !self.protocol_fee_treasury().is_empty()
+    }
+
+    /// The LP tokens the treasury would receive on the next liquidity change.
+    #[view(getPendingProtocolFee)]
+    fn get_pending_protocol_fee(&self) -> BigUint {
+        if !self.is_protocol_fee_enabled() {
+            return BigUint::zero();
+        }
+
+        let first_token_reserve = self.pair_reserve(&self.first_token_id().get()).get();
+        let second_token_reserve = self.pair_reserve(&self.second_token_id().get()).get();
+        self.compute_protocol_fee_liquidity(
+            &first_token_reserve,
+            &second_token_reserve,
+            &self.lp_token_supply().get(),
+        )
+    }
+
+    /// Called before the liquidity changes, the root of k is then updated afterwards.
+    fn mint_protocol_fee(&self, storage_cache: &mut StorageCache<Self>) {
+        if !self.is_protocol_fee_enabled() {
+            return;
+        }
+
+        let liquidity = self.compute_protocol_fee_liquidity(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+            &storage_cache.lp_token_supply,
+        );
+        if liquidity == 0u64 {
+            return;
+        }
+
+        storage_cache.lp_token_supply += &liquidity;
+
+        let treasury_address = self.protocol_fee_treasury().get();
+        self.send()
+            .esdt_local_mint(&storage_cache.lp_token_id, 0, &liquidity);
+        self.send()
+            .direct_esdt(&treasury_address, &storage_cache.lp_token_id, 0, &liquidity);
+
+        self.emit_protocol_fee_event(storage_cache, treasury_address, liquidity);
+    }
+
+    fn update_protocol_fee_root_k(&self, storage_cache: &StorageCache<Self>) {
+        if !self.is_protocol_fee_enabled() {
+            return;
+        }
+
+        let root_k = self.get_liquidity_root(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        self.protocol_fee_root_k_last().set(root_k);
+    }
+
+    /// supply * (root_k - root_k_last) * share / (root_k * (MAX - share) + root_k_last * share)
+    fn compute_protocol_fee_liquidity(
+        &self,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+        lp_token_supply: &BigUint,
+    ) -> BigUint {
+        let root_k_last = self.protocol_fee_root_k_last().get();
+        if root_k_last == 0u64 || lp_token_supply == &0u64 {
+            return BigUint::zero();
+        }
+
+        let root_k = self.get_liquidity_root(first_token_reserve, second_token_reserve);
+        if root_k <= root_k_last {
+            return BigUint::zero();
+        }
+
+        let share_percent = self.protocol_fee_share_percent().get();
+        let numerator = lp_token_supply * &(&root_k - &root_k_last) * share_percent;
+        let denominator = root_k * (MAX_PERCENTAGE - share_percent) + root_k_last * share_percent;
+
+        numerator / denominator
+    }
+
+    /// The stable swap invariant D already grows linearly with the reserves.
+    fn get_liquidity_root(
+        &self,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> BigUint {
+        if self.is_stable_swap() {
+            return self.compute_stable_invariant(first_token_reserve, second_token_reserve);
+        }
+
+        (first_token_reserve * second_token_reserve).sqrt()
+    }
+
+    /// While enabled, the fees are kept in the contract, per destination, instead of being
+    /// forwarded on every swap. They are then sent in bulk by distributeAccumulatedFees.
+    #[endpoint(setFeeAccumulation)]
+    fn set_fee_accumulation(&self, enabled: bool) {
+        self.require_caller_has_owner_permissions();
+        if !enabled {
+            let mut storage_cache = StorageCache::new(self);
+            self.distribute_all_accumulated_fees(&mut storage_cache);
+        }
+
+        self.fee_accumulation_enabled().set(enabled);
+    }
+
+    /// The conversions into the requested fee tokens are done at the spot price, so the
+    /// distribution is restricted to the owner and the admins, to keep it from being
+    /// sandwiched.
+    #[endpoint(distributeAccumulatedFees)]
+    fn distribute_accumulated_fees(&self) {
+        self.require_caller_has_owner_or_admin_permissions();
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            storage_cache.contract_state == State::Active,
+            ERROR_SWAP_NOT_ENABLED
+        );
+
+        self.distribute_all_accumulated_fees(&mut storage_cache);
+    }
+
+    fn distribute_all_accumulated_fees(&self, storage_cache: &mut StorageCache<Self>) {
+        self.distribute_fees_collector_cut();
+        for (fee_address, requested_fee_token) in self.destination_map().iter() {
+            self.distribute_destination_fees(storage_cache, &fee_address, &requested_fee_token);
+        }
+    }
+
+    fn distribute_fees_collector_cut(&self) {
+        if self.fees_collector_address().is_empty() {
+            return;
+        }
+
+        for fee_token in [self.first_token_id().get(), self.second_token_id().get()] {
+            let cut_amount = self.accumulated_fees_collector_cut(&fee_token).take();
+            if cut_amount > 0 {
+                self.send_fees_collector_cut(fee_token, cut_amount);
+            }
+        }
+    }
+
+    fn distribute_destination_fees(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        fee_address: &ManagedAddress,
+        requested_fee_token: &TokenIdentifier,
+    ) {
+        let fee_tokens = [
+            (
+                SwapTokensOrder::PoolOrder,
+                storage_cache.first_token_id.clone(),
+            ),
+            (
+                SwapTokensOrder::ReverseOrder,
+                storage_cache.second_token_id.clone(),
+            ),
+        ];
+        for (swap_tokens_order, fee_token) in fee_tokens {
+            let fee_slice = self.accumulated_fees(fee_address, &fee_token).take();
+            if fee_slice > 0 {
+                self.send_fee_slice(
+                    storage_cache,
+                    swap_tokens_order,
+                    &fee_token,
+                    &fee_slice,
+                    fee_address,
+                    requested_fee_token,
+                );
+            }
+        }
+    }
+
+    /// The fees not yet distributed, for every destination. The fees collector cut is listed
+    /// under the fees collector address.
+    #[view(getPendingFeeBalances)]
+    fn get_pending_fee_balances(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, EsdtTokenPayment>> {
+        let mut result = MultiValueEncoded::new();
+        let fee_tokens = [self.first_token_id().get(), self.second_token_id().get()];
+        if !self.fees_collector_address().is_empty() {
+            let fees_collector_address = self.fees_collector_address().get();
+            for fee_token in fee_tokens.iter() {
+                let amount = self.accumulated_fees_collector_cut(fee_token).get();
+                if amount > 0 {
+                    let payment = EsdtTokenPayment::new(fee_token.clone(), 0, amount);
+                    result.push((fees_collector_address.clone(), payment).into());
+                }
+            }
+        }
+
+        for fee_address in self.destination_map().keys() {
+            for fee_token in fee_tokens.iter() {
+                let amount = self.accumulated_fees(&fee_address, fee_token).get();
+                if amount > 0 {
+                    let payment = EsdtTokenPayment::new(fee_token.clone(), 0, amount);
+                    result.push((fee_address.clone(), payment).into());
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Total of the fees held for the destinations, which is not part of the reserves.
+    fn get_accumulated_fees_total(&self, fee_token: &TokenIdentifier) -> BigUint {
+        let mut total = self.accumulated_fees_collector_cut(fee_token).get();
+        for fee_address in self.destination_map().keys() {
+            total += self.accumulated_fees(&fee_address, fee_token).get();
+        }
+
+        total
+    }
+
     fn send_fee(
         &self,
         storage_cache: &mut StorageCache<Self>,
@@ -133,6 +402,7 @@ pub trait FeeModule:
             return;
         }
 
+        let accumulate_fees = self.fee_accumulation_enabled().get();
         let fees_collector_configured = !self.fees_collector_address().is_empty();
         let remaining_fee = if fees_collector_configured {
             let fees_collector_cut_percentage = self.fees_collector_cut_percentage().get();
@@ -140,7 +410,12 @@ pub trait FeeModule:
             let reminder = fee_amount - &cut_amount;
 
             if cut_amount > 0 {
-                self.send_fees_collector_cut(fee_token.clone(), cut_amount);
+                if accumulate_fees {
+                    self.accumulated_fees_collector_cut(fee_token)
+                        .update(|accumulated| *accumulated += &cut_amount);
+                } else {
+                    self.send_fees_collector_cut(fee_token.clone(), cut_amount);
+                }
             }
 
             reminder
@@ -159,6 +434,12 @@ pub trait FeeModule:
         }
 
         for (fee_address, fee_token_requested) in self.destination_map().iter() {
+            if accumulate_fees {
+                self.accumulated_fees(&fee_address, fee_token)
+                    .update(|accumulated| *accumulated += &fee_slice);
+                continue;
+            }
+
             self.send_fee_slice(
                 storage_cache,
                 swap_tokens_order,
@@ -354,6 +635,10 @@ pub trait FeeModule:
         fee_token: TokenIdentifier,
     ) {
         self.require_caller_has_owner_permissions();
+        require!(
+            !enabled || !self.is_protocol_fee_enabled(),
+            ERROR_PROTOCOL_FEE_ENABLED
+        );
         let is_dest = self
             .destination_map()
             .keys()
@@ -366,6 +651,9 @@ pub trait FeeModule:
             require!(is_dest, ERROR_NOT_FEE_DEST);
             let dest_fee_token = self.destination_map().get(&fee_to_address).unwrap();
             require!(fee_token == dest_fee_token, ERROR_BAD_TOKEN_FEE_DEST);
+
+            let mut storage_cache = StorageCache::new(self);
+            self.distribute_destination_fees(&mut storage_cache, &fee_to_address, &fee_token);
             self.destination_map().remove(&fee_to_address);
         }
     }
@@ -419,4 +707,34 @@ pub trait FeeModule:
 
     #[storage_mapper("whitelist")]
     fn whitelist(&self) -> SetMapper<ManagedAddress>;
+
+    #[view(getProtocolFeeTreasury)]
+    #[storage_mapper("protocol_fee_treasury")]
+    fn protocol_fee_treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getProtocolFeeSharePercent)]
+    #[storage_mapper("protocol_fee_share_percent")]
+    fn protocol_fee_share_percent(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("protocol_fee_root_k_last")]
+    fn protocol_fee_root_k_last(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(isFeeAccumulationEnabled)]
+    #[storage_mapper("fee_accumulation_enabled")]
+    fn fee_accumulation_enabled(&self) -> SingleValueMapper<bool>;
+
+    #[view(getAccumulatedFees)]
+    #[storage_mapper("accumulated_fees")]
+    fn accumulated_fees(
+        &self,
+        fee_address: &ManagedAddress,
+        fee_token: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getAccumulatedFeesCollectorCut)]
+    #[storage_mapper("accumulated_fees_collector_cut")]
+    fn accumulated_fees_collector_cut(
+        &self,
+        fee_token: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
 }
@@ -0,0 +1,128 @@
+multiversx_sc::imports!();
+
+use crate::{
+    amm,
+    config::{self, MAX_PERCENTAGE},
+    contexts::{base::StorageCache, swap::SwapContext},
+    dynamic_fee, dynamic_fee_reader, events, fee, liquidity_pool, read_pair_storage,
+    safe_price_reader, stable_swap,
+};
+
+pub const FEE_GROWTH_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+/// Tracks the fees left in the reserves for the LPs, as a cumulative amount per LP token,
+/// scaled by FEE_GROWTH_PRECISION. The LP token is fungible, so the snapshot of the
+/// accumulators has to be kept by the LP, or by the contract holding the LP tokens.
+#[multiversx_sc::module]
+pub trait FeeGrowthModule:
+    config::ConfigModule
+    + fee::FeeModule
+    + liquidity_pool::LiquidityPoolModule
+    + amm::AmmModule
+    + dynamic_fee::DynamicFeeModule
+    + safe_price_reader::SafePriceReaderModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + read_pair_storage::ReadPairStorageModule
+    + stable_swap::StableSwapModule
+    + events::EventsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    /// The part of the swap fee that stays in the reserves, i.e. without the special fee
+    /// sent to the fee destinations. Returns the accumulator growth.
+    fn accrue_swap_lp_fee(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        context: &SwapContext<Self::Api>,
+    ) -> BigUint {
+        let total_fee_amount =
+            &context.final_input_amount * context.total_fee_percent / MAX_PERCENTAGE;
+        if total_fee_amount <= context.fee_amount {
+            return BigUint::zero();
+        }
+
+        let lp_fee_amount = total_fee_amount - &context.fee_amount;
+        self.accrue_lp_fee(storage_cache, &context.input_token_id, &lp_fee_amount)
+    }
+
+    fn accrue_lp_fee(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        token_id: &TokenIdentifier,
+        lp_fee_amount: &BigUint,
+    ) -> BigUint {
+        if lp_fee_amount == &0u64 || storage_cache.lp_token_supply == 0u64 {
+            return BigUint::zero();
+        }
+
+        // the protocol fee share is later minted as LP tokens out of this growth
+        let lp_share_percent = MAX_PERCENTAGE - self.protocol_fee_share_percent().get();
+        let fee_growth = lp_fee_amount * lp_share_percent * FEE_GROWTH_PRECISION
+            / MAX_PERCENTAGE
+            / &storage_cache.lp_token_supply;
+        self.fee_growth_per_lp_token(token_id)
+            .update(|accumulated| *accumulated += &fee_growth);
+
+        fee_growth
+    }
+
+    /// The current accumulators, in the pool order. These are the snapshot values
+    /// for getFeesEarned.
+    #[view(getFeeGrowth)]
+    fn get_fee_growth(&self) -> MultiValue2<BigUint, BigUint> {
+        let first_fee_growth = self
+            .fee_growth_per_lp_token(&self.first_token_id().get())
+            .get();
+        let second_fee_growth = self
+            .fee_growth_per_lp_token(&self.second_token_id().get())
+            .get();
+
+        (first_fee_growth, second_fee_growth).into()
+    }
+
+    /// The fees earned by `lp_token_amount` since the given snapshot of getFeeGrowth.
+    #[view(getFeesEarned)]
+    fn get_fees_earned(
+        &self,
+        lp_token_amount: BigUint,
+        first_fee_growth_snapshot: BigUint,
+        second_fee_growth_snapshot: BigUint,
+    ) -> MultiValue2<EsdtTokenPayment, EsdtTokenPayment> {
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let first_fees_earned = self.compute_fees_earned(
+            &lp_token_amount,
+            &self.fee_growth_per_lp_token(&first_token_id).get(),
+            &first_fee_growth_snapshot,
+        );
+        let second_fees_earned = self.compute_fees_earned(
+            &lp_token_amount,
+            &self.fee_growth_per_lp_token(&second_token_id).get(),
+            &second_fee_growth_snapshot,
+        );
+
+        (
+            EsdtTokenPayment::new(first_token_id, 0, first_fees_earned),
+            EsdtTokenPayment::new(second_token_id, 0, second_fees_earned),
+        )
+            .into()
+    }
+
+    fn compute_fees_earned(
+        &self,
+        lp_token_amount: &BigUint,
+        fee_growth: &BigUint,
+        fee_growth_snapshot: &BigUint,
+    ) -> BigUint {
+        if fee_growth <= fee_growth_snapshot {
+            return BigUint::zero();
+        }
+
+        lp_token_amount * &(fee_growth - fee_growth_snapshot) / FEE_GROWTH_PRECISION
+    }
+
+    #[view(getFeeGrowthPerLpToken)]
+    #[storage_mapper("fee_growth_per_lp_token")]
+    fn fee_growth_per_lp_token(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
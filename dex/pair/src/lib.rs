@@ -4,17 +4,24 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 mod amm;
+pub mod circuit_breaker;
 pub mod config;
-mod contexts;
+pub mod contexts;
+pub mod dynamic_fee;
+pub mod dynamic_fee_reader;
 pub mod errors;
 mod events;
 pub mod fee;
+pub mod fee_growth;
 mod liquidity_pool;
 pub mod locking_wrapper;
 pub mod pair_actions;
+pub mod pair_hooks;
 pub mod read_pair_storage;
 pub mod safe_price;
+pub mod safe_price_reader;
 pub mod safe_price_view;
+pub mod stable_swap;
 
 use crate::errors::*;
 
@@ -29,13 +36,20 @@ use permissions_module::Permissions;
 #[multiversx_sc::contract]
 pub trait Pair<ContractReader>:
     amm::AmmModule
+    + stable_swap::StableSwapModule
+    + circuit_breaker::CircuitBreakerModule
+    + dynamic_fee::DynamicFeeModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
     + fee::FeeModule
+    + fee_growth::FeeGrowthModule
+    + pair_hooks::PairHooksModule
     + liquidity_pool::LiquidityPoolModule
     + config::ConfigModule
     + token_send::TokenSendModule
     + events::EventsModule
     + read_pair_storage::ReadPairStorageModule
     + safe_price::SafePriceModule
+    + safe_price_reader::SafePriceReaderModule
     + safe_price_view::SafePriceViewModule
     + contexts::output_builder::OutputBuilderModule
     + locking_wrapper::LockingWrapperModule
@@ -46,6 +60,8 @@ pub trait Pair<ContractReader>:
     + pair_actions::remove_liq::RemoveLiquidityModule
     + pair_actions::swap::SwapModule
     + pair_actions::views::ViewsModule
+    + pair_actions::zap::ZapModule
+    + pair_actions::flash_swap::FlashSwapModule
     + pair_actions::common_methods::CommonMethodsModule
     + utils::UtilsModule
 {
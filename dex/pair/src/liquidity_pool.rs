@@ -8,12 +8,22 @@ use crate::errors::*;
 
 use super::amm;
 use super::config;
+use super::dynamic_fee;
+use super::dynamic_fee_reader;
+use super::read_pair_storage;
+use super::safe_price_reader;
+use super::stable_swap;
 
 const MINIMUM_LIQUIDITY: u64 = 1_000;
 
 #[multiversx_sc::module]
 pub trait LiquidityPoolModule:
     amm::AmmModule
+    + dynamic_fee::DynamicFeeModule
+    + safe_price_reader::SafePriceReaderModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + read_pair_storage::ReadPairStorageModule
+    + stable_swap::StableSwapModule
     + config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
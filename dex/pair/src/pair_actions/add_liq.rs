@@ -1,7 +1,7 @@
 use crate::{
-    contexts::add_liquidity::AddLiquidityContext, StorageCache, ERROR_BAD_PAYMENT_TOKENS,
-    ERROR_INITIAL_LIQUIDITY_NOT_ADDED, ERROR_INVALID_ARGS, ERROR_K_INVARIANT_FAILED,
-    ERROR_LP_TOKEN_NOT_ISSUED, ERROR_NOT_ACTIVE,
+    contexts::add_liquidity::AddLiquidityContext, pair_hooks::PairHookType, StorageCache,
+    ERROR_BAD_PAYMENT_TOKENS, ERROR_INITIAL_LIQUIDITY_NOT_ADDED, ERROR_INVALID_ARGS,
+    ERROR_K_INVARIANT_FAILED, ERROR_LP_TOKEN_NOT_ISSUED, ERROR_NOT_ACTIVE,
 };
 
 use super::common_result_types::AddLiquidityResultType;
@@ -12,10 +12,17 @@ multiversx_sc::imports!();
 pub trait AddLiquidityModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::dynamic_fee_reader::DynamicFeeReaderModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::stable_swap::StableSwapModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
     + crate::safe_price::SafePriceModule
+    + crate::safe_price_reader::SafePriceReaderModule
+    + crate::fee::FeeModule
+    + crate::pair_hooks::PairHooksModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
@@ -67,6 +74,7 @@ pub trait AddLiquidityModule:
             &storage_cache.second_token_reserve,
             &storage_cache.lp_token_supply,
         );
+        self.mint_protocol_fee(&mut storage_cache);
 
         let initial_k = self.calculate_k_constant(
             &storage_cache.first_token_reserve,
@@ -79,6 +87,13 @@ pub trait AddLiquidityModule:
             first_token_amount_min,
             second_token_amount_min,
         );
+        self.call_hooks(
+            PairHookType::BeforeAddLiquidity,
+            &caller,
+            &add_liq_context,
+            &storage_cache,
+        );
+
         self.set_optimal_amounts(&mut add_liq_context, &storage_cache);
 
         add_liq_context.liq_added = if storage_cache.lp_token_supply == 0u64 {
@@ -100,6 +115,7 @@ pub trait AddLiquidityModule:
             &storage_cache.second_token_reserve,
         );
         require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
+        self.update_protocol_fee_root_k(&storage_cache);
 
         self.send()
             .esdt_local_mint(&storage_cache.lp_token_id, 0, &add_liq_context.liq_added);
@@ -116,6 +132,13 @@ pub trait AddLiquidityModule:
 
         self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
 
+        self.call_hooks(
+            PairHookType::AfterAddLiquidity,
+            &caller,
+            &add_liq_context,
+            &storage_cache,
+        );
+
         let output = self.build_add_liq_results(&storage_cache, &add_liq_context);
 
         self.emit_add_liquidity_event(&storage_cache, add_liq_context);
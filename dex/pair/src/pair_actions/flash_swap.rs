@@ -0,0 +1,301 @@
+use crate::{
+    config::{MAX_FEE_PERCENTAGE, MAX_PERCENTAGE},
+    contexts::swap::SwapContext,
+    pair_hooks::PairHookType,
+    StorageCache, SwapTokensOrder, ERROR_BAD_PERCENTS, ERROR_FLASH_SWAP_CALLER_NOT_SC,
+    ERROR_FLASH_SWAP_DISABLED, ERROR_INVALID_ARGS, ERROR_K_INVARIANT_FAILED,
+    ERROR_NOT_ENOUGH_RESERVE, ERROR_NOT_WHITELISTED, ERROR_SWAP_NOT_ENABLED, ERROR_UNKNOWN_TOKEN,
+};
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait FlashSwapModule:
+    crate::liquidity_pool::LiquidityPoolModule
+    + crate::amm::AmmModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::dynamic_fee_reader::DynamicFeeReaderModule
+    + crate::stable_swap::StableSwapModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::events::EventsModule
+    + crate::safe_price::SafePriceModule
+    + crate::safe_price_reader::SafePriceReaderModule
+    + crate::fee::FeeModule
+    + crate::fee_growth::FeeGrowthModule
+    + crate::pair_hooks::PairHooksModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + super::common_methods::CommonMethodsModule
+{
+    /// Enables flash swaps. The fee is taken from the repaid amounts and is split like the
+    /// regular swap fee: the special fee share goes to the fee destinations, if any are
+    /// configured, and the rest to the reserves.
+    #[endpoint(setFlashSwapFee)]
+    fn set_flash_swap_fee(&self, fee_percent: u64) {
+        self.require_caller_has_owner_permissions();
+        require!(fee_percent <= MAX_FEE_PERCENTAGE, ERROR_BAD_PERCENTS);
+
+        self.flash_swap_fee_percent().set(fee_percent);
+    }
+
+    #[endpoint(disableFlashSwaps)]
+    fn disable_flash_swaps(&self) {
+        self.require_caller_has_owner_permissions();
+        self.flash_swap_fee_percent().clear();
+    }
+
+    #[endpoint(setFlashSwapWhitelistOnly)]
+    fn set_flash_swap_whitelist_only(&self, whitelist_only: bool) {
+        self.require_caller_has_owner_permissions();
+        self.flash_swap_whitelist_only().set(whitelist_only);
+    }
+
+    #[endpoint(addFlashSwapBorrowers)]
+    fn add_flash_swap_borrowers(&self, borrowers: MultiValueEncoded<ManagedAddress>) {
+        self.require_caller_has_owner_permissions();
+        for borrower in borrowers {
+            let _ = self.flash_swap_borrowers().insert(borrower);
+        }
+    }
+
+    #[endpoint(removeFlashSwapBorrowers)]
+    fn remove_flash_swap_borrowers(&self, borrowers: MultiValueEncoded<ManagedAddress>) {
+        self.require_caller_has_owner_permissions();
+        for borrower in borrowers {
+            let _ = self.flash_swap_borrowers().swap_remove(&borrower);
+        }
+    }
+
+    /// Sends `amount_out` of `token_out` to the calling contract through `callback_endpoint`.
+    /// Before the callback returns, the caller has to send back any of the two tokens, so that
+    /// the k invariant still holds once the flash fee is deducted from the repaid amounts.
+    /// The swap hooks see a swap of the other token for the net amount of `token_out`.
+    #[endpoint(flashSwap)]
+    fn flash_swap(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out: BigUint,
+        callback_endpoint: ManagedBuffer,
+        callback_args: MultiValueEncoded<ManagedBuffer>,
+    ) {
+        require!(amount_out > 0u64, ERROR_INVALID_ARGS);
+        require!(
+            !self.flash_swap_fee_percent().is_empty(),
+            ERROR_FLASH_SWAP_DISABLED
+        );
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.blockchain().is_smart_contract(&caller),
+            ERROR_FLASH_SWAP_CALLER_NOT_SC
+        );
+        require!(
+            !self.flash_swap_whitelist_only().get()
+                || self.flash_swap_borrowers().contains(&caller),
+            ERROR_NOT_WHITELISTED
+        );
+
+        // the reserves are written to storage before the callback, but the pair
+        // cannot be used again until the callback returns
+        let (initial_k, first_token_surplus, second_token_surplus, mut swap_context) = {
+            let mut storage_cache = StorageCache::new(self);
+            require!(
+                self.can_swap(storage_cache.contract_state),
+                ERROR_SWAP_NOT_ENABLED
+            );
+
+            self.update_safe_price(
+                &storage_cache.first_token_reserve,
+                &storage_cache.second_token_reserve,
+                &storage_cache.lp_token_supply,
+            );
+
+            let initial_k = self.calculate_k_constant(
+                &storage_cache.first_token_reserve,
+                &storage_cache.second_token_reserve,
+            );
+            let first_token_surplus = self.get_unaccounted_balance(
+                &storage_cache.first_token_id,
+                &storage_cache.first_token_reserve,
+            );
+            let second_token_surplus = self.get_unaccounted_balance(
+                &storage_cache.second_token_id,
+                &storage_cache.second_token_reserve,
+            );
+
+            let token_in = if token_out == storage_cache.first_token_id {
+                storage_cache.second_token_id.clone()
+            } else if token_out == storage_cache.second_token_id {
+                storage_cache.first_token_id.clone()
+            } else {
+                sc_panic!(ERROR_UNKNOWN_TOKEN);
+            };
+            let swap_tokens_order = storage_cache.get_swap_tokens_order(&token_in, &token_out);
+            let swap_context = SwapContext::new(
+                token_in,
+                BigUint::zero(),
+                token_out.clone(),
+                amount_out.clone(),
+                swap_tokens_order,
+            );
+            self.call_hooks(
+                PairHookType::BeforeSwap,
+                &caller,
+                &swap_context,
+                &storage_cache,
+            );
+
+            let reserve_out = storage_cache.get_mut_reserve_out(swap_tokens_order);
+            require!(*reserve_out > amount_out, ERROR_NOT_ENOUGH_RESERVE);
+            *reserve_out -= &amount_out;
+
+            (
+                initial_k,
+                first_token_surplus,
+                second_token_surplus,
+                swap_context,
+            )
+        };
+
+        self.hook_call_in_progress().set(true);
+        self.tx()
+            .to(&caller)
+            .raw_call(callback_endpoint)
+            .arguments_raw(callback_args.to_arg_buffer())
+            .single_esdt(&token_out, 0, &amount_out)
+            .sync_call();
+        self.hook_call_in_progress().clear();
+
+        let mut storage_cache = StorageCache::new(self);
+        let first_token_amount_in = self.get_unaccounted_balance(
+            &storage_cache.first_token_id,
+            &storage_cache.first_token_reserve,
+        ) - first_token_surplus;
+        let second_token_amount_in = self.get_unaccounted_balance(
+            &storage_cache.second_token_id,
+            &storage_cache.second_token_reserve,
+        ) - second_token_surplus;
+
+        let fee_percent = self.flash_swap_fee_percent().get();
+        let first_token_fee = &first_token_amount_in * fee_percent / MAX_PERCENTAGE;
+        let second_token_fee = &second_token_amount_in * fee_percent / MAX_PERCENTAGE;
+
+        storage_cache.first_token_reserve += &first_token_amount_in - &first_token_fee;
+        storage_cache.second_token_reserve += &second_token_amount_in - &second_token_fee;
+        let new_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
+
+        let first_token_special_fee = self.get_flash_swap_special_fee(&first_token_fee);
+        let second_token_special_fee = self.get_flash_swap_special_fee(&second_token_fee);
+        let first_token_lp_fee = &first_token_fee - &first_token_special_fee;
+        let second_token_lp_fee = &second_token_fee - &second_token_special_fee;
+        storage_cache.first_token_reserve += &first_token_lp_fee;
+        storage_cache.second_token_reserve += &second_token_lp_fee;
+
+        let first_token_id = storage_cache.first_token_id.clone();
+        let second_token_id = storage_cache.second_token_id.clone();
+        let first_token_lp_fee_growth =
+            self.accrue_lp_fee(&storage_cache, &first_token_id, &first_token_lp_fee);
+        let second_token_lp_fee_growth =
+            self.accrue_lp_fee(&storage_cache, &second_token_id, &second_token_lp_fee);
+
+        self.send_fee(
+            &mut storage_cache,
+            SwapTokensOrder::PoolOrder,
+            &first_token_id,
+            &first_token_special_fee,
+        );
+        self.send_fee(
+            &mut storage_cache,
+            SwapTokensOrder::ReverseOrder,
+            &second_token_id,
+            &second_token_special_fee,
+        );
+
+        self.check_price_deviation(&storage_cache);
+
+        let (amount_in, amount_out_repaid, special_fee, lp_fee_growth) =
+            match swap_context.swap_tokens_order {
+                SwapTokensOrder::PoolOrder => (
+                    &first_token_amount_in,
+                    &second_token_amount_in,
+                    first_token_special_fee,
+                    first_token_lp_fee_growth,
+                ),
+                SwapTokensOrder::ReverseOrder => (
+                    &second_token_amount_in,
+                    &first_token_amount_in,
+                    second_token_special_fee,
+                    second_token_lp_fee_growth,
+                ),
+            };
+        swap_context.final_input_amount = amount_in.clone();
+        swap_context.final_output_amount = if amount_out > *amount_out_repaid {
+            &amount_out - amount_out_repaid
+        } else {
+            BigUint::zero()
+        };
+        swap_context.fee_amount = special_fee;
+        swap_context.total_fee_percent = fee_percent;
+        swap_context.lp_fee_growth = lp_fee_growth;
+        self.call_hooks(
+            PairHookType::AfterSwap,
+            &caller,
+            &swap_context,
+            &storage_cache,
+        );
+
+        self.emit_flash_swap_event(
+            &storage_cache,
+            EsdtTokenPayment::new(token_out, 0, amount_out),
+            first_token_amount_in,
+            second_token_amount_in,
+            first_token_fee,
+            second_token_fee,
+        );
+    }
+
+    /// The part of the flash fee that goes to the fee destinations, in the same proportion
+    /// as the special fee is to the total fee of the regular swaps.
+    fn get_flash_swap_special_fee(&self, flash_fee: &BigUint) -> BigUint {
+        let total_fee_percent = self.total_fee_percent().get();
+        if !self.is_fee_enabled() || total_fee_percent == 0 {
+            return BigUint::zero();
+        }
+
+        flash_fee * self.special_fee_percent().get() / total_fee_percent
+    }
+
+    /// The part of the contract balance that is neither in the reserves nor held for the
+    /// fee destinations.
+    fn get_unaccounted_balance(&self, token_id: &TokenIdentifier, reserve: &BigUint) -> BigUint {
+        let balance =
+            self.blockchain()
+                .get_esdt_balance(&self.blockchain().get_sc_address(), token_id, 0);
+        let accounted = reserve + &self.get_accumulated_fees_total(token_id);
+        if balance > accounted {
+            balance - accounted
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    #[view(getFlashSwapFeePercent)]
+    #[storage_mapper("flash_swap_fee_percent")]
+    fn flash_swap_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(isFlashSwapWhitelistOnly)]
+    #[storage_mapper("flash_swap_whitelist_only")]
+    fn flash_swap_whitelist_only(&self) -> SingleValueMapper<bool>;
+
+    #[view(getFlashSwapBorrowers)]
+    #[storage_mapper("flash_swap_borrowers")]
+    fn flash_swap_borrowers(&self) -> UnorderedSetMapper<ManagedAddress>;
+}
@@ -14,9 +14,15 @@ multiversx_sc::imports!();
 pub trait InitialLiquidityModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::safe_price_reader::SafePriceReaderModule
+    + crate::dynamic_fee_reader::DynamicFeeReaderModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::stable_swap::StableSwapModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
+    + crate::fee::FeeModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
@@ -62,6 +68,7 @@ pub trait InitialLiquidityModule:
             second_token_optimal_amount,
             &mut storage_cache,
         );
+        self.update_protocol_fee_root_k(&storage_cache);
 
         self.send()
             .esdt_local_mint(&storage_cache.lp_token_id, 0, &liq_added);
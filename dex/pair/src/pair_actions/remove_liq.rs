@@ -1,6 +1,6 @@
 use crate::{
-    contexts::remove_liquidity::RemoveLiquidityContext, StorageCache, SwapTokensOrder,
-    ERROR_BAD_PAYMENT_TOKENS, ERROR_INVALID_ARGS, ERROR_K_INVARIANT_FAILED,
+    contexts::remove_liquidity::RemoveLiquidityContext, pair_hooks::PairHookType, StorageCache,
+    SwapTokensOrder, ERROR_BAD_PAYMENT_TOKENS, ERROR_INVALID_ARGS, ERROR_K_INVARIANT_FAILED,
     ERROR_LP_TOKEN_NOT_ISSUED, ERROR_NOT_ACTIVE, ERROR_NOT_WHITELISTED, ERROR_SLIPPAGE_ON_REMOVE,
 };
 
@@ -12,11 +12,17 @@ multiversx_sc::imports!();
 pub trait RemoveLiquidityModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::dynamic_fee_reader::DynamicFeeReaderModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::stable_swap::StableSwapModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
     + crate::safe_price::SafePriceModule
+    + crate::safe_price_reader::SafePriceReaderModule
     + crate::fee::FeeModule
+    + crate::pair_hooks::PairHooksModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
@@ -58,6 +64,7 @@ pub trait RemoveLiquidityModule:
             &storage_cache.second_token_reserve,
             &storage_cache.lp_token_supply,
         );
+        self.mint_protocol_fee(&mut storage_cache);
 
         let initial_k = self.calculate_k_constant(
             &storage_cache.first_token_reserve,
@@ -69,6 +76,13 @@ pub trait RemoveLiquidityModule:
             first_token_amount_min,
             second_token_amount_min,
         );
+        self.call_hooks(
+            PairHookType::BeforeRemoveLiquidity,
+            &caller,
+            &remove_liq_context,
+            &storage_cache,
+        );
+
         self.pool_remove_liquidity(&mut remove_liq_context, &mut storage_cache);
 
         let new_k = self.calculate_k_constant(
@@ -76,6 +90,7 @@ pub trait RemoveLiquidityModule:
             &storage_cache.second_token_reserve,
         );
         require!(new_k <= initial_k, ERROR_K_INVARIANT_FAILED);
+        self.update_protocol_fee_root_k(&storage_cache);
 
         self.burn(
             &storage_cache.lp_token_id,
@@ -98,6 +113,13 @@ pub trait RemoveLiquidityModule:
 
         self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
 
+        self.call_hooks(
+            PairHookType::AfterRemoveLiquidity,
+            &caller,
+            &remove_liq_context,
+            &storage_cache,
+        );
+
         self.emit_remove_liquidity_event(&storage_cache, remove_liq_context);
 
         self.build_remove_liq_results(&output_payments)
@@ -125,10 +147,15 @@ pub trait RemoveLiquidityModule:
             &storage_cache.second_token_reserve,
             &storage_cache.lp_token_supply,
         );
+        self.mint_protocol_fee(&mut storage_cache);
 
-        let mut remove_liq_context =
-            RemoveLiquidityContext::new(payment.amount.clone(), BigUint::from(1u64), BigUint::from(1u64));
+        let mut remove_liq_context = RemoveLiquidityContext::new(
+            payment.amount.clone(),
+            BigUint::from(1u64),
+            BigUint::from(1u64),
+        );
         self.pool_remove_liquidity(&mut remove_liq_context, &mut storage_cache);
+        self.update_protocol_fee_root_k(&storage_cache);
 
         self.burn(
             &storage_cache.lp_token_id,
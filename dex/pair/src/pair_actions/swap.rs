@@ -1,7 +1,7 @@
 use crate::{
-    contexts::swap::SwapContext, StorageCache, ERROR_INVALID_ARGS, ERROR_K_INVARIANT_FAILED,
-    ERROR_NOT_ENOUGH_RESERVE, ERROR_NOT_WHITELISTED, ERROR_SLIPPAGE_EXCEEDED,
-    ERROR_SWAP_NOT_ENABLED, ERROR_ZERO_AMOUNT,
+    contexts::swap::SwapContext, pair_hooks::PairHookType, StorageCache, ERROR_INVALID_ARGS,
+    ERROR_K_INVARIANT_FAILED, ERROR_NOT_ENOUGH_RESERVE, ERROR_NOT_WHITELISTED,
+    ERROR_SLIPPAGE_EXCEEDED, ERROR_SWAP_NOT_ENABLED, ERROR_ZERO_AMOUNT,
 };
 
 use super::common_result_types::{SwapTokensFixedInputResultType, SwapTokensFixedOutputResultType};
@@ -20,11 +20,20 @@ pub enum SwapType {
 pub trait SwapModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::dynamic_fee_reader::DynamicFeeReaderModule
+    + crate::stable_swap::StableSwapModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::read_pair_storage::ReadPairStorageModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
     + crate::safe_price::SafePriceModule
+    + crate::safe_price_reader::SafePriceReaderModule
     + crate::fee::FeeModule
+    + crate::pair_hooks::PairHooksModule
+    + crate::fee_growth::FeeGrowthModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
@@ -131,6 +140,14 @@ pub trait SwapModule:
             amount_out_min,
             swap_tokens_order,
         );
+        let caller = self.blockchain().get_caller();
+        self.call_hooks(
+            PairHookType::BeforeSwap,
+            &caller,
+            &swap_context,
+            &storage_cache,
+        );
+
         self.perform_swap_fixed_input(&mut swap_context, &mut storage_cache);
 
         let new_k = self.calculate_k_constant(
@@ -139,6 +156,8 @@ pub trait SwapModule:
         );
         require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
 
+        self.check_price_deviation(&storage_cache);
+
         if swap_context.fee_amount > 0 {
             self.send_fee(
                 &mut storage_cache,
@@ -147,8 +166,8 @@ pub trait SwapModule:
                 &swap_context.fee_amount,
             );
         }
+        swap_context.lp_fee_growth = self.accrue_swap_lp_fee(&storage_cache, &swap_context);
 
-        let caller = self.blockchain().get_caller();
         let output_payments = self.build_swap_output_payments(&swap_context);
 
         require!(
@@ -158,6 +177,16 @@ pub trait SwapModule:
 
         self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
 
+        self.call_hooks(
+            PairHookType::AfterSwap,
+            &caller,
+            &swap_context,
+            &storage_cache,
+        );
+
+        if self.is_dynamic_fee_enabled() {
+            self.emit_swap_fee_event(&swap_context);
+        }
         self.emit_swap_event(&storage_cache, swap_context);
 
         self.build_swap_fixed_input_results(output_payments)
@@ -203,6 +232,14 @@ pub trait SwapModule:
             amount_out,
             swap_tokens_order,
         );
+        let caller = self.blockchain().get_caller();
+        self.call_hooks(
+            PairHookType::BeforeSwap,
+            &caller,
+            &swap_context,
+            &storage_cache,
+        );
+
         self.perform_swap_fixed_output(&mut swap_context, &mut storage_cache);
 
         let new_k = self.calculate_k_constant(
@@ -211,6 +248,8 @@ pub trait SwapModule:
         );
         require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
 
+        self.check_price_deviation(&storage_cache);
+
         if swap_context.fee_amount > 0 {
             self.send_fee(
                 &mut storage_cache,
@@ -219,12 +258,22 @@ pub trait SwapModule:
                 &swap_context.fee_amount,
             );
         }
+        swap_context.lp_fee_growth = self.accrue_swap_lp_fee(&storage_cache, &swap_context);
 
-        let caller = self.blockchain().get_caller();
         let output_payments = self.build_swap_output_payments(&swap_context);
 
         self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
 
+        self.call_hooks(
+            PairHookType::AfterSwap,
+            &caller,
+            &swap_context,
+            &storage_cache,
+        );
+
+        if self.is_dynamic_fee_enabled() {
+            self.emit_swap_fee_event(&swap_context);
+        }
         self.emit_swap_event(&storage_cache, swap_context);
 
         self.build_swap_fixed_output_results(output_payments)
@@ -240,8 +289,13 @@ pub trait SwapModule:
         let reserve_in = storage_cache.get_reserve_in(context.swap_tokens_order);
         let reserve_out = storage_cache.get_reserve_out(context.swap_tokens_order);
 
-        let amount_out_optimal =
-            self.get_amount_out(&context.input_token_amount, reserve_in, reserve_out);
+        context.total_fee_percent = self.get_effective_total_fee_percent();
+        let amount_out_optimal = self.get_amount_out_with_fee(
+            &context.input_token_amount,
+            reserve_in,
+            reserve_out,
+            context.total_fee_percent,
+        );
         require!(
             amount_out_optimal >= context.output_token_amount,
             ERROR_SLIPPAGE_EXCEEDED
@@ -274,8 +328,13 @@ pub trait SwapModule:
         let reserve_in = storage_cache.get_reserve_in(context.swap_tokens_order);
         let reserve_out = storage_cache.get_reserve_out(context.swap_tokens_order);
 
-        let amount_in_optimal =
-            self.get_amount_in(&context.output_token_amount, reserve_in, reserve_out);
+        context.total_fee_percent = self.get_effective_total_fee_percent();
+        let amount_in_optimal = self.get_amount_in_with_fee(
+            &context.output_token_amount,
+            reserve_in,
+            reserve_out,
+            context.total_fee_percent,
+        );
         require!(
             amount_in_optimal <= context.input_token_amount,
             ERROR_SLIPPAGE_EXCEEDED
@@ -6,10 +6,17 @@ multiversx_sc::imports!();
 pub trait ViewsModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::dynamic_fee_reader::DynamicFeeReaderModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::stable_swap::StableSwapModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::safe_price_view::SafePriceViewModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
     + crate::safe_price::SafePriceModule
+    + crate::safe_price_reader::SafePriceReaderModule
     + crate::fee::FeeModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
@@ -61,6 +68,53 @@ pub trait ViewsModule:
         }
     }
 
+    /// Whether a fixed input swap would currently go through, for contracts that cannot
+    /// afford a failed swap. The pair hooks can still veto it.
+    #[view(canSwapFixedInput)]
+    fn can_swap_fixed_input(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+        amount_out_min: BigUint,
+    ) -> bool {
+        if amount_in == 0u64
+            || amount_out_min == 0u64
+            || !self.can_swap(self.state().get())
+            || self.hook_call_in_progress().get()
+        {
+            return false;
+        }
+
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let mut first_token_reserve = self.pair_reserve(&first_token_id).get();
+        let mut second_token_reserve = self.pair_reserve(&second_token_id).get();
+        let (reserve_in, reserve_out) = if token_in == first_token_id {
+            (&mut first_token_reserve, &mut second_token_reserve)
+        } else if token_in == second_token_id {
+            (&mut second_token_reserve, &mut first_token_reserve)
+        } else {
+            return false;
+        };
+        if *reserve_in == 0u64 || *reserve_out == 0u64 {
+            return false;
+        }
+
+        let amount_out = self.get_amount_out(&amount_in, reserve_in, reserve_out);
+        if amount_out < amount_out_min || amount_out >= *reserve_out {
+            return false;
+        }
+
+        let mut amount_in_after_fee = amount_in;
+        if self.is_fee_enabled() {
+            amount_in_after_fee -= self.get_special_fee_from_input(&amount_in_after_fee);
+        }
+        *reserve_in += amount_in_after_fee;
+        *reserve_out -= amount_out;
+
+        !self.is_price_deviation_reverted(&first_token_reserve, &second_token_reserve)
+    }
+
     #[view(getAmountIn)]
     fn get_amount_in_view(&self, token_wanted: TokenIdentifier, amount_wanted: BigUint) -> BigUint {
         require!(amount_wanted > 0u64, ERROR_ZERO_AMOUNT);
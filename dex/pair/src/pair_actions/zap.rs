@@ -0,0 +1,399 @@
+use crate::{
+    config::MAX_PERCENTAGE,
+    contexts::{
+        add_liquidity::AddLiquidityContext, remove_liquidity::RemoveLiquidityContext,
+        swap::SwapContext,
+    },
+    pair_hooks::PairHookType,
+    StorageCache, SwapTokensOrder, ERROR_BAD_PAYMENT_TOKENS, ERROR_INITIAL_LIQUIDITY_NOT_ADDED,
+    ERROR_INVALID_ARGS, ERROR_K_INVARIANT_FAILED, ERROR_LP_TOKEN_NOT_ISSUED,
+    ERROR_SLIPPAGE_EXCEEDED, ERROR_SWAP_NOT_ENABLED, ERROR_UNKNOWN_TOKEN, ERROR_ZAP_AMOUNT_TOO_LOW,
+    ERROR_ZAP_OUTPUT_LOCKED,
+};
+
+use super::common_result_types::AddLiquidityResultType;
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait ZapModule:
+    crate::liquidity_pool::LiquidityPoolModule
+    + crate::amm::AmmModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::dynamic_fee_reader::DynamicFeeReaderModule
+    + crate::stable_swap::StableSwapModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::contexts::output_builder::OutputBuilderModule
+    + crate::locking_wrapper::LockingWrapperModule
+    + crate::events::EventsModule
+    + crate::safe_price::SafePriceModule
+    + crate::safe_price_reader::SafePriceReaderModule
+    + crate::fee::FeeModule
+    + crate::fee_growth::FeeGrowthModule
+    + crate::pair_hooks::PairHooksModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + super::common_methods::CommonMethodsModule
+    + super::swap::SwapModule
+    + utils::UtilsModule
+{
+    /// Swaps part of the payment into the other pool token, so that the rest of the
+    /// payment and the swap output match the new reserves ratio, then adds both as
+    /// liquidity. Any rounding leftovers are sent back.
+    #[payable("*")]
+    #[endpoint(addLiquiditySingleToken)]
+    fn add_liquidity_single_token(
+        &self,
+        lp_token_amount_min: BigUint,
+    ) -> AddLiquidityResultType<Self::Api> {
+        require!(lp_token_amount_min > 0, ERROR_INVALID_ARGS);
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt().clone();
+        require!(payment.amount > 0, ERROR_BAD_PAYMENT_TOKENS);
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+        require!(
+            storage_cache.lp_token_id.is_valid_esdt_identifier(),
+            ERROR_LP_TOKEN_NOT_ISSUED
+        );
+        require!(
+            storage_cache.lp_token_supply != 0,
+            ERROR_INITIAL_LIQUIDITY_NOT_ADDED
+        );
+
+        let other_token_id = if payment.token_identifier == storage_cache.first_token_id {
+            storage_cache.second_token_id.clone()
+        } else if payment.token_identifier == storage_cache.second_token_id {
+            storage_cache.first_token_id.clone()
+        } else {
+            sc_panic!(ERROR_BAD_PAYMENT_TOKENS);
+        };
+        let swap_tokens_order =
+            storage_cache.get_swap_tokens_order(&payment.token_identifier, &other_token_id);
+
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+            &storage_cache.lp_token_supply,
+        );
+
+        let swap_amount = self.get_zap_swap_amount(
+            &payment.amount,
+            storage_cache.get_reserve_in(swap_tokens_order),
+            storage_cache.get_reserve_out(swap_tokens_order),
+        );
+        require!(
+            swap_amount > 0u64 && swap_amount < payment.amount,
+            ERROR_ZAP_AMOUNT_TOO_LOW
+        );
+
+        let swap_context = self.perform_zap_swap(
+            &mut storage_cache,
+            &caller,
+            payment.token_identifier.clone(),
+            swap_amount.clone(),
+            other_token_id,
+            swap_tokens_order,
+        );
+
+        let payment_amount_left = &payment.amount - &swap_amount;
+        let (first_payment, second_payment) = match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => (
+                EsdtTokenPayment::new(payment.token_identifier, 0, payment_amount_left),
+                EsdtTokenPayment::new(
+                    swap_context.output_token_id.clone(),
+                    0,
+                    swap_context.final_output_amount.clone(),
+                ),
+            ),
+            SwapTokensOrder::ReverseOrder => (
+                EsdtTokenPayment::new(
+                    swap_context.output_token_id.clone(),
+                    0,
+                    swap_context.final_output_amount.clone(),
+                ),
+                EsdtTokenPayment::new(payment.token_identifier, 0, payment_amount_left),
+            ),
+        };
+        self.emit_swap_event(&storage_cache, swap_context);
+        self.mint_protocol_fee(&mut storage_cache);
+
+        let mut add_liq_context = AddLiquidityContext::new(
+            first_payment,
+            second_payment,
+            BigUint::from(1u64),
+            BigUint::from(1u64),
+        );
+        self.call_hooks(
+            PairHookType::BeforeAddLiquidity,
+            &caller,
+            &add_liq_context,
+            &storage_cache,
+        );
+
+        self.set_optimal_amounts(&mut add_liq_context, &storage_cache);
+        add_liq_context.liq_added = self.pool_add_liquidity(
+            &add_liq_context.first_token_optimal_amount,
+            &add_liq_context.second_token_optimal_amount,
+            &mut storage_cache,
+        );
+        self.update_protocol_fee_root_k(&storage_cache);
+        require!(
+            add_liq_context.liq_added >= lp_token_amount_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+
+        self.send()
+            .esdt_local_mint(&storage_cache.lp_token_id, 0, &add_liq_context.liq_added);
+
+        let lp_payment = EsdtTokenPayment::new(
+            storage_cache.lp_token_id.clone(),
+            0,
+            add_liq_context.liq_added.clone(),
+        );
+
+        let mut output_payments =
+            self.build_add_liq_output_payments(&storage_cache, &add_liq_context);
+        output_payments.push(lp_payment);
+
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        self.call_hooks(
+            PairHookType::AfterAddLiquidity,
+            &caller,
+            &add_liq_context,
+            &storage_cache,
+        );
+
+        let output = self.build_add_liq_results(&storage_cache, &add_liq_context);
+
+        self.emit_add_liquidity_event(&storage_cache, add_liq_context);
+
+        output
+    }
+
+    /// Removes the liquidity and swaps the other pool token into `token_out`.
+    #[payable("*")]
+    #[endpoint(removeLiquidityToSingleToken)]
+    fn remove_liquidity_to_single_token(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment {
+        require!(amount_out_min > 0, ERROR_INVALID_ARGS);
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+        require!(
+            storage_cache.lp_token_id.is_valid_esdt_identifier(),
+            ERROR_LP_TOKEN_NOT_ISSUED
+        );
+        require!(
+            payment.token_identifier == storage_cache.lp_token_id && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+        // the swap output would otherwise skip the locking
+        require!(
+            !self.should_generate_locked_asset(),
+            ERROR_ZAP_OUTPUT_LOCKED
+        );
+
+        let token_in = if token_out == storage_cache.first_token_id {
+            storage_cache.second_token_id.clone()
+        } else if token_out == storage_cache.second_token_id {
+            storage_cache.first_token_id.clone()
+        } else {
+            sc_panic!(ERROR_UNKNOWN_TOKEN);
+        };
+        let swap_tokens_order = storage_cache.get_swap_tokens_order(&token_in, &token_out);
+
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+            &storage_cache.lp_token_supply,
+        );
+        self.mint_protocol_fee(&mut storage_cache);
+
+        let mut remove_liq_context = RemoveLiquidityContext::new(
+            payment.amount.clone(),
+            BigUint::from(1u64),
+            BigUint::from(1u64),
+        );
+        self.call_hooks(
+            PairHookType::BeforeRemoveLiquidity,
+            &caller,
+            &remove_liq_context,
+            &storage_cache,
+        );
+
+        self.pool_remove_liquidity(&mut remove_liq_context, &mut storage_cache);
+        self.update_protocol_fee_root_k(&storage_cache);
+
+        self.burn(
+            &storage_cache.lp_token_id,
+            &remove_liq_context.lp_token_payment_amount,
+        );
+
+        let (swap_amount, amount_kept) = match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => (
+                remove_liq_context.first_token_amount_removed.clone(),
+                remove_liq_context.second_token_amount_removed.clone(),
+            ),
+            SwapTokensOrder::ReverseOrder => (
+                remove_liq_context.second_token_amount_removed.clone(),
+                remove_liq_context.first_token_amount_removed.clone(),
+            ),
+        };
+        self.call_hooks(
+            PairHookType::AfterRemoveLiquidity,
+            &caller,
+            &remove_liq_context,
+            &storage_cache,
+        );
+        self.emit_remove_liquidity_event(&storage_cache, remove_liq_context);
+
+        let swap_context = self.perform_zap_swap(
+            &mut storage_cache,
+            &caller,
+            token_in,
+            swap_amount,
+            token_out.clone(),
+            swap_tokens_order,
+        );
+
+        let amount_out = amount_kept + &swap_context.final_output_amount;
+        require!(amount_out >= amount_out_min, ERROR_SLIPPAGE_EXCEEDED);
+
+        self.emit_swap_event(&storage_cache, swap_context);
+
+        let output_payment = EsdtTokenPayment::new(token_out, 0, amount_out);
+        self.send().direct_esdt(
+            &caller,
+            &output_payment.token_identifier,
+            0,
+            &output_payment.amount,
+        );
+
+        output_payment
+    }
+
+    /// Swaps against the pool reserves, with the same checks and fee handling as a
+    /// regular fixed input swap, but keeps the output in the contract.
+    fn perform_zap_swap(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        caller: &ManagedAddress,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+        token_out: TokenIdentifier,
+        swap_tokens_order: SwapTokensOrder,
+    ) -> SwapContext<Self::Api> {
+        let initial_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let mut swap_context = SwapContext::new(
+            token_in,
+            amount_in,
+            token_out,
+            BigUint::from(1u64),
+            swap_tokens_order,
+        );
+        self.call_hooks(
+            PairHookType::BeforeSwap,
+            caller,
+            &swap_context,
+            storage_cache,
+        );
+
+        self.perform_swap_fixed_input(&mut swap_context, storage_cache);
+
+        let new_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
+
+        self.check_price_deviation(storage_cache);
+
+        if swap_context.fee_amount > 0 {
+            self.send_fee(
+                storage_cache,
+                swap_context.swap_tokens_order,
+                &swap_context.input_token_id,
+                &swap_context.fee_amount,
+            );
+        }
+        swap_context.lp_fee_growth = self.accrue_swap_lp_fee(storage_cache, &swap_context);
+        self.call_hooks(
+            PairHookType::AfterSwap,
+            caller,
+            &swap_context,
+            storage_cache,
+        );
+
+        swap_context
+    }
+
+    /// The amount to swap so that the output and the remaining input are in the
+    /// post-swap reserves ratio, for the constant product curve with fee `f`:
+    /// s = (sqrt(r^2 * (2 - f)^2 + 4 * (1 - f) * a * r) - r * (2 - f)) / (2 * (1 - f))
+    fn get_zap_swap_amount(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+    ) -> BigUint {
+        if self.is_stable_swap() {
+            return self.get_stable_zap_swap_amount(amount_in, reserve_in, reserve_out);
+        }
+
+        let fee_complement = MAX_PERCENTAGE - self.get_effective_total_fee_percent();
+        let fee_sum = MAX_PERCENTAGE + fee_complement;
+
+        let reserve_term = reserve_in * fee_sum;
+        let discriminant = &reserve_term * &reserve_term
+            + BigUint::from(4 * fee_complement * MAX_PERCENTAGE) * amount_in * reserve_in;
+
+        (discriminant.sqrt() - reserve_term) / (2 * fee_complement)
+    }
+
+    /// The stable curve has no closed form, so the swap amount is searched for: the
+    /// largest amount for which the remaining input still covers the post-swap ratio.
+    fn get_stable_zap_swap_amount(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+    ) -> BigUint {
+        let mut low = BigUint::zero();
+        let mut high = amount_in.clone();
+        while &low + 1u64 < high {
+            let swap_amount = (&low + &high) / 2u64;
+            let amount_out = self.get_amount_out(&swap_amount, reserve_in, reserve_out);
+            let new_reserve_in = reserve_in + &swap_amount;
+            let new_reserve_out = reserve_out - &amount_out;
+            if (amount_in - &swap_amount) * new_reserve_out >= amount_out * new_reserve_in {
+                low = swap_amount;
+            } else {
+                high = swap_amount;
+            }
+        }
+
+        low
+    }
+}
@@ -0,0 +1,126 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{config, contexts::base::StorageCache, errors::*};
+
+pub const MAX_HOOKS_PER_TYPE: usize = 5;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PairHookType {
+    BeforeSwap,
+    AfterSwap,
+    BeforeAddLiquidity,
+    AfterAddLiquidity,
+    BeforeRemoveLiquidity,
+    AfterRemoveLiquidity,
+}
+
+impl PairHookType {
+    /// The endpoint called on the hook contract, with the caller of the pair action
+    /// and the action context as arguments.
+    pub fn endpoint_name(&self) -> &'static [u8] {
+        match self {
+            PairHookType::BeforeSwap => b"beforeSwap",
+            PairHookType::AfterSwap => b"afterSwap",
+            PairHookType::BeforeAddLiquidity => b"beforeAddLiquidity",
+            PairHookType::AfterAddLiquidity => b"afterAddLiquidity",
+            PairHookType::BeforeRemoveLiquidity => b"beforeRemoveLiquidity",
+            PairHookType::AfterRemoveLiquidity => b"afterRemoveLiquidity",
+        }
+    }
+
+    pub fn is_pre_hook(&self) -> bool {
+        matches!(
+            self,
+            PairHookType::BeforeSwap
+                | PairHookType::BeforeAddLiquidity
+                | PairHookType::BeforeRemoveLiquidity
+        )
+    }
+}
+
+/// External contracts called around the swaps, flash swaps included, and the liquidity
+/// changes, in the order they were added. Pre-hooks have to return `true` for the action
+/// to go on, anything else vetoes it. The pair cannot be used from within a hook, but its
+/// storage is up to date when a hook reads it.
+#[multiversx_sc::module]
+pub trait PairHooksModule:
+    config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    #[endpoint(addHook)]
+    fn add_hook(&self, hook_type: PairHookType, hook_address: ManagedAddress, gas_limit: u64) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            self.blockchain().is_smart_contract(&hook_address),
+            ERROR_HOOK_NOT_A_CONTRACT
+        );
+        require!(gas_limit > 0, ERROR_INVALID_ARGS);
+
+        let mut hooks_mapper = self.hooks(hook_type);
+        require!(
+            hooks_mapper.len() < MAX_HOOKS_PER_TYPE,
+            ERROR_TOO_MANY_HOOKS
+        );
+        let is_new = hooks_mapper.insert(hook_address, gas_limit).is_none();
+        require!(is_new, ERROR_HOOK_ALREADY_ADDED);
+    }
+
+    #[endpoint(removeHook)]
+    fn remove_hook(&self, hook_type: PairHookType, hook_address: ManagedAddress) {
+        self.require_caller_has_owner_permissions();
+        let is_removed = self.hooks(hook_type).remove(&hook_address).is_some();
+        require!(is_removed, ERROR_HOOK_NOT_FOUND);
+    }
+
+    fn call_hooks<T: multiversx_sc::codec::TopEncodeMulti>(
+        &self,
+        hook_type: PairHookType,
+        caller: &ManagedAddress,
+        context: &T,
+        storage_cache: &StorageCache<Self>,
+    ) {
+        let hooks_mapper = self.hooks(hook_type);
+        if hooks_mapper.is_empty() {
+            return;
+        }
+
+        storage_cache.commit();
+        self.hook_call_in_progress().set(true);
+        for (hook_address, gas_limit) in hooks_mapper.iter() {
+            let result = self
+                .tx()
+                .to(&hook_address)
+                .gas(gas_limit)
+                .raw_call(hook_type.endpoint_name())
+                .argument(caller)
+                .argument(context)
+                .returns(ReturnsRawResult)
+                .sync_call();
+            if hook_type.is_pre_hook() {
+                let allowed = !result.is_empty() && result.get(0).parse_as_u64() == Some(1);
+                require!(allowed, ERROR_ACTION_VETOED);
+            }
+        }
+        self.hook_call_in_progress().clear();
+    }
+
+    #[view(getHooks)]
+    fn get_hooks(
+        &self,
+        hook_type: PairHookType,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, u64>> {
+        let mut result = MultiValueEncoded::new();
+        for (hook_address, gas_limit) in self.hooks(hook_type).iter() {
+            result.push((hook_address, gas_limit).into());
+        }
+
+        result
+    }
+
+    #[storage_mapper("hooks")]
+    fn hooks(&self, hook_type: PairHookType) -> MapMapper<ManagedAddress, u64>;
+}
@@ -1,6 +1,9 @@
 use multiversx_sc::storage::StorageKey;
 
+use crate::dynamic_fee::DynamicFeeConfig;
 use crate::safe_price::PriceObservation;
+use crate::stable_swap::{AmmMode, AmplificationRamp};
+use pausable::State;
 
 multiversx_sc::imports!();
 
@@ -10,6 +13,13 @@ pub static SECOND_TOKEN_ID_STORAGE_KEY: &[u8] = b"second_token_id";
 pub static SAFE_PRICE_CURRENT_INDEX_STORAGE_KEY: &[u8] = b"safe_price_current_index";
 pub static PRICE_OBSERVATIONS_STORAGE_KEY: &[u8] = b"price_observations";
 pub static PAIR_RESERVE_BASE_STORAGE_KEY: &[u8] = b"reserve";
+pub static PAIR_STATE_STORAGE_KEY: &[u8] = b"state";
+pub static LP_TOKEN_ID_STORAGE_KEY: &[u8] = b"lpTokenIdentifier";
+pub static TOTAL_FEE_PERCENT_STORAGE_KEY: &[u8] = b"total_fee_percent";
+pub static SPECIAL_FEE_PERCENT_STORAGE_KEY: &[u8] = b"special_fee_percent";
+pub static AMM_MODE_STORAGE_KEY: &[u8] = b"ammMode";
+pub static AMPLIFICATION_RAMP_STORAGE_KEY: &[u8] = b"amplificationRamp";
+pub static DYNAMIC_FEE_CONFIG_STORAGE_KEY: &[u8] = b"dynamic_fee_config";
 
 #[multiversx_sc::module]
 pub trait ReadPairStorageModule {
@@ -73,4 +83,74 @@ pub trait ReadPairStorageModule {
 
         SingleValueMapper::<_, _, ManagedAddress>::new_from_address(pair_address, storage_key)
     }
+
+    fn get_pair_state_mapper(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> SingleValueMapper<State, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            pair_address,
+            StorageKey::new(PAIR_STATE_STORAGE_KEY),
+        )
+    }
+
+    fn get_lp_token_id_mapper(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> SingleValueMapper<TokenIdentifier, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            pair_address,
+            StorageKey::new(LP_TOKEN_ID_STORAGE_KEY),
+        )
+    }
+
+    fn get_total_fee_percent_mapper(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> SingleValueMapper<u64, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            pair_address,
+            StorageKey::new(TOTAL_FEE_PERCENT_STORAGE_KEY),
+        )
+    }
+
+    fn get_special_fee_percent_mapper(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> SingleValueMapper<u64, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            pair_address,
+            StorageKey::new(SPECIAL_FEE_PERCENT_STORAGE_KEY),
+        )
+    }
+
+    fn get_amm_mode_mapper(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> SingleValueMapper<AmmMode, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            pair_address,
+            StorageKey::new(AMM_MODE_STORAGE_KEY),
+        )
+    }
+
+    fn get_amplification_ramp_mapper(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> SingleValueMapper<AmplificationRamp, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            pair_address,
+            StorageKey::new(AMPLIFICATION_RAMP_STORAGE_KEY),
+        )
+    }
+
+    fn get_dynamic_fee_config_mapper(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> SingleValueMapper<DynamicFeeConfig, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            pair_address,
+            StorageKey::new(DYNAMIC_FEE_CONFIG_STORAGE_KEY),
+        )
+    }
 }
@@ -3,11 +3,18 @@ multiversx_sc::derive_imports!();
 
 use multiversx_sc::codec::{NestedDecodeInput, TopDecodeInput};
 
-use crate::{amm, config, errors::ERROR_SAFE_PRICE_CURRENT_INDEX};
+use crate::{
+    amm, config, dynamic_fee, dynamic_fee_reader, errors::ERROR_SAFE_PRICE_CURRENT_INDEX,
+    read_pair_storage, safe_price_reader, stable_swap,
+};
 
 pub type Round = u64;
 
 pub const MAX_OBSERVATIONS: usize = 65_536; // 2^{16} records, to optimise binary search
+pub const LN_PRECISION: u64 = 1_000_000_000;
+pub const LN_2: i64 = 693_147_181;
+const EXP_PRECISION: u64 = 1_000_000_000_000_000_000;
+const EXP_MAX_ITERATIONS: u64 = 30;
 
 #[type_abi]
 #[derive(ManagedVecItem, Clone, TopEncode, NestedEncode, Debug)]
@@ -17,6 +24,8 @@ pub struct PriceObservation<M: ManagedTypeApi> {
     pub weight_accumulated: u64,
     pub recording_round: Round,
     pub lp_supply_accumulated: BigUint<M>,
+    pub log_price_accumulated: BigInt<M>,
+    pub log_price_weight_accumulated: u64,
 }
 
 impl<M: ManagedTypeApi> Default for PriceObservation<M> {
@@ -27,6 +36,8 @@ impl<M: ManagedTypeApi> Default for PriceObservation<M> {
             weight_accumulated: 0,
             recording_round: 0,
             lp_supply_accumulated: BigUint::zero(),
+            log_price_accumulated: BigInt::zero(),
+            log_price_weight_accumulated: 0,
         }
     }
 }
@@ -54,6 +65,13 @@ impl<M: ManagedTypeApi> NestedDecode for PriceObservation<M> {
             BigUint::zero()
         };
 
+        // observations recorded before the log price tracking start from zero
+        let (log_price_accumulated, log_price_weight_accumulated) = if !input.is_depleted() {
+            (BigInt::dep_decode(input)?, u64::dep_decode(input)?)
+        } else {
+            (BigInt::zero(), 0)
+        };
+
         if !input.is_depleted() {
             return Result::Err(DecodeError::INPUT_TOO_LONG);
         }
@@ -64,15 +82,63 @@ impl<M: ManagedTypeApi> NestedDecode for PriceObservation<M> {
             weight_accumulated,
             recording_round,
             lp_supply_accumulated,
+            log_price_accumulated,
+            log_price_weight_accumulated,
         })
     }
 }
 
+/// Natural logarithm of the first token price, expressed in second tokens, with LN_PRECISION.
+pub fn ln_price<M: ManagedTypeApi>(first_reserve: &BigUint<M>, second_reserve: &BigUint<M>) -> i64 {
+    ln_raw(second_reserve) - ln_raw(first_reserve)
+}
+
+/// Multiplies the amount by e^(ln_value / LN_PRECISION).
+pub fn mul_by_exp<M: ManagedTypeApi>(amount: &BigUint<M>, ln_value: i64) -> BigUint<M> {
+    // e^x = 2^k * e^r, with r in [0, ln 2)
+    let k = ln_value.div_euclid(LN_2);
+    let r = ln_value.rem_euclid(LN_2) as u64;
+
+    let scaled_r = BigUint::from(r) * (EXP_PRECISION / LN_PRECISION);
+    let mut term = BigUint::from(EXP_PRECISION);
+    let mut exp_r = term.clone();
+    for i in 1..=EXP_MAX_ITERATIONS {
+        term = term * &scaled_r / EXP_PRECISION / i;
+        if term == 0 {
+            break;
+        }
+        exp_r += &term;
+    }
+
+    let result = amount * &exp_r / EXP_PRECISION;
+    if k >= 0 {
+        result << k as usize
+    } else {
+        result >> k.unsigned_abs() as usize
+    }
+}
+
+fn ln_raw<M: ManagedTypeApi>(value: &BigUint<M>) -> i64 {
+    if value <= &1u64 {
+        return 0;
+    }
+
+    match value.ln() {
+        Some(ln_value) => ln_value.into_raw_units().to_u64().unwrap_or_default() as i64,
+        None => 0,
+    }
+}
+
 #[multiversx_sc::module]
 pub trait SafePriceModule:
     config::ConfigModule
     + token_send::TokenSendModule
     + amm::AmmModule
+    + dynamic_fee::DynamicFeeModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
+    + stable_swap::StableSwapModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
 {
@@ -86,6 +152,9 @@ pub trait SafePriceModule:
             return;
         }
 
+        let (first_token_reserve, second_token_reserve) =
+            self.get_spot_price_reserves(first_token_reserve, second_token_reserve);
+
         let current_round = self.blockchain().get_block_round();
         let safe_price_current_index = self.safe_price_current_index().get();
         require!(
@@ -107,8 +176,8 @@ pub trait SafePriceModule:
 
         let new_price_observation = self.compute_new_observation(
             current_round,
-            first_token_reserve,
-            second_token_reserve,
+            &first_token_reserve,
+            &second_token_reserve,
             lp_supply,
             &last_price_observation,
         );
@@ -122,32 +191,6 @@ pub trait SafePriceModule:
         self.safe_price_current_index().set(new_index);
     }
 
-    fn compute_new_observation(
-        &self,
-        new_round: Round,
-        new_first_reserve: &BigUint,
-        new_second_reserve: &BigUint,
-        new_lp_supply: &BigUint,
-        current_price_observation: &PriceObservation<Self::Api>,
-    ) -> PriceObservation<Self::Api> {
-        let new_weight = if current_price_observation.recording_round == 0 {
-            1
-        } else {
-            new_round - current_price_observation.recording_round
-        };
-
-        let mut new_price_observation = current_price_observation.clone();
-        new_price_observation.first_token_reserve_accumulated +=
-            BigUint::from(new_weight) * new_first_reserve;
-        new_price_observation.second_token_reserve_accumulated +=
-            BigUint::from(new_weight) * new_second_reserve;
-        new_price_observation.lp_supply_accumulated += BigUint::from(new_weight) * new_lp_supply;
-        new_price_observation.weight_accumulated += new_weight;
-        new_price_observation.recording_round = new_round;
-
-        new_price_observation
-    }
-
     #[storage_mapper("price_observations")]
     fn price_observations(&self) -> VecMapper<PriceObservation<Self::Api>>;
 
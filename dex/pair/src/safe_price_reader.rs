@@ -0,0 +1,444 @@
+multiversx_sc::imports!();
+
+use common_errors::{ERROR_BAD_INPUT_TOKEN, ERROR_PARAMETERS};
+use core::cmp::Ordering;
+
+use crate::{
+    errors::{
+        ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST, ERROR_SAFE_PRICE_SAME_ROUNDS,
+        ERROR_STABLE_SWAP_NOT_CONVERGING,
+    },
+    read_pair_storage,
+    safe_price::{ln_price, PriceObservation, Round, MAX_OBSERVATIONS},
+    stable_swap::{get_ramp_amplification, get_spot_price_reserves, AmmMode},
+};
+
+pub const DEFAULT_SAFE_PRICE_ROUNDS_OFFSET: u64 = 10 * 60;
+
+pub struct PriceObservationWeightedAmounts<M: ManagedTypeApi> {
+    pub weighted_first_token_reserve: BigUint<M>,
+    pub weighted_second_token_reserve: BigUint<M>,
+    pub weighted_lp_supply: BigUint<M>,
+}
+
+/// Safe price computations that only read the pair storage, so they can be
+/// used both by the pair itself and by contracts on the same shard.
+#[multiversx_sc::module]
+pub trait SafePriceReaderModule: read_pair_storage::ReadPairStorageModule {
+    fn compute_safe_price(
+        &self,
+        pair_address: &ManagedAddress,
+        start_round: Round,
+        end_round: Round,
+        input_payment: EsdtTokenPayment,
+    ) -> EsdtTokenPayment {
+        let (first_price_observation, last_price_observation) =
+            self.get_interval_price_observations(pair_address, start_round, end_round);
+
+        self.compute_weighted_price(
+            pair_address,
+            input_payment,
+            &first_price_observation,
+            &last_price_observation,
+        )
+    }
+
+    fn compute_safe_price_by_default_offset(
+        &self,
+        pair_address: &ManagedAddress,
+        input_payment: EsdtTokenPayment,
+    ) -> EsdtTokenPayment {
+        let current_round = self.blockchain().get_block_round();
+        let default_offset_rounds = self.get_default_offset_rounds(pair_address, current_round);
+        let start_round = current_round - default_offset_rounds;
+        self.compute_safe_price(pair_address, start_round, current_round, input_payment)
+    }
+
+    /// Time-weighted reserves and LP supply, over the same rounds as the default safe price.
+    fn compute_weighted_amounts_by_default_offset(
+        &self,
+        pair_address: &ManagedAddress,
+    ) -> PriceObservationWeightedAmounts<Self::Api> {
+        let current_round = self.blockchain().get_block_round();
+        let default_offset_rounds = self.get_default_offset_rounds(pair_address, current_round);
+        let start_round = current_round - default_offset_rounds;
+        let (first_price_observation, last_price_observation) =
+            self.get_interval_price_observations(pair_address, start_round, current_round);
+
+        self.compute_weighted_amounts(&first_price_observation, &last_price_observation)
+    }
+
+    /// The safe price needs at least one observation before the current round.
+    fn has_safe_price_observations(&self, pair_address: &ManagedAddress) -> bool {
+        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+        if price_observations.is_empty() {
+            return false;
+        }
+
+        let current_index = self
+            .get_safe_price_current_index_mapper(pair_address.clone())
+            .get();
+        let oldest_observation =
+            self.get_oldest_price_observation(current_index, &price_observations);
+        oldest_observation.recording_round < self.blockchain().get_block_round()
+    }
+
+    fn compute_weighted_price(
+        &self,
+        pair_address: &ManagedAddress,
+        input_payment: EsdtTokenPayment,
+        first_price_observation: &PriceObservation<Self::Api>,
+        last_price_observation: &PriceObservation<Self::Api>,
+    ) -> EsdtTokenPayment {
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+
+        let weighted_amounts =
+            self.compute_weighted_amounts(first_price_observation, last_price_observation);
+
+        if input_payment.token_identifier == first_token_id {
+            let output_amount = input_payment.amount
+                * weighted_amounts.weighted_second_token_reserve
+                / weighted_amounts.weighted_first_token_reserve;
+            EsdtTokenPayment::new(second_token_id, 0, output_amount)
+        } else if input_payment.token_identifier == second_token_id {
+            let output_amount = input_payment.amount
+                * weighted_amounts.weighted_first_token_reserve
+                / weighted_amounts.weighted_second_token_reserve;
+            EsdtTokenPayment::new(first_token_id, 0, output_amount)
+        } else {
+            sc_panic!(ERROR_BAD_INPUT_TOKEN);
+        }
+    }
+
+    fn get_interval_price_observations(
+        &self,
+        pair_address: &ManagedAddress,
+        start_round: Round,
+        end_round: Round,
+    ) -> (PriceObservation<Self::Api>, PriceObservation<Self::Api>) {
+        require!(end_round > start_round, ERROR_PARAMETERS);
+
+        let safe_price_current_index = self
+            .get_safe_price_current_index_mapper(pair_address.clone())
+            .get();
+        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+
+        let oldest_price_observation =
+            self.get_oldest_price_observation(safe_price_current_index, &price_observations);
+        require!(
+            oldest_price_observation.recording_round <= start_round,
+            ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
+        );
+
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+        let first_price_observation = self.get_price_observation(
+            pair_address,
+            &first_token_id,
+            &second_token_id,
+            safe_price_current_index,
+            &price_observations,
+            start_round,
+        );
+        let last_price_observation = self.get_price_observation(
+            pair_address,
+            &first_token_id,
+            &second_token_id,
+            safe_price_current_index,
+            &price_observations,
+            end_round,
+        );
+
+        (first_price_observation, last_price_observation)
+    }
+
+    fn get_price_observation(
+        &self,
+        pair_address: &ManagedAddress,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+        current_index: usize,
+        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>, ManagedAddress>,
+        search_round: Round,
+    ) -> PriceObservation<Self::Api> {
+        require!(
+            !price_observations.is_empty(),
+            ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
+        );
+
+        // Check if the requested price observation is the last one
+        let last_observation = price_observations.get(current_index);
+        if last_observation.recording_round == search_round {
+            return last_observation;
+        }
+
+        // Simulate a new price observation, based on the current reserves,
+        // in case the searched round is bigger than the last recording round
+        // The search round is limited to the current blockchain round
+        // As when recording, stable pairs use the reserves at their spot price
+        if last_observation.recording_round < search_round {
+            let current_round = self.blockchain().get_block_round();
+            require!(
+                search_round <= current_round,
+                ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
+            );
+
+            let first_token_reserve = self
+                .get_pair_reserve_mapper(pair_address.clone(), first_token_id)
+                .get();
+            let second_token_reserve = self
+                .get_pair_reserve_mapper(pair_address.clone(), second_token_id)
+                .get();
+            let (first_token_reserve, second_token_reserve) = self.get_pair_spot_price_reserves(
+                pair_address,
+                &first_token_reserve,
+                &second_token_reserve,
+            );
+            let current_lp_supply = self.get_lp_token_supply_mapper(pair_address.clone()).get();
+            return self.compute_new_observation(
+                search_round,
+                &first_token_reserve,
+                &second_token_reserve,
+                &current_lp_supply,
+                &last_observation,
+            );
+        }
+
+        let (mut price_observation, last_search_index) = self.price_observation_by_binary_search(
+            current_index,
+            price_observations,
+            search_round,
+        );
+
+        if price_observation.recording_round > 0 {
+            return price_observation;
+        }
+
+        price_observation = self.price_observation_by_linear_interpolation(
+            price_observations,
+            search_round,
+            last_search_index,
+        );
+
+        price_observation
+    }
+
+    fn get_pair_spot_price_reserves(
+        &self,
+        pair_address: &ManagedAddress,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> (BigUint, BigUint) {
+        if self.get_amm_mode_mapper(pair_address.clone()).get() != AmmMode::StableSwap {
+            return (first_token_reserve.clone(), second_token_reserve.clone());
+        }
+
+        let ramp = self
+            .get_amplification_ramp_mapper(pair_address.clone())
+            .get();
+        let amplification =
+            get_ramp_amplification::<Self::Api>(&ramp, self.blockchain().get_block_nonce());
+        get_spot_price_reserves(amplification, first_token_reserve, second_token_reserve)
+            .unwrap_or_else(|| sc_panic!(ERROR_STABLE_SWAP_NOT_CONVERGING))
+    }
+
+    fn get_oldest_price_observation(
+        &self,
+        current_index: usize,
+        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>, ManagedAddress>,
+    ) -> PriceObservation<Self::Api> {
+        require!(
+            !price_observations.is_empty(),
+            ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
+        );
+
+        // VecMapper index starts at 1
+        let mut oldest_observation_index = 1;
+        if price_observations.len() == MAX_OBSERVATIONS {
+            oldest_observation_index = (current_index % MAX_OBSERVATIONS) + 1
+        }
+        price_observations.get(oldest_observation_index)
+    }
+
+    fn price_observation_by_binary_search(
+        &self,
+        current_index: usize,
+        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>, ManagedAddress>,
+        search_round: Round,
+    ) -> (PriceObservation<Self::Api>, usize) {
+        let mut search_index = 1;
+        let mut left_index;
+        let mut right_index;
+        let observation_at_index_1 = price_observations.get(search_index);
+        if observation_at_index_1.recording_round <= search_round {
+            left_index = search_index;
+            right_index = current_index - 1;
+        } else {
+            left_index = current_index + 1;
+            right_index = price_observations.len();
+        }
+
+        while left_index <= right_index {
+            search_index = (left_index + right_index) / 2;
+            let price_observation = price_observations.get(search_index);
+            match price_observation.recording_round.cmp(&search_round) {
+                Ordering::Equal => return (price_observation, search_index),
+                Ordering::Less => left_index = search_index + 1,
+                Ordering::Greater => right_index = search_index - 1,
+            }
+        }
+
+        (PriceObservation::default(), search_index)
+    }
+
+    fn price_observation_by_linear_interpolation(
+        &self,
+        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>, ManagedAddress>,
+        search_round: Round,
+        search_index: usize,
+    ) -> PriceObservation<Self::Api> {
+        let last_found_observation = price_observations.get(search_index);
+        let left_observation;
+        let right_observation;
+        if last_found_observation.recording_round < search_round {
+            left_observation = last_found_observation;
+            let right_observation_index = (search_index % MAX_OBSERVATIONS) + 1;
+            right_observation = price_observations.get(right_observation_index);
+        } else {
+            let left_observation_index = if search_index == 1 {
+                MAX_OBSERVATIONS
+            } else {
+                search_index - 1
+            };
+            left_observation = price_observations.get(left_observation_index);
+            right_observation = last_found_observation;
+        };
+
+        // For a proper linear interpolation calculation, we compute the weights as follows
+        // Left observation has a weight equal to the remaining time, starting from the searched round until the end round
+        // Right observation has a weight equal to the elapsed time, from starting round until the searched round
+        let left_weight = right_observation.recording_round - search_round;
+        let right_weight = search_round - left_observation.recording_round;
+
+        let weight_sum = left_weight + right_weight;
+        let first_token_reserve_sum = BigUint::from(left_weight)
+            * left_observation.first_token_reserve_accumulated
+            + BigUint::from(right_weight) * right_observation.first_token_reserve_accumulated;
+        let second_token_reserve_sum = BigUint::from(left_weight)
+            * left_observation.second_token_reserve_accumulated
+            + BigUint::from(right_weight) * right_observation.second_token_reserve_accumulated;
+        let lp_supply_sum = BigUint::from(left_weight) * left_observation.lp_supply_accumulated
+            + BigUint::from(right_weight) * right_observation.lp_supply_accumulated;
+
+        let log_price_sum = BigInt::from(left_weight as i64)
+            * left_observation.log_price_accumulated
+            + BigInt::from(right_weight as i64) * right_observation.log_price_accumulated;
+        let log_price_weight_sum = left_weight * left_observation.log_price_weight_accumulated
+            + right_weight * right_observation.log_price_weight_accumulated;
+
+        let first_token_reserve_accumulated = first_token_reserve_sum / weight_sum;
+        let second_token_reserve_accumulated = second_token_reserve_sum / weight_sum;
+        let lp_supply_accumulated = lp_supply_sum / weight_sum;
+        let log_price_accumulated = log_price_sum / BigInt::from(weight_sum as i64);
+        let log_price_weight_accumulated = log_price_weight_sum / weight_sum;
+        let weight_accumulated =
+            left_observation.weight_accumulated + search_round - left_observation.recording_round;
+
+        PriceObservation {
+            first_token_reserve_accumulated,
+            second_token_reserve_accumulated,
+            weight_accumulated,
+            recording_round: search_round,
+            lp_supply_accumulated,
+            log_price_accumulated,
+            log_price_weight_accumulated,
+        }
+    }
+
+    fn compute_weighted_amounts(
+        &self,
+        first_price_observation: &PriceObservation<Self::Api>,
+        last_price_observation: &PriceObservation<Self::Api>,
+    ) -> PriceObservationWeightedAmounts<Self::Api> {
+        let weight_diff =
+            last_price_observation.weight_accumulated - first_price_observation.weight_accumulated;
+
+        require!(weight_diff > 0, ERROR_SAFE_PRICE_SAME_ROUNDS);
+
+        let first_token_reserve_diff = last_price_observation
+            .first_token_reserve_accumulated
+            .clone()
+            - first_price_observation
+                .first_token_reserve_accumulated
+                .clone();
+        let second_token_reserve_diff = last_price_observation
+            .second_token_reserve_accumulated
+            .clone()
+            - first_price_observation
+                .second_token_reserve_accumulated
+                .clone();
+
+        let weighted_first_token_reserve = first_token_reserve_diff / weight_diff;
+        let weighted_second_token_reserve = second_token_reserve_diff / weight_diff;
+
+        let weighted_lp_supply = if first_price_observation.lp_supply_accumulated > 0 {
+            let lp_supply_diff = &last_price_observation.lp_supply_accumulated
+                - &first_price_observation.lp_supply_accumulated;
+            lp_supply_diff / weight_diff
+        } else {
+            BigUint::zero()
+        };
+
+        PriceObservationWeightedAmounts {
+            weighted_first_token_reserve,
+            weighted_second_token_reserve,
+            weighted_lp_supply,
+        }
+    }
+
+    fn get_default_offset_rounds(&self, pair_address: &ManagedAddress, end_round: Round) -> u64 {
+        let safe_price_current_index = self
+            .get_safe_price_current_index_mapper(pair_address.clone())
+            .get();
+        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+        let oldest_price_observation =
+            self.get_oldest_price_observation(safe_price_current_index, &price_observations);
+
+        let mut default_offset_rounds = end_round - oldest_price_observation.recording_round;
+        if default_offset_rounds > DEFAULT_SAFE_PRICE_ROUNDS_OFFSET {
+            default_offset_rounds = DEFAULT_SAFE_PRICE_ROUNDS_OFFSET;
+        }
+
+        default_offset_rounds
+    }
+
+    fn compute_new_observation(
+        &self,
+        new_round: Round,
+        new_first_reserve: &BigUint,
+        new_second_reserve: &BigUint,
+        new_lp_supply: &BigUint,
+        current_price_observation: &PriceObservation<Self::Api>,
+    ) -> PriceObservation<Self::Api> {
+        let new_weight = if current_price_observation.recording_round == 0 {
+            1
+        } else {
+            new_round - current_price_observation.recording_round
+        };
+
+        let mut new_price_observation = current_price_observation.clone();
+        new_price_observation.first_token_reserve_accumulated +=
+            BigUint::from(new_weight) * new_first_reserve;
+        new_price_observation.second_token_reserve_accumulated +=
+            BigUint::from(new_weight) * new_second_reserve;
+        new_price_observation.lp_supply_accumulated += BigUint::from(new_weight) * new_lp_supply;
+        new_price_observation.log_price_accumulated += BigInt::from(new_weight as i64)
+            * BigInt::from(ln_price(new_first_reserve, new_second_reserve));
+        new_price_observation.log_price_weight_accumulated += new_weight;
+        new_price_observation.weight_accumulated += new_weight;
+        new_price_observation.recording_round = new_round;
+
+        new_price_observation
+    }
+}
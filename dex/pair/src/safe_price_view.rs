@@ -1,23 +1,23 @@
 multiversx_sc::imports!();
 
 use common_errors::{ERROR_BAD_INPUT_TOKEN, ERROR_PARAMETERS};
-use core::cmp::Ordering;
 
 use crate::{
-    amm, config,
-    errors::{ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST, ERROR_SAFE_PRICE_SAME_ROUNDS},
+    amm, config, dynamic_fee, dynamic_fee_reader,
+    errors::{
+        ERROR_SAFE_PRICE_LOG_PRICE_NOT_AVAILABLE, ERROR_SAFE_PRICE_NOT_ENOUGH_OBSERVATIONS,
+        ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST, ERROR_SAFE_PRICE_SAME_ROUNDS,
+    },
     read_pair_storage,
-    safe_price::{self, PriceObservation, Round, MAX_OBSERVATIONS},
+    safe_price::{self, mul_by_exp, PriceObservation, Round, MAX_OBSERVATIONS},
+    safe_price_reader, stable_swap,
 };
 
-pub const DEFAULT_SAFE_PRICE_ROUNDS_OFFSET: u64 = 10 * 60;
-pub const SECONDS_PER_ROUND: u64 = 6;
+pub use crate::safe_price_reader::DEFAULT_SAFE_PRICE_ROUNDS_OFFSET;
 
-struct PriceObservationWeightedAmounts<M: ManagedTypeApi> {
-    weighted_first_token_reserve: BigUint<M>,
-    weighted_second_token_reserve: BigUint<M>,
-    weighted_lp_supply: BigUint<M>,
-}
+pub const SECONDS_PER_ROUND: u64 = 6;
+pub const MAX_MEDIAN_OBSERVATIONS: usize = 100;
+const MEDIAN_PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
 
 #[multiversx_sc::module]
 pub trait SafePriceViewModule:
@@ -25,9 +25,13 @@ pub trait SafePriceViewModule:
     + config::ConfigModule
     + token_send::TokenSendModule
     + amm::AmmModule
+    + dynamic_fee::DynamicFeeModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + stable_swap::StableSwapModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
     + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
 {
     #[label("safe-price-view")]
     #[view(getLpTokensSafePriceByDefaultOffset)]
@@ -208,45 +212,116 @@ pub trait SafePriceViewModule:
         end_round: Round,
         input_payment: EsdtTokenPayment,
     ) -> EsdtTokenPayment {
-        require!(end_round > start_round, ERROR_PARAMETERS);
+        self.compute_safe_price(&pair_address, start_round, end_round, input_payment)
+    }
 
-        let safe_price_current_index = self
-            .get_safe_price_current_index_mapper(pair_address.clone())
-            .get();
-        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+    #[label("safe-price-view")]
+    #[view(getGeometricSafePriceByDefaultOffset)]
+    fn get_geometric_safe_price_by_default_offset(
+        &self,
+        pair_address: ManagedAddress,
+        input_payment: EsdtTokenPayment,
+    ) -> EsdtTokenPayment {
+        let current_round = self.blockchain().get_block_round();
+        let default_offset_rounds = self.get_default_offset_rounds(&pair_address, current_round);
+        let start_round = current_round - default_offset_rounds;
+        self.get_geometric_safe_price(pair_address, start_round, current_round, input_payment)
+    }
 
-        let oldest_price_observation =
-            self.get_oldest_price_observation(safe_price_current_index, &price_observations);
+    /// Time weighted geometric mean of the price, which is harder to move by
+    /// short lived reserve changes than the arithmetic mean of the reserves.
+    #[label("safe-price-view")]
+    #[view(getGeometricSafePrice)]
+    fn get_geometric_safe_price(
+        &self,
+        pair_address: ManagedAddress,
+        start_round: Round,
+        end_round: Round,
+        input_payment: EsdtTokenPayment,
+    ) -> EsdtTokenPayment {
+        let (first_price_observation, last_price_observation) =
+            self.get_interval_price_observations(&pair_address, start_round, end_round);
+
+        let weight_diff =
+            last_price_observation.weight_accumulated - first_price_observation.weight_accumulated;
+        require!(weight_diff > 0, ERROR_SAFE_PRICE_SAME_ROUNDS);
+
+        let log_price_weight_diff = last_price_observation.log_price_weight_accumulated
+            - first_price_observation.log_price_weight_accumulated;
         require!(
-            oldest_price_observation.recording_round <= start_round,
-            ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
+            log_price_weight_diff == weight_diff,
+            ERROR_SAFE_PRICE_LOG_PRICE_NOT_AVAILABLE
         );
 
+        let mean_log_price = (last_price_observation.log_price_accumulated
+            - first_price_observation.log_price_accumulated)
+            / BigInt::from(weight_diff as i64);
+        let mean_log_price = mean_log_price
+            .to_i64()
+            .unwrap_or_else(|| sc_panic!(ERROR_SAFE_PRICE_LOG_PRICE_NOT_AVAILABLE));
+
         let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
-        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
-        let first_price_observation = self.get_price_observation(
-            &pair_address,
-            &first_token_id,
-            &second_token_id,
-            safe_price_current_index,
-            &price_observations,
-            start_round,
-        );
-        let last_price_observation = self.get_price_observation(
-            &pair_address,
-            &first_token_id,
-            &second_token_id,
-            safe_price_current_index,
-            &price_observations,
-            end_round,
-        );
+        let second_token_id = self.get_second_token_id_mapper(pair_address).get();
+        if input_payment.token_identifier == first_token_id {
+            let output_amount = mul_by_exp(&input_payment.amount, mean_log_price);
+            EsdtTokenPayment::new(second_token_id, 0, output_amount)
+        } else if input_payment.token_identifier == second_token_id {
+            let output_amount = mul_by_exp(&input_payment.amount, -mean_log_price);
+            EsdtTokenPayment::new(first_token_id, 0, output_amount)
+        } else {
+            sc_panic!(ERROR_BAD_INPUT_TOKEN);
+        }
+    }
 
-        self.compute_weighted_price(
-            &pair_address,
-            input_payment,
-            &first_price_observation,
-            &last_price_observation,
-        )
+    #[label("safe-price-view")]
+    #[view(getMedianSafePriceByDefaultOffset)]
+    fn get_median_safe_price_by_default_offset(
+        &self,
+        pair_address: ManagedAddress,
+        input_payment: EsdtTokenPayment,
+    ) -> EsdtTokenPayment {
+        let current_round = self.blockchain().get_block_round();
+        let default_offset_rounds = self.get_default_offset_rounds(&pair_address, current_round);
+        let start_round = current_round - default_offset_rounds;
+        self.get_median_safe_price(pair_address, start_round, current_round, input_payment)
+    }
+
+    /// Median of the prices recorded by the observations inside the interval,
+    /// each observation counting once. At most the latest MAX_MEDIAN_OBSERVATIONS are used.
+    #[label("safe-price-view")]
+    #[view(getMedianSafePrice)]
+    fn get_median_safe_price(
+        &self,
+        pair_address: ManagedAddress,
+        start_round: Round,
+        end_round: Round,
+        input_payment: EsdtTokenPayment,
+    ) -> EsdtTokenPayment {
+        require!(end_round > start_round, ERROR_PARAMETERS);
+
+        let mut prices = self.get_interval_prices(&pair_address, start_round, end_round);
+        require!(!prices.is_empty(), ERROR_SAFE_PRICE_NOT_ENOUGH_OBSERVATIONS);
+
+        prices.sort_unstable();
+        let middle_index = prices.len() / 2;
+        let median_price = if prices.len() % 2 == 0 {
+            (prices.get(middle_index - 1).clone() + prices.get(middle_index).clone()) / 2u64
+        } else {
+            prices.get(middle_index).clone()
+        };
+        require!(median_price > 0, ERROR_SAFE_PRICE_NOT_ENOUGH_OBSERVATIONS);
+
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address).get();
+        if input_payment.token_identifier == first_token_id {
+            let output_amount = input_payment.amount * median_price / MEDIAN_PRICE_PRECISION;
+            EsdtTokenPayment::new(second_token_id, 0, output_amount)
+        } else if input_payment.token_identifier == second_token_id {
+            let output_amount = input_payment.amount * MEDIAN_PRICE_PRECISION / median_price;
+            EsdtTokenPayment::new(first_token_id, 0, output_amount)
+        } else {
+            sc_panic!(ERROR_BAD_INPUT_TOKEN);
+        }
     }
 
     #[label("safe-price-view")]
@@ -280,257 +355,62 @@ pub trait SafePriceViewModule:
         )
     }
 
-    fn compute_weighted_price(
+    /// Walks the ring buffer backwards from the latest observation. Each observation
+    /// gives the price held since the previous one, with MEDIAN_PRICE_PRECISION.
+    fn get_interval_prices(
         &self,
         pair_address: &ManagedAddress,
-        input_payment: EsdtTokenPayment,
-        first_price_observation: &PriceObservation<Self::Api>,
-        last_price_observation: &PriceObservation<Self::Api>,
-    ) -> EsdtTokenPayment {
-        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
-        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
-
-        let weighted_amounts =
-            self.compute_weighted_amounts(first_price_observation, last_price_observation);
-
-        if input_payment.token_identifier == first_token_id {
-            let output_amount = input_payment.amount
-                * weighted_amounts.weighted_second_token_reserve
-                / weighted_amounts.weighted_first_token_reserve;
-            EsdtTokenPayment::new(second_token_id, 0, output_amount)
-        } else if input_payment.token_identifier == second_token_id {
-            let output_amount = input_payment.amount
-                * weighted_amounts.weighted_first_token_reserve
-                / weighted_amounts.weighted_second_token_reserve;
-            EsdtTokenPayment::new(first_token_id, 0, output_amount)
-        } else {
-            sc_panic!(ERROR_BAD_INPUT_TOKEN);
-        }
-    }
-
-    fn get_price_observation(
-        &self,
-        pair_address: &ManagedAddress,
-        first_token_id: &TokenIdentifier,
-        second_token_id: &TokenIdentifier,
-        current_index: usize,
-        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>, ManagedAddress>,
-        search_round: Round,
-    ) -> PriceObservation<Self::Api> {
-        require!(
-            !price_observations.is_empty(),
-            ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
-        );
-
-        // Check if the requested price observation is the last one
-        let last_observation = price_observations.get(current_index);
-        if last_observation.recording_round == search_round {
-            return last_observation;
-        }
-
-        // Simulate a new price observation, based on the current reserves,
-        // in case the searched round is bigger than the last recording round
-        // The search round is limited to the current blockchain round
-        if last_observation.recording_round < search_round {
-            let current_round = self.blockchain().get_block_round();
-            require!(
-                search_round <= current_round,
-                ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
-            );
-
-            let first_token_reserve = self
-                .get_pair_reserve_mapper(pair_address.clone(), first_token_id)
-                .get();
-            let second_token_reserve = self
-                .get_pair_reserve_mapper(pair_address.clone(), second_token_id)
-                .get();
-            let current_lp_supply = self.get_lp_token_supply_mapper(pair_address.clone()).get();
-            return self.compute_new_observation(
-                search_round,
-                &first_token_reserve,
-                &second_token_reserve,
-                &current_lp_supply,
-                &last_observation,
-            );
-        }
-
-        let (mut price_observation, last_search_index) = self.price_observation_by_binary_search(
-            current_index,
-            price_observations,
-            search_round,
-        );
-
-        if price_observation.recording_round > 0 {
-            return price_observation;
-        }
-
-        price_observation = self.price_observation_by_linear_interpolation(
-            price_observations,
-            search_round,
-            last_search_index,
-        );
-
-        price_observation
-    }
-
-    fn get_oldest_price_observation(
-        &self,
-        current_index: usize,
-        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>, ManagedAddress>,
-    ) -> PriceObservation<Self::Api> {
-        require!(
-            !price_observations.is_empty(),
-            ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST
-        );
-
-        // VecMapper index starts at 1
-        let mut oldest_observation_index = 1;
-        if price_observations.len() == MAX_OBSERVATIONS {
-            oldest_observation_index = (current_index % MAX_OBSERVATIONS) + 1
-        }
-        price_observations.get(oldest_observation_index)
-    }
-
-    fn price_observation_by_binary_search(
-        &self,
-        current_index: usize,
-        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>, ManagedAddress>,
-        search_round: Round,
-    ) -> (PriceObservation<Self::Api>, usize) {
-        let mut search_index = 1;
-        let mut left_index;
-        let mut right_index;
-        let observation_at_index_1 = price_observations.get(search_index);
-        if observation_at_index_1.recording_round <= search_round {
-            left_index = search_index;
-            right_index = current_index - 1;
-        } else {
-            left_index = current_index + 1;
-            right_index = price_observations.len();
+        start_round: Round,
+        end_round: Round,
+    ) -> ManagedVec<BigUint> {
+        let mut prices = ManagedVec::new();
+        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+        let observations_len = price_observations.len();
+        if observations_len < 2 {
+            return prices;
         }
 
-        while left_index <= right_index {
-            search_index = (left_index + right_index) / 2;
-            let price_observation = price_observations.get(search_index);
-            match price_observation.recording_round.cmp(&search_round) {
-                Ordering::Equal => return (price_observation, search_index),
-                Ordering::Less => left_index = search_index + 1,
-                Ordering::Greater => right_index = search_index - 1,
+        let mut index = self
+            .get_safe_price_current_index_mapper(pair_address.clone())
+            .get();
+        let mut observation = price_observations.get(index);
+        for _ in 1..observations_len {
+            if observation.recording_round <= start_round {
+                break;
             }
-        }
 
-        (PriceObservation::default(), search_index)
-    }
-
-    fn price_observation_by_linear_interpolation(
-        &self,
-        price_observations: &VecMapper<Self::Api, PriceObservation<Self::Api>, ManagedAddress>,
-        search_round: Round,
-        search_index: usize,
-    ) -> PriceObservation<Self::Api> {
-        let last_found_observation = price_observations.get(search_index);
-        let left_observation;
-        let right_observation;
-        if last_found_observation.recording_round < search_round {
-            left_observation = last_found_observation;
-            let right_observation_index = (search_index % MAX_OBSERVATIONS) + 1;
-            right_observation = price_observations.get(right_observation_index);
-        } else {
-            let left_observation_index = if search_index == 1 {
+            // VecMapper index starts at 1, the buffer only wraps around once full
+            let previous_index = if index > 1 {
+                index - 1
+            } else if observations_len == MAX_OBSERVATIONS {
                 MAX_OBSERVATIONS
             } else {
-                search_index - 1
+                break;
             };
-            left_observation = price_observations.get(left_observation_index);
-            right_observation = last_found_observation;
-        };
-
-        // For a proper linear interpolation calculation, we compute the weights as follows
-        // Left observation has a weight equal to the remaining time, starting from the searched round until the end round
-        // Right observation has a weight equal to the elapsed time, from starting round until the searched round
-        let left_weight = right_observation.recording_round - search_round;
-        let right_weight = search_round - left_observation.recording_round;
-
-        let weight_sum = left_weight + right_weight;
-        let first_token_reserve_sum = BigUint::from(left_weight)
-            * left_observation.first_token_reserve_accumulated
-            + BigUint::from(right_weight) * right_observation.first_token_reserve_accumulated;
-        let second_token_reserve_sum = BigUint::from(left_weight)
-            * left_observation.second_token_reserve_accumulated
-            + BigUint::from(right_weight) * right_observation.second_token_reserve_accumulated;
-        let lp_supply_sum = BigUint::from(left_weight) * left_observation.lp_supply_accumulated
-            + BigUint::from(right_weight) * right_observation.lp_supply_accumulated;
-
-        let first_token_reserve_accumulated = first_token_reserve_sum / weight_sum;
-        let second_token_reserve_accumulated = second_token_reserve_sum / weight_sum;
-        let lp_supply_accumulated = lp_supply_sum / weight_sum;
-        let weight_accumulated =
-            left_observation.weight_accumulated + search_round - left_observation.recording_round;
-
-        PriceObservation {
-            first_token_reserve_accumulated,
-            second_token_reserve_accumulated,
-            weight_accumulated,
-            recording_round: search_round,
-            lp_supply_accumulated,
-        }
-    }
-
-    fn compute_weighted_amounts(
-        &self,
-        first_price_observation: &PriceObservation<Self::Api>,
-        last_price_observation: &PriceObservation<Self::Api>,
-    ) -> PriceObservationWeightedAmounts<Self::Api> {
-        let weight_diff =
-            last_price_observation.weight_accumulated - first_price_observation.weight_accumulated;
-
-        require!(weight_diff > 0, ERROR_SAFE_PRICE_SAME_ROUNDS);
-
-        let first_token_reserve_diff = last_price_observation
-            .first_token_reserve_accumulated
-            .clone()
-            - first_price_observation
-                .first_token_reserve_accumulated
-                .clone();
-        let second_token_reserve_diff = last_price_observation
-            .second_token_reserve_accumulated
-            .clone()
-            - first_price_observation
-                .second_token_reserve_accumulated
-                .clone();
-
-        let weighted_first_token_reserve = first_token_reserve_diff / weight_diff;
-        let weighted_second_token_reserve = second_token_reserve_diff / weight_diff;
-
-        let weighted_lp_supply = if first_price_observation.lp_supply_accumulated > 0 {
-            let lp_supply_diff = &last_price_observation.lp_supply_accumulated
-                - &first_price_observation.lp_supply_accumulated;
-            lp_supply_diff / weight_diff
-        } else {
-            BigUint::zero()
-        };
-
-        PriceObservationWeightedAmounts {
-            weighted_first_token_reserve,
-            weighted_second_token_reserve,
-            weighted_lp_supply,
-        }
-    }
-
-    fn get_default_offset_rounds(&self, pair_address: &ManagedAddress, end_round: Round) -> u64 {
-        let safe_price_current_index = self
-            .get_safe_price_current_index_mapper(pair_address.clone())
-            .get();
-        let price_observations = self.get_price_observation_mapper(pair_address.clone());
-        let oldest_price_observation =
-            self.get_oldest_price_observation(safe_price_current_index, &price_observations);
+            let previous_observation = price_observations.get(previous_index);
+
+            if observation.recording_round <= end_round {
+                let first_token_reserve_diff = &observation.first_token_reserve_accumulated
+                    - &previous_observation.first_token_reserve_accumulated;
+                let second_token_reserve_diff = &observation.second_token_reserve_accumulated
+                    - &previous_observation.second_token_reserve_accumulated;
+                if first_token_reserve_diff > 0 {
+                    prices.push(
+                        second_token_reserve_diff * MEDIAN_PRICE_PRECISION
+                            / first_token_reserve_diff,
+                    );
+                }
+
+                if prices.len() == MAX_MEDIAN_OBSERVATIONS {
+                    break;
+                }
+            }
 
-        let mut default_offset_rounds = end_round - oldest_price_observation.recording_round;
-        if default_offset_rounds > DEFAULT_SAFE_PRICE_ROUNDS_OFFSET {
-            default_offset_rounds = DEFAULT_SAFE_PRICE_ROUNDS_OFFSET;
+            index = previous_index;
+            observation = previous_observation;
         }
 
-        default_offset_rounds
+        prices
     }
 
     // legacy endpoints
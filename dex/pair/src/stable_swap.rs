@@ -0,0 +1,330 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::config;
+use crate::errors::*;
+
+pub const MIN_AMPLIFICATION: u64 = 1;
+pub const MAX_AMPLIFICATION: u64 = 1_000_000;
+pub const MAX_AMPLIFICATION_CHANGE: u64 = 10;
+pub const MIN_RAMP_BLOCKS: u64 = 14_400; // one day
+
+const NR_TOKENS: u64 = 2;
+const MAX_ITERATIONS: usize = 255;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum AmmMode {
+    ConstantProduct,
+    StableSwap,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Debug)]
+pub struct AmplificationRamp {
+    pub initial_amplification: u64,
+    pub future_amplification: u64,
+    pub initial_block: u64,
+    pub future_block: u64,
+}
+
+#[multiversx_sc::module]
+pub trait StableSwapModule:
+    config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    /// Switches the pair to the stable curve. Only possible before any liquidity is added.
+    #[endpoint(enableStableSwap)]
+    fn enable_stable_swap(&self, amplification: u64) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            self.lp_token_supply().get() == 0,
+            ERROR_AMM_MODE_CHANGE_AFTER_LIQUIDITY
+        );
+        require!(
+            (MIN_AMPLIFICATION..=MAX_AMPLIFICATION).contains(&amplification),
+            ERROR_BAD_AMPLIFICATION
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        self.amm_mode().set(AmmMode::StableSwap);
+        self.amplification_ramp().set(AmplificationRamp {
+            initial_amplification: amplification,
+            future_amplification: amplification,
+            initial_block: current_block,
+            future_block: current_block,
+        });
+    }
+
+    /// Linearly changes the amplification from its current value to `future_amplification`,
+    /// reaching it at `future_block`
+    #[endpoint(rampAmplification)]
+    fn ramp_amplification(&self, future_amplification: u64, future_block: u64) {
+        self.require_caller_has_owner_permissions();
+        require!(self.is_stable_swap(), ERROR_NOT_STABLE_SWAP);
+        require!(
+            (MIN_AMPLIFICATION..=MAX_AMPLIFICATION).contains(&future_amplification),
+            ERROR_BAD_AMPLIFICATION
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        require!(
+            future_block >= current_block + MIN_RAMP_BLOCKS,
+            ERROR_RAMP_TOO_SHORT
+        );
+
+        let current_amplification = self.get_amplification();
+        require!(
+            future_amplification <= current_amplification * MAX_AMPLIFICATION_CHANGE
+                && future_amplification * MAX_AMPLIFICATION_CHANGE >= current_amplification,
+            ERROR_BAD_AMPLIFICATION
+        );
+
+        self.amplification_ramp().set(AmplificationRamp {
+            initial_amplification: current_amplification,
+            future_amplification,
+            initial_block: current_block,
+            future_block,
+        });
+    }
+
+    #[endpoint(stopAmplificationRamp)]
+    fn stop_amplification_ramp(&self) {
+        self.require_caller_has_owner_permissions();
+        require!(self.is_stable_swap(), ERROR_NOT_STABLE_SWAP);
+
+        let current_amplification = self.get_amplification();
+        let current_block = self.blockchain().get_block_nonce();
+        self.amplification_ramp().set(AmplificationRamp {
+            initial_amplification: current_amplification,
+            future_amplification: current_amplification,
+            initial_block: current_block,
+            future_block: current_block,
+        });
+    }
+
+    #[view(getAmplification)]
+    fn get_amplification(&self) -> u64 {
+        let ramp_mapper = self.amplification_ramp();
+        if ramp_mapper.is_empty() {
+            return 0;
+        }
+
+        let current_block = self.blockchain().get_block_nonce();
+        get_ramp_amplification::<Self::Api>(&ramp_mapper.get(), current_block)
+    }
+
+    fn is_stable_swap(&self) -> bool {
+        self.amm_mode().get() == AmmMode::StableSwap
+    }
+
+    fn compute_stable_invariant(
+        &self,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> BigUint {
+        compute_stable_invariant(
+            self.get_amplification(),
+            first_token_reserve,
+            second_token_reserve,
+        )
+        .unwrap_or_else(|| sc_panic!(ERROR_STABLE_SWAP_NOT_CONVERGING))
+    }
+
+    fn compute_stable_reserve(&self, other_reserve: &BigUint, d: &BigUint) -> BigUint {
+        require!(other_reserve > &0u64, ERROR_NOT_ENOUGH_RESERVE);
+
+        compute_stable_reserve(self.get_amplification(), other_reserve, d)
+            .unwrap_or_else(|| sc_panic!(ERROR_STABLE_SWAP_NOT_CONVERGING))
+    }
+
+    fn get_stable_amount_out(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+    ) -> BigUint {
+        get_stable_amount_out(self.get_amplification(), amount_in, reserve_in, reserve_out)
+            .unwrap_or_else(|| sc_panic!(ERROR_STABLE_SWAP_NOT_CONVERGING))
+    }
+
+    fn get_stable_amount_in(
+        &self,
+        amount_out: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+    ) -> BigUint {
+        require!(reserve_out > amount_out, ERROR_NOT_ENOUGH_RESERVE);
+
+        let d = self.compute_stable_invariant(reserve_in, reserve_out);
+        let new_reserve_in = self.compute_stable_reserve(&(reserve_out - amount_out), &d);
+        require!(&new_reserve_in > reserve_in, ERROR_ZERO_AMOUNT);
+
+        new_reserve_in - reserve_in + 1u64
+    }
+
+    /// Reserves holding the same value as the given ones at the spot price of the curve,
+    /// in the ratio of that spot price. For stable pairs the price observations record these,
+    /// as the constant product ratio of the actual reserves is not their price.
+    fn get_spot_price_reserves(
+        &self,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> (BigUint, BigUint) {
+        if !self.is_stable_swap() {
+            return (first_token_reserve.clone(), second_token_reserve.clone());
+        }
+
+        get_spot_price_reserves(
+            self.get_amplification(),
+            first_token_reserve,
+            second_token_reserve,
+        )
+        .unwrap_or_else(|| sc_panic!(ERROR_STABLE_SWAP_NOT_CONVERGING))
+    }
+
+    #[view(getAmmMode)]
+    #[storage_mapper("ammMode")]
+    fn amm_mode(&self) -> SingleValueMapper<AmmMode>;
+
+    #[view(getAmplificationRamp)]
+    #[storage_mapper("amplificationRamp")]
+    fn amplification_ramp(&self) -> SingleValueMapper<AmplificationRamp>;
+}
+
+/// The amplification reached by the ramp at `current_block`
+pub fn get_ramp_amplification<M: ManagedTypeApi>(
+    ramp: &AmplificationRamp,
+    current_block: u64,
+) -> u64 {
+    if current_block >= ramp.future_block {
+        return ramp.future_amplification;
+    }
+
+    math::linear_interpolation::<M, _>(
+        ramp.initial_block,
+        ramp.future_block,
+        current_block,
+        ramp.initial_amplification,
+        ramp.future_amplification,
+    )
+}
+
+/// Computes the StableSwap invariant D, for which
+/// A * n^n * (x + y) + D = A * n^n * D + D^(n + 1) / (n^n * x * y).
+/// Returns None if the iterations do not converge.
+pub fn compute_stable_invariant<M: ManagedTypeApi>(
+    amplification: u64,
+    first_token_reserve: &BigUint<M>,
+    second_token_reserve: &BigUint<M>,
+) -> Option<BigUint<M>> {
+    if first_token_reserve == &0u64 || second_token_reserve == &0u64 {
+        return Some(BigUint::zero());
+    }
+
+    let amp_times_n = BigUint::from(amplification * NR_TOKENS);
+    let sum = first_token_reserve + second_token_reserve;
+
+    let mut d = sum.clone();
+    for _ in 0..MAX_ITERATIONS {
+        let mut d_product = d.clone();
+        d_product = d_product * &d / (first_token_reserve * NR_TOKENS);
+        d_product = d_product * &d / (second_token_reserve * NR_TOKENS);
+
+        let d_prev = d.clone();
+        let numerator = (&amp_times_n * &sum + &d_product * NR_TOKENS) * &d;
+        let denominator = (&amp_times_n - 1u64) * &d + d_product * (NR_TOKENS + 1);
+        d = numerator / denominator;
+
+        if abs_diff(&d, &d_prev) <= 1u64 {
+            return Some(d);
+        }
+    }
+
+    None
+}
+
+/// Computes the reserve of one token that keeps the invariant D,
+/// given the non-zero reserve of the other token
+pub fn compute_stable_reserve<M: ManagedTypeApi>(
+    amplification: u64,
+    other_reserve: &BigUint<M>,
+    d: &BigUint<M>,
+) -> Option<BigUint<M>> {
+    let amp_times_n = BigUint::from(amplification * NR_TOKENS);
+    let mut c = d * d / (other_reserve * NR_TOKENS);
+    c = c * d / (&amp_times_n * NR_TOKENS);
+    let b = other_reserve + &(d / &amp_times_n);
+
+    let mut y = d.clone();
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y.clone();
+        y = (&y * &y + &c) / (&y * 2u64 + &b - d);
+
+        if abs_diff(&y, &y_prev) <= 1u64 {
+            return Some(y);
+        }
+    }
+
+    None
+}
+
+pub fn get_stable_amount_out<M: ManagedTypeApi>(
+    amplification: u64,
+    amount_in: &BigUint<M>,
+    reserve_in: &BigUint<M>,
+    reserve_out: &BigUint<M>,
+) -> Option<BigUint<M>> {
+    if reserve_in == &0u64 || reserve_out == &0u64 {
+        return Some(BigUint::zero());
+    }
+
+    let d = compute_stable_invariant(amplification, reserve_in, reserve_out)?;
+    let new_reserve_out = compute_stable_reserve(amplification, &(reserve_in + amount_in), &d)?;
+
+    // one unit is kept in the pool to cover the rounding of the invariant
+    let remaining_reserve_out = new_reserve_out + 1u64;
+    if &remaining_reserve_out >= reserve_out {
+        return Some(BigUint::zero());
+    }
+
+    Some(reserve_out - &remaining_reserve_out)
+}
+
+/// Reserves holding the same value as the given ones, in the ratio of the spot price
+/// of the stable curve. Returns None if the invariant does not converge.
+pub fn get_spot_price_reserves<M: ManagedTypeApi>(
+    amplification: u64,
+    first_token_reserve: &BigUint<M>,
+    second_token_reserve: &BigUint<M>,
+) -> Option<(BigUint<M>, BigUint<M>)> {
+    if first_token_reserve == &0u64 || second_token_reserve == &0u64 {
+        return Some((first_token_reserve.clone(), second_token_reserve.clone()));
+    }
+
+    // dy/dx from the invariant: the first token is priced at
+    // (4 * Ann * x^2 * y^2 + D^3 * y) / (4 * Ann * x^2 * y^2 + D^3 * x) second tokens
+    let d = compute_stable_invariant(amplification, first_token_reserve, second_token_reserve)?;
+    let d_cubed = &d * &d * &d;
+    let amp_times_n = BigUint::from(amplification * NR_TOKENS);
+    let reserves_product = first_token_reserve * second_token_reserve;
+    let curve_term = amp_times_n * NR_TOKENS * NR_TOKENS * &reserves_product * &reserves_product;
+    let price_numerator = &d_cubed * second_token_reserve + &curve_term;
+    let price_denominator = curve_term + d_cubed * first_token_reserve;
+
+    let value = first_token_reserve * &price_numerator + second_token_reserve * &price_denominator;
+    Some((
+        &value / &(price_numerator * NR_TOKENS),
+        value / (price_denominator * NR_TOKENS),
+    ))
+}
+
+fn abs_diff<M: ManagedTypeApi>(first: &BigUint<M>, second: &BigUint<M>) -> BigUint<M> {
+    if first > second {
+        first - second
+    } else {
+        second - first
+    }
+}
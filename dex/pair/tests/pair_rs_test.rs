@@ -12,20 +12,32 @@ use multiversx_sc::{
         multi_types::OptionalValue,
         top_encode_to_vec_u8,
     },
-    types::{BigUint, EsdtLocalRole, MultiValueEncoded},
+    types::{BigInt, BigUint, EsdtLocalRole, EsdtTokenPayment, MultiValueEncoded},
 };
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
     whitebox_legacy::TxTokenTransfer, DebugApi,
 };
 use pair::{
-    config::MAX_PERCENTAGE,
+    circuit_breaker::CircuitBreakerAction,
+    config::{ConfigModule as PairConfigModule, MAX_PERCENTAGE},
+    dynamic_fee::DynamicFeeModule,
     fee::FeeModule,
+    fee_growth::FeeGrowthModule,
     locking_wrapper::LockingWrapperModule,
-    pair_actions::swap::SwapModule,
+    pair_actions::{
+        flash_swap::FlashSwapModule, remove_liq::RemoveLiquidityModule, swap::SwapModule,
+        views::ViewsModule, zap::ZapModule,
+    },
+    pair_hooks::{PairHookType, PairHooksModule},
     safe_price::{PriceObservation, Round, SafePriceModule},
+    safe_price_reader::SafePriceReaderModule,
+    safe_price_view::SafePriceViewModule,
+    stable_swap::{StableSwapModule, MIN_RAMP_BLOCKS},
 };
+use pair_setup::pair_hook_mock::PairHookMock;
 use pair_setup::*;
+use pausable::{PausableModule, State};
 use simple_lock::{
     locked_token::{LockedTokenAttributes, LockedTokenModule},
     proxy_lp::{LpProxyTokenAttributes, ProxyLpModule},
@@ -105,6 +117,266 @@ fn test_perfect_swap_fixed_output() {
     );
 }
 
+#[test]
+fn test_stable_swap() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_stable_swap(100);
+
+    let token_amount = 1_001_000_000;
+    pair_setup.add_liquidity(
+        token_amount,
+        token_amount,
+        token_amount,
+        token_amount,
+        token_amount - 1_000,
+        token_amount,
+        token_amount,
+    );
+
+    // close to a 1:1 rate, minus the 0.3% fee
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000_000, MEX_TOKEN_ID, 996_000, 996_990);
+    pair_setup.swap_fixed_output(MEX_TOKEN_ID, 2_000_000, WEGLD_TOKEN_ID, 1_000_000, 996_999);
+
+    // large swaps move the price away from the peg
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 800_000_000, MEX_TOKEN_ID, 1, 782_131_394);
+
+    // the invariant never decreases, whatever the rounding
+    for i in 0..20u64 {
+        let (token_in, token_out) = if i % 2 == 0 {
+            (MEX_TOKEN_ID, WEGLD_TOKEN_ID)
+        } else {
+            (WEGLD_TOKEN_ID, MEX_TOKEN_ID)
+        };
+        pair_setup
+            .b_mock
+            .execute_esdt_transfer(
+                &pair_setup.user_address,
+                &pair_setup.pair_wrapper,
+                token_in,
+                0,
+                &rust_biguint!(1_234_567 * (i + 1)),
+                |sc| {
+                    let initial_k = sc.compute_stable_invariant(
+                        &sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                        &sc.pair_reserve(&managed_token_id!(MEX_TOKEN_ID)).get(),
+                    );
+                    let _ = sc
+                        .swap_tokens_fixed_input(managed_token_id!(token_out), managed_biguint!(1));
+                    let new_k = sc.compute_stable_invariant(
+                        &sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                        &sc.pair_reserve(&managed_token_id!(MEX_TOKEN_ID)).get(),
+                    );
+                    assert!(initial_k <= new_k);
+                },
+            )
+            .assert_ok();
+    }
+}
+
+#[test]
+fn test_stable_swap_price_observations() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_stable_swap(100);
+
+    let token_amount = 1_001_000_000;
+    pair_setup.add_liquidity(
+        token_amount,
+        token_amount,
+        token_amount,
+        token_amount,
+        token_amount - 1_000,
+        token_amount,
+        token_amount,
+    );
+
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 800_000_000, MEX_TOKEN_ID, 1, 782_130_946);
+
+    let mut reserves = (0, 0);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            reserves = (
+                sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID))
+                    .get()
+                    .to_u64()
+                    .unwrap(),
+                sc.pair_reserve(&managed_token_id!(MEX_TOKEN_ID))
+                    .get()
+                    .to_u64()
+                    .unwrap(),
+            );
+        })
+        .assert_ok();
+
+    // the next swap records the reserves left by the previous one
+    pair_setup.b_mock.set_block_round(20);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 1, 906);
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let first_token_reserve = managed_biguint!(reserves.0);
+            let second_token_reserve = managed_biguint!(reserves.1);
+
+            let current_index = sc.safe_price_current_index().get();
+            let last_observation = sc.price_observations().get(current_index);
+            let previous_observation = sc.price_observations().get(current_index - 1);
+            let weight =
+                last_observation.weight_accumulated - previous_observation.weight_accumulated;
+            let recorded_first_token_reserve = (last_observation.first_token_reserve_accumulated
+                - previous_observation.first_token_reserve_accumulated)
+                / weight;
+            let recorded_second_token_reserve = (last_observation.second_token_reserve_accumulated
+                - previous_observation.second_token_reserve_accumulated)
+                / weight;
+
+            // the recorded ratio is the spot price of the curve, not the reserves ratio
+            let marginal_amount_out = sc.get_stable_amount_out(
+                &managed_biguint!(1_000_000),
+                &first_token_reserve,
+                &second_token_reserve,
+            );
+            let recorded_amount_out = managed_biguint!(1_000_000) * &recorded_second_token_reserve
+                / &recorded_first_token_reserve;
+            assert!(recorded_amount_out >= marginal_amount_out);
+            assert!(&recorded_amount_out * 1_000u64 <= &marginal_amount_out * 1_001u64);
+
+            let reserves_ratio_amount_out =
+                managed_biguint!(1_000_000) * &second_token_reserve / &first_token_reserve;
+            assert!(marginal_amount_out > reserves_ratio_amount_out * 2u64);
+
+            // same value as the actual reserves at that price
+            let recorded_value = &recorded_first_token_reserve * &recorded_amount_out
+                + &recorded_second_token_reserve * 1_000_000u64;
+            let actual_value =
+                &first_token_reserve * &recorded_amount_out + &second_token_reserve * 1_000_000u64;
+            assert!(&recorded_value * 1_000u64 <= &actual_value * 1_001u64);
+            assert!(recorded_value * 1_001u64 >= actual_value * 1_000u64);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_stable_swap_safe_price_without_swaps() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_stable_swap(100);
+    let pair_address = pair_setup.pair_wrapper.address_ref().clone();
+
+    let token_amount = 1_001_000_000;
+    pair_setup.add_liquidity(
+        token_amount,
+        token_amount,
+        token_amount,
+        token_amount,
+        token_amount - 1_000,
+        token_amount,
+        token_amount,
+    );
+
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 800_000_000, MEX_TOKEN_ID, 1, 782_130_946);
+
+    // the rounds after the last observation are priced at the spot price of the curve
+    pair_setup.b_mock.set_block_round(30);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let safe_price = sc.get_safe_price(
+                managed_address!(&pair_address),
+                20,
+                30,
+                EsdtTokenPayment::new(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    0,
+                    managed_biguint!(1_000_000),
+                ),
+            );
+            let marginal_amount_out = sc.get_stable_amount_out(
+                &managed_biguint!(1_000_000),
+                &sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                &sc.pair_reserve(&managed_token_id!(MEX_TOKEN_ID)).get(),
+            );
+            assert!(safe_price.amount >= marginal_amount_out);
+            assert!(&safe_price.amount * 1_000u64 <= &marginal_amount_out * 1_001u64);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_stable_swap_amplification_ramp() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_stable_swap(100);
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.ramp_amplification(200, MIN_RAMP_BLOCKS - 1);
+            },
+        )
+        .assert_user_error("Amplification ramp too short");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.ramp_amplification(1_001, MIN_RAMP_BLOCKS);
+            },
+        )
+        .assert_user_error("Bad amplification");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.ramp_amplification(200, MIN_RAMP_BLOCKS);
+            },
+        )
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_nonce(MIN_RAMP_BLOCKS / 2);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_amplification(), 150);
+        })
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_nonce(MIN_RAMP_BLOCKS * 2);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_amplification(), 200);
+        })
+        .assert_ok();
+
+    // the curve cannot be changed once liquidity was added
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.enable_stable_swap(100);
+            },
+        )
+        .assert_user_error("The AMM mode can only be changed before adding liquidity");
+}
+
 #[test]
 fn test_safe_price_observation_decoding() {
     let mut pair_setup = PairSetup::new(pair::contract_obj);
@@ -127,6 +399,8 @@ fn test_safe_price_observation_decoding() {
                 new_observation.lp_supply_accumulated,
                 managed_biguint!(0u64)
             );
+            assert_eq!(new_observation.log_price_accumulated, BigInt::zero());
+            assert_eq!(new_observation.log_price_weight_accumulated, 0);
 
             new_observation.lp_supply_accumulated = managed_biguint!(2u64);
             sc.price_observations().push(&new_observation.clone());
@@ -139,6 +413,977 @@ fn test_safe_price_observation_decoding() {
     );
 }
 
+#[test]
+fn test_geometric_and_median_safe_price() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let pair_address = pair_setup.pair_wrapper.address_ref().clone();
+    pair_setup.b_mock.set_block_round(130);
+
+    // the WEGLD price is 4 MEX for 10 rounds, then 1 MEX and 1.1 MEX
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let lp_supply = managed_biguint!(1_000);
+                let mut observation = PriceObservation::default();
+                for (round, second_token_reserve) in
+                    [(100, 1_000u64), (110, 4_000), (120, 1_000), (130, 1_100)]
+                {
+                    observation = sc.compute_new_observation(
+                        round,
+                        &managed_biguint!(1_000),
+                        &managed_biguint!(second_token_reserve),
+                        &lp_supply,
+                        &observation,
+                    );
+                    sc.price_observations().push(&observation);
+                }
+                sc.safe_price_current_index().set(4);
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let input_payment = EsdtTokenPayment::new(
+                managed_token_id!(WEGLD_TOKEN_ID),
+                0,
+                managed_biguint!(1_000),
+            );
+
+            let arithmetic_price = sc.get_safe_price(
+                managed_address!(&pair_address),
+                100,
+                130,
+                input_payment.clone(),
+            );
+            assert_eq!(arithmetic_price.amount, managed_biguint!(2_033));
+
+            // (4 * 1 * 1.1) ^ (1 / 3) = 1.6386
+            let geometric_price = sc.get_geometric_safe_price(
+                managed_address!(&pair_address),
+                100,
+                130,
+                input_payment.clone(),
+            );
+            assert_eq!(
+                geometric_price.token_identifier,
+                managed_token_id!(MEX_TOKEN_ID)
+            );
+            assert_eq!(geometric_price.amount, managed_biguint!(1_638));
+
+            let median_price =
+                sc.get_median_safe_price(managed_address!(&pair_address), 100, 130, input_payment);
+            assert_eq!(median_price.amount, managed_biguint!(1_100));
+
+            // a window with an even number of observations uses the middle average
+            let median_price = sc.get_median_safe_price(
+                managed_address!(&pair_address),
+                110,
+                130,
+                EsdtTokenPayment::new(managed_token_id!(MEX_TOKEN_ID), 0, managed_biguint!(1_050)),
+            );
+            assert_eq!(
+                median_price.token_identifier,
+                managed_token_id!(WEGLD_TOKEN_ID)
+            );
+            assert_eq!(median_price.amount, managed_biguint!(1_000));
+        })
+        .assert_ok();
+
+    // the first two observations were recorded before the log price tracking
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let lp_supply = managed_biguint!(1_000);
+                let mut observation = PriceObservation::default();
+                for index in 1..=2 {
+                    observation = sc.price_observations().get(index);
+                    observation.log_price_accumulated = BigInt::zero();
+                    observation.log_price_weight_accumulated = 0;
+                    sc.price_observations().set(index, &observation);
+                }
+                for (index, round, second_token_reserve) in [(3, 120, 1_000u64), (4, 130, 1_100)] {
+                    observation = sc.compute_new_observation(
+                        round,
+                        &managed_biguint!(1_000),
+                        &managed_biguint!(second_token_reserve),
+                        &lp_supply,
+                        &observation,
+                    );
+                    sc.price_observations().set(index, &observation);
+                }
+            },
+        )
+        .assert_ok();
+
+    let mut query_geometric_price = |start_round: Round| {
+        pair_setup
+            .b_mock
+            .execute_query(&pair_setup.pair_wrapper, |sc| {
+                let geometric_price = sc.get_geometric_safe_price(
+                    managed_address!(&pair_address),
+                    start_round,
+                    130,
+                    EsdtTokenPayment::new(
+                        managed_token_id!(WEGLD_TOKEN_ID),
+                        0,
+                        managed_biguint!(1_000),
+                    ),
+                );
+                // (1 * 1.1) ^ (1 / 2) = 1.0488
+                assert_eq!(geometric_price.amount, managed_biguint!(1_048));
+            })
+    };
+    query_geometric_price(110).assert_ok();
+    query_geometric_price(100)
+        .assert_user_error("Log price observations do not cover the whole interval");
+}
+
+#[test]
+fn test_price_deviation_circuit_breaker() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 996);
+
+    // 10% max deviation from the safe price of the last 10 rounds
+    pair_setup.set_circuit_breaker(10_000, 10, CircuitBreakerAction::Revert);
+    pair_setup.b_mock.set_block_round(30);
+
+    pair_setup
+        .try_swap_fixed_input(WEGLD_TOKEN_ID, 200_000, MEX_TOKEN_ID, 1)
+        .assert_user_error("Price deviation from safe price too high");
+
+    // small swaps are not affected
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 994);
+
+    pair_setup.set_circuit_breaker(10_000, 10, CircuitBreakerAction::SetPartialActive);
+    pair_setup
+        .try_swap_fixed_input(WEGLD_TOKEN_ID, 200_000, MEX_TOKEN_ID, 1)
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.state().get(), State::PartialActive);
+        })
+        .assert_ok();
+
+    pair_setup
+        .try_swap_fixed_input(WEGLD_TOKEN_ID, 200_000, MEX_TOKEN_ID, 1)
+        .assert_user_error("Swap is not enabled");
+}
+
+#[test]
+fn test_dynamic_fee() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.b_mock.set_block_round(10);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+    pair_setup.b_mock.set_block_round(20);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 900, 996);
+
+    // the max fee is reached at 10% volatility
+    pair_setup.set_dynamic_fee(100, 1_000, 100, 10_000);
+    pair_setup.b_mock.set_block_round(30);
+    pair_setup.check_effective_fee_percent(100);
+
+    // the price has been stable so far, the swap itself pays the min fee
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1, 90_661);
+
+    pair_setup.b_mock.set_block_round(40);
+    pair_setup.check_effective_fee_percent(1_000);
+
+    let mut expected_amount_out = 0;
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            expected_amount_out = sc
+                .get_amount_out_view(managed_token_id!(MEX_TOKEN_ID), managed_biguint!(10_000))
+                .to_u64()
+                .unwrap();
+        })
+        .assert_ok();
+    pair_setup.swap_fixed_input(MEX_TOKEN_ID, 10_000, WEGLD_TOKEN_ID, 1, expected_amount_out);
+
+    // the volatile observations are out of a shorter window
+    pair_setup.set_dynamic_fee(100, 1_000, 5, 10_000);
+    pair_setup.b_mock.set_block_round(100);
+    pair_setup.check_effective_fee_percent(100);
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.disable_dynamic_fee();
+            },
+        )
+        .assert_ok();
+    pair_setup.check_effective_fee_percent(300);
+}
+
+#[test]
+fn test_single_token_liquidity() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let (lp_payment, first_payment, second_payment) = sc
+                    .add_liquidity_single_token(managed_biguint!(40_000))
+                    .into_tuple();
+                assert_eq!(lp_payment.token_identifier, managed_token_id!(LP_TOKEN_ID));
+                assert_eq!(lp_payment.amount, managed_biguint!(48_735));
+                assert_eq!(first_payment.amount, managed_biguint!(51_115));
+                assert_eq!(second_payment.amount, managed_biguint!(46_473));
+            },
+        )
+        .assert_ok();
+
+    // only the rounding leftovers are sent back
+    pair_setup.b_mock.check_esdt_balance(
+        &pair_setup.user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000 - 100_000 + 2),
+    );
+    pair_setup.b_mock.check_esdt_balance(
+        &pair_setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_MEX_TOKENS - 1_001_000),
+    );
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(48_735),
+            |sc| {
+                sc.remove_liquidity_to_single_token(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(95_000),
+                );
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(48_735),
+            |sc| {
+                let output_payment = sc.remove_liquidity_to_single_token(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(90_000),
+                );
+                assert_eq!(
+                    output_payment.token_identifier,
+                    managed_token_id!(MEX_TOKEN_ID)
+                );
+                assert_eq!(output_payment.amount, managed_biguint!(90_659));
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn test_stable_single_token_liquidity() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_stable_swap(100);
+
+    let token_amount = 1_001_000_000;
+    pair_setup.add_liquidity(
+        token_amount,
+        token_amount,
+        token_amount,
+        token_amount,
+        token_amount - 1_000,
+        token_amount,
+        token_amount,
+    );
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000_000),
+            |sc| {
+                let (lp_payment, first_payment, second_payment) = sc
+                    .add_liquidity_single_token(managed_biguint!(45_000_000))
+                    .into_tuple();
+                assert_eq!(lp_payment.amount, managed_biguint!(49_913_100));
+                assert_eq!(first_payment.amount, managed_biguint!(52_291_977));
+                assert_eq!(second_payment.amount, managed_biguint!(47_542_479));
+            },
+        )
+        .assert_ok();
+
+    // the swap amount is searched on the stable curve, so only rounding is sent back
+    pair_setup.b_mock.check_esdt_balance(
+        &pair_setup.user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - token_amount - 100_000_000 + 2),
+    );
+    pair_setup.b_mock.check_esdt_balance(
+        &pair_setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_MEX_TOKENS - token_amount),
+    );
+}
+
+#[test]
+fn test_flash_swap() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let borrower_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&pair_setup.owner_address),
+        flash_borrower_mock::contract_obj,
+        "flash-borrower-mock.wasm",
+    );
+    let borrower_address = borrower_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&borrower_address, WEGLD_TOKEN_ID, &rust_biguint!(100));
+
+    pair_setup
+        .try_flash_swap(
+            &borrower_address,
+            WEGLD_TOKEN_ID,
+            10_000,
+            WEGLD_TOKEN_ID,
+            10_011,
+        )
+        .assert_user_error("Flash swaps are not enabled");
+
+    // 0.1%
+    pair_setup.set_flash_swap_fee(100);
+
+    let user_address = pair_setup.user_address.clone();
+    pair_setup
+        .try_flash_swap(
+            &user_address,
+            WEGLD_TOKEN_ID,
+            10_000,
+            WEGLD_TOKEN_ID,
+            10_011,
+        )
+        .assert_user_error("Flash swaps are only available to contracts");
+
+    pair_setup
+        .try_flash_swap(
+            &borrower_address,
+            WEGLD_TOKEN_ID,
+            10_000,
+            WEGLD_TOKEN_ID,
+            10_000,
+        )
+        .assert_user_error("K invariant failed");
+
+    pair_setup
+        .try_flash_swap(
+            &borrower_address,
+            WEGLD_TOKEN_ID,
+            10_000,
+            WEGLD_TOKEN_ID,
+            10_011,
+        )
+        .assert_ok();
+
+    // no fee destinations, the fee stays in the reserves
+    pair_setup.b_mock.check_esdt_balance(
+        &borrower_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(100 - 11),
+    );
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                managed_biguint!(1_001_011)
+            );
+            assert_eq!(
+                sc.pair_reserve(&managed_token_id!(MEX_TOKEN_ID)).get(),
+                managed_biguint!(1_001_000)
+            );
+            let (first_fee_growth, _) = sc.get_fee_growth().into_tuple();
+            assert_eq!(first_fee_growth, managed_biguint!(9_990_009_990_009u64));
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_flash_swap_whitelist_only(true);
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .try_flash_swap(
+            &borrower_address,
+            WEGLD_TOKEN_ID,
+            10_000,
+            WEGLD_TOKEN_ID,
+            10_011,
+        )
+        .assert_user_error("Not whitelisted");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut borrowers = MultiValueEncoded::new();
+                borrowers.push(managed_address!(&borrower_address));
+                sc.add_flash_swap_borrowers(borrowers);
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .try_flash_swap(
+            &borrower_address,
+            WEGLD_TOKEN_ID,
+            10_000,
+            WEGLD_TOKEN_ID,
+            10_011,
+        )
+        .assert_ok();
+
+    // the special fee share goes to the fee destination, the rest to the LPs
+    let fee_address = pair_setup.b_mock.create_user_account(&rust_biguint!(0));
+    let pair_address = pair_setup.pair_wrapper.address_ref().clone();
+    pair_setup.b_mock.set_esdt_local_roles(
+        &pair_address,
+        WEGLD_TOKEN_ID,
+        &[EsdtLocalRole::Burn][..],
+    );
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_fee_on(
+                    true,
+                    managed_address!(&fee_address),
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                );
+            },
+        )
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&borrower_address, WEGLD_TOKEN_ID, &rust_biguint!(1_000));
+
+    pair_setup
+        .try_flash_swap(
+            &borrower_address,
+            WEGLD_TOKEN_ID,
+            100_000,
+            WEGLD_TOKEN_ID,
+            100_101,
+        )
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            // 84 of the 100 fee, the other 16 are burned for the destination
+            assert_eq!(
+                sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                managed_biguint!(1_001_107)
+            );
+            let (first_fee_growth, _) = sc.get_fee_growth().into_tuple();
+            assert_eq!(first_fee_growth, managed_biguint!(103_896_103_896_101u64));
+        })
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&pair_address, WEGLD_TOKEN_ID, &rust_biguint!(1_001_107));
+
+    // the pair cannot be used from the borrower callback
+    pair_setup
+        .try_flash_swap_reentering(
+            &borrower_address,
+            WEGLD_TOKEN_ID,
+            10_000,
+            b"swapTokensFixedInput",
+            &[MEX_TOKEN_ID, &[1u8]],
+        )
+        .assert_user_error("Pair cannot be used from a hook or a flash swap callback");
+    pair_setup
+        .try_flash_swap_reentering(
+            &borrower_address,
+            WEGLD_TOKEN_ID,
+            10_000,
+            b"addLiquiditySingleToken",
+            &[&[1u8]],
+        )
+        .assert_user_error("Pair cannot be used from a hook or a flash swap callback");
+}
+
+#[test]
+fn test_fee_growth() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    // no fee destinations, the whole 0.3% fee stays with the LPs
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1, 90_669);
+
+    let mut first_fee_growth_snapshot = 0u64;
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let (first_fee_growth, second_fee_growth) = sc.get_fee_growth().into_tuple();
+            assert_eq!(first_fee_growth, managed_biguint!(299_700_299_700_299u64));
+            assert_eq!(second_fee_growth, managed_biguint!(0));
+            first_fee_growth_snapshot = first_fee_growth.to_u64().unwrap();
+
+            let (first_fees, second_fees) = sc
+                .get_fees_earned(
+                    managed_biguint!(1_000_000),
+                    managed_biguint!(0),
+                    managed_biguint!(0),
+                )
+                .into_tuple();
+            assert_eq!(
+                first_fees.token_identifier,
+                managed_token_id!(WEGLD_TOKEN_ID)
+            );
+            assert_eq!(first_fees.amount, managed_biguint!(299));
+            assert_eq!(second_fees.amount, managed_biguint!(0));
+        })
+        .assert_ok();
+
+    pair_setup.swap_fixed_input(MEX_TOKEN_ID, 10_000, WEGLD_TOKEN_ID, 1, 11_927);
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let (first_fees, second_fees) = sc
+                .get_fees_earned(
+                    managed_biguint!(1_000_000),
+                    managed_biguint!(first_fee_growth_snapshot),
+                    managed_biguint!(0),
+                )
+                .into_tuple();
+            assert_eq!(first_fees.amount, managed_biguint!(0));
+            assert_eq!(
+                second_fees.token_identifier,
+                managed_token_id!(MEX_TOKEN_ID)
+            );
+            assert_eq!(second_fees.amount, managed_biguint!(29));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_protocol_fee() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let treasury_address = pair_setup.b_mock.create_user_account(&rust_biguint!(0));
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                // a sixth of the liquidity growth
+                sc.set_protocol_fee(managed_address!(&treasury_address), 16_666);
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_fee_on(
+                    true,
+                    managed_address!(&treasury_address),
+                    managed_token_id!(MEX_TOKEN_ID),
+                );
+            },
+        )
+        .assert_user_error("Protocol fee is enabled");
+
+    // nothing is sent out during the swap
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1, 90_669);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_pending_protocol_fee(), managed_biguint!(22));
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                sc.remove_liquidity(managed_biguint!(1), managed_biguint!(1));
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&treasury_address, LP_TOKEN_ID, &rust_biguint!(22));
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_pending_protocol_fee(), managed_biguint!(0));
+            assert_eq!(
+                sc.lp_token_supply().get(),
+                managed_biguint!(1_001_000 + 22 - 1_000)
+            );
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.disable_protocol_fee();
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.disable_protocol_fee();
+            },
+        )
+        .assert_user_error("Protocol fee is not enabled");
+}
+
+#[test]
+fn test_fee_accumulation() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let fee_address = pair_setup.b_mock.create_user_account(&rust_biguint!(0));
+    let pair_address = pair_setup.pair_wrapper.address_ref().clone();
+    pair_setup.b_mock.set_esdt_local_roles(
+        &pair_address,
+        WEGLD_TOKEN_ID,
+        &[EsdtLocalRole::Burn][..],
+    );
+    pair_setup
+        .b_mock
+        .set_esdt_local_roles(&pair_address, MEX_TOKEN_ID, &[EsdtLocalRole::Burn][..]);
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_fee_on(
+                    true,
+                    managed_address!(&fee_address),
+                    managed_token_id!(MEX_TOKEN_ID),
+                );
+                sc.set_fee_accumulation(true);
+            },
+        )
+        .assert_ok();
+
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1, 90_669);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1, 75_592);
+
+    // the special fee of both swaps is held for the destination, outside the reserves
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&pair_address, WEGLD_TOKEN_ID, &rust_biguint!(1_201_000));
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let wegld_token_id = managed_token_id!(WEGLD_TOKEN_ID);
+            assert_eq!(
+                sc.pair_reserve(&wegld_token_id).get(),
+                managed_biguint!(1_200_900)
+            );
+
+            let mut pending_fees = sc.get_pending_fee_balances().into_iter();
+            let (address, payment) = pending_fees.next().unwrap().into_tuple();
+            assert!(pending_fees.next().is_none());
+            assert_eq!(address, managed_address!(&fee_address));
+            assert_eq!(
+                payment,
+                EsdtTokenPayment::new(wegld_token_id, 0, managed_biguint!(100))
+            );
+        })
+        .assert_ok();
+
+    // only the owner and the admins can trigger the distribution
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.distribute_accumulated_fees();
+            },
+        )
+        .assert_user_error("Permission denied");
+
+    // the fee is swapped locally and burned
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.distribute_accumulated_fees();
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&pair_address, WEGLD_TOKEN_ID, &rust_biguint!(1_201_000));
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                managed_biguint!(1_201_000)
+            );
+            assert!(sc.get_pending_fee_balances().is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_pair_hooks() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let hook_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&pair_setup.owner_address),
+        pair_hook_mock::contract_obj,
+        "pair-hook-mock.wasm",
+    );
+    let hook_address = hook_wrapper.address_ref().clone();
+    let user_address = pair_setup.user_address.clone();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_hook(
+                    PairHookType::BeforeSwap,
+                    managed_address!(&user_address),
+                    10_000_000,
+                );
+            },
+        )
+        .assert_user_error("Hook address is not a contract");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_hook(
+                    PairHookType::BeforeSwap,
+                    managed_address!(&hook_address),
+                    10_000_000,
+                );
+                sc.add_hook(
+                    PairHookType::AfterSwap,
+                    managed_address!(&hook_address),
+                    10_000_000,
+                );
+            },
+        )
+        .assert_ok();
+
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1, 90_669);
+    pair_setup
+        .b_mock
+        .execute_query(&hook_wrapper, |sc| {
+            assert_eq!(sc.last_swap_output().get(), managed_biguint!(90_669));
+            // the reserves are already written when the hook runs
+            assert_eq!(
+                sc.last_swap_reserve_out().get(),
+                managed_biguint!(1_001_000 - 90_669)
+            );
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &hook_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.block_caller(managed_address!(&user_address));
+            },
+        )
+        .assert_ok();
+    pair_setup
+        .try_swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1)
+        .assert_user_error("Action vetoed by hook");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.remove_hook(PairHookType::BeforeSwap, managed_address!(&hook_address));
+            },
+        )
+        .assert_ok();
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1, 75_589);
+
+    // flash swaps go through the swap hooks as well
+    let borrower_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&pair_setup.owner_address),
+        flash_borrower_mock::contract_obj,
+        "flash-borrower-mock.wasm",
+    );
+    let borrower_address = borrower_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&borrower_address, WEGLD_TOKEN_ID, &rust_biguint!(2_000));
+    pair_setup.set_flash_swap_fee(100);
+
+    pair_setup
+        .try_flash_swap(
+            &borrower_address,
+            MEX_TOKEN_ID,
+            1_000,
+            WEGLD_TOKEN_ID,
+            1_500,
+        )
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .execute_query(&hook_wrapper, |sc| {
+            assert_eq!(sc.last_swap_output().get(), managed_biguint!(1_000));
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_hook(
+                    PairHookType::BeforeSwap,
+                    managed_address!(&hook_address),
+                    10_000_000,
+                );
+            },
+        )
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &hook_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.block_caller(managed_address!(&borrower_address));
+            },
+        )
+        .assert_ok();
+    pair_setup
+        .try_flash_swap(
+            &borrower_address,
+            MEX_TOKEN_ID,
+            1_000,
+            WEGLD_TOKEN_ID,
+            1_500,
+        )
+        .assert_user_error("Action vetoed by hook");
+}
+
 #[test]
 fn test_safe_price_migration() {
     let mut pair_setup = PairSetup::new(pair::contract_obj);
@@ -0,0 +1,32 @@
+multiversx_sc::imports!();
+
+/// Pays back `repay_amount` of `repay_token_id` from its own balance when it
+/// receives the flash swap tokens.
+#[multiversx_sc::contract]
+pub trait FlashBorrowerMock {
+    #[init]
+    fn init(&self) {}
+
+    #[payable("*")]
+    #[endpoint(repayFlashSwap)]
+    fn repay_flash_swap(&self, repay_token_id: TokenIdentifier, repay_amount: BigUint) {
+        let caller = self.blockchain().get_caller();
+        self.send()
+            .direct_esdt(&caller, &repay_token_id, 0, &repay_amount);
+    }
+
+    /// Sends the received flash swap tokens back into `endpoint` of the pair, without
+    /// paying back.
+    #[payable("*")]
+    #[endpoint(reenterPair)]
+    fn reenter_pair(&self, endpoint: ManagedBuffer, args: MultiValueEncoded<ManagedBuffer>) {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt().clone();
+        self.tx()
+            .to(&caller)
+            .raw_call(endpoint)
+            .arguments_raw(args.to_arg_buffer())
+            .single_esdt(&payment.token_identifier, 0, &payment.amount)
+            .sync_call();
+    }
+}
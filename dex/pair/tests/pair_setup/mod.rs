@@ -1,10 +1,14 @@
+pub mod flash_borrower_mock;
+pub mod pair_hook_mock;
+
 use multiversx_sc::codec::multi_types::MultiValue3;
 use multiversx_sc::types::{
     Address, BigUint, EsdtLocalRole, EsdtTokenPayment, ManagedAddress, MultiValueEncoded,
 };
 use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
 use multiversx_sc_scenario::{
-    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+    managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint,
+    whitebox_legacy::*, DebugApi,
 };
 
 pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
@@ -19,11 +23,15 @@ pub const LP_PROXY_TOKEN_ID: &[u8] = b"LPPROXY-abcdef";
 pub const USER_TOTAL_MEX_TOKENS: u64 = 5_000_000_000;
 pub const USER_TOTAL_WEGLD_TOKENS: u64 = 5_000_000_000;
 
+use pair::circuit_breaker::{CircuitBreakerAction, CircuitBreakerModule};
 use pair::config::ConfigModule as PairConfigModule;
+use pair::dynamic_fee::DynamicFeeModule;
 use pair::pair_actions::add_liq::AddLiquidityModule;
+use pair::pair_actions::flash_swap::FlashSwapModule;
 use pair::pair_actions::swap::SwapModule;
 use pair::safe_price::SafePriceModule;
 use pair::safe_price_view::*;
+use pair::stable_swap::StableSwapModule;
 use pair::*;
 use pausable::{PausableModule, State};
 
@@ -209,6 +217,28 @@ where
             .assert_ok();
     }
 
+    pub fn try_swap_fixed_input(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        desired_token_id: &[u8],
+        desired_amount_min: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.user_address,
+            &self.pair_wrapper,
+            payment_token_id,
+            0,
+            &rust_biguint!(payment_amount),
+            |sc| {
+                sc.swap_tokens_fixed_input(
+                    managed_token_id!(desired_token_id),
+                    managed_biguint!(desired_amount_min),
+                );
+            },
+        )
+    }
+
     pub fn swap_fixed_output(
         &mut self,
         payment_token_id: &[u8],
@@ -475,4 +505,136 @@ where
             );
         });
     }
+
+    pub fn enable_stable_swap(&mut self, amplification: u64) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.enable_stable_swap(amplification);
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn set_circuit_breaker(
+        &mut self,
+        max_deviation_percent: u64,
+        window_rounds: u64,
+        action: CircuitBreakerAction,
+    ) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.set_circuit_breaker(max_deviation_percent, window_rounds, action);
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn set_dynamic_fee(
+        &mut self,
+        min_fee_percent: u64,
+        max_fee_percent: u64,
+        window_rounds: u64,
+        max_volatility_percent: u64,
+    ) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.set_dynamic_fee(
+                        min_fee_percent,
+                        max_fee_percent,
+                        window_rounds,
+                        max_volatility_percent,
+                    );
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn check_effective_fee_percent(&mut self, expected_fee_percent: u64) {
+        self.b_mock
+            .execute_query(&self.pair_wrapper, |sc| {
+                assert_eq!(sc.get_effective_total_fee_percent(), expected_fee_percent);
+            })
+            .assert_ok();
+    }
+
+    pub fn set_flash_swap_fee(&mut self, fee_percent: u64) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.set_flash_swap_fee(fee_percent);
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn try_flash_swap(
+        &mut self,
+        borrower_address: &Address,
+        token_out: &[u8],
+        amount_out: u64,
+        repay_token_id: &[u8],
+        repay_amount: u64,
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            borrower_address,
+            &self.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut callback_args = MultiValueEncoded::new();
+                callback_args.push(managed_buffer!(repay_token_id));
+                callback_args.push(managed_biguint!(repay_amount).to_bytes_be_buffer());
+
+                sc.flash_swap(
+                    managed_token_id!(token_out),
+                    managed_biguint!(amount_out),
+                    managed_buffer!(b"repayFlashSwap"),
+                    callback_args,
+                );
+            },
+        )
+    }
+
+    pub fn try_flash_swap_reentering(
+        &mut self,
+        borrower_address: &Address,
+        token_out: &[u8],
+        amount_out: u64,
+        endpoint: &[u8],
+        endpoint_args: &[&[u8]],
+    ) -> TxResult {
+        self.b_mock.execute_tx(
+            borrower_address,
+            &self.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut callback_args = MultiValueEncoded::new();
+                callback_args.push(managed_buffer!(endpoint));
+                for arg in endpoint_args {
+                    callback_args.push(managed_buffer!(arg));
+                }
+
+                sc.flash_swap(
+                    managed_token_id!(token_out),
+                    managed_biguint!(amount_out),
+                    managed_buffer!(b"reenterPair"),
+                    callback_args,
+                );
+            },
+        )
+    }
 }
@@ -0,0 +1,40 @@
+multiversx_sc::imports!();
+
+use pair::{contexts::swap::SwapContext, read_pair_storage};
+
+/// Vetoes the swaps of the blocked callers and keeps the output of the last swap,
+/// along with the output token reserve the pair had stored at that point.
+#[multiversx_sc::contract]
+pub trait PairHookMock: read_pair_storage::ReadPairStorageModule {
+    #[init]
+    fn init(&self) {}
+
+    #[endpoint(blockCaller)]
+    fn block_caller(&self, caller: ManagedAddress) {
+        let _ = self.blocked_callers().insert(caller);
+    }
+
+    #[endpoint(beforeSwap)]
+    fn before_swap(&self, caller: ManagedAddress, _context: SwapContext<Self::Api>) -> bool {
+        !self.blocked_callers().contains(&caller)
+    }
+
+    #[endpoint(afterSwap)]
+    fn after_swap(&self, _caller: ManagedAddress, context: SwapContext<Self::Api>) {
+        let pair_address = self.blockchain().get_caller();
+        let reserve_out = self
+            .get_pair_reserve_mapper(pair_address, &context.output_token_id)
+            .get();
+        self.last_swap_reserve_out().set(reserve_out);
+        self.last_swap_output().set(context.final_output_amount);
+    }
+
+    #[storage_mapper("blocked_callers")]
+    fn blocked_callers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("last_swap_output")]
+    fn last_swap_output(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("last_swap_reserve_out")]
+    fn last_swap_reserve_out(&self) -> SingleValueMapper<BigUint>;
+}
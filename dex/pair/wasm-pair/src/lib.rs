@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           54
+// Endpoints:                           98
 // Async Callback (empty):               1
-// Total number of exported functions:  57
+// Total number of exported functions: 101
 
 #![no_std]
 
@@ -21,18 +21,50 @@ multiversx_sc_wasm_adapter::endpoints! {
         init => init
         upgrade => upgrade
         setLpTokenIdentifier => set_lp_token_identifier
+        enableStableSwap => enable_stable_swap
+        rampAmplification => ramp_amplification
+        stopAmplificationRamp => stop_amplification_ramp
+        getAmplification => get_amplification
+        getAmmMode => amm_mode
+        getAmplificationRamp => amplification_ramp
+        setCircuitBreaker => set_circuit_breaker
+        disableCircuitBreaker => disable_circuit_breaker
+        getCircuitBreakerConfig => circuit_breaker_config
+        setDynamicFee => set_dynamic_fee
+        disableDynamicFee => disable_dynamic_fee
+        isDynamicFeeEnabled => is_dynamic_fee_enabled
+        getEffectiveFeePercent => get_effective_total_fee_percent
+        getDynamicFeeConfig => dynamic_fee_config
         getFeeState => is_fee_enabled
         whitelist => whitelist_endpoint
         removeWhitelist => remove_whitelist
         addTrustedSwapPair => add_trusted_swap_pair
         removeTrustedSwapPair => remove_trusted_swap_pair
         setupFeesCollector => setup_fees_collector
+        setProtocolFee => set_protocol_fee
+        disableProtocolFee => disable_protocol_fee
+        isProtocolFeeEnabled => is_protocol_fee_enabled
+        getPendingProtocolFee => get_pending_protocol_fee
+        setFeeAccumulation => set_fee_accumulation
+        distributeAccumulatedFees => distribute_accumulated_fees
+        getPendingFeeBalances => get_pending_fee_balances
         setFeeOn => set_fee_on
         getFeeDestinations => get_fee_destinations
         getTrustedSwapPairs => get_trusted_swap_pairs
         getWhitelistedManagedAddresses => get_whitelisted_managed_addresses
         getFeesCollectorAddress => fees_collector_address
         getFeesCollectorCutPercentage => fees_collector_cut_percentage
+        getProtocolFeeTreasury => protocol_fee_treasury
+        getProtocolFeeSharePercent => protocol_fee_share_percent
+        isFeeAccumulationEnabled => fee_accumulation_enabled
+        getAccumulatedFees => accumulated_fees
+        getAccumulatedFeesCollectorCut => accumulated_fees_collector_cut
+        getFeeGrowth => get_fee_growth
+        getFeesEarned => get_fees_earned
+        getFeeGrowthPerLpToken => fee_growth_per_lp_token
+        addHook => add_hook
+        removeHook => remove_hook
+        getHooks => get_hooks
         setStateActiveNoSwaps => set_state_active_no_swaps
         setFeePercents => set_fee_percent
         getLpTokenIdentifier => get_lp_token_identifier
@@ -72,8 +104,20 @@ multiversx_sc_wasm_adapter::endpoints! {
         getTokensForGivenPosition => get_tokens_for_given_position
         getReservesAndTotalSupply => get_reserves_and_total_supply
         getAmountOut => get_amount_out_view
+        canSwapFixedInput => can_swap_fixed_input
         getAmountIn => get_amount_in_view
         getEquivalent => get_equivalent
+        addLiquiditySingleToken => add_liquidity_single_token
+        removeLiquidityToSingleToken => remove_liquidity_to_single_token
+        setFlashSwapFee => set_flash_swap_fee
+        disableFlashSwaps => disable_flash_swaps
+        setFlashSwapWhitelistOnly => set_flash_swap_whitelist_only
+        addFlashSwapBorrowers => add_flash_swap_borrowers
+        removeFlashSwapBorrowers => remove_flash_swap_borrowers
+        flashSwap => flash_swap
+        getFlashSwapFeePercent => flash_swap_fee_percent
+        isFlashSwapWhitelistOnly => flash_swap_whitelist_only
+        getFlashSwapBorrowers => flash_swap_borrowers
     )
 }
 
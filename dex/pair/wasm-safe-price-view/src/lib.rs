@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            9
+// Endpoints:                           13
 // Async Callback (empty):               1
-// Total number of exported functions:  11
+// Total number of exported functions:  15
 
 #![no_std]
 
@@ -27,6 +27,10 @@ multiversx_sc_wasm_adapter::external_view_endpoints! {
         getSafePriceByRoundOffset => get_safe_price_by_round_offset
         getSafePriceByTimestampOffset => get_safe_price_by_timestamp_offset
         getSafePrice => get_safe_price
+        getGeometricSafePriceByDefaultOffset => get_geometric_safe_price_by_default_offset
+        getGeometricSafePrice => get_geometric_safe_price
+        getMedianSafePriceByDefaultOffset => get_median_safe_price_by_default_offset
+        getMedianSafePrice => get_median_safe_price
         getPriceObservation => get_price_observation_view
     )
 }
@@ -0,0 +1,197 @@
+multiversx_sc::imports!();
+
+use crate::split_route_swap::SwapStep;
+use crate::{config, events, factory, fee_tiers, multi_pair_swap};
+use pair::{pair_actions::views::ProxyTrait as _, read_pair_storage};
+
+#[multiversx_sc::module]
+pub trait AtomicSwapModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + factory::FactoryModule
+    + fee_tiers::FeeTiersModule
+    + multi_pair_swap::MultiPairSwap
+    + token_send::TokenSendModule
+    + events::EventsModule
+{
+    /// Swaps the whole payment through `path`. Intermediate hops are not bounded,
+    /// the entire chain is reverted if the final output is below `amount_out_min`.
+    #[payable("*")]
+    #[endpoint(swapExactInWithDeadline)]
+    fn swap_exact_in_with_deadline(
+        &self,
+        amount_out_min: BigUint,
+        deadline: u64,
+        path: MultiValueEncoded<SwapStep<Self::Api>>,
+    ) -> EsdtTokenPayment {
+        require!(self.is_active(), "Not active");
+        self.require_deadline_not_passed(deadline);
+        require!(amount_out_min > 0u64, "Invalid amount. Should not be zero");
+
+        let payment = self.get_atomic_swap_payment();
+        let path = path.to_vec();
+        self.require_valid_path(&path, &payment.token_identifier);
+
+        let mut last_payment = payment.clone();
+        for step in path.iter() {
+            last_payment = self.actual_swap_fixed_input(
+                step.pair_address.clone(),
+                last_payment.token_identifier,
+                last_payment.amount,
+                step.token_out.clone(),
+                BigUint::from(1u64),
+            );
+        }
+        require!(last_payment.amount >= amount_out_min, "Slippage exceeded");
+
+        let caller = self.blockchain().get_caller();
+        self.send_atomic_swap_output(caller, payment, last_payment.clone(), BigUint::zero());
+
+        last_payment
+    }
+
+    /// Buys exactly `amount_out` of the last token in `path`. The amounts are computed
+    /// backwards through the chain and the payment acts as the maximum amount in.
+    /// Only the unused part of the payment is refunded, no intermediate tokens.
+    #[payable("*")]
+    #[endpoint(swapExactOutWithDeadline)]
+    fn swap_exact_out_with_deadline(
+        &self,
+        amount_out: BigUint,
+        deadline: u64,
+        path: MultiValueEncoded<SwapStep<Self::Api>>,
+    ) -> MultiValue2<EsdtTokenPayment, EsdtTokenPayment> {
+        require!(self.is_active(), "Not active");
+        self.require_deadline_not_passed(deadline);
+        require!(amount_out > 0u64, "Invalid amount. Should not be zero");
+
+        let payment = self.get_atomic_swap_payment();
+        let path = path.to_vec();
+        self.require_valid_path(&path, &payment.token_identifier);
+
+        let amounts_in = self.get_amounts_in(&path, &amount_out);
+        let amount_in = amounts_in.get(0).clone();
+        require!(amount_in <= payment.amount, "Slippage exceeded");
+
+        let mut last_payment =
+            EsdtTokenPayment::new(payment.token_identifier.clone(), 0, amount_in.clone());
+        for (index, step) in path.iter().enumerate() {
+            let amount_wanted = if index + 1 < path.len() {
+                amounts_in.get(index + 1).clone()
+            } else {
+                amount_out.clone()
+            };
+
+            let (output, residuum) = self.actual_swap_fixed_output(
+                step.pair_address.clone(),
+                last_payment.token_identifier,
+                last_payment.amount,
+                step.token_out.clone(),
+                amount_wanted,
+            );
+            require!(
+                residuum.amount == 0u64,
+                "Route amounts changed during execution"
+            );
+
+            last_payment = output;
+        }
+
+        let caller = self.blockchain().get_caller();
+        let refund = EsdtTokenPayment::new(
+            payment.token_identifier.clone(),
+            0,
+            &payment.amount - &amount_in,
+        );
+        self.send_atomic_swap_output(caller, payment, last_payment.clone(), refund.amount.clone());
+
+        (last_payment, refund).into()
+    }
+
+    /// Returns the amount needed at the start of every hop, in path order.
+    fn get_amounts_in(
+        &self,
+        path: &ManagedVec<SwapStep<Self::Api>>,
+        amount_out: &BigUint,
+    ) -> ManagedVec<BigUint> {
+        let mut amounts_in_reversed = ManagedVec::<Self::Api, BigUint>::new();
+        let mut amount_wanted = amount_out.clone();
+        for index in (0..path.len()).rev() {
+            let step = path.get(index);
+            amount_wanted = self
+                .pair_contract_proxy(step.pair_address.clone())
+                .get_amount_in_view(step.token_out.clone(), amount_wanted)
+                .execute_on_dest_context();
+            amounts_in_reversed.push(amount_wanted.clone());
+        }
+
+        let mut amounts_in = ManagedVec::new();
+        for index in (0..amounts_in_reversed.len()).rev() {
+            amounts_in.push(amounts_in_reversed.get(index).clone());
+        }
+
+        amounts_in
+    }
+
+    fn get_atomic_swap_payment(&self) -> EsdtTokenPayment {
+        let payment = self.call_value().single_esdt().clone();
+        require!(payment.token_nonce == 0, "Invalid nonce. Should be zero");
+        require!(payment.amount > 0u64, "Invalid amount. Should not be zero");
+
+        payment
+    }
+
+    fn require_valid_path(
+        &self,
+        path: &ManagedVec<SwapStep<Self::Api>>,
+        token_in: &TokenIdentifier,
+    ) {
+        require!(!path.is_empty(), "Invalid swap path. Should not be empty");
+
+        let mut last_token = token_in.clone();
+        for step in path.iter() {
+            self.check_is_pair_sc(&step.pair_address);
+
+            let first_token_id = self
+                .get_first_token_id_mapper(step.pair_address.clone())
+                .get();
+            let second_token_id = self
+                .get_second_token_id_mapper(step.pair_address.clone())
+                .get();
+            let is_valid_step = (last_token == first_token_id && step.token_out == second_token_id)
+                || (last_token == second_token_id && step.token_out == first_token_id);
+            require!(is_valid_step, "Invalid swap path");
+
+            last_token = step.token_out.clone();
+        }
+    }
+
+    fn require_deadline_not_passed(&self, deadline: u64) {
+        require!(
+            self.blockchain().get_block_timestamp() <= deadline,
+            "Deadline passed"
+        );
+    }
+
+    fn send_atomic_swap_output(
+        &self,
+        caller: ManagedAddress,
+        payment: EsdtTokenPayment,
+        output: EsdtTokenPayment,
+        refund_amount: BigUint,
+    ) {
+        // the output goes last, as expected by the multi pair swap event
+        let mut payments = ManagedVec::new();
+        if refund_amount > 0u64 {
+            payments.push(EsdtTokenPayment::new(
+                payment.token_identifier.clone(),
+                0,
+                refund_amount,
+            ));
+        }
+        payments.push(output);
+        self.send().direct_multi(&caller, &payments);
+
+        self.emit_multi_pair_swap_event(caller, payment.token_identifier, payment.amount, payments);
+    }
+}
@@ -0,0 +1,391 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{config, events, factory, factory::PairTokens, fee_tiers, multi_pair_swap};
+use pair::{
+    config::MAX_PERCENTAGE,
+    dynamic_fee_reader, read_pair_storage, safe_price_reader,
+    stable_swap::{get_ramp_amplification, get_stable_amount_out, AmmMode},
+};
+use pausable::State;
+
+pub const DEFAULT_MAX_ROUTE_HOPS: usize = 3;
+pub const MAX_ROUTE_HOPS: usize = 4;
+pub const MAX_ROUTE_BASE_TOKENS: usize = 10;
+pub const MAX_ROUTE_CANDIDATE_PAIRS: usize = 30;
+
+#[type_abi]
+#[derive(
+    ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug,
+)]
+pub struct RouteHop<M: ManagedTypeApi> {
+    pub pair_address: ManagedAddress<M>,
+    pub token_in: TokenIdentifier<M>,
+    pub token_out: TokenIdentifier<M>,
+    pub amount_in: BigUint<M>,
+    pub amount_out: BigUint<M>,
+}
+
+/// The pair state a route is priced with. The amplification is zero for constant product pairs.
+#[derive(ManagedVecItem, Clone)]
+pub struct RoutablePair<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    pub first_token_id: TokenIdentifier<M>,
+    pub second_token_id: TokenIdentifier<M>,
+    pub first_token_reserve: BigUint<M>,
+    pub second_token_reserve: BigUint<M>,
+    pub total_fee_percent: u64,
+    pub amplification: u64,
+}
+
+#[multiversx_sc::module]
+pub trait AutoRouteModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
+    + factory::FactoryModule
+    + fee_tiers::FeeTiersModule
+    + multi_pair_swap::MultiPairSwap
+    + token_send::TokenSendModule
+    + events::EventsModule
+{
+    #[only_owner]
+    #[endpoint(setMaxRouteHops)]
+    fn set_max_route_hops(&self, max_route_hops: usize) {
+        require!(
+            max_route_hops > 0 && max_route_hops <= MAX_ROUTE_HOPS,
+            "Invalid max route hops"
+        );
+
+        self.max_route_hops().set(max_route_hops);
+    }
+
+    /// The tokens a route can go through. Routes that are not direct swaps only use these.
+    #[only_owner]
+    #[endpoint(addRouteBaseTokens)]
+    fn add_route_base_tokens(&self, tokens: MultiValueEncoded<TokenIdentifier>) {
+        let mut route_base_tokens = self.route_base_tokens();
+        for token in tokens {
+            require!(token.is_valid_esdt_identifier(), "Invalid token ID");
+            let _ = route_base_tokens.insert(token);
+        }
+
+        require!(
+            route_base_tokens.len() <= MAX_ROUTE_BASE_TOKENS,
+            "Too many route base tokens"
+        );
+    }
+
+    #[only_owner]
+    #[endpoint(removeRouteBaseTokens)]
+    fn remove_route_base_tokens(&self, tokens: MultiValueEncoded<TokenIdentifier>) {
+        let mut route_base_tokens = self.route_base_tokens();
+        for token in tokens {
+            let _ = route_base_tokens.swap_remove(&token);
+        }
+    }
+
+    /// Searches the pairs between the two tokens and the route base tokens for the route
+    /// with the largest output, with the amounts computed from the pair reserves and the
+    /// current fees. Returns an empty list if the tokens are not connected.
+    #[view(getBestRoute)]
+    fn get_best_route(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+        amount_in: BigUint,
+    ) -> ManagedVec<RouteHop<Self::Api>> {
+        require!(token_in != token_out, "Tokens cannot be the same");
+        require!(amount_in > 0u64, "Invalid amount. Should not be zero");
+
+        let mut base_tokens = ManagedVec::new();
+        for token in self.route_base_tokens().iter() {
+            if token != token_in && token != token_out {
+                base_tokens.push(token);
+            }
+        }
+        let pairs = self.get_routable_pairs(&token_in, &token_out, &base_tokens);
+
+        let mut current_route = ManagedVec::new();
+        let mut best_route = ManagedVec::new();
+        self.search_best_route(
+            &pairs,
+            &base_tokens,
+            &token_in,
+            &token_out,
+            &amount_in,
+            &mut current_route,
+            &mut best_route,
+        );
+
+        best_route
+    }
+
+    #[payable("*")]
+    #[endpoint(swapWithAutoRoute)]
+    fn swap_with_auto_route(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment {
+        require!(self.is_active(), "Not active");
+
+        let (token_id, nonce, amount) = self.call_value().single_esdt().clone().into_tuple();
+        require!(nonce == 0, "Invalid nonce. Should be zero");
+
+        let route = self.get_best_route(token_id.clone(), token_out, amount.clone());
+        require!(!route.is_empty(), "No route found");
+
+        // intermediate hops are only bounded by the final output check, as the
+        // pairs' fee swaps may move the reserves between the hops
+        let mut last_payment = EsdtTokenPayment::new(token_id.clone(), 0, amount.clone());
+        let mut payments = ManagedVec::new();
+        for hop in route.iter() {
+            last_payment = self.actual_swap_fixed_input(
+                hop.pair_address.clone(),
+                last_payment.token_identifier,
+                last_payment.amount,
+                hop.token_out.clone(),
+                BigUint::from(1u64),
+            );
+            payments.push(last_payment.clone());
+        }
+        require!(last_payment.amount >= amount_out_min, "Slippage exceeded");
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(
+            &caller,
+            &last_payment.token_identifier,
+            0,
+            &last_payment.amount,
+        );
+
+        self.emit_multi_pair_swap_event(caller, token_id, amount, payments);
+
+        last_payment
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_best_route(
+        &self,
+        pairs: &ManagedVec<RoutablePair<Self::Api>>,
+        base_tokens: &ManagedVec<TokenIdentifier>,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_in: &BigUint,
+        current_route: &mut ManagedVec<RouteHop<Self::Api>>,
+        best_route: &mut ManagedVec<RouteHop<Self::Api>>,
+    ) {
+        let max_route_hops = self.get_max_route_hops();
+        for pair in pairs.iter() {
+            let next_token = if &pair.first_token_id == token_in {
+                pair.second_token_id.clone()
+            } else if &pair.second_token_id == token_in {
+                pair.first_token_id.clone()
+            } else {
+                continue;
+            };
+
+            if &next_token != token_out && !base_tokens.contains(&next_token) {
+                continue;
+            }
+
+            let is_visited = current_route
+                .iter()
+                .any(|hop| hop.token_in == next_token || hop.token_out == next_token);
+            if is_visited {
+                continue;
+            }
+
+            let amount_out = self.get_route_amount_out(&pair, token_in, amount_in);
+            if amount_out == 0u64 {
+                continue;
+            }
+
+            current_route.push(RouteHop {
+                pair_address: pair.address.clone(),
+                token_in: token_in.clone(),
+                token_out: next_token.clone(),
+                amount_in: amount_in.clone(),
+                amount_out: amount_out.clone(),
+            });
+
+            if &next_token == token_out {
+                if best_route.is_empty()
+                    || amount_out > best_route.get(best_route.len() - 1).amount_out
+                {
+                    *best_route = current_route.clone();
+                }
+            } else if current_route.len() < max_route_hops {
+                self.search_best_route(
+                    pairs,
+                    base_tokens,
+                    &next_token,
+                    token_out,
+                    &amount_out,
+                    current_route,
+                    best_route,
+                );
+            }
+
+            current_route.remove(current_route.len() - 1);
+        }
+    }
+
+    /// Only the pairs between the two tokens and the route base tokens are candidates,
+    /// at most MAX_ROUTE_CANDIDATE_PAIRS of them, the ones of the two tokens first.
+    fn get_routable_pairs(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        base_tokens: &ManagedVec<TokenIdentifier>,
+    ) -> ManagedVec<RoutablePair<Self::Api>> {
+        let mut route_tokens = ManagedVec::<Self::Api, TokenIdentifier>::new();
+        route_tokens.push(token_in.clone());
+        route_tokens.push(token_out.clone());
+        route_tokens.append_vec(base_tokens.clone());
+
+        let mut pairs = ManagedVec::new();
+        for first_index in 0..route_tokens.len() {
+            for second_index in first_index + 1..route_tokens.len() {
+                let first_token_id = route_tokens.get(first_index).clone();
+                let second_token_id = route_tokens.get(second_index).clone();
+                let pair_addresses =
+                    self.get_pair_addresses(first_token_id.clone(), second_token_id.clone());
+                for address in pair_addresses.iter() {
+                    if pairs.len() == MAX_ROUTE_CANDIDATE_PAIRS {
+                        return pairs;
+                    }
+
+                    if let Some(pair) = self.get_routable_pair(
+                        address.clone(),
+                        first_token_id.clone(),
+                        second_token_id.clone(),
+                    ) {
+                        pairs.push(pair);
+                    }
+                }
+            }
+        }
+
+        pairs
+    }
+
+    /// The pair created through createPair, if any, then the fee tier pairs of the tokens.
+    fn get_pair_addresses(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+    ) -> ManagedVec<ManagedAddress> {
+        let mut pair_addresses = ManagedVec::new();
+        let pair_address = self.get_pair(first_token_id.clone(), second_token_id.clone());
+        if !pair_address.is_zero() {
+            pair_addresses.push(pair_address);
+        }
+
+        let pair_tokens = PairTokens {
+            first_token_id: first_token_id.clone(),
+            second_token_id: second_token_id.clone(),
+        };
+        let reverse_pair_tokens = PairTokens {
+            first_token_id: second_token_id,
+            second_token_id: first_token_id,
+        };
+        for pair_tokens in [pair_tokens, reverse_pair_tokens] {
+            for pair_address in self.fee_tier_pairs(&pair_tokens).values() {
+                pair_addresses.push(pair_address);
+            }
+        }
+
+        pair_addresses
+    }
+
+    /// Only the active pairs that hold liquidity can be part of a route.
+    fn get_routable_pair(
+        &self,
+        address: ManagedAddress,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+    ) -> Option<RoutablePair<Self::Api>> {
+        if self.get_pair_state_mapper(address.clone()).get() != State::Active {
+            return None;
+        }
+
+        let first_token_reserve = self
+            .get_pair_reserve_mapper(address.clone(), &first_token_id)
+            .get();
+        let second_token_reserve = self
+            .get_pair_reserve_mapper(address.clone(), &second_token_id)
+            .get();
+        if first_token_reserve == 0u64 || second_token_reserve == 0u64 {
+            return None;
+        }
+
+        let amplification =
+            if self.get_amm_mode_mapper(address.clone()).get() == AmmMode::StableSwap {
+                let ramp = self.get_amplification_ramp_mapper(address.clone()).get();
+                let current_block = self.blockchain().get_block_nonce();
+                get_ramp_amplification::<Self::Api>(&ramp, current_block)
+            } else {
+                0
+            };
+
+        Some(RoutablePair {
+            first_token_id,
+            second_token_id,
+            first_token_reserve,
+            second_token_reserve,
+            total_fee_percent: self.get_pair_effective_total_fee_percent(&address),
+            amplification,
+            address,
+        })
+    }
+
+    /// Same as the pair's amount out.
+    fn get_route_amount_out(
+        &self,
+        pair: &RoutablePair<Self::Api>,
+        token_in: &TokenIdentifier,
+        amount_in: &BigUint,
+    ) -> BigUint {
+        let (reserve_in, reserve_out) = if token_in == &pair.first_token_id {
+            (&pair.first_token_reserve, &pair.second_token_reserve)
+        } else {
+            (&pair.second_token_reserve, &pair.first_token_reserve)
+        };
+
+        let amount_in_with_fee = amount_in * (MAX_PERCENTAGE - pair.total_fee_percent);
+        if pair.amplification > 0 {
+            let amount_in_after_fee = amount_in_with_fee / MAX_PERCENTAGE;
+            return get_stable_amount_out(
+                pair.amplification,
+                &amount_in_after_fee,
+                reserve_in,
+                reserve_out,
+            )
+            .unwrap_or_default();
+        }
+
+        let numerator = &amount_in_with_fee * reserve_out;
+        let denominator = reserve_in * MAX_PERCENTAGE + amount_in_with_fee;
+
+        numerator / denominator
+    }
+
+    fn get_max_route_hops(&self) -> usize {
+        if self.max_route_hops().is_empty() {
+            DEFAULT_MAX_ROUTE_HOPS
+        } else {
+            self.max_route_hops().get()
+        }
+    }
+
+    #[view(getMaxRouteHops)]
+    #[storage_mapper("max_route_hops")]
+    fn max_route_hops(&self) -> SingleValueMapper<usize>;
+
+    #[view(getRouteBaseTokens)]
+    #[storage_mapper("route_base_tokens")]
+    fn route_base_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+}
@@ -11,6 +11,10 @@ pub trait ConfigModule: read_pair_storage::ReadPairStorageModule {
     }
 
     fn check_is_pair_sc(&self, pair_address: &ManagedAddress) {
+        if self.concentrated_pools().contains(pair_address) {
+            return;
+        }
+
         let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
         let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
 
@@ -18,22 +22,27 @@ pub trait ConfigModule: read_pair_storage::ReadPairStorageModule {
             first_token_id: first_token_id.clone(),
             second_token_id: second_token_id.clone(),
         };
+        let reverse_pair_tokens = PairTokens {
+            first_token_id: second_token_id,
+            second_token_id: first_token_id,
+        };
 
-        let mut pair_map_address_opt = self.pair_map().get(&pair_tokens);
-        if pair_map_address_opt.is_none() {
-            let reverse_pair_tokens = PairTokens {
-                first_token_id: second_token_id.clone(),
-                second_token_id: first_token_id.clone(),
-            };
-            pair_map_address_opt = self.pair_map().get(&reverse_pair_tokens);
-        }
-
-        require!(pair_map_address_opt.is_some(), "Not a pair SC");
+        let fee_tier_mapper = self.pair_fee_tier(pair_address);
+        let registered_address_opt = if fee_tier_mapper.is_empty() {
+            self.pair_map()
+                .get(&pair_tokens)
+                .or_else(|| self.pair_map().get(&reverse_pair_tokens))
+        } else {
+            let fee_tier = fee_tier_mapper.get();
+            self.fee_tier_pairs(&pair_tokens)
+                .get(&fee_tier)
+                .or_else(|| self.fee_tier_pairs(&reverse_pair_tokens).get(&fee_tier))
+        };
 
-        unsafe {
-            let pair_map_address = pair_map_address_opt.unwrap_unchecked();
-            require!(&pair_map_address == pair_address, "Not a pair SC");
-        }
+        require!(
+            registered_address_opt.as_ref() == Some(pair_address),
+            "Not a pair SC"
+        );
     }
 
     #[view(getPairCreationEnabled)]
@@ -63,6 +72,22 @@ pub trait ConfigModule: read_pair_storage::ReadPairStorageModule {
     #[storage_mapper("pair_map")]
     fn pair_map(&self) -> MapMapper<PairTokens<Self::Api>, ManagedAddress>;
 
+    #[storage_mapper("fee_tier_pairs")]
+    fn fee_tier_pairs(&self, pair_tokens: &PairTokens<Self::Api>)
+        -> MapMapper<u64, ManagedAddress>;
+
+    /// The fee tier pairs by address, in creation order
+    #[storage_mapper("fee_tier_pair_tokens")]
+    fn fee_tier_pair_tokens(&self) -> MapMapper<ManagedAddress, PairTokens<Self::Api>>;
+
+    #[view(getPairFeeTier)]
+    #[storage_mapper("pair_fee_tier")]
+    fn pair_fee_tier(&self, pair_address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[view(getConcentratedPools)]
+    #[storage_mapper("concentrated_pools")]
+    fn concentrated_pools(&self) -> UnorderedSetMapper<ManagedAddress>;
+
     #[view(getPairTemplateAddress)]
     #[storage_mapper("pair_template_address")]
     fn pair_template_address(&self) -> SingleValueMapper<ManagedAddress>;
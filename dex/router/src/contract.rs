@@ -4,16 +4,23 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+pub mod atomic_swap;
+pub mod auto_route;
 pub mod config;
 pub mod enable_swap_by_user;
 mod events;
 pub mod factory;
+pub mod fee_tiers;
 pub mod multi_pair_swap;
+pub mod permissionless_listing;
+pub mod route_oracle;
+pub mod split_route_swap;
 
 use factory::PairTokens;
 use pair::config::ProxyTrait as _;
 use pair::fee::ProxyTrait as _;
-use pair::{read_pair_storage, ProxyTrait as _};
+use pair::stable_swap::ProxyTrait as _;
+use pair::{dynamic_fee_reader, read_pair_storage, safe_price_reader, ProxyTrait as _};
 use pausable::ProxyTrait as _;
 
 const LP_TOKEN_DECIMALS: usize = 18;
@@ -24,15 +31,30 @@ const DEFAULT_SPECIAL_FEE_PERCENT: u64 = 50;
 const MAX_TOTAL_FEE_PERCENT: u64 = 100_000;
 const USER_DEFINED_TOTAL_FEE_PERCENT: u64 = 1_000;
 
+/// How a new pair differs from a default createPair one
+#[derive(Default)]
+pub struct PairCreationOptions {
+    pub stable_swap_amplification: Option<u64>,
+    pub fee_tier: Option<u64>,
+}
+
 #[multiversx_sc::contract]
 pub trait Router:
     config::ConfigModule
     + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
+    + dynamic_fee_reader::DynamicFeeReaderModule
     + factory::FactoryModule
+    + fee_tiers::FeeTiersModule
     + events::EventsModule
     + multi_pair_swap::MultiPairSwap
     + token_send::TokenSendModule
     + enable_swap_by_user::EnableSwapByUserModule
+    + permissionless_listing::PermissionlessListingModule
+    + auto_route::AutoRouteModule
+    + split_route_swap::SplitRouteSwapModule
+    + atomic_swap::AtomicSwapModule
+    + route_oracle::RouteOracleModule
 {
     #[init]
     fn init(&self, pair_template_address_opt: OptionalValue<ManagedAddress>) {
@@ -84,71 +106,66 @@ pub trait Router:
         second_token_id: TokenIdentifier,
         initial_liquidity_adder: ManagedAddress,
         opt_fee_percents: OptionalValue<MultiValue2<u64, u64>>,
-        mut admins: MultiValueEncoded<ManagedAddress>,
+        admins: MultiValueEncoded<ManagedAddress>,
     ) -> ManagedAddress {
-        require!(self.is_active(), "Not active");
-        let owner = self.owner().get();
-        let caller = self.blockchain().get_caller();
-
-        if caller != owner {
-            require!(
-                self.pair_creation_enabled().get(),
-                "Pair creation is disabled"
-            );
-        }
-
-        require!(first_token_id != second_token_id, "Identical tokens");
-        require!(
-            first_token_id.is_valid_esdt_identifier(),
-            "First Token ID is not a valid esdt token ID"
-        );
-        require!(
-            second_token_id.is_valid_esdt_identifier(),
-            "Second Token ID is not a valid esdt token ID"
-        );
-        let pair_address = self.get_pair(first_token_id.clone(), second_token_id.clone());
-        require!(pair_address.is_zero(), "Pair already exists");
-
-        let mut total_fee_percent_requested = DEFAULT_TOTAL_FEE_PERCENT;
-        let mut special_fee_percent_requested = DEFAULT_SPECIAL_FEE_PERCENT;
-
-        if caller == owner {
-            if let Some(fee_percents_multi_arg) = opt_fee_percents.into_option() {
-                let fee_percents_tuple = fee_percents_multi_arg.into_tuple();
-                total_fee_percent_requested = fee_percents_tuple.0;
-                special_fee_percent_requested = fee_percents_tuple.1;
-
-                require!(
-                    total_fee_percent_requested >= special_fee_percent_requested
-                        && total_fee_percent_requested < MAX_TOTAL_FEE_PERCENT,
-                    "Bad percents"
-                );
-            } else {
-                sc_panic!("Bad percents length");
-            }
-        }
-
-        admins.push(caller.clone());
+        self.create_pair_with_options(
+            first_token_id,
+            second_token_id,
+            initial_liquidity_adder,
+            PairCreationOptions::default(),
+            opt_fee_percents,
+            admins,
+        )
+    }
 
-        let address = self.create_pair(
-            &first_token_id,
-            &second_token_id,
-            &owner,
-            total_fee_percent_requested,
-            special_fee_percent_requested,
-            &initial_liquidity_adder,
+    /// Same as createPair, but the new pair uses the stable swap curve
+    /// with the given amplification coefficient
+    #[allow_multiple_var_args]
+    #[endpoint(createStableSwapPair)]
+    fn create_stable_swap_pair_endpoint(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        initial_liquidity_adder: ManagedAddress,
+        amplification: u64,
+        opt_fee_percents: OptionalValue<MultiValue2<u64, u64>>,
+        admins: MultiValueEncoded<ManagedAddress>,
+    ) -> ManagedAddress {
+        self.create_pair_with_options(
+            first_token_id,
+            second_token_id,
+            initial_liquidity_adder,
+            PairCreationOptions {
+                stable_swap_amplification: Some(amplification),
+                fee_tier: None,
+            },
+            opt_fee_percents,
             admins,
-        );
+        )
+    }
 
-        self.emit_create_pair_event(
-            caller,
+    /// Creates the pair of the two tokens for the given fee tier, with the fee percents
+    /// of the tier
+    #[endpoint(createPairWithFeeTier)]
+    fn create_pair_with_fee_tier_endpoint(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        initial_liquidity_adder: ManagedAddress,
+        fee_tier: u64,
+        admins: MultiValueEncoded<ManagedAddress>,
+    ) -> ManagedAddress {
+        self.create_pair_with_options(
             first_token_id,
             second_token_id,
-            total_fee_percent_requested,
-            special_fee_percent_requested,
-            address.clone(),
-        );
-        address
+            initial_liquidity_adder,
+            PairCreationOptions {
+                stable_swap_amplification: None,
+                fee_tier: Some(fee_tier),
+            },
+            OptionalValue::None,
+            admins,
+        )
     }
 
     #[only_owner]
@@ -157,6 +174,7 @@ pub trait Router:
         &self,
         first_token_id: TokenIdentifier,
         second_token_id: TokenIdentifier,
+        opt_fee_tier: OptionalValue<u64>,
     ) {
         require!(self.is_active(), "Not active");
 
@@ -169,7 +187,7 @@ pub trait Router:
             second_token_id.is_valid_esdt_identifier(),
             "Second Token ID is not a valid esdt token ID"
         );
-        let pair_address = self.get_pair(first_token_id.clone(), second_token_id.clone());
+        let pair_address = self.get_pair_view(first_token_id, second_token_id, opt_fee_tier);
         require!(!pair_address.is_zero(), "Pair does not exists");
 
         self.upgrade_pair(pair_address);
@@ -187,7 +205,8 @@ pub trait Router:
 
         require!(self.is_active(), "Not active");
         let caller = self.blockchain().get_caller();
-        if caller != self.owner().get() {
+        let is_listing_creator = self.listing_creator(&pair_address).get() == caller;
+        if caller != self.owner().get() && !is_listing_creator {
             require!(
                 self.pair_creation_enabled().get(),
                 "Pair creation is disabled"
@@ -256,6 +275,7 @@ pub trait Router:
             .esdt_system_sc_proxy()
             .set_special_roles(&pair_address, &pair_token, roles.iter().cloned())
             .async_call()
+            .with_callback(self.callbacks().set_local_roles_callback(&pair_address))
             .call_and_exit()
     }
 
@@ -265,6 +285,7 @@ pub trait Router:
         &self,
         first_token_id: TokenIdentifier,
         second_token_id: TokenIdentifier,
+        opt_fee_tier: OptionalValue<u64>,
     ) -> ManagedAddress {
         require!(self.is_active(), "Not active");
 
@@ -277,6 +298,10 @@ pub trait Router:
             second_token_id.is_valid_esdt_identifier(),
             "Second Token ID is not a valid esdt token ID"
         );
+        if let Some(fee_tier) = opt_fee_tier.into_option() {
+            return self.remove_fee_tier_pair(first_token_id, second_token_id, fee_tier);
+        }
+
         let mut pair_address = self.get_pair(first_token_id.clone(), second_token_id.clone());
         require!(!pair_address.is_zero(), "Pair does not exists");
 
@@ -301,6 +326,34 @@ pub trait Router:
         pair_address
     }
 
+    /// Allows multiPairSwap to route through a concentrated liquidity pool.
+    /// The pool must already be deployed, its swap endpoints are compatible with the pair ones.
+    #[only_owner]
+    #[endpoint(registerConcentratedPool)]
+    fn register_concentrated_pool(&self, pool_address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&pool_address),
+            "Invalid pool address"
+        );
+
+        let first_token_id = self.get_first_token_id_mapper(pool_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pool_address.clone()).get();
+        require!(
+            first_token_id.is_valid_esdt_identifier() && second_token_id.is_valid_esdt_identifier(),
+            "Not a concentrated pool"
+        );
+
+        let is_new = self.concentrated_pools().insert(pool_address);
+        require!(is_new, "Pool already registered");
+    }
+
+    #[only_owner]
+    #[endpoint(removeConcentratedPool)]
+    fn remove_concentrated_pool(&self, pool_address: ManagedAddress) {
+        let was_removed = self.concentrated_pools().swap_remove(&pool_address);
+        require!(was_removed, "Pool not registered");
+    }
+
     #[only_owner]
     #[endpoint(setFeeOn)]
     fn set_fee_on(
@@ -359,9 +412,143 @@ pub trait Router:
         }
     }
 
+    #[callback]
+    fn set_local_roles_callback(
+        &self,
+        pair_address: &ManagedAddress,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        if result.is_ok() && !self.listing_creator(pair_address).is_empty() {
+            self.listing_local_roles_set(pair_address).set(true);
+        }
+    }
+
     #[only_owner]
     #[endpoint(setPairCreationEnabled)]
     fn set_pair_creation_enabled(&self, enabled: bool) {
         self.pair_creation_enabled().set(enabled);
     }
+
+    fn remove_fee_tier_pair(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        fee_tier: u64,
+    ) -> ManagedAddress {
+        let pair_address =
+            self.get_fee_tier_pair(first_token_id.clone(), second_token_id.clone(), fee_tier);
+        require!(!pair_address.is_zero(), "Pair does not exists");
+
+        self.fee_tier_pairs(&PairTokens {
+            first_token_id: first_token_id.clone(),
+            second_token_id: second_token_id.clone(),
+        })
+        .remove(&fee_tier);
+        self.fee_tier_pairs(&PairTokens {
+            first_token_id: second_token_id,
+            second_token_id: first_token_id,
+        })
+        .remove(&fee_tier);
+        self.pair_fee_tier(&pair_address).clear();
+        self.fee_tier_pair_tokens().remove(&pair_address);
+
+        pair_address
+    }
+
+    fn create_pair_with_options(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        initial_liquidity_adder: ManagedAddress,
+        options: PairCreationOptions,
+        opt_fee_percents: OptionalValue<MultiValue2<u64, u64>>,
+        mut admins: MultiValueEncoded<ManagedAddress>,
+    ) -> ManagedAddress {
+        require!(self.is_active(), "Not active");
+        let owner = self.owner().get();
+        let caller = self.blockchain().get_caller();
+
+        if caller != owner {
+            require!(
+                self.pair_creation_enabled().get(),
+                "Pair creation is disabled"
+            );
+        }
+
+        require!(first_token_id != second_token_id, "Identical tokens");
+        require!(
+            first_token_id.is_valid_esdt_identifier(),
+            "First Token ID is not a valid esdt token ID"
+        );
+        require!(
+            second_token_id.is_valid_esdt_identifier(),
+            "Second Token ID is not a valid esdt token ID"
+        );
+        let PairCreationOptions {
+            stable_swap_amplification,
+            fee_tier,
+        } = options;
+        let pair_address = match fee_tier {
+            Some(fee_tier) => {
+                self.get_fee_tier_pair(first_token_id.clone(), second_token_id.clone(), fee_tier)
+            }
+            None => self.get_pair(first_token_id.clone(), second_token_id.clone()),
+        };
+        require!(pair_address.is_zero(), "Pair already exists");
+
+        let mut total_fee_percent_requested = DEFAULT_TOTAL_FEE_PERCENT;
+        let mut special_fee_percent_requested = DEFAULT_SPECIAL_FEE_PERCENT;
+
+        if let Some(fee_tier) = fee_tier {
+            special_fee_percent_requested = self
+                .fee_tiers()
+                .get(&fee_tier)
+                .unwrap_or_else(|| sc_panic!("Unknown fee tier"));
+            total_fee_percent_requested = fee_tier;
+        } else if caller == owner {
+            if let Some(fee_percents_multi_arg) = opt_fee_percents.into_option() {
+                let fee_percents_tuple = fee_percents_multi_arg.into_tuple();
+                total_fee_percent_requested = fee_percents_tuple.0;
+                special_fee_percent_requested = fee_percents_tuple.1;
+
+                require!(
+                    total_fee_percent_requested >= special_fee_percent_requested
+                        && total_fee_percent_requested < MAX_TOTAL_FEE_PERCENT,
+                    "Bad percents"
+                );
+            } else {
+                sc_panic!("Bad percents length");
+            }
+        }
+
+        admins.push(caller.clone());
+
+        let address = self.create_pair(
+            &first_token_id,
+            &second_token_id,
+            &owner,
+            total_fee_percent_requested,
+            special_fee_percent_requested,
+            &initial_liquidity_adder,
+            admins,
+            fee_tier,
+        );
+
+        if let Some(amplification) = stable_swap_amplification {
+            let _: IgnoreValue = self
+                .pair_contract_proxy(address.clone())
+                .enable_stable_swap(amplification)
+                .execute_on_dest_context();
+        }
+
+        self.emit_create_pair_event(
+            caller,
+            first_token_id,
+            second_token_id,
+            total_fee_percent_requested,
+            special_fee_percent_requested,
+            address.clone(),
+        );
+        address
+    }
 }
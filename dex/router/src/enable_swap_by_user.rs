@@ -31,6 +31,7 @@ pub trait EnableSwapByUserModule:
     config::ConfigModule
     + read_pair_storage::ReadPairStorageModule
     + crate::factory::FactoryModule
+    + crate::fee_tiers::FeeTiersModule
     + crate::events::EventsModule
 {
     #[only_owner]
@@ -230,6 +231,11 @@ pub trait EnableSwapByUserModule:
     }
 
     fn set_fee_percents(&self, pair_address: ManagedAddress) {
+        // the fee tier pairs keep the fees of their tier
+        if !self.pair_fee_tier(&pair_address).is_empty() {
+            return;
+        }
+
         let _: IgnoreValue = self
             .user_pair_proxy(pair_address)
             .set_fee_percent(USER_DEFINED_TOTAL_FEE_PERCENT, DEFAULT_SPECIAL_FEE_PERCENT)
@@ -38,6 +38,21 @@ pub struct MultiPairSwapEvent<M: ManagedTypeApi> {
     timestamp: u64,
 }
 
+#[type_abi]
+#[derive(TopEncode)]
+pub struct SplitRouteSwapEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    token_in: TokenIdentifier<M>,
+    amount_in: BigUint<M>,
+    token_out: TokenIdentifier<M>,
+    amount_out: BigUint<M>,
+    route_amounts_in: ManagedVec<M, BigUint<M>>,
+    route_amounts_out: ManagedVec<M, BigUint<M>>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[multiversx_sc::module]
 pub trait EventsModule {
     fn emit_create_pair_event(
@@ -128,6 +143,37 @@ pub trait EventsModule {
         )
     }
 
+    fn emit_split_route_swap_event(
+        &self,
+        caller: ManagedAddress,
+        payment_in: EsdtTokenPayment,
+        payment_out: EsdtTokenPayment,
+        route_amounts_in: ManagedVec<BigUint>,
+        route_amounts_out: ManagedVec<BigUint>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.split_route_swap_event(
+            caller.clone(),
+            payment_in.token_identifier.clone(),
+            payment_in.amount.clone(),
+            payment_out.token_identifier.clone(),
+            payment_out.amount.clone(),
+            epoch,
+            SplitRouteSwapEvent {
+                caller,
+                token_in: payment_in.token_identifier,
+                amount_in: payment_in.amount,
+                token_out: payment_out.token_identifier,
+                amount_out: payment_out.amount,
+                route_amounts_in,
+                route_amounts_out,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
     #[event("create_pair")]
     fn create_pair_event(
         self,
@@ -159,4 +205,16 @@ pub trait EventsModule {
         #[indexed] epoch: u64,
         multi_pair_swap_event: MultiPairSwapEvent<Self::Api>,
     );
+
+    #[event("splitRouteSwap")]
+    fn split_route_swap_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] token_in: TokenIdentifier,
+        #[indexed] amount_in: BigUint,
+        #[indexed] token_out: TokenIdentifier,
+        #[indexed] amount_out: BigUint,
+        #[indexed] epoch: u64,
+        split_route_swap_event: SplitRouteSwapEvent<Self::Api>,
+    );
 }
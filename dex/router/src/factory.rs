@@ -1,10 +1,15 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use crate::config;
+use crate::{config, fee_tiers};
 use pair::read_pair_storage;
+use pausable::State;
 
 const TEMPORARY_OWNER_PERIOD_BLOCKS: u64 = 50;
+const SC_ADDRESS_PREFIX_LEN: usize = 10;
+const SHARD_ID_SUFFIX_LEN: usize = 2;
+const WASM_VM_TYPE: [u8; 2] = [5, 0];
+pub const MAX_PAGE_SIZE: usize = 100;
 
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi)]
 pub struct PairTokens<M: ManagedTypeApi> {
@@ -14,13 +19,27 @@ pub struct PairTokens<M: ManagedTypeApi> {
 
 #[derive(ManagedVecItem, TopEncode, TopDecode, PartialEq, TypeAbi)]
 pub struct PairContractMetadata<M: ManagedTypeApi> {
-    first_token_id: TokenIdentifier<M>,
-    second_token_id: TokenIdentifier<M>,
-    address: ManagedAddress<M>,
+    pub first_token_id: TokenIdentifier<M>,
+    pub second_token_id: TokenIdentifier<M>,
+    pub address: ManagedAddress<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
+pub struct PairRegistryEntry<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    pub first_token_id: TokenIdentifier<M>,
+    pub second_token_id: TokenIdentifier<M>,
+    pub lp_token_id: TokenIdentifier<M>,
+    pub state: State,
+    pub total_fee_percent: u64,
+    pub special_fee_percent: u64,
 }
 
 #[multiversx_sc::module]
-pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStorageModule {
+pub trait FactoryModule:
+    config::ConfigModule + fee_tiers::FeeTiersModule + read_pair_storage::ReadPairStorageModule
+{
     #[proxy]
     fn pair_contract_deploy_proxy(&self) -> pair::Proxy<Self::Api>;
 
@@ -42,6 +61,7 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
         special_fee_percent: u64,
         initial_liquidity_adder: &ManagedAddress,
         admins: MultiValueEncoded<ManagedAddress>,
+        fee_tier: Option<u64>,
     ) -> ManagedAddress {
         require!(
             !self.pair_template_address().is_empty(),
@@ -65,13 +85,22 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
                 CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE | CodeMetadata::PAYABLE_BY_SC,
             );
 
-        self.pair_map().insert(
-            PairTokens {
-                first_token_id: first_token_id.clone(),
-                second_token_id: second_token_id.clone(),
-            },
-            new_address.clone(),
-        );
+        let pair_tokens = PairTokens {
+            first_token_id: first_token_id.clone(),
+            second_token_id: second_token_id.clone(),
+        };
+        match fee_tier {
+            Some(fee_tier) => {
+                self.fee_tier_pairs(&pair_tokens)
+                    .insert(fee_tier, new_address.clone());
+                self.pair_fee_tier(&new_address).set(fee_tier);
+                self.fee_tier_pair_tokens()
+                    .insert(new_address.clone(), pair_tokens);
+            }
+            None => {
+                self.pair_map().insert(pair_tokens, new_address.clone());
+            }
+        }
         self.pair_temporary_owner().insert(
             new_address.clone(),
             (
@@ -94,12 +123,16 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
             .upgrade_async_call_and_exit();
     }
 
+    /// The fee tier pairs are listed after the ones created through createPair.
     #[view(getAllPairsManagedAddresses)]
     fn get_all_pairs_addresses(&self) -> MultiValueEncoded<ManagedAddress> {
         let mut result = MultiValueEncoded::new();
         for pair in self.pair_map().values() {
             result.push(pair);
         }
+        for pair in self.fee_tier_pair_tokens().keys() {
+            result.push(pair);
+        }
         result
     }
 
@@ -109,13 +142,18 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
         for pair in self.pair_map().keys() {
             result.push(pair);
         }
+        for pair in self.fee_tier_pair_tokens().values() {
+            result.push(pair);
+        }
         result
     }
 
     #[view(getAllPairContractMetadata)]
     fn get_all_pair_contract_metadata(&self) -> MultiValueEncoded<PairContractMetadata<Self::Api>> {
         let mut result = MultiValueEncoded::new();
-        for (k, v) in self.pair_map().iter() {
+        let fee_tier_pair_tokens = self.fee_tier_pair_tokens();
+        let fee_tier_pairs = fee_tier_pair_tokens.iter().map(|(v, k)| (k, v));
+        for (k, v) in self.pair_map().iter().chain(fee_tier_pairs) {
             let pair_metadata = PairContractMetadata {
                 first_token_id: k.first_token_id,
                 second_token_id: k.second_token_id,
@@ -126,7 +164,107 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
         result
     }
 
+    /// Pairs created through createPair, in creation order, then the fee tier pairs,
+    /// starting at `start_index`. At most `MAX_PAGE_SIZE` pairs are returned per page.
+    #[view(getPairsPage)]
+    fn get_pairs_page(
+        &self,
+        start_index: usize,
+        count: usize,
+    ) -> MultiValueEncoded<PairRegistryEntry<Self::Api>> {
+        require!(count <= MAX_PAGE_SIZE, "Page size too large");
+
+        let mut result = MultiValueEncoded::new();
+        let pair_map = self.pair_map();
+        let fee_tier_pair_tokens = self.fee_tier_pair_tokens();
+        let pair_addresses = pair_map.values().chain(fee_tier_pair_tokens.keys());
+        for pair_address in pair_addresses.skip(start_index).take(count) {
+            result.push(self.get_pair_registry_entry(pair_address));
+        }
+        result
+    }
+
+    /// Same as getPairsPage, but only counts the pairs that have `token_id` on either side.
+    #[view(getPairsByToken)]
+    fn get_pairs_by_token(
+        &self,
+        token_id: TokenIdentifier,
+        start_index: usize,
+        count: usize,
+    ) -> MultiValueEncoded<PairRegistryEntry<Self::Api>> {
+        require!(count <= MAX_PAGE_SIZE, "Page size too large");
+
+        let mut result = MultiValueEncoded::new();
+        let pair_map = self.pair_map();
+        let fee_tier_pair_tokens = self.fee_tier_pair_tokens();
+        let fee_tier_pairs = fee_tier_pair_tokens
+            .iter()
+            .map(|(pair_address, pair_tokens)| (pair_tokens, pair_address));
+        let pairs = pair_map
+            .iter()
+            .chain(fee_tier_pairs)
+            .filter(|(pair_tokens, _)| {
+                pair_tokens.first_token_id == token_id || pair_tokens.second_token_id == token_id
+            });
+        for (_, pair_address) in pairs.skip(start_index).take(count) {
+            result.push(self.get_pair_registry_entry(pair_address));
+        }
+        result
+    }
+
+    fn get_pair_registry_entry(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> PairRegistryEntry<Self::Api> {
+        PairRegistryEntry {
+            first_token_id: self.get_first_token_id_mapper(pair_address.clone()).get(),
+            second_token_id: self.get_second_token_id_mapper(pair_address.clone()).get(),
+            lp_token_id: self.get_lp_token_id_mapper(pair_address.clone()).get(),
+            state: self.get_pair_state_mapper(pair_address.clone()).get(),
+            total_fee_percent: self
+                .get_total_fee_percent_mapper(pair_address.clone())
+                .get(),
+            special_fee_percent: self
+                .get_special_fee_percent_mapper(pair_address.clone())
+                .get(),
+            address: pair_address,
+        }
+    }
+
+    /// The address the next pair deployed by the router will get, given the current account
+    /// nonce of the router. Same derivation as the protocol: keccak256 of the creator address
+    /// and its account nonce, with the VM type prefix and the creator's shard suffix written
+    /// over the hash. Contracts cannot read account nonces, so it is read off-chain.
+    #[view(predictPairAddress)]
+    fn predict_pair_address(&self, router_account_nonce: u64) -> ManagedAddress {
+        let sc_address = self.blockchain().get_sc_address().to_byte_array();
+        let mut hash_input = ManagedBuffer::new_from_bytes(&sc_address);
+        hash_input.append_bytes(&router_account_nonce.to_le_bytes());
+
+        let mut address = self.crypto().keccak256(hash_input).to_byte_array();
+        address[..SC_ADDRESS_PREFIX_LEN].fill(0);
+        address[SC_ADDRESS_PREFIX_LEN - WASM_VM_TYPE.len()..SC_ADDRESS_PREFIX_LEN]
+            .copy_from_slice(&WASM_VM_TYPE);
+        address[32 - SHARD_ID_SUFFIX_LEN..]
+            .copy_from_slice(&sc_address[32 - SHARD_ID_SUFFIX_LEN..]);
+
+        ManagedAddress::from(&address)
+    }
+
+    /// Without `opt_fee_tier`, returns the pair created through createPair.
     #[view(getPair)]
+    fn get_pair_view(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        opt_fee_tier: OptionalValue<u64>,
+    ) -> ManagedAddress {
+        match opt_fee_tier.into_option() {
+            Some(fee_tier) => self.get_fee_tier_pair(first_token_id, second_token_id, fee_tier),
+            None => self.get_pair(first_token_id, second_token_id),
+        }
+    }
+
     fn get_pair(
         &self,
         first_token_id: TokenIdentifier,
@@ -0,0 +1,93 @@
+multiversx_sc::imports!();
+
+use crate::{config, factory::PairTokens, MAX_TOTAL_FEE_PERCENT};
+use pair::read_pair_storage;
+
+/// Fee tiers are identified by their total fee percent. A token pair can have one pair
+/// per fee tier, besides the one created through createPair.
+#[multiversx_sc::module]
+pub trait FeeTiersModule: config::ConfigModule + read_pair_storage::ReadPairStorageModule {
+    #[only_owner]
+    #[endpoint(addFeeTier)]
+    fn add_fee_tier(&self, total_fee_percent: u64, special_fee_percent: u64) {
+        require!(
+            total_fee_percent >= special_fee_percent && total_fee_percent < MAX_TOTAL_FEE_PERCENT,
+            "Bad percents"
+        );
+
+        let is_new = self
+            .fee_tiers()
+            .insert(total_fee_percent, special_fee_percent)
+            .is_none();
+        require!(is_new, "Fee tier already exists");
+    }
+
+    /// Only stops the creation of new pairs in the tier, the existing ones are kept.
+    #[only_owner]
+    #[endpoint(removeFeeTier)]
+    fn remove_fee_tier(&self, total_fee_percent: u64) {
+        let is_removed = self.fee_tiers().remove(&total_fee_percent).is_some();
+        require!(is_removed, "Unknown fee tier");
+    }
+
+    fn get_fee_tier_pair(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        fee_tier: u64,
+    ) -> ManagedAddress {
+        let address_opt = self
+            .fee_tier_pairs(&PairTokens {
+                first_token_id: first_token_id.clone(),
+                second_token_id: second_token_id.clone(),
+            })
+            .get(&fee_tier);
+        if let Some(address) = address_opt {
+            return address;
+        }
+
+        self.fee_tier_pairs(&PairTokens {
+            first_token_id: second_token_id,
+            second_token_id: first_token_id,
+        })
+        .get(&fee_tier)
+        .unwrap_or_else(ManagedAddress::zero)
+    }
+
+    #[view(getFeeTiers)]
+    fn get_fee_tiers(&self) -> MultiValueEncoded<MultiValue2<u64, u64>> {
+        let mut result = MultiValueEncoded::new();
+        for (total_fee_percent, special_fee_percent) in self.fee_tiers().iter() {
+            result.push((total_fee_percent, special_fee_percent).into());
+        }
+        result
+    }
+
+    /// The fee tier and the address of every fee tier pair of the two tokens.
+    #[view(getPairFeeTiers)]
+    fn get_pair_fee_tiers(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+    ) -> MultiValueEncoded<MultiValue2<u64, ManagedAddress>> {
+        let pair_tokens = PairTokens {
+            first_token_id: first_token_id.clone(),
+            second_token_id: second_token_id.clone(),
+        };
+        let reverse_pair_tokens = PairTokens {
+            first_token_id: second_token_id,
+            second_token_id: first_token_id,
+        };
+
+        let mut result = MultiValueEncoded::new();
+        for tokens in [pair_tokens, reverse_pair_tokens].iter() {
+            for (fee_tier, address) in self.fee_tier_pairs(tokens).iter() {
+                result.push((fee_tier, address).into());
+            }
+        }
+        result
+    }
+
+    #[storage_mapper("fee_tiers")]
+    fn fee_tiers(&self) -> MapMapper<u64, u64>;
+}
@@ -2,7 +2,7 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use super::factory;
-use crate::{config, events};
+use crate::{config, events, fee_tiers};
 use pair::{pair_actions::swap::ProxyTrait as _, read_pair_storage};
 
 type SwapOperationType<M> =
@@ -16,6 +16,7 @@ pub trait MultiPairSwap:
     config::ConfigModule
     + read_pair_storage::ReadPairStorageModule
     + factory::FactoryModule
+    + fee_tiers::FeeTiersModule
     + token_send::TokenSendModule
     + events::EventsModule
 {
@@ -0,0 +1,289 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use pair::{
+    pair_actions::{common_result_types::AddLiquidityResultType, initial_liq::ProxyTrait as _},
+    read_pair_storage,
+};
+use simple_lock::ProxyTrait as _;
+
+use crate::{config, factory::PairTokens, DEFAULT_SPECIAL_FEE_PERCENT, DEFAULT_TOTAL_FEE_PERCENT};
+
+pub const MAX_LOCKED_LP_PERCENT: u64 = 10_000;
+pub const LISTING_EXPIRY_EPOCHS: u64 = 7;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, PartialEq, Debug)]
+pub enum ListingStatus {
+    NotListed,
+    LpTokenNotIssued,
+    LocalRolesNotSet,
+    PendingInitialLiquidity,
+    Active,
+}
+
+/// Listing of a token against one of the common tokens for user pairs, without any
+/// action from the owner. The steps are:
+/// - `createListing`, which deploys the pair
+/// - `issueLpToken` and `setLocalRoles`, for the new pair
+/// - `addListingLiquidity`, which adds the initial liquidity, locks the configured share
+///     of the LP tokens through simple-lock and enables the swaps, if the locked LP tokens
+///     meet the thresholds of the common token
+///
+/// A listing without initial liquidity `LISTING_EXPIRY_EPOCHS` after its creation can be
+/// removed by anyone, or replaced by a new listing of the same tokens.
+#[multiversx_sc::module]
+pub trait PermissionlessListingModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + crate::factory::FactoryModule
+    + crate::fee_tiers::FeeTiersModule
+    + crate::events::EventsModule
+    + crate::enable_swap_by_user::EnableSwapByUserModule
+{
+    #[only_owner]
+    #[endpoint(setListingParameters)]
+    fn set_listing_parameters(&self, simple_lock_address: ManagedAddress, locked_lp_percent: u64) {
+        require!(
+            self.blockchain().is_smart_contract(&simple_lock_address),
+            "Invalid simple lock address"
+        );
+        require!(
+            locked_lp_percent > 0 && locked_lp_percent <= MAX_LOCKED_LP_PERCENT,
+            "Bad percents"
+        );
+
+        self.listing_simple_lock_address().set(simple_lock_address);
+        self.listing_locked_lp_percent().set(locked_lp_percent);
+    }
+
+    /// Pair creation does not have to be enabled, the common tokens which have
+    /// an enable swap by user config set are open for listing.
+    #[endpoint(createListing)]
+    fn create_listing(
+        &self,
+        token_id: TokenIdentifier,
+        common_token_id: TokenIdentifier,
+    ) -> ManagedAddress {
+        require!(self.is_active(), "Not active");
+        require!(
+            !self.listing_simple_lock_address().is_empty(),
+            "Listing not configured"
+        );
+        require!(
+            self.common_tokens_for_user_pairs()
+                .contains(&common_token_id),
+            "Common token not whitelisted"
+        );
+        let _ = self.try_get_config(&common_token_id);
+
+        require!(token_id != common_token_id, "Identical tokens");
+        require!(
+            token_id.is_valid_esdt_identifier(),
+            "First Token ID is not a valid esdt token ID"
+        );
+        let pair_address = self.get_pair(token_id.clone(), common_token_id.clone());
+        if !pair_address.is_zero() {
+            require!(
+                self.is_listing_expired(&pair_address),
+                "Pair already exists"
+            );
+            self.remove_listing(&pair_address, token_id.clone(), common_token_id.clone());
+        }
+
+        let caller = self.blockchain().get_caller();
+        let mut admins = MultiValueEncoded::new();
+        admins.push(caller.clone());
+
+        let pair_address = self.create_pair(
+            &token_id,
+            &common_token_id,
+            &self.owner().get(),
+            DEFAULT_TOTAL_FEE_PERCENT,
+            DEFAULT_SPECIAL_FEE_PERCENT,
+            &self.blockchain().get_sc_address(),
+            admins,
+            None,
+        );
+        self.listing_creator(&pair_address).set(&caller);
+        self.listing_creation_epoch(&pair_address)
+            .set(self.blockchain().get_block_epoch());
+
+        self.emit_create_pair_event(
+            caller,
+            token_id,
+            common_token_id,
+            DEFAULT_TOTAL_FEE_PERCENT,
+            DEFAULT_SPECIAL_FEE_PERCENT,
+            pair_address.clone(),
+        );
+        pair_address
+    }
+
+    /// Expects the tokens of the pair as payment. Returns the locked LP tokens
+    /// and the LP tokens left unlocked.
+    #[payable("*")]
+    #[endpoint(addListingLiquidity)]
+    fn add_listing_liquidity(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> MultiValue2<EgldOrEsdtTokenPayment, EsdtTokenPayment> {
+        require!(self.is_active(), "Not active");
+        let caller = self.blockchain().get_caller();
+        let listing_creator_mapper = self.listing_creator(&pair_address);
+        require!(!listing_creator_mapper.is_empty(), "Not a listing pair");
+        require!(
+            caller == listing_creator_mapper.get(),
+            "Caller is not the listing creator"
+        );
+
+        let payments = self.call_value().all_esdt_transfers().clone_value();
+        let add_liq_result: AddLiquidityResultType<Self::Api> = self
+            .user_pair_proxy(pair_address.clone())
+            .add_initial_liquidity()
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (mut lp_tokens, _, _) = add_liq_result.into_tuple();
+
+        let locked_lp_amount =
+            &lp_tokens.amount * self.listing_locked_lp_percent().get() / MAX_LOCKED_LP_PERCENT;
+        lp_tokens.amount -= &locked_lp_amount;
+
+        let lp_token_safe_price_result =
+            self.get_lp_token_value(pair_address.clone(), locked_lp_amount.clone());
+        let config = self.try_get_config(&lp_token_safe_price_result.common_token_id);
+        require!(
+            lp_token_safe_price_result.safe_price_in_common_token >= config.min_locked_token_value,
+            "Not enough value locked"
+        );
+
+        let unlock_epoch = self.blockchain().get_block_epoch() + config.min_lock_period_epochs;
+        let locked_lp_tokens: EgldOrEsdtTokenPayment = self
+            .listing_simple_lock_proxy(self.listing_simple_lock_address().get())
+            .lock_tokens_endpoint(unlock_epoch, OptionalValue::Some(caller.clone()))
+            .with_esdt_transfer((lp_tokens.token_identifier.clone(), 0, locked_lp_amount))
+            .execute_on_dest_context();
+        require!(
+            locked_lp_tokens.token_identifier == config.locked_token_id,
+            "Invalid locked token"
+        );
+
+        self.set_fee_percents(pair_address.clone());
+        self.pair_resume(pair_address.clone());
+
+        if lp_tokens.amount > 0 {
+            self.send().direct_esdt(
+                &caller,
+                &lp_tokens.token_identifier,
+                lp_tokens.token_nonce,
+                &lp_tokens.amount,
+            );
+        }
+
+        self.emit_user_swaps_enabled_event(
+            caller,
+            lp_token_safe_price_result.first_token_id,
+            lp_token_safe_price_result.second_token_id,
+            pair_address,
+        );
+
+        (locked_lp_tokens, lp_tokens).into()
+    }
+
+    #[endpoint(removeExpiredListing)]
+    fn remove_expired_listing(&self, pair_address: ManagedAddress) {
+        require!(self.is_active(), "Not active");
+        require!(
+            !self.listing_creator(&pair_address).is_empty(),
+            "Not a listing pair"
+        );
+        require!(
+            self.is_listing_expired(&pair_address),
+            "Listing not expired"
+        );
+
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+        self.remove_listing(&pair_address, first_token_id, second_token_id);
+    }
+
+    #[view(getListingStatus)]
+    fn get_listing_status(&self, pair_address: ManagedAddress) -> ListingStatus {
+        if self.listing_creator(&pair_address).is_empty() {
+            return ListingStatus::NotListed;
+        }
+
+        let lp_token_id = self.get_lp_token_id_mapper(pair_address.clone()).get();
+        if !lp_token_id.is_valid_esdt_identifier() {
+            return ListingStatus::LpTokenNotIssued;
+        }
+
+        // the initial liquidity and the swaps activation are done in the same transaction
+        if self.get_lp_token_supply_mapper(pair_address.clone()).get() > 0 {
+            return ListingStatus::Active;
+        }
+
+        if !self.listing_local_roles_set(&pair_address).get() {
+            return ListingStatus::LocalRolesNotSet;
+        }
+
+        ListingStatus::PendingInitialLiquidity
+    }
+
+    fn is_listing_expired(&self, pair_address: &ManagedAddress) -> bool {
+        if self.listing_creator(pair_address).is_empty() {
+            return false;
+        }
+
+        let expiry_epoch = self.listing_creation_epoch(pair_address).get() + LISTING_EXPIRY_EPOCHS;
+        self.blockchain().get_block_epoch() >= expiry_epoch
+            && self.get_lp_token_supply_mapper(pair_address.clone()).get() == 0
+    }
+
+    /// The pair contract is left as it is, it is only no longer registered.
+    fn remove_listing(
+        &self,
+        pair_address: &ManagedAddress,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+    ) {
+        let mut pair_map = self.pair_map();
+        let removed_address = pair_map.remove(&PairTokens {
+            first_token_id: first_token_id.clone(),
+            second_token_id: second_token_id.clone(),
+        });
+        if removed_address.is_none() {
+            let _ = pair_map.remove(&PairTokens {
+                first_token_id: second_token_id,
+                second_token_id: first_token_id,
+            });
+        }
+
+        let _ = self.pair_temporary_owner().remove(pair_address);
+        self.listing_creator(pair_address).clear();
+        self.listing_creation_epoch(pair_address).clear();
+        self.listing_local_roles_set(pair_address).clear();
+    }
+
+    #[proxy]
+    fn listing_simple_lock_proxy(&self, to: ManagedAddress) -> simple_lock::Proxy<Self::Api>;
+
+    #[view(getListingCreator)]
+    #[storage_mapper("listingCreator")]
+    fn listing_creator(&self, pair_address: &ManagedAddress) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("listingCreationEpoch")]
+    fn listing_creation_epoch(&self, pair_address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    /// Set by the callback of setLocalRoles
+    #[storage_mapper("listingLocalRolesSet")]
+    fn listing_local_roles_set(&self, pair_address: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[view(getListingSimpleLockAddress)]
+    #[storage_mapper("listingSimpleLockAddress")]
+    fn listing_simple_lock_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getListingLockedLpPercent)]
+    #[storage_mapper("listingLockedLpPercent")]
+    fn listing_locked_lp_percent(&self) -> SingleValueMapper<u64>;
+}
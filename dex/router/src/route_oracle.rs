@@ -0,0 +1,105 @@
+multiversx_sc::imports!();
+
+use crate::config;
+use pair::{
+    read_pair_storage,
+    safe_price::{PriceObservation, Round, MAX_OBSERVATIONS},
+    safe_price_reader,
+    safe_price_view::SECONDS_PER_ROUND,
+};
+
+pub const DEFAULT_MAX_OBSERVATION_STALENESS_ROUNDS: Round = 100;
+
+#[multiversx_sc::module]
+pub trait RouteOracleModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
+{
+    #[only_owner]
+    #[endpoint(setMaxObservationStaleness)]
+    fn set_max_observation_staleness(&self, max_staleness_rounds: Round) {
+        require!(max_staleness_rounds > 0, "Invalid max staleness");
+
+        self.max_observation_staleness_rounds()
+            .set(max_staleness_rounds);
+    }
+
+    /// Chains the safe prices of the given pairs, each computed over the last
+    /// `timestamp_offset` seconds. The output of every hop is the input of the next one.
+    #[view(getRouteSafePrice)]
+    fn get_route_safe_price(
+        &self,
+        input_payment: EsdtTokenPayment,
+        timestamp_offset: u64,
+        path: MultiValueEncoded<ManagedAddress>,
+    ) -> EsdtTokenPayment {
+        require!(!path.is_empty(), "Invalid path. Should not be empty");
+
+        let current_round = self.blockchain().get_block_round();
+        let round_offset = timestamp_offset / SECONDS_PER_ROUND;
+        require!(
+            round_offset > 0 && round_offset < current_round,
+            "Invalid timestamp offset"
+        );
+        let start_round = current_round - round_offset;
+
+        let mut last_payment = input_payment;
+        for pair_address in path.into_iter() {
+            self.check_is_pair_sc(&pair_address);
+            self.require_usable_observations(&pair_address, start_round, current_round);
+
+            last_payment =
+                self.compute_safe_price(&pair_address, start_round, current_round, last_payment);
+        }
+
+        last_payment
+    }
+
+    fn require_usable_observations(
+        &self,
+        pair_address: &ManagedAddress,
+        start_round: Round,
+        current_round: Round,
+    ) {
+        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+        require!(
+            !price_observations.is_empty(),
+            "Not enough price observations on route"
+        );
+
+        let current_index = self
+            .get_safe_price_current_index_mapper(pair_address.clone())
+            .get();
+        let last_observation: PriceObservation<Self::Api> = price_observations.get(current_index);
+        require!(
+            current_round - last_observation.recording_round
+                <= self.get_max_observation_staleness_rounds(),
+            "Price observations are stale"
+        );
+
+        // VecMapper index starts at 1
+        let oldest_observation_index = if price_observations.len() == MAX_OBSERVATIONS {
+            (current_index % MAX_OBSERVATIONS) + 1
+        } else {
+            1
+        };
+        let oldest_observation = price_observations.get(oldest_observation_index);
+        require!(
+            oldest_observation.recording_round <= start_round,
+            "Not enough price observations on route"
+        );
+    }
+
+    fn get_max_observation_staleness_rounds(&self) -> Round {
+        if self.max_observation_staleness_rounds().is_empty() {
+            DEFAULT_MAX_OBSERVATION_STALENESS_ROUNDS
+        } else {
+            self.max_observation_staleness_rounds().get()
+        }
+    }
+
+    #[view(getMaxObservationStaleness)]
+    #[storage_mapper("max_observation_staleness_rounds")]
+    fn max_observation_staleness_rounds(&self) -> SingleValueMapper<Round>;
+}
@@ -0,0 +1,150 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{config, events, factory, fee_tiers, multi_pair_swap};
+use pair::read_pair_storage;
+
+pub const MAX_SPLIT_PERCENTAGE: u64 = 100_000;
+pub const MAX_SPLIT_ROUTES: usize = 5;
+
+#[type_abi]
+#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SwapStep<M: ManagedTypeApi> {
+    pub pair_address: ManagedAddress<M>,
+    pub token_out: TokenIdentifier<M>,
+}
+
+#[type_abi]
+#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SplitRoute<M: ManagedTypeApi> {
+    pub percentage: u64,
+    pub steps: ManagedVec<M, SwapStep<M>>,
+}
+
+#[multiversx_sc::module]
+pub trait SplitRouteSwapModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + factory::FactoryModule
+    + fee_tiers::FeeTiersModule
+    + multi_pair_swap::MultiPairSwap
+    + token_send::TokenSendModule
+    + events::EventsModule
+{
+    /// Splits the payment between several parallel routes, given as percentages
+    /// of MAX_SPLIT_PERCENTAGE. The last route also receives the rounding dust.
+    /// All the routes must end in `token_out` and the merged output is checked
+    /// against `amount_out_min`.
+    #[payable("*")]
+    #[endpoint(splitRouteSwap)]
+    fn split_route_swap(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+        routes: MultiValueEncoded<SplitRoute<Self::Api>>,
+    ) -> EsdtTokenPayment {
+        require!(self.is_active(), "Not active");
+
+        let payment = self.call_value().single_esdt().clone();
+        require!(payment.token_nonce == 0, "Invalid nonce. Should be zero");
+        require!(payment.amount > 0u64, "Invalid amount. Should not be zero");
+        require!(
+            payment.token_identifier != token_out,
+            "Tokens cannot be the same"
+        );
+
+        let routes = routes.to_vec();
+        require!(
+            !routes.is_empty() && routes.len() <= MAX_SPLIT_ROUTES,
+            "Invalid number of routes"
+        );
+
+        let mut total_percentage = 0u64;
+        for route in routes.iter() {
+            require!(route.percentage > 0, "Invalid route percentage");
+            require!(
+                !route.steps.is_empty(),
+                "Invalid route. Should not be empty"
+            );
+
+            let last_step = route.steps.get(route.steps.len() - 1);
+            require!(
+                last_step.token_out == token_out,
+                "Invalid route output token"
+            );
+
+            total_percentage += route.percentage;
+        }
+        require!(
+            total_percentage == MAX_SPLIT_PERCENTAGE,
+            "Route percentages must add up to 100%"
+        );
+
+        let mut route_amounts_in = ManagedVec::new();
+        let mut route_amounts_out = ManagedVec::new();
+        let mut amount_left = payment.amount.clone();
+        let mut total_amount_out = BigUint::zero();
+        let last_route_index = routes.len() - 1;
+        for (index, route) in routes.iter().enumerate() {
+            let route_amount_in = if index == last_route_index {
+                amount_left.clone()
+            } else {
+                &payment.amount * route.percentage / MAX_SPLIT_PERCENTAGE
+            };
+            require!(route_amount_in > 0u64, "Route amount is zero");
+            amount_left -= &route_amount_in;
+
+            let route_amount_out = self.execute_split_route(
+                &route.steps,
+                payment.token_identifier.clone(),
+                route_amount_in.clone(),
+            );
+            total_amount_out += &route_amount_out;
+
+            route_amounts_in.push(route_amount_in);
+            route_amounts_out.push(route_amount_out);
+        }
+        require!(total_amount_out >= amount_out_min, "Slippage exceeded");
+
+        let caller = self.blockchain().get_caller();
+        let output_payment = EsdtTokenPayment::new(token_out, 0, total_amount_out);
+        self.send().direct_esdt(
+            &caller,
+            &output_payment.token_identifier,
+            0,
+            &output_payment.amount,
+        );
+
+        self.emit_split_route_swap_event(
+            caller,
+            payment,
+            output_payment.clone(),
+            route_amounts_in,
+            route_amounts_out,
+        );
+
+        output_payment
+    }
+
+    fn execute_split_route(
+        &self,
+        steps: &ManagedVec<SwapStep<Self::Api>>,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+    ) -> BigUint {
+        let mut last_payment = EsdtTokenPayment::new(token_in, 0, amount_in);
+        for step in steps.iter() {
+            self.check_is_pair_sc(&step.pair_address);
+
+            last_payment = self.actual_swap_fixed_input(
+                step.pair_address.clone(),
+                last_payment.token_identifier,
+                last_payment.amount,
+                step.token_out.clone(),
+                BigUint::from(1u64),
+            );
+        }
+
+        last_payment.amount
+    }
+}
@@ -1,5 +1,7 @@
 use multiversx_sc::codec::multi_types::{MultiValue4, OptionalValue};
-use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use multiversx_sc::types::{
+    Address, EsdtLocalRole, EsdtTokenPayment, ManagedAddress, ManagedVec, MultiValueEncoded,
+};
 use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint,
@@ -8,11 +10,13 @@ use multiversx_sc_scenario::{
 
 pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
 pub const ROUTER_WASM_PATH: &str = "router/output/router.wasm";
+pub const CONCENTRATED_PAIR_WASM_PATH: &str = "concentrated-pair/output/concentrated-pair.wasm";
 pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
 pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
 pub const USDC_TOKEN_ID: &[u8] = b"USDC-abcdef";
 pub const LPMEX_TOKEN_ID: &[u8] = b"LPMEX-abcdef";
 pub const LPUSDC_TOKEN_ID: &[u8] = b"LPUSDC-abcdef";
+pub const POSITION_TOKEN_ID: &[u8] = b"CLPOS-abcdef";
 
 pub const USER_TOTAL_MEX_TOKENS: u64 = 5_001_001_000;
 pub const USER_TOTAL_WEGLD_TOKENS: u64 = 5_002_002_000;
@@ -31,11 +35,18 @@ use pair::config::ConfigModule as PairConfigModule;
 use pair::pair_actions::add_liq::AddLiquidityModule;
 use pair::*;
 use pausable::{PausableModule, State};
+use router::atomic_swap::AtomicSwapModule;
+use router::auto_route::AutoRouteModule;
 use router::config::ConfigModule;
 use router::factory::*;
 use router::multi_pair_swap::*;
+use router::route_oracle::RouteOracleModule;
+use router::split_route_swap::*;
 use router::*;
 
+/// The percentage of the payment and the (pair address, token out) steps of a split route
+pub type SplitRouteArgs<'a> = (u64, &'a [(Address, &'a [u8])]);
+
 #[allow(dead_code)]
 pub struct RouterSetup<RouterObjBuilder, PairObjBuilder>
 where
@@ -284,4 +295,167 @@ where
             )
             .assert_ok();
     }
+
+    pub fn swap_with_auto_route(
+        &mut self,
+        payment_token: &[u8],
+        payment_amount: u64,
+        token_out: &[u8],
+        amount_out_min: u64,
+    ) -> TxResult {
+        self.blockchain_wrapper.execute_esdt_transfer(
+            &self.user_address,
+            &self.router_wrapper,
+            payment_token,
+            0,
+            &rust_biguint!(payment_amount),
+            |sc| {
+                let output_payment = sc.swap_with_auto_route(
+                    managed_token_id!(token_out),
+                    managed_biguint!(amount_out_min),
+                );
+                assert_eq!(
+                    output_payment.token_identifier,
+                    managed_token_id!(token_out)
+                );
+            },
+        )
+    }
+
+    pub fn split_route_swap(
+        &mut self,
+        payment_token: &[u8],
+        payment_amount: u64,
+        token_out: &[u8],
+        amount_out_min: u64,
+        routes: &[SplitRouteArgs],
+    ) -> TxResult {
+        self.blockchain_wrapper.execute_esdt_transfer(
+            &self.user_address,
+            &self.router_wrapper,
+            payment_token,
+            0,
+            &rust_biguint!(payment_amount),
+            |sc| {
+                let mut split_routes = MultiValueEncoded::new();
+                for (percentage, steps) in routes.iter() {
+                    let mut swap_steps = ManagedVec::new();
+                    for (pair_address, step_token_out) in steps.iter() {
+                        swap_steps.push(SwapStep {
+                            pair_address: managed_address!(pair_address),
+                            token_out: managed_token_id!(*step_token_out),
+                        });
+                    }
+
+                    split_routes.push(SplitRoute {
+                        percentage: *percentage,
+                        steps: swap_steps,
+                    });
+                }
+
+                let output_payment = sc.split_route_swap(
+                    managed_token_id!(token_out),
+                    managed_biguint!(amount_out_min),
+                    split_routes,
+                );
+                assert_eq!(
+                    output_payment.token_identifier,
+                    managed_token_id!(token_out)
+                );
+            },
+        )
+    }
+
+    pub fn swap_exact_in_with_deadline(
+        &mut self,
+        payment_token: &[u8],
+        payment_amount: u64,
+        amount_out_min: u64,
+        deadline: u64,
+        path: &[(Address, &[u8])],
+    ) -> TxResult {
+        self.blockchain_wrapper.execute_esdt_transfer(
+            &self.user_address,
+            &self.router_wrapper,
+            payment_token,
+            0,
+            &rust_biguint!(payment_amount),
+            |sc| {
+                sc.swap_exact_in_with_deadline(
+                    managed_biguint!(amount_out_min),
+                    deadline,
+                    Self::build_swap_path(path),
+                );
+            },
+        )
+    }
+
+    pub fn swap_exact_out_with_deadline(
+        &mut self,
+        payment_token: &[u8],
+        payment_amount_max: u64,
+        amount_out: u64,
+        deadline: u64,
+        path: &[(Address, &[u8])],
+    ) -> TxResult {
+        self.blockchain_wrapper.execute_esdt_transfer(
+            &self.user_address,
+            &self.router_wrapper,
+            payment_token,
+            0,
+            &rust_biguint!(payment_amount_max),
+            |sc| {
+                let (output, _) = sc
+                    .swap_exact_out_with_deadline(
+                        managed_biguint!(amount_out),
+                        deadline,
+                        Self::build_swap_path(path),
+                    )
+                    .into_tuple();
+                assert_eq!(output.amount, managed_biguint!(amount_out));
+            },
+        )
+    }
+
+    fn build_swap_path(
+        path: &[(Address, &[u8])],
+    ) -> MultiValueEncoded<DebugApi, SwapStep<DebugApi>> {
+        let mut swap_path = MultiValueEncoded::new();
+        for (pair_address, token_out) in path.iter() {
+            swap_path.push(SwapStep {
+                pair_address: managed_address!(pair_address),
+                token_out: managed_token_id!(*token_out),
+            });
+        }
+
+        swap_path
+    }
+
+    pub fn get_route_safe_price(
+        &mut self,
+        input_token: &[u8],
+        input_amount: u64,
+        timestamp_offset: u64,
+        path: &[Address],
+        expected_amount: u64,
+    ) -> TxResult {
+        self.blockchain_wrapper
+            .execute_query(&self.router_wrapper, |sc| {
+                let mut pair_addresses = MultiValueEncoded::new();
+                for pair_address in path.iter() {
+                    pair_addresses.push(managed_address!(pair_address));
+                }
+
+                let output = sc.get_route_safe_price(
+                    EsdtTokenPayment::new(
+                        managed_token_id!(input_token),
+                        0,
+                        managed_biguint!(input_amount),
+                    ),
+                    timestamp_offset,
+                    pair_addresses,
+                );
+                assert_eq!(output.amount, managed_biguint!(expected_amount));
+            })
+    }
 }
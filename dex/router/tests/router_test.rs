@@ -5,23 +5,41 @@ use multiversx_sc::{
     codec::multi_types::OptionalValue,
     
     types::{
-        Address, EgldOrEsdtTokenIdentifier, EsdtLocalRole, ManagedAddress, ManagedVec,
-        MultiValueEncoded,
+        Address, EgldOrEsdtTokenIdentifier, EsdtLocalRole, EsdtTokenPayment, ManagedAddress,
+        ManagedVec, MultiValueEncoded,
     },
 };
 use pair::{
-    config::ConfigModule as PairConfigModule, pair_actions::initial_liq::InitialLiquidityModule,
+    config::ConfigModule as PairConfigModule,
+    dynamic_fee::DynamicFeeModule,
+    pair_actions::{
+        add_liq::AddLiquidityModule, initial_liq::InitialLiquidityModule, views::ViewsModule,
+    },
+    safe_price_view::SafePriceViewModule,
     Pair,
 };
 use pausable::{PausableModule, State};
 use router::{
-    config::ConfigModule, enable_swap_by_user::EnableSwapByUserModule, factory::PairTokens,
-    multi_pair_swap::SWAP_TOKENS_FIXED_INPUT_FUNC_NAME, Router,
+    auto_route::AutoRouteModule,
+    config::ConfigModule,
+    enable_swap_by_user::EnableSwapByUserModule,
+    factory::{FactoryModule, PairRegistryEntry, PairTokens, MAX_PAGE_SIZE},
+    fee_tiers::FeeTiersModule,
+    multi_pair_swap::SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+    permissionless_listing::{ListingStatus, PermissionlessListingModule, LISTING_EXPIRY_EPOCHS},
+    route_oracle::RouteOracleModule,
+    Router,
 };
 use router_setup::*;
 
+use concentrated_pair::{
+    math::tick_math::get_sqrt_ratio_at_tick, position_token::PositionTokenModule,
+    positions::PositionsModule, ConcentratedPair,
+};
+
 use multiversx_sc_scenario::{
-    managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
+    managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped,
+    multiversx_chain_vm::crypto_functions::keccak256, rust_biguint,
     whitebox_legacy::BlockchainStateWrapper, whitebox_legacy::TxTokenTransfer, DebugApi,
 };
 use simple_lock::{
@@ -113,7 +131,7 @@ fn test_router_upgrade_pair() {
         .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
             let first_token_id = managed_token_id!(CUSTOM_TOKEN_ID);
             let second_token_id = managed_token_id!(USDC_TOKEN_ID);
-            sc.upgrade_pair_endpoint(first_token_id, second_token_id);
+            sc.upgrade_pair_endpoint(first_token_id, second_token_id, OptionalValue::None);
         })
         .assert_ok();
 
@@ -125,6 +143,307 @@ fn test_router_upgrade_pair() {
         .assert_ok();
 }
 
+#[test]
+fn test_create_pair_with_fee_tier() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_zero);
+
+    let router_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        router::contract_obj,
+        ROUTER_WASM_PATH,
+    );
+    let pair_template_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(router_wrapper.address_ref()),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.init(OptionalValue::Some(managed_address!(
+                pair_template_wrapper.address_ref()
+            )));
+            sc.set_pair_creation_enabled(true);
+            sc.add_fee_tier(50, 5);
+            sc.add_fee_tier(1_000, 50);
+        })
+        .assert_ok();
+
+    let default_pair_wrapper =
+        b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), pair::contract_obj);
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.create_pair_endpoint(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(&user),
+                OptionalValue::None,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.create_pair_with_fee_tier_endpoint(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(&user),
+                300,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_user_error("Unknown fee tier");
+
+    let tier_pair_wrapper =
+        b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), pair::contract_obj);
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.create_pair_with_fee_tier_endpoint(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(&user),
+                1_000,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.create_pair_with_fee_tier_endpoint(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_address!(&user),
+                1_000,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_user_error("Pair already exists");
+
+    b_mock
+        .execute_query(&tier_pair_wrapper, |sc| {
+            assert_eq!(sc.total_fee_percent().get(), 1_000);
+            assert_eq!(sc.special_fee_percent().get(), 50);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            let default_pair_address = managed_address!(default_pair_wrapper.address_ref());
+            let tier_pair_address = managed_address!(tier_pair_wrapper.address_ref());
+            assert_eq!(
+                sc.get_pair_view(
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    OptionalValue::None,
+                ),
+                default_pair_address
+            );
+            assert_eq!(
+                sc.get_pair_view(
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    OptionalValue::Some(1_000),
+                ),
+                tier_pair_address
+            );
+            assert!(sc
+                .get_pair_view(
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                    OptionalValue::Some(50),
+                )
+                .is_zero());
+
+            let pair_fee_tiers: Vec<_> = sc
+                .get_pair_fee_tiers(
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                )
+                .into_iter()
+                .map(|fee_tier| fee_tier.into_tuple())
+                .collect();
+            assert_eq!(pair_fee_tiers, vec![(1_000, tier_pair_address.clone())]);
+
+            // the fee tier pairs are known to the router
+            sc.check_is_pair_sc(&tier_pair_address);
+            let all_pairs: Vec<_> = sc.get_all_pairs_addresses().into_iter().collect();
+            assert_eq!(all_pairs, vec![default_pair_address, tier_pair_address]);
+            let all_pair_tokens: Vec<_> = sc.get_all_token_pairs().into_iter().collect();
+            assert_eq!(all_pair_tokens.len(), 2);
+            assert!(all_pair_tokens[0] == all_pair_tokens[1]);
+            assert_eq!(sc.get_all_pair_contract_metadata().len(), 2);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.remove_pair(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                OptionalValue::Some(1_000),
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            assert_eq!(sc.get_all_pairs_addresses().len(), 1);
+            assert!(sc
+                .get_pair_fee_tiers(
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                )
+                .is_empty());
+            assert!(!sc
+                .get_pair_view(
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                    OptionalValue::None,
+                )
+                .is_zero());
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            sc.check_is_pair_sc(&managed_address!(tier_pair_wrapper.address_ref()));
+        })
+        .assert_user_error("Not a pair SC");
+}
+
+#[test]
+fn test_pair_registry_and_address_prediction() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let router_address = router_setup.router_wrapper.address_ref().clone();
+
+    let rust_zero = rust_biguint!(0u64);
+    let owner = router_setup.owner_address.clone();
+    let tier_pair_wrapper = router_setup.blockchain_wrapper.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &tier_pair_wrapper, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(WEGLD_TOKEN_ID),
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_address!(&router_address),
+                managed_address!(&owner),
+                1_000,
+                50,
+                ManagedAddress::zero(),
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_ok();
+    let tier_pair_address = tier_pair_wrapper.address_ref().clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &router_setup.router_wrapper, &rust_zero, |sc| {
+            let pair_tokens = PairTokens {
+                first_token_id: managed_token_id!(WEGLD_TOKEN_ID),
+                second_token_id: managed_token_id!(MEX_TOKEN_ID),
+            };
+            sc.fee_tier_pairs(&pair_tokens)
+                .insert(1_000, managed_address!(&tier_pair_address));
+            sc.pair_fee_tier(&managed_address!(&tier_pair_address))
+                .set(1_000);
+            sc.fee_tier_pair_tokens()
+                .insert(managed_address!(&tier_pair_address), pair_tokens);
+        })
+        .assert_ok();
+
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let page: Vec<_> = sc.get_pairs_page(0, 10).into_iter().collect();
+            assert_eq!(page.len(), 3);
+            assert_eq!(
+                page[0],
+                PairRegistryEntry {
+                    address: managed_address!(&mex_pair_address),
+                    first_token_id: managed_token_id!(WEGLD_TOKEN_ID),
+                    second_token_id: managed_token_id!(MEX_TOKEN_ID),
+                    lp_token_id: managed_token_id!(LPMEX_TOKEN_ID),
+                    state: State::Active,
+                    total_fee_percent: 300,
+                    special_fee_percent: 50,
+                }
+            );
+
+            let page: Vec<_> = sc.get_pairs_page(1, 1).into_iter().collect();
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].address, managed_address!(&usdc_pair_address));
+            assert_eq!(page[0].lp_token_id, managed_token_id!(LPUSDC_TOKEN_ID));
+
+            // the fee tier pairs come after the ones created through createPair
+            let page: Vec<_> = sc.get_pairs_page(2, 10).into_iter().collect();
+            assert_eq!(page.len(), 1);
+            assert_eq!(page[0].address, managed_address!(&tier_pair_address));
+            assert_eq!(page[0].total_fee_percent, 1_000);
+
+            assert_eq!(sc.get_pairs_page(3, 10).len(), 0);
+
+            let by_token: Vec<_> = sc
+                .get_pairs_by_token(managed_token_id!(MEX_TOKEN_ID), 0, 10)
+                .into_iter()
+                .collect();
+            assert_eq!(by_token.len(), 2);
+            assert_eq!(by_token[0].address, managed_address!(&mex_pair_address));
+            assert_eq!(by_token[1].address, managed_address!(&tier_pair_address));
+
+            let by_token: Vec<_> = sc
+                .get_pairs_by_token(managed_token_id!(WEGLD_TOKEN_ID), 1, 1)
+                .into_iter()
+                .collect();
+            assert_eq!(by_token.len(), 1);
+            assert_eq!(by_token[0].address, managed_address!(&usdc_pair_address));
+        })
+        .assert_ok();
+
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let _ = sc.get_pairs_page(0, MAX_PAGE_SIZE + 1);
+        })
+        .assert_user_error("Page size too large");
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let _ = sc.get_pairs_by_token(managed_token_id!(MEX_TOKEN_ID), 0, MAX_PAGE_SIZE + 1);
+        })
+        .assert_user_error("Page size too large");
+
+    let mut hash_input = router_address.to_vec();
+    hash_input.extend_from_slice(&5u64.to_le_bytes());
+    let mut expected_address = keccak256(&hash_input);
+    expected_address[..8].fill(0);
+    expected_address[8..10].copy_from_slice(&[5, 0]);
+    expected_address[30..].copy_from_slice(&router_address.as_bytes()[30..]);
+
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            assert_eq!(
+                sc.predict_pair_address(5),
+                managed_address!(&Address::from(expected_address))
+            );
+        })
+        .assert_ok();
+}
+
 #[test]
 fn test_multi_pair_swap() {
     let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
@@ -182,216 +501,592 @@ fn test_multi_pair_swap() {
 }
 
 #[test]
-fn user_enable_pair_swaps_through_router_test() {
-    let rust_zero = rust_biguint!(0u64);
-    let mut b_mock = BlockchainStateWrapper::new();
-    let owner = b_mock.create_user_account(&rust_zero);
-    let user = b_mock.create_user_account(&rust_zero);
-
-    let current_epoch = 5;
-    b_mock.set_block_epoch(current_epoch);
-
-    b_mock.set_esdt_balance(
-        &user,
-        CUSTOM_TOKEN_ID,
-        &rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
-    );
-    b_mock.set_esdt_balance(&user, USDC_TOKEN_ID, &rust_biguint!(USER_USDC_BALANCE));
+fn test_multi_pair_swap_through_concentrated_pool() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.add_liquidity();
 
-    let router_wrapper = b_mock.create_sc_account(
+    let rust_zero = rust_biguint!(0u64);
+    let owner = router_setup.owner_address.clone();
+    let user = router_setup.user_address.clone();
+    let pool_wrapper = router_setup.blockchain_wrapper.create_sc_account(
         &rust_zero,
         Some(&owner),
-        router::contract_obj,
-        ROUTER_WASM_PATH,
+        concentrated_pair::contract_obj,
+        CONCENTRATED_PAIR_WASM_PATH,
     );
-    let pair_wrapper = b_mock.create_sc_account(
-        &rust_zero,
-        Some(router_wrapper.address_ref()),
-        pair::contract_obj,
-        PAIR_WASM_PATH,
-    );
-    let simple_lock_wrapper = b_mock.create_sc_account(
-        &rust_zero,
-        Some(&owner),
-        simple_lock::contract_obj,
-        "simple-lock.wasm",
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &pool_wrapper, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(WEGLD_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(router_setup.router_wrapper.address_ref()),
+                managed_address!(&owner),
+                300,
+                10,
+                get_sqrt_ratio_at_tick(0),
+                MultiValueEncoded::new(),
+            );
+
+            sc.position_token()
+                .set_token_id(managed_token_id!(POSITION_TOKEN_ID));
+            sc.state().set(State::Active);
+        })
+        .assert_ok();
+    router_setup.blockchain_wrapper.set_esdt_local_roles(
+        pool_wrapper.address_ref(),
+        POSITION_TOKEN_ID,
+        &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn][..],
     );
 
-    // setup simple-lock
-    b_mock
-        .execute_tx(&owner, &simple_lock_wrapper, &rust_zero, |sc| {
-            sc.locked_token()
-                .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
+    let position_payments = [WEGLD_TOKEN_ID, USDC_TOKEN_ID].map(|token_id| TxTokenTransfer {
+        token_identifier: token_id.to_vec(),
+        nonce: 0,
+        value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+    });
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_multi_transfer(&user, &pool_wrapper, &position_payments, |sc| {
+            let _ = sc.open_position(-1_000, 1_000, managed_biguint!(0), managed_biguint!(0));
         })
         .assert_ok();
 
-    b_mock.set_esdt_local_roles(
-        simple_lock_wrapper.address_ref(),
-        LOCKED_TOKEN_ID,
-        &[
-            EsdtLocalRole::NftCreate,
-            EsdtLocalRole::NftAddQuantity,
-            EsdtLocalRole::NftBurn,
-        ],
-    );
+    let pool_address = pool_wrapper.address_ref().clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            sc.check_is_pair_sc(&managed_address!(&pool_address));
+        })
+        .assert_user_error("Not a pair SC");
 
-    // setup router
-    b_mock
-        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
-            sc.init(OptionalValue::None);
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &router_setup.router_wrapper, &rust_zero, |sc| {
+            sc.register_concentrated_pool(managed_address!(&pool_address));
+        })
+        .assert_ok();
 
-            sc.pair_map().insert(
-                PairTokens {
-                    first_token_id: managed_token_id!(CUSTOM_TOKEN_ID),
-                    second_token_id: managed_token_id!(USDC_TOKEN_ID),
-                },
-                managed_address!(pair_wrapper.address_ref()),
-            );
+    let ops = vec![
+        (
+            router_setup.mex_pair_wrapper.address_ref().clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            WEGLD_TOKEN_ID,
+            1,
+        ),
+        (
+            pool_address.clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            USDC_TOKEN_ID,
+            1,
+        ),
+    ];
+    router_setup.multi_pair_swap(MEX_TOKEN_ID, 100_000, &ops);
 
-            sc.add_common_tokens_for_user_pairs(MultiValueEncoded::from(ManagedVec::from(vec![
-                managed_token_id!(USDC_TOKEN_ID),
-            ])));
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &user,
+        MEX_TOKEN_ID,
+        &rust_biguint!(4_999_900_000),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &user,
+        USDC_TOKEN_ID,
+        &rust_biguint!(4_999_088_999), //gained 89_999
+    );
 
-            sc.config_enable_by_user_parameters(
-                managed_token_id!(USDC_TOKEN_ID),
-                managed_token_id!(LOCKED_TOKEN_ID),
-                managed_biguint!(MIN_LOCKED_TOKEN_VALUE),
-                MIN_LOCKED_PERIOD_EPOCHS,
-            )
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &router_setup.router_wrapper, &rust_zero, |sc| {
+            sc.remove_concentrated_pool(managed_address!(&pool_address));
         })
         .assert_ok();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            sc.check_is_pair_sc(&managed_address!(&pool_address));
+        })
+        .assert_user_error("Not a pair SC");
+}
 
-    // setup pair
-    b_mock
-        .execute_tx(&owner, &pair_wrapper, &rust_zero, |sc| {
-            let first_token_id = managed_token_id!(CUSTOM_TOKEN_ID);
-            let second_token_id = managed_token_id!(USDC_TOKEN_ID);
-            let router_address = managed_address!(router_wrapper.address_ref());
-            let router_owner_address = managed_address!(&owner);
+#[test]
+fn test_swap_with_auto_route() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
 
-            sc.init(
-                first_token_id,
-                second_token_id,
-                router_address,
-                router_owner_address,
-                0,
-                0,
-                managed_address!(&user),
-                MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
-            );
+    // pairs without liquidity are not part of any route
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, USDC_TOKEN_ID, 1)
+        .assert_user_error("No route found");
 
-            assert_eq!(sc.state().get(), State::Inactive);
+    router_setup.add_liquidity();
 
-            sc.lp_token_identifier()
-                .set(&managed_token_id!(LPUSDC_TOKEN_ID));
-        })
+    // only the base tokens can be intermediate hops
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, USDC_TOKEN_ID, 1)
+        .assert_user_error("No route found");
+
+    let owner_address = router_setup.owner_address.clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &router_setup.router_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut base_tokens = MultiValueEncoded::new();
+                base_tokens.push(managed_token_id!(WEGLD_TOKEN_ID));
+                sc.add_route_base_tokens(base_tokens);
+            },
+        )
         .assert_ok();
 
-    b_mock.set_esdt_local_roles(
-        pair_wrapper.address_ref(),
-        LPUSDC_TOKEN_ID,
-        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
-    );
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let route = sc.get_best_route(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_biguint!(100_000),
+            );
+            assert_eq!(route.len(), 2);
 
-    // add liquidity
-    let payments = vec![
-        TxTokenTransfer {
-            token_identifier: CUSTOM_TOKEN_ID.to_vec(),
-            nonce: 0,
-            value: rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
-        },
-        TxTokenTransfer {
-            token_identifier: USDC_TOKEN_ID.to_vec(),
-            nonce: 0,
-            value: rust_biguint!(USER_USDC_BALANCE),
-        },
-    ];
+            let first_hop = route.get(0);
+            assert_eq!(first_hop.pair_address, managed_address!(&mex_pair_address));
+            assert_eq!(first_hop.token_out, managed_token_id!(WEGLD_TOKEN_ID));
+            assert_eq!(first_hop.amount_in, managed_biguint!(100_000));
 
-    let user_lp_tokens_balance = 999_000u64;
-    b_mock
-        .execute_esdt_multi_transfer(&user, &pair_wrapper, &payments, |sc| {
-            let (lp_tokens_received, _, _) = sc.add_initial_liquidity().into_tuple();
+            let second_hop = route.get(1);
             assert_eq!(
-                lp_tokens_received.token_identifier,
-                managed_token_id!(LPUSDC_TOKEN_ID)
-            );
-            assert_eq!(
-                lp_tokens_received.amount,
-                managed_biguint!(user_lp_tokens_balance)
+                second_hop.pair_address,
+                managed_address!(&usdc_pair_address)
             );
+            assert_eq!(second_hop.amount_in, first_hop.amount_out);
+            assert_eq!(second_hop.amount_out, managed_biguint!(82_909));
         })
         .assert_ok();
 
-    // lock LP tokens
-    b_mock
-        .execute_esdt_transfer(
-            &user,
-            &simple_lock_wrapper,
-            LPUSDC_TOKEN_ID,
-            0,
-            &rust_biguint!(user_lp_tokens_balance),
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, USDC_TOKEN_ID, 82_910)
+        .assert_user_error("Slippage exceeded");
+
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, USDC_TOKEN_ID, 82_909)
+        .assert_ok();
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(4_999_900_000),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_082_909),
+    );
+
+    // a single hop limit leaves only the direct pairs
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &router_setup.router_wrapper,
+            &rust_biguint!(0),
             |sc| {
-                sc.lock_tokens_endpoint(
-                    current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
-                    OptionalValue::None,
-                );
+                sc.set_max_route_hops(1);
             },
         )
         .assert_ok();
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, USDC_TOKEN_ID, 1)
+        .assert_user_error("No route found");
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, WEGLD_TOKEN_ID, 1)
+        .assert_ok();
 
-    DebugApi::dummy();
-    b_mock.check_nft_balance(
-        &user,
-        LOCKED_TOKEN_ID,
-        1,
-        &rust_biguint!(user_lp_tokens_balance),
-        Some(&LockedTokenAttributes::<DebugApi> {
-            original_token_id: managed_token_id_wrapped!(LPUSDC_TOKEN_ID),
-            original_token_nonce: 0,
-            unlock_epoch: current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
-        }),
+    // fee tier pairs are routed as well
+    let rust_zero = rust_biguint!(0u64);
+    let tier_pair_wrapper = router_setup.blockchain_wrapper.create_sc_account(
+        &rust_zero,
+        Some(&owner_address),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(&owner_address, &tier_pair_wrapper, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(router_setup.router_wrapper.address_ref()),
+                managed_address!(&owner_address),
+                1_000,
+                50,
+                ManagedAddress::zero(),
+                MultiValueEncoded::new(),
+            );
+            sc.lp_token_identifier()
+                .set(managed_token_id!(LPMEX_TOKEN_ID));
+            sc.state().set(State::Active);
+        })
+        .assert_ok();
+    router_setup.blockchain_wrapper.set_esdt_local_roles(
+        tier_pair_wrapper.address_ref(),
+        LPMEX_TOKEN_ID,
+        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn][..],
     );
 
-    // pass blocks time to update safe price
-    b_mock.set_block_nonce(1_000_000);
+    let liquidity_payments = [MEX_TOKEN_ID, USDC_TOKEN_ID].map(|token_id| TxTokenTransfer {
+        token_identifier: token_id.to_vec(),
+        nonce: 0,
+        value: rust_biguint!(ADD_LIQUIDITY_TOKENS),
+    });
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_multi_transfer(
+            &router_setup.user_address,
+            &tier_pair_wrapper,
+            &liquidity_payments,
+            |sc| {
+                sc.add_liquidity(managed_biguint!(1), managed_biguint!(1));
+            },
+        )
+        .assert_ok();
 
-    // activate swaps through router
-    b_mock
-        .execute_esdt_transfer(
-            &user,
-            &router_wrapper,
-            LOCKED_TOKEN_ID,
-            1,
-            &rust_biguint!(user_lp_tokens_balance),
+    let tier_pair_address = tier_pair_wrapper.address_ref().clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &router_setup.router_wrapper,
+            &rust_zero,
             |sc| {
-                sc.set_swap_enabled_by_user(managed_address!(pair_wrapper.address_ref()));
+                let pair_tokens = PairTokens {
+                    first_token_id: managed_token_id!(MEX_TOKEN_ID),
+                    second_token_id: managed_token_id!(USDC_TOKEN_ID),
+                };
+                sc.fee_tier_pairs(&pair_tokens)
+                    .insert(1_000, managed_address!(&tier_pair_address));
+                sc.pair_fee_tier(&managed_address!(&tier_pair_address))
+                    .set(1_000);
+                sc.fee_tier_pair_tokens()
+                    .insert(managed_address!(&tier_pair_address), pair_tokens);
             },
         )
         .assert_ok();
 
-    // check pair state is active
-    b_mock
-        .execute_query(&pair_wrapper, |sc| {
-            assert_eq!(sc.state().get(), State::Active);
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let route = sc.get_best_route(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_biguint!(100_000),
+            );
+            assert_eq!(route.len(), 1);
+            assert_eq!(
+                route.get(0).pair_address,
+                managed_address!(&tier_pair_address)
+            );
         })
         .assert_ok();
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, USDC_TOKEN_ID, 1)
+        .assert_ok();
+}
 
-    // check user received the locked tokens back
-    b_mock.check_nft_balance(
-        &user,
-        LOCKED_TOKEN_ID,
-        1,
-        &rust_biguint!(user_lp_tokens_balance),
-        Some(&LockedTokenAttributes::<DebugApi> {
-            original_token_id: managed_token_id_wrapped!(LPUSDC_TOKEN_ID),
-            original_token_nonce: 0,
-            unlock_epoch: current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
-        }),
+#[test]
+fn test_auto_route_dynamic_fee() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.add_liquidity();
+
+    let owner_address = router_setup.owner_address.clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &router_setup.mex_pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_dynamic_fee(1_000, 1_000, 10, 1_000);
+            },
+        )
+        .assert_ok();
+
+    // the route is priced with the fee the pair charges now, not the static one
+    let mut pair_amount_out = 0u64;
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.mex_pair_wrapper, |sc| {
+            assert_eq!(sc.get_effective_total_fee_percent(), 1_000);
+            pair_amount_out = sc
+                .get_amount_out_view(managed_token_id!(MEX_TOKEN_ID), managed_biguint!(100_000))
+                .to_u64()
+                .unwrap();
+        })
+        .assert_ok();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let route = sc.get_best_route(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(WEGLD_TOKEN_ID),
+                managed_biguint!(100_000),
+            );
+            assert_eq!(route.len(), 1);
+            assert_eq!(route.get(0).amount_out, managed_biguint!(pair_amount_out));
+        })
+        .assert_ok();
+
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, WEGLD_TOKEN_ID, pair_amount_out + 1)
+        .assert_user_error("Slippage exceeded");
+    router_setup
+        .swap_with_auto_route(MEX_TOKEN_ID, 100_000, WEGLD_TOKEN_ID, pair_amount_out)
+        .assert_ok();
+}
+
+#[test]
+fn test_split_route_swap() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.add_liquidity();
+
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let mex_to_usdc_steps: &[(Address, &[u8])] = &[
+        (mex_pair_address.clone(), WEGLD_TOKEN_ID),
+        (usdc_pair_address.clone(), USDC_TOKEN_ID),
+    ];
+
+    router_setup
+        .split_route_swap(
+            MEX_TOKEN_ID,
+            100_000,
+            USDC_TOKEN_ID,
+            1,
+            &[(60_000, mex_to_usdc_steps), (30_000, mex_to_usdc_steps)],
+        )
+        .assert_user_error("Route percentages must add up to 100%");
+    router_setup
+        .split_route_swap(
+            MEX_TOKEN_ID,
+            100_000,
+            USDC_TOKEN_ID,
+            1,
+            &[
+                (60_000, mex_to_usdc_steps),
+                (40_000, &[(mex_pair_address, WEGLD_TOKEN_ID)]),
+            ],
+        )
+        .assert_user_error("Invalid route output token");
+
+    // the merged output is checked against a single minimum
+    router_setup
+        .split_route_swap(
+            MEX_TOKEN_ID,
+            100_000,
+            USDC_TOKEN_ID,
+            82_900,
+            &[(60_000, mex_to_usdc_steps), (40_000, mex_to_usdc_steps)],
+        )
+        .assert_user_error("Slippage exceeded");
+
+    router_setup
+        .split_route_swap(
+            MEX_TOKEN_ID,
+            100_000,
+            USDC_TOKEN_ID,
+            82_899,
+            &[(60_000, mex_to_usdc_steps), (40_000, mex_to_usdc_steps)],
+        )
+        .assert_ok();
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(4_999_900_000),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_082_899),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        router_setup.router_wrapper.address_ref(),
+        USDC_TOKEN_ID,
+        &rust_biguint!(0),
     );
 }
 
 #[test]
-fn user_enable_pair_swaps_fail_test() {
+fn test_atomic_swap_with_deadline() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.add_liquidity();
+    router_setup.blockchain_wrapper.set_block_timestamp(100);
+
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let mex_to_usdc_path: &[(Address, &[u8])] = &[
+        (mex_pair_address, WEGLD_TOKEN_ID),
+        (usdc_pair_address.clone(), USDC_TOKEN_ID),
+    ];
+
+    router_setup
+        .swap_exact_in_with_deadline(MEX_TOKEN_ID, 100_000, 1, 99, mex_to_usdc_path)
+        .assert_user_error("Deadline passed");
+    router_setup
+        .swap_exact_in_with_deadline(
+            MEX_TOKEN_ID,
+            100_000,
+            1,
+            100,
+            &[(usdc_pair_address, USDC_TOKEN_ID)],
+        )
+        .assert_user_error("Invalid swap path");
+    router_setup
+        .swap_exact_in_with_deadline(MEX_TOKEN_ID, 100_000, 82_910, 100, mex_to_usdc_path)
+        .assert_user_error("Slippage exceeded");
+
+    router_setup
+        .swap_exact_in_with_deadline(MEX_TOKEN_ID, 100_000, 82_909, 100, mex_to_usdc_path)
+        .assert_ok();
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_082_909),
+    );
+
+    // exact output, the amount in is computed backwards through the path
+    router_setup
+        .swap_exact_out_with_deadline(MEX_TOKEN_ID, 50_000, 50_000, 100, mex_to_usdc_path)
+        .assert_user_error("Slippage exceeded");
+
+    let mex_balance_before = router_setup.blockchain_wrapper.get_esdt_balance(
+        &router_setup.user_address,
+        MEX_TOKEN_ID,
+        0,
+    );
+    router_setup
+        .swap_exact_out_with_deadline(MEX_TOKEN_ID, 200_000, 50_000, 100, mex_to_usdc_path)
+        .assert_ok();
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_132_909),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(5_000_000_000),
+    );
+
+    let mex_spent = mex_balance_before
+        - router_setup.blockchain_wrapper.get_esdt_balance(
+            &router_setup.user_address,
+            MEX_TOKEN_ID,
+            0,
+        );
+    assert!(mex_spent > rust_biguint!(50_000) && mex_spent < rust_biguint!(200_000));
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        router_setup.router_wrapper.address_ref(),
+        MEX_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+}
+
+#[test]
+fn test_route_safe_price() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+    router_setup.blockchain_wrapper.set_block_round(10);
+    router_setup.add_liquidity();
+
+    // the first observations are recorded on the next swap
+    router_setup.blockchain_wrapper.set_block_round(20);
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let ops = vec![
+        (
+            mex_pair_address.clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            WEGLD_TOKEN_ID,
+            1,
+        ),
+        (
+            usdc_pair_address.clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            USDC_TOKEN_ID,
+            1,
+        ),
+    ];
+    router_setup.multi_pair_swap(MEX_TOKEN_ID, 100_000, &ops);
+
+    router_setup.blockchain_wrapper.set_block_round(80);
+    let start_round = 30;
+    let timestamp_offset = 50 * 6;
+
+    let mut wegld_amount = 0;
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.mex_pair_wrapper, |sc| {
+            let wegld_payment = sc.get_safe_price(
+                managed_address!(&mex_pair_address),
+                start_round,
+                80,
+                EsdtTokenPayment::new(managed_token_id!(MEX_TOKEN_ID), 0, managed_biguint!(10_000)),
+            );
+            wegld_amount = wegld_payment.amount.to_u64().unwrap();
+        })
+        .assert_ok();
+
+    let mut expected_usdc_amount = 0;
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.usdc_pair_wrapper, |sc| {
+            let usdc_payment = sc.get_safe_price(
+                managed_address!(&usdc_pair_address),
+                start_round,
+                80,
+                EsdtTokenPayment::new(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    0,
+                    managed_biguint!(wegld_amount),
+                ),
+            );
+            expected_usdc_amount = usdc_payment.amount.to_u64().unwrap();
+        })
+        .assert_ok();
+    assert!(expected_usdc_amount > 0);
+
+    let path = [mex_pair_address, usdc_pair_address];
+    router_setup
+        .get_route_safe_price(
+            MEX_TOKEN_ID,
+            10_000,
+            timestamp_offset,
+            &path,
+            expected_usdc_amount,
+        )
+        .assert_ok();
+    router_setup
+        .get_route_safe_price(MEX_TOKEN_ID, 10_000, 70 * 6, &path, 0)
+        .assert_user_error("Not enough price observations on route");
+
+    let owner_address = router_setup.owner_address.clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &router_setup.router_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_max_observation_staleness(50);
+            },
+        )
+        .assert_ok();
+    router_setup
+        .get_route_safe_price(MEX_TOKEN_ID, 10_000, timestamp_offset, &path, 0)
+        .assert_user_error("Price observations are stale");
+}
+
+#[test]
+fn user_enable_pair_swaps_through_router_test() {
     let rust_zero = rust_biguint!(0u64);
     let mut b_mock = BlockchainStateWrapper::new();
     let owner = b_mock.create_user_account(&rust_zero);
@@ -419,6 +1114,30 @@ fn user_enable_pair_swaps_fail_test() {
         pair::contract_obj,
         PAIR_WASM_PATH,
     );
+    let simple_lock_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        simple_lock::contract_obj,
+        "simple-lock.wasm",
+    );
+
+    // setup simple-lock
+    b_mock
+        .execute_tx(&owner, &simple_lock_wrapper, &rust_zero, |sc| {
+            sc.locked_token()
+                .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
+        })
+        .assert_ok();
+
+    b_mock.set_esdt_local_roles(
+        simple_lock_wrapper.address_ref(),
+        LOCKED_TOKEN_ID,
+        &[
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftAddQuantity,
+            EsdtLocalRole::NftBurn,
+        ],
+    );
 
     // setup router
     b_mock
@@ -507,10 +1226,195 @@ fn user_enable_pair_swaps_fail_test() {
         })
         .assert_ok();
 
-    let custom_locked_token = b"LTOK2-123456";
-    DebugApi::dummy();
-    b_mock.set_nft_balance(
-        &user,
+    // lock LP tokens
+    b_mock
+        .execute_esdt_transfer(
+            &user,
+            &simple_lock_wrapper,
+            LPUSDC_TOKEN_ID,
+            0,
+            &rust_biguint!(user_lp_tokens_balance),
+            |sc| {
+                sc.lock_tokens_endpoint(
+                    current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_ok();
+
+    DebugApi::dummy();
+    b_mock.check_nft_balance(
+        &user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(user_lp_tokens_balance),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(LPUSDC_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
+        }),
+    );
+
+    // pass blocks time to update safe price
+    b_mock.set_block_nonce(1_000_000);
+
+    // activate swaps through router
+    b_mock
+        .execute_esdt_transfer(
+            &user,
+            &router_wrapper,
+            LOCKED_TOKEN_ID,
+            1,
+            &rust_biguint!(user_lp_tokens_balance),
+            |sc| {
+                sc.set_swap_enabled_by_user(managed_address!(pair_wrapper.address_ref()));
+            },
+        )
+        .assert_ok();
+
+    // check pair state is active
+    b_mock
+        .execute_query(&pair_wrapper, |sc| {
+            assert_eq!(sc.state().get(), State::Active);
+        })
+        .assert_ok();
+
+    // check user received the locked tokens back
+    b_mock.check_nft_balance(
+        &user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(user_lp_tokens_balance),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(LPUSDC_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
+        }),
+    );
+}
+
+#[test]
+fn user_enable_pair_swaps_fail_test() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_zero);
+
+    let current_epoch = 5;
+    b_mock.set_block_epoch(current_epoch);
+
+    b_mock.set_esdt_balance(
+        &user,
+        CUSTOM_TOKEN_ID,
+        &rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
+    );
+    b_mock.set_esdt_balance(&user, USDC_TOKEN_ID, &rust_biguint!(USER_USDC_BALANCE));
+
+    let router_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        router::contract_obj,
+        ROUTER_WASM_PATH,
+    );
+    let pair_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(router_wrapper.address_ref()),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+
+    // setup router
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.init(OptionalValue::None);
+
+            sc.pair_map().insert(
+                PairTokens {
+                    first_token_id: managed_token_id!(CUSTOM_TOKEN_ID),
+                    second_token_id: managed_token_id!(USDC_TOKEN_ID),
+                },
+                managed_address!(pair_wrapper.address_ref()),
+            );
+
+            sc.add_common_tokens_for_user_pairs(MultiValueEncoded::from(ManagedVec::from(vec![
+                managed_token_id!(USDC_TOKEN_ID),
+            ])));
+
+            sc.config_enable_by_user_parameters(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(LOCKED_TOKEN_ID),
+                managed_biguint!(MIN_LOCKED_TOKEN_VALUE),
+                MIN_LOCKED_PERIOD_EPOCHS,
+            )
+        })
+        .assert_ok();
+
+    // setup pair
+    b_mock
+        .execute_tx(&owner, &pair_wrapper, &rust_zero, |sc| {
+            let first_token_id = managed_token_id!(CUSTOM_TOKEN_ID);
+            let second_token_id = managed_token_id!(USDC_TOKEN_ID);
+            let router_address = managed_address!(router_wrapper.address_ref());
+            let router_owner_address = managed_address!(&owner);
+
+            sc.init(
+                first_token_id,
+                second_token_id,
+                router_address,
+                router_owner_address,
+                0,
+                0,
+                managed_address!(&user),
+                MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+            );
+
+            assert_eq!(sc.state().get(), State::Inactive);
+
+            sc.lp_token_identifier()
+                .set(&managed_token_id!(LPUSDC_TOKEN_ID));
+        })
+        .assert_ok();
+
+    b_mock.set_esdt_local_roles(
+        pair_wrapper.address_ref(),
+        LPUSDC_TOKEN_ID,
+        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+    );
+
+    // add liquidity
+    let payments = vec![
+        TxTokenTransfer {
+            token_identifier: CUSTOM_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
+        },
+        TxTokenTransfer {
+            token_identifier: USDC_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(USER_USDC_BALANCE),
+        },
+    ];
+
+    let user_lp_tokens_balance = 999_000u64;
+    b_mock
+        .execute_esdt_multi_transfer(&user, &pair_wrapper, &payments, |sc| {
+            let (lp_tokens_received, _, _) = sc.add_initial_liquidity().into_tuple();
+            assert_eq!(
+                lp_tokens_received.token_identifier,
+                managed_token_id!(LPUSDC_TOKEN_ID)
+            );
+            assert_eq!(
+                lp_tokens_received.amount,
+                managed_biguint!(user_lp_tokens_balance)
+            );
+        })
+        .assert_ok();
+
+    let custom_locked_token = b"LTOK2-123456";
+    DebugApi::dummy();
+    b_mock.set_nft_balance(
+        &user,
         custom_locked_token,
         1,
         &rust_biguint!(user_lp_tokens_balance),
@@ -558,3 +1462,349 @@ fn user_enable_pair_swaps_fail_test() {
         }),
     );
 }
+
+#[test]
+fn permissionless_listing_test() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_zero);
+
+    let current_epoch = 5;
+    b_mock.set_block_epoch(current_epoch);
+
+    b_mock.set_esdt_balance(
+        &user,
+        CUSTOM_TOKEN_ID,
+        &rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
+    );
+    b_mock.set_esdt_balance(&user, USDC_TOKEN_ID, &rust_biguint!(USER_USDC_BALANCE));
+
+    let router_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        router::contract_obj,
+        ROUTER_WASM_PATH,
+    );
+    let pair_template_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(router_wrapper.address_ref()),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+    let simple_lock_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        simple_lock::contract_obj,
+        "simple-lock.wasm",
+    );
+
+    // setup simple-lock
+    b_mock
+        .execute_tx(&owner, &simple_lock_wrapper, &rust_zero, |sc| {
+            sc.locked_token()
+                .set_token_id(managed_token_id!(LOCKED_TOKEN_ID));
+        })
+        .assert_ok();
+
+    b_mock.set_esdt_local_roles(
+        simple_lock_wrapper.address_ref(),
+        LOCKED_TOKEN_ID,
+        &[
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftAddQuantity,
+            EsdtLocalRole::NftBurn,
+        ],
+    );
+
+    // setup router, pair creation stays disabled
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.init(OptionalValue::Some(managed_address!(
+                pair_template_wrapper.address_ref()
+            )));
+
+            sc.add_common_tokens_for_user_pairs(MultiValueEncoded::from(ManagedVec::from(vec![
+                managed_token_id!(USDC_TOKEN_ID),
+            ])));
+
+            sc.config_enable_by_user_parameters(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(LOCKED_TOKEN_ID),
+                managed_biguint!(MIN_LOCKED_TOKEN_VALUE),
+                MIN_LOCKED_PERIOD_EPOCHS,
+            );
+
+            sc.set_listing_parameters(managed_address!(simple_lock_wrapper.address_ref()), 5_000);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            sc.create_listing(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(CUSTOM_TOKEN_ID),
+            );
+        })
+        .assert_user_error("Common token not whitelisted");
+
+    let pair_wrapper =
+        b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), pair::contract_obj);
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            let pair_address = sc.create_listing(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+            );
+            assert_eq!(
+                sc.get_listing_status(pair_address),
+                ListingStatus::LpTokenNotIssued
+            );
+        })
+        .assert_ok();
+
+    // the LP token issue and the local roles are async calls to the system SC
+    b_mock
+        .execute_tx(&owner, &pair_wrapper, &rust_zero, |sc| {
+            sc.lp_token_identifier()
+                .set(&managed_token_id!(LPUSDC_TOKEN_ID));
+        })
+        .assert_ok();
+
+    b_mock.set_esdt_local_roles(
+        pair_wrapper.address_ref(),
+        LPUSDC_TOKEN_ID,
+        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+    );
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            assert_eq!(
+                sc.get_listing_status(managed_address!(pair_wrapper.address_ref())),
+                ListingStatus::LocalRolesNotSet
+            );
+        })
+        .assert_ok();
+
+    // done by the callback of setLocalRoles
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.listing_local_roles_set(&managed_address!(pair_wrapper.address_ref()))
+                .set(true);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            assert_eq!(
+                sc.get_listing_status(managed_address!(pair_wrapper.address_ref())),
+                ListingStatus::PendingInitialLiquidity
+            );
+        })
+        .assert_ok();
+
+    let payments = vec![
+        TxTokenTransfer {
+            token_identifier: CUSTOM_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(USER_CUSTOM_TOKEN_BALANCE),
+        },
+        TxTokenTransfer {
+            token_identifier: USDC_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(USER_USDC_BALANCE),
+        },
+    ];
+
+    // half of the 999_000 LP tokens is worth less than the min locked value
+    b_mock
+        .execute_esdt_multi_transfer(&user, &router_wrapper, &payments, |sc| {
+            sc.add_listing_liquidity(managed_address!(pair_wrapper.address_ref()));
+        })
+        .assert_user_error("Not enough value locked");
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.set_listing_parameters(managed_address!(simple_lock_wrapper.address_ref()), 6_000);
+        })
+        .assert_ok();
+
+    let locked_lp_amount = 599_400u64;
+    let unlocked_lp_amount = 399_600u64;
+    b_mock
+        .execute_esdt_multi_transfer(&user, &router_wrapper, &payments, |sc| {
+            let (locked_lp_tokens, lp_tokens) = sc
+                .add_listing_liquidity(managed_address!(pair_wrapper.address_ref()))
+                .into_tuple();
+            assert_eq!(locked_lp_tokens.amount, managed_biguint!(locked_lp_amount));
+            assert_eq!(lp_tokens.amount, managed_biguint!(unlocked_lp_amount));
+        })
+        .assert_ok();
+
+    DebugApi::dummy();
+    b_mock.check_nft_balance(
+        &user,
+        LOCKED_TOKEN_ID,
+        1,
+        &rust_biguint!(locked_lp_amount),
+        Some(&LockedTokenAttributes::<DebugApi> {
+            original_token_id: managed_token_id_wrapped!(LPUSDC_TOKEN_ID),
+            original_token_nonce: 0,
+            unlock_epoch: current_epoch + MIN_LOCKED_PERIOD_EPOCHS,
+        }),
+    );
+    b_mock.check_esdt_balance(&user, LPUSDC_TOKEN_ID, &rust_biguint!(unlocked_lp_amount));
+
+    b_mock
+        .execute_query(&pair_wrapper, |sc| {
+            assert_eq!(sc.state().get(), State::Active);
+            assert_eq!(sc.total_fee_percent().get(), 1_000);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            assert_eq!(
+                sc.get_listing_status(managed_address!(pair_wrapper.address_ref())),
+                ListingStatus::Active
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn permissionless_listing_expiry_test() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let first_user = b_mock.create_user_account(&rust_zero);
+    let second_user = b_mock.create_user_account(&rust_zero);
+
+    let creation_epoch = 5;
+    b_mock.set_block_epoch(creation_epoch);
+
+    let router_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        router::contract_obj,
+        ROUTER_WASM_PATH,
+    );
+    let pair_template_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(router_wrapper.address_ref()),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+    let simple_lock_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        simple_lock::contract_obj,
+        "simple-lock.wasm",
+    );
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.init(OptionalValue::Some(managed_address!(
+                pair_template_wrapper.address_ref()
+            )));
+
+            sc.add_common_tokens_for_user_pairs(MultiValueEncoded::from(ManagedVec::from(vec![
+                managed_token_id!(USDC_TOKEN_ID),
+            ])));
+
+            sc.config_enable_by_user_parameters(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(LOCKED_TOKEN_ID),
+                managed_biguint!(MIN_LOCKED_TOKEN_VALUE),
+                MIN_LOCKED_PERIOD_EPOCHS,
+            );
+
+            sc.set_listing_parameters(managed_address!(simple_lock_wrapper.address_ref()), 5_000);
+        })
+        .assert_ok();
+
+    let first_pair_wrapper =
+        b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), pair::contract_obj);
+    let first_pair_address = first_pair_wrapper.address_ref().clone();
+    b_mock
+        .execute_tx(&first_user, &router_wrapper, &rust_zero, |sc| {
+            sc.create_listing(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&second_user, &router_wrapper, &rust_zero, |sc| {
+            sc.create_listing(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+            );
+        })
+        .assert_user_error("Pair already exists");
+    b_mock
+        .execute_tx(&second_user, &router_wrapper, &rust_zero, |sc| {
+            sc.remove_expired_listing(managed_address!(&first_pair_address));
+        })
+        .assert_user_error("Listing not expired");
+
+    // without initial liquidity, the listing can be taken over once expired
+    b_mock.set_block_epoch(creation_epoch + LISTING_EXPIRY_EPOCHS);
+    let second_pair_wrapper =
+        b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), pair::contract_obj);
+    let second_pair_address = second_pair_wrapper.address_ref().clone();
+    b_mock
+        .execute_tx(&second_user, &router_wrapper, &rust_zero, |sc| {
+            let pair_address = sc.create_listing(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+            );
+            assert_eq!(pair_address, managed_address!(&second_pair_address));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            assert_eq!(
+                sc.get_listing_status(managed_address!(&first_pair_address)),
+                ListingStatus::NotListed
+            );
+            assert_eq!(
+                sc.listing_creator(&managed_address!(&second_pair_address))
+                    .get(),
+                managed_address!(&second_user)
+            );
+            assert_eq!(
+                sc.get_pair(
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_token_id!(CUSTOM_TOKEN_ID)
+                ),
+                managed_address!(&second_pair_address)
+            );
+        })
+        .assert_ok();
+
+    // or removed by anyone
+    b_mock.set_block_epoch(creation_epoch + 2 * LISTING_EXPIRY_EPOCHS);
+    b_mock
+        .execute_tx(&first_user, &router_wrapper, &rust_zero, |sc| {
+            sc.remove_expired_listing(managed_address!(&second_pair_address));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            assert_eq!(
+                sc.get_listing_status(managed_address!(&second_pair_address)),
+                ListingStatus::NotListed
+            );
+            assert!(sc
+                .get_pair(
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID)
+                )
+                .is_zero());
+        })
+        .assert_ok();
+}
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           29
+// Endpoints:                           63
 // Async Callback:                       1
-// Total number of exported functions:  32
+// Total number of exported functions:  66
 
 #![no_std]
 
@@ -23,10 +23,14 @@ multiversx_sc_wasm_adapter::endpoints! {
         pause => pause
         resume => resume
         createPair => create_pair_endpoint
+        createStableSwapPair => create_stable_swap_pair_endpoint
+        createPairWithFeeTier => create_pair_with_fee_tier_endpoint
         upgradePair => upgrade_pair_endpoint
         issueLpToken => issue_lp_token
         setLocalRoles => set_local_roles
         removePair => remove_pair
+        registerConcentratedPool => register_concentrated_pool
+        removeConcentratedPool => remove_concentrated_pool
         setFeeOn => set_fee_on
         setFeeOff => set_fee_off
         setPairCreationEnabled => set_pair_creation_enabled
@@ -35,20 +39,50 @@ multiversx_sc_wasm_adapter::endpoints! {
         getOwner => owner
         setTemporaryOwnerPeriod => set_temporary_owner_period
         setPairTemplateAddress => set_pair_template_address
+        getPairFeeTier => pair_fee_tier
+        getConcentratedPools => concentrated_pools
         getPairTemplateAddress => pair_template_address
         getTemporaryOwnerPeriod => temporary_owner_period
         getCommonTokensForUserPairs => common_tokens_for_user_pairs
         getAllPairsManagedAddresses => get_all_pairs_addresses
         getAllPairTokens => get_all_token_pairs
         getAllPairContractMetadata => get_all_pair_contract_metadata
-        getPair => get_pair
+        getPairsPage => get_pairs_page
+        getPairsByToken => get_pairs_by_token
+        predictPairAddress => predict_pair_address
+        getPair => get_pair_view
         clearPairTemporaryOwnerStorage => clear_pair_temporary_owner_storage
+        addFeeTier => add_fee_tier
+        removeFeeTier => remove_fee_tier
+        getFeeTiers => get_fee_tiers
+        getPairFeeTiers => get_pair_fee_tiers
         multiPairSwap => multi_pair_swap
         configEnableByUserParameters => config_enable_by_user_parameters
         addCommonTokensForUserPairs => add_common_tokens_for_user_pairs
         removeCommonTokensForUserPairs => remove_common_tokens_for_user_pairs
         setSwapEnabledByUser => set_swap_enabled_by_user
         getEnableSwapByUserConfig => try_get_config
+        setListingParameters => set_listing_parameters
+        createListing => create_listing
+        addListingLiquidity => add_listing_liquidity
+        removeExpiredListing => remove_expired_listing
+        getListingStatus => get_listing_status
+        getListingCreator => listing_creator
+        getListingSimpleLockAddress => listing_simple_lock_address
+        getListingLockedLpPercent => listing_locked_lp_percent
+        setMaxRouteHops => set_max_route_hops
+        addRouteBaseTokens => add_route_base_tokens
+        removeRouteBaseTokens => remove_route_base_tokens
+        getBestRoute => get_best_route
+        swapWithAutoRoute => swap_with_auto_route
+        getMaxRouteHops => max_route_hops
+        getRouteBaseTokens => route_base_tokens
+        splitRouteSwap => split_route_swap
+        swapExactInWithDeadline => swap_exact_in_with_deadline
+        swapExactOutWithDeadline => swap_exact_out_with_deadline
+        setMaxObservationStaleness => set_max_observation_staleness
+        getRouteSafePrice => get_route_safe_price
+        getMaxObservationStaleness => max_observation_staleness_rounds
     )
 }
 
@@ -0,0 +1,215 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use farm::exit_penalty::PenaltyCurvePoint;
+use multiversx_sc::storage::StorageKey;
+use pair::read_pair_storage;
+
+use crate::errors::*;
+
+pub const MAX_PERCENTAGE: u64 = 10_000;
+pub const MAX_PERFORMANCE_FEE_PERCENT: u64 = 3_000;
+
+pub static FARMING_TOKEN_ID_STORAGE_KEY: &[u8] = b"farming_token_id";
+pub static REWARD_TOKEN_ID_STORAGE_KEY: &[u8] = b"reward_token_id";
+pub static FARM_TOKEN_ID_STORAGE_KEY: &[u8] = b"farm_token_id";
+pub static PENALTY_PERCENT_STORAGE_KEY: &[u8] = b"penalty_percent";
+pub static MINIMUM_FARMING_EPOCHS_STORAGE_KEY: &[u8] = b"minimum_farming_epochs";
+pub static PENALTY_CURVE_STORAGE_KEY: &[u8] = b"penalty_curve";
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, Clone, Copy, PartialEq, Debug)]
+pub enum FarmType {
+    LpFarm,
+    StakingFarm,
+}
+
+#[multiversx_sc::module]
+pub trait ConfigModule: read_pair_storage::ReadPairStorageModule {
+    /// The share of the harvested rewards sent to the fee receiver, out of 10_000.
+    #[only_owner]
+    #[endpoint(setPerformanceFee)]
+    fn set_performance_fee_endpoint(
+        &self,
+        performance_fee_percent: u64,
+        fee_receiver: ManagedAddress,
+    ) {
+        self.set_performance_fee(performance_fee_percent, fee_receiver);
+    }
+
+    /// The maximum difference between the swap output at the spot price and at the
+    /// safe price of the pair, out of 10_000. The rewards are not compounded beyond it.
+    #[only_owner]
+    #[endpoint(setMaxSwapSlippage)]
+    fn set_max_swap_slippage(&self, max_swap_slippage_percent: u64) {
+        require!(
+            max_swap_slippage_percent <= MAX_PERCENTAGE,
+            ERROR_BAD_PERCENTS
+        );
+        self.max_swap_slippage_percent()
+            .set(max_swap_slippage_percent);
+    }
+
+    /// Smaller reward amounts are kept for the next harvest.
+    #[only_owner]
+    #[endpoint(setMinCompoundAmount)]
+    fn set_min_compound_amount(&self, min_compound_amount: BigUint) {
+        self.min_compound_amount().set(min_compound_amount);
+    }
+
+    fn set_performance_fee(&self, performance_fee_percent: u64, fee_receiver: ManagedAddress) {
+        require!(
+            performance_fee_percent <= MAX_PERFORMANCE_FEE_PERCENT,
+            ERROR_BAD_PERCENTS
+        );
+        self.performance_fee_percent().set(performance_fee_percent);
+        self.fee_receiver().set(fee_receiver);
+    }
+
+    /// The token IDs are read from the farm, and for LP farms, the farming token
+    /// has to be the LP token of the given pair and the reward token one of its tokens.
+    /// LP farms with an exit penalty are not accepted, as every deposit and compounding
+    /// starts the farming epochs of the whole vault position over.
+    fn set_farm(
+        &self,
+        farm_type: FarmType,
+        farm_address: ManagedAddress,
+        opt_pair_address: Option<ManagedAddress>,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&farm_address),
+            ERROR_NOT_A_SC
+        );
+
+        let farming_token_id = self
+            .get_farm_storage_token_id_mapper(farm_address.clone(), FARMING_TOKEN_ID_STORAGE_KEY)
+            .get();
+        let reward_token_id = self
+            .get_farm_storage_token_id_mapper(farm_address.clone(), REWARD_TOKEN_ID_STORAGE_KEY)
+            .get();
+        let farm_token_id = self
+            .get_farm_storage_token_id_mapper(farm_address.clone(), FARM_TOKEN_ID_STORAGE_KEY)
+            .get();
+
+        match farm_type {
+            FarmType::LpFarm => {
+                let pair_address = opt_pair_address.unwrap_or_else(|| sc_panic!(ERROR_BAD_PAIR));
+                require!(
+                    self.blockchain().is_smart_contract(&pair_address),
+                    ERROR_NOT_A_SC
+                );
+
+                let lp_token_id = self.get_lp_token_id_mapper(pair_address.clone()).get();
+                let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+                let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+                require!(
+                    lp_token_id == farming_token_id
+                        && (reward_token_id == first_token_id
+                            || reward_token_id == second_token_id),
+                    ERROR_BAD_PAIR
+                );
+                require!(
+                    !self.lp_farm_has_exit_penalty(farm_address.clone()),
+                    ERROR_FARM_EXIT_PENALTY
+                );
+
+                self.pair_address().set(pair_address);
+            }
+            FarmType::StakingFarm => {
+                require!(reward_token_id == farming_token_id, ERROR_BAD_STAKING_FARM);
+            }
+        }
+
+        self.farm_type().set(farm_type);
+        self.farm_address().set(farm_address);
+        self.farming_token_id().set(farming_token_id);
+        self.reward_token_id().set(reward_token_id);
+        self.farm_token_id().set(farm_token_id);
+    }
+
+    fn farm_has_exit_penalty(&self) -> bool {
+        self.farm_type().get() == FarmType::LpFarm
+            && self.lp_farm_has_exit_penalty(self.farm_address().get())
+    }
+
+    /// Same as the exit penalty of the farm: the penalty curve if any,
+    /// otherwise the penalty percent before the minimum farming epochs.
+    fn lp_farm_has_exit_penalty(&self, farm_address: ManagedAddress) -> bool {
+        let penalty_curve = SingleValueMapper::<
+            _,
+            ManagedVec<PenaltyCurvePoint>,
+            ManagedAddress,
+        >::new_from_address(
+            farm_address.clone(),
+            StorageKey::new(PENALTY_CURVE_STORAGE_KEY),
+        )
+        .get();
+        if !penalty_curve.is_empty() {
+            return penalty_curve.iter().any(|point| point.penalty_percent > 0);
+        }
+
+        let penalty_percent = SingleValueMapper::<_, u64, ManagedAddress>::new_from_address(
+            farm_address.clone(),
+            StorageKey::new(PENALTY_PERCENT_STORAGE_KEY),
+        )
+        .get();
+        let minimum_farming_epochs = SingleValueMapper::<_, u64, ManagedAddress>::new_from_address(
+            farm_address,
+            StorageKey::new(MINIMUM_FARMING_EPOCHS_STORAGE_KEY),
+        )
+        .get();
+
+        penalty_percent > 0 && minimum_farming_epochs > 0
+    }
+
+    fn get_farm_storage_token_id_mapper(
+        &self,
+        farm_address: ManagedAddress,
+        storage_key: &[u8],
+    ) -> SingleValueMapper<TokenIdentifier, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            farm_address,
+            StorageKey::new(storage_key),
+        )
+    }
+
+    #[view(getFarmType)]
+    #[storage_mapper("farm_type")]
+    fn farm_type(&self) -> SingleValueMapper<FarmType>;
+
+    #[view(getFarmAddress)]
+    #[storage_mapper("farm_address")]
+    fn farm_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getPairAddress)]
+    #[storage_mapper("pair_address")]
+    fn pair_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getFarmingTokenId)]
+    #[storage_mapper("farming_token_id")]
+    fn farming_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getRewardTokenId)]
+    #[storage_mapper("reward_token_id")]
+    fn reward_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getFarmTokenId)]
+    #[storage_mapper("farm_token_id")]
+    fn farm_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getPerformanceFeePercent)]
+    #[storage_mapper("performance_fee_percent")]
+    fn performance_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getFeeReceiver)]
+    #[storage_mapper("fee_receiver")]
+    fn fee_receiver(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getMaxSwapSlippagePercent)]
+    #[storage_mapper("max_swap_slippage_percent")]
+    fn max_swap_slippage_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getMinCompoundAmount)]
+    #[storage_mapper("min_compound_amount")]
+    fn min_compound_amount(&self) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,8 @@
+pub static ERROR_BAD_PERCENTS: &[u8] = b"Bad percents";
+pub static ERROR_NOT_A_SC: &[u8] = b"Not a smart contract";
+pub static ERROR_BAD_PAIR: &[u8] = b"Pair does not match the farm tokens";
+pub static ERROR_BAD_STAKING_FARM: &[u8] = b"Staking farm must reward its farming token";
+pub static ERROR_FARM_EXIT_PENALTY: &[u8] = b"Farm has an exit penalty";
+pub static ERROR_BAD_PAYMENT_TOKEN: &[u8] = b"Bad payment token";
+pub static ERROR_ZERO_AMOUNT: &[u8] = b"Zero amount";
+pub static ERROR_NO_POSITION: &[u8] = b"Vault has no farm position";
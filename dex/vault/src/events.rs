@@ -0,0 +1,118 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct DepositEvent<M: ManagedTypeApi> {
+    farming_tokens: EsdtTokenPayment<M>,
+    shares: EsdtTokenPayment<M>,
+    farm_position: EsdtTokenPayment<M>,
+    block: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct WithdrawEvent<M: ManagedTypeApi> {
+    shares: EsdtTokenPayment<M>,
+    output_payments: ManagedVec<M, EsdtTokenPayment<M>>,
+    block: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct HarvestEvent<M: ManagedTypeApi> {
+    rewards: EsdtTokenPayment<M>,
+    performance_fee: BigUint<M>,
+    farm_position: EsdtTokenPayment<M>,
+    block: u64,
+    timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_deposit_event(
+        &self,
+        caller: &ManagedAddress,
+        farming_tokens: EsdtTokenPayment,
+        shares: EsdtTokenPayment,
+        farm_position: EsdtTokenPayment,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.deposit_event(
+            caller,
+            epoch,
+            &DepositEvent {
+                farming_tokens,
+                shares,
+                farm_position,
+                block: self.blockchain().get_block_nonce(),
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_withdraw_event(
+        &self,
+        caller: &ManagedAddress,
+        shares: EsdtTokenPayment,
+        output_payments: ManagedVec<EsdtTokenPayment>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.withdraw_event(
+            caller,
+            epoch,
+            &WithdrawEvent {
+                shares,
+                output_payments,
+                block: self.blockchain().get_block_nonce(),
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_harvest_event(
+        &self,
+        rewards: EsdtTokenPayment,
+        performance_fee: BigUint,
+        farm_position: EsdtTokenPayment,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.harvest_event(
+            &self.blockchain().get_caller(),
+            epoch,
+            &HarvestEvent {
+                rewards,
+                performance_fee,
+                farm_position,
+                block: self.blockchain().get_block_nonce(),
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    #[event("deposit")]
+    fn deposit_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        deposit_event: &DepositEvent<Self::Api>,
+    );
+
+    #[event("withdraw")]
+    fn withdraw_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        withdraw_event: &WithdrawEvent<Self::Api>,
+    );
+
+    #[event("harvest")]
+    fn harvest_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        harvest_event: &HarvestEvent<Self::Api>,
+    );
+}
@@ -0,0 +1,267 @@
+multiversx_sc::imports!();
+
+use farm::{
+    base_functions::ClaimRewardsResultType, ClaimRewardsWithStreamsResultType, EnterFarmResultType,
+    ExitFarmWithPartialPosResultType, ExitFarmWithStreamsResultType, ProxyTrait as _,
+};
+use farm_staking::{
+    claim_stake_farm_rewards::ProxyTrait as _, stake_farm::ProxyTrait as _,
+    unstake_farm::ProxyTrait as _,
+};
+use pair::{
+    pair_actions::{
+        add_liq::ProxyTrait as _, common_result_types::AddLiquidityResultType,
+        swap::ProxyTrait as _, views::ProxyTrait as _,
+    },
+    read_pair_storage, safe_price_reader,
+};
+
+use crate::config::{self, FarmType, MAX_PERCENTAGE};
+
+pub struct FarmEnterResult<M: ManagedTypeApi> {
+    pub farm_tokens: EsdtTokenPayment<M>,
+    pub boosted_rewards: EsdtTokenPayment<M>,
+}
+
+pub struct FarmClaimResult<M: ManagedTypeApi> {
+    pub farm_tokens: EsdtTokenPayment<M>,
+    pub rewards: EsdtTokenPayment<M>,
+    pub other_rewards: ManagedVec<M, EsdtTokenPayment<M>>,
+}
+
+pub struct FarmExitResult<M: ManagedTypeApi> {
+    pub farming_tokens: EsdtTokenPayment<M>,
+    pub rewards: EsdtTokenPayment<M>,
+    pub other_rewards: ManagedVec<M, EsdtTokenPayment<M>>,
+}
+
+pub struct AddLiquidityResult<M: ManagedTypeApi> {
+    pub lp_tokens: EsdtTokenPayment<M>,
+    pub first_token_leftover: EsdtTokenPayment<M>,
+    pub second_token_leftover: EsdtTokenPayment<M>,
+}
+
+/// The calls to the LP farm (`farm`), the staking farm (`farm-staking`) and the pair.
+/// The vault enters the farms on its own behalf.
+#[multiversx_sc::module]
+pub trait FarmInteractionsModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
+{
+    fn farm_enter(
+        &self,
+        farming_tokens: EsdtTokenPayment,
+        opt_position: Option<EsdtTokenPayment>,
+    ) -> FarmEnterResult<Self::Api> {
+        let mut payments = ManagedVec::from_single_item(farming_tokens);
+        if let Some(position) = opt_position {
+            payments.push(position);
+        }
+
+        let farm_address = self.farm_address().get();
+        let enter_result: EnterFarmResultType<Self::Api> = match self.farm_type().get() {
+            FarmType::LpFarm => self
+                .lp_farm_proxy(farm_address)
+                .enter_farm_endpoint(OptionalValue::<ManagedAddress>::None)
+                .with_multi_token_transfer(payments)
+                .execute_on_dest_context(),
+            FarmType::StakingFarm => self
+                .staking_farm_proxy(farm_address)
+                .stake_farm_endpoint(OptionalValue::<ManagedAddress>::None)
+                .with_multi_token_transfer(payments)
+                .execute_on_dest_context(),
+        };
+        let (farm_tokens, boosted_rewards) = enter_result.into_tuple();
+
+        FarmEnterResult {
+            farm_tokens,
+            boosted_rewards,
+        }
+    }
+
+    fn farm_claim_rewards(&self, position: EsdtTokenPayment) -> FarmClaimResult<Self::Api> {
+        let farm_address = self.farm_address().get();
+        match self.farm_type().get() {
+            FarmType::LpFarm => {
+                let claim_result: ClaimRewardsWithStreamsResultType<Self::Api> = self
+                    .lp_farm_proxy(farm_address)
+                    .claim_rewards_endpoint(OptionalValue::<ManagedAddress>::None)
+                    .with_esdt_transfer(position)
+                    .execute_on_dest_context();
+                let (farm_tokens, rewards, stream_rewards) = claim_result.into_tuple();
+
+                FarmClaimResult {
+                    farm_tokens,
+                    rewards,
+                    other_rewards: stream_rewards.to_vec(),
+                }
+            }
+            FarmType::StakingFarm => {
+                let claim_result: ClaimRewardsResultType<Self::Api> = self
+                    .staking_farm_proxy(farm_address)
+                    .claim_rewards(OptionalValue::<ManagedAddress>::None)
+                    .with_esdt_transfer(position)
+                    .execute_on_dest_context();
+                let (farm_tokens, rewards) = claim_result.into_tuple();
+
+                FarmClaimResult {
+                    farm_tokens,
+                    rewards,
+                    other_rewards: ManagedVec::new(),
+                }
+            }
+        }
+    }
+
+    /// For staking farms, the farming tokens are the unbond tokens of the staking farm.
+    fn farm_exit(&self, farm_tokens: EsdtTokenPayment) -> FarmExitResult<Self::Api> {
+        let farm_address = self.farm_address().get();
+        match self.farm_type().get() {
+            FarmType::LpFarm => {
+                let exit_result: ExitFarmWithStreamsResultType<Self::Api> = self
+                    .lp_farm_proxy(farm_address)
+                    .exit_farm_endpoint(OptionalValue::<ManagedAddress>::None)
+                    .with_esdt_transfer(farm_tokens)
+                    .execute_on_dest_context();
+                let (farming_tokens, rewards, stream_rewards) = exit_result.into_tuple();
+
+                FarmExitResult {
+                    farming_tokens,
+                    rewards,
+                    other_rewards: stream_rewards.to_vec(),
+                }
+            }
+            FarmType::StakingFarm => {
+                let exit_result: ExitFarmWithPartialPosResultType<Self::Api> = self
+                    .staking_farm_proxy(farm_address)
+                    .unstake_farm(OptionalValue::<ManagedAddress>::None)
+                    .with_esdt_transfer(farm_tokens)
+                    .execute_on_dest_context();
+                let (farming_tokens, rewards) = exit_result.into_tuple();
+
+                FarmExitResult {
+                    farming_tokens,
+                    rewards,
+                    other_rewards: ManagedVec::new(),
+                }
+            }
+        }
+    }
+
+    /// Returns `None` if the spot price of the pair is too far from its safe price,
+    /// which would make the swap easy to sandwich.
+    fn get_swap_amount_out(
+        &self,
+        pair_address: ManagedAddress,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+    ) -> Option<BigUint> {
+        let spot_amount_out: BigUint = self
+            .vault_pair_proxy(pair_address.clone())
+            .get_amount_out_view(token_in.clone(), amount_in.clone())
+            .execute_on_dest_context();
+        if spot_amount_out == 0 {
+            return None;
+        }
+
+        // new pairs do not have any price observations yet
+        if !self.has_safe_price_observations(&pair_address) {
+            return Some(spot_amount_out);
+        }
+
+        let safe_price_payment = self.compute_safe_price_by_default_offset(
+            &pair_address,
+            EsdtTokenPayment::new(token_in, 0, amount_in),
+        );
+        let min_amount_out = safe_price_payment.amount
+            * (MAX_PERCENTAGE - self.max_swap_slippage_percent().get())
+            / MAX_PERCENTAGE;
+        if spot_amount_out < min_amount_out {
+            return None;
+        }
+
+        Some(spot_amount_out)
+    }
+
+    /// First token reserve, second token reserve and LP supply of the pair. Time-weighted
+    /// when the pair has price observations with the LP supply, the current ones otherwise.
+    fn get_pair_reserves_and_lp_supply(
+        &self,
+        pair_address: &ManagedAddress,
+    ) -> (BigUint, BigUint, BigUint) {
+        if self.has_safe_price_observations(pair_address) {
+            let weighted_amounts = self.compute_weighted_amounts_by_default_offset(pair_address);
+            if weighted_amounts.weighted_lp_supply > 0 {
+                return (
+                    weighted_amounts.weighted_first_token_reserve,
+                    weighted_amounts.weighted_second_token_reserve,
+                    weighted_amounts.weighted_lp_supply,
+                );
+            }
+        }
+
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+        (
+            self.get_pair_reserve_mapper(pair_address.clone(), &first_token_id)
+                .get(),
+            self.get_pair_reserve_mapper(pair_address.clone(), &second_token_id)
+                .get(),
+            self.get_lp_token_supply_mapper(pair_address.clone()).get(),
+        )
+    }
+
+    fn pair_swap(
+        &self,
+        pair_address: ManagedAddress,
+        payment: EsdtTokenPayment,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment {
+        self.vault_pair_proxy(pair_address)
+            .swap_tokens_fixed_input(token_out, amount_out_min)
+            .with_esdt_transfer(payment)
+            .execute_on_dest_context()
+    }
+
+    fn pair_add_liquidity(
+        &self,
+        pair_address: ManagedAddress,
+        first_payment: EsdtTokenPayment,
+        second_payment: EsdtTokenPayment,
+    ) -> AddLiquidityResult<Self::Api> {
+        let mut payments = ManagedVec::from_single_item(first_payment.clone());
+        payments.push(second_payment.clone());
+
+        let add_liq_result: AddLiquidityResultType<Self::Api> = self
+            .vault_pair_proxy(pair_address)
+            .add_liquidity(BigUint::from(1u32), BigUint::from(1u32))
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (lp_tokens, first_token_used, second_token_used) = add_liq_result.into_tuple();
+
+        AddLiquidityResult {
+            lp_tokens,
+            first_token_leftover: EsdtTokenPayment::new(
+                first_payment.token_identifier,
+                0,
+                first_payment.amount - first_token_used.amount,
+            ),
+            second_token_leftover: EsdtTokenPayment::new(
+                second_payment.token_identifier,
+                0,
+                second_payment.amount - second_token_used.amount,
+            ),
+        }
+    }
+
+    #[proxy]
+    fn lp_farm_proxy(&self, to: ManagedAddress) -> farm::Proxy<Self::Api>;
+
+    #[proxy]
+    fn staking_farm_proxy(&self, to: ManagedAddress) -> farm_staking::Proxy<Self::Api>;
+
+    #[proxy]
+    fn vault_pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+}
@@ -0,0 +1,47 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod farm_interactions;
+pub mod share_token;
+pub mod vault_actions;
+
+use config::FarmType;
+
+/// Holds a single aggregated position in an LP farm or in a staking farm, and issues
+/// fungible shares of it. The farm rewards are harvested and re-invested into the
+/// position, so the farming tokens of each share grow over time. For LP farms, half
+/// of the rewards is swapped through the pair and liquidity is added with both halves.
+#[multiversx_sc::contract]
+pub trait Vault:
+    config::ConfigModule
+    + events::EventsModule
+    + farm_interactions::FarmInteractionsModule
+    + share_token::ShareTokenModule
+    + vault_actions::VaultActionsModule
+    + pair::read_pair_storage::ReadPairStorageModule
+    + pair::safe_price_reader::SafePriceReaderModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// The pair address is required for LP farms, and has to be the pair of the farming token.
+    #[init]
+    fn init(
+        &self,
+        farm_type: FarmType,
+        farm_address: ManagedAddress,
+        performance_fee_percent: u64,
+        fee_receiver: ManagedAddress,
+        max_swap_slippage_percent: u64,
+        opt_pair_address: OptionalValue<ManagedAddress>,
+    ) {
+        self.set_farm(farm_type, farm_address, opt_pair_address.into_option());
+        self.set_performance_fee(performance_fee_percent, fee_receiver);
+        self.set_max_swap_slippage(max_swap_slippage_percent);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
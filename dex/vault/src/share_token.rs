@@ -0,0 +1,44 @@
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait ShareTokenModule:
+    multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueShareToken)]
+    fn issue_share_token(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+        num_decimals: usize,
+    ) {
+        let payment_amount = self.call_value().egld().clone_value();
+        self.share_token().issue_and_set_all_roles(
+            payment_amount,
+            token_display_name,
+            token_ticker,
+            num_decimals,
+            None,
+        );
+    }
+
+    fn mint_shares(&self, to: &ManagedAddress, amount: BigUint) -> EsdtTokenPayment {
+        self.share_token_supply()
+            .update(|supply| *supply += &amount);
+        self.share_token().mint_and_send(to, amount)
+    }
+
+    fn burn_shares(&self, amount: &BigUint) {
+        self.share_token().burn(amount);
+        self.share_token_supply().update(|supply| *supply -= amount);
+    }
+
+    #[view(getShareTokenId)]
+    #[storage_mapper("share_token")]
+    fn share_token(&self) -> FungibleTokenMapper;
+
+    #[view(getShareTokenSupply)]
+    #[storage_mapper("share_token_supply")]
+    fn share_token_supply(&self) -> SingleValueMapper<BigUint>;
+}
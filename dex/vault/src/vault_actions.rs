@@ -0,0 +1,397 @@
+multiversx_sc::imports!();
+
+use pair::{read_pair_storage, safe_price_reader};
+
+use crate::{
+    config::{self, FarmType, MAX_PERCENTAGE},
+    errors::*,
+    events, farm_interactions, share_token,
+};
+
+#[multiversx_sc::module]
+pub trait VaultActionsModule:
+    config::ConfigModule
+    + events::EventsModule
+    + farm_interactions::FarmInteractionsModule
+    + share_token::ShareTokenModule
+    + read_pair_storage::ReadPairStorageModule
+    + safe_price_reader::SafePriceReaderModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// Expects the farming token of the farm. The vault position is harvested first,
+    /// so that the new shares are priced at the compounded position, along with the
+    /// amounts still pending compounding. Deposits are closed while the LP farm has an
+    /// exit penalty.
+    #[payable("*")]
+    #[endpoint]
+    fn deposit(&self) -> EsdtTokenPayment {
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            payment.token_identifier == self.farming_token_id().get(),
+            ERROR_BAD_PAYMENT_TOKEN
+        );
+        require!(payment.amount > 0, ERROR_ZERO_AMOUNT);
+        require!(!self.farm_has_exit_penalty(), ERROR_FARM_EXIT_PENALTY);
+
+        self.harvest_position();
+
+        let farm_position_mapper = self.farm_position();
+        let opt_position = if farm_position_mapper.is_empty() {
+            None
+        } else {
+            Some(farm_position_mapper.get())
+        };
+        let share_supply = self.share_token_supply().get();
+        let shares_amount = match &opt_position {
+            Some(position) if share_supply > 0 => {
+                let vault_value = &position.amount + &self.get_pending_farming_token_amount();
+                &payment.amount * &share_supply / vault_value
+            }
+            _ => payment.amount.clone(),
+        };
+        require!(shares_amount > 0, ERROR_ZERO_AMOUNT);
+
+        let enter_result = self.farm_enter(payment.clone(), opt_position);
+        farm_position_mapper.set(&enter_result.farm_tokens);
+        self.add_pending_amount(&enter_result.boosted_rewards);
+
+        let caller = self.blockchain().get_caller();
+        let shares = self.mint_shares(&caller, shares_amount);
+        self.emit_deposit_event(&caller, payment, shares.clone(), enter_result.farm_tokens);
+
+        shares
+    }
+
+    /// Exits the farm with the part of the vault position of the given shares. The farm
+    /// rewards of the exited part and its part of the rewards not compounded yet, including
+    /// the reward streams of LP farms, are paid out as well. For staking farms, the unbond
+    /// tokens of the farm are returned.
+    #[payable("*")]
+    #[endpoint]
+    fn withdraw(&self) -> MultiValueEncoded<EsdtTokenPayment> {
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            payment.token_identifier == self.share_token().get_token_id(),
+            ERROR_BAD_PAYMENT_TOKEN
+        );
+        require!(payment.amount > 0, ERROR_ZERO_AMOUNT);
+
+        let farm_position_mapper = self.farm_position();
+        require!(!farm_position_mapper.is_empty(), ERROR_NO_POSITION);
+
+        let mut position = farm_position_mapper.get();
+        let share_supply = self.share_token_supply().get();
+        let farm_token_amount = &payment.amount * &position.amount / &share_supply;
+        require!(farm_token_amount > 0, ERROR_ZERO_AMOUNT);
+
+        let reward_token_id = self.reward_token_id().get();
+        let pending_rewards =
+            self.take_pending_amount_share(&reward_token_id, &payment.amount, &share_supply);
+        let opt_pending_other_tokens = match self.farm_type().get() {
+            FarmType::LpFarm => {
+                let other_token_id = self.get_other_pair_token_id(&reward_token_id);
+                let amount =
+                    self.take_pending_amount_share(&other_token_id, &payment.amount, &share_supply);
+                Some(EsdtTokenPayment::new(other_token_id, 0, amount))
+            }
+            FarmType::StakingFarm => None,
+        };
+        let mut other_rewards = ManagedVec::new();
+        for token_id in self.pending_other_reward_tokens().iter() {
+            let amount = self.take_pending_amount_share(&token_id, &payment.amount, &share_supply);
+            other_rewards.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+        self.burn_shares(&payment.amount);
+
+        let exit_result = self.farm_exit(EsdtTokenPayment::new(
+            position.token_identifier.clone(),
+            position.token_nonce,
+            farm_token_amount.clone(),
+        ));
+        position.amount -= farm_token_amount;
+        if position.amount == 0 {
+            farm_position_mapper.clear();
+        } else {
+            farm_position_mapper.set(&position);
+        }
+
+        let mut rewards = self.take_performance_fee(exit_result.rewards);
+        rewards.amount += pending_rewards;
+
+        let mut output_payments = ManagedVec::from_single_item(exit_result.farming_tokens);
+        output_payments.push(rewards);
+        if let Some(pending_other_tokens) = opt_pending_other_tokens {
+            output_payments.push(pending_other_tokens);
+        }
+        for exit_other_reward in exit_result.other_rewards.iter() {
+            add_payment(&mut other_rewards, exit_other_reward.clone());
+        }
+        output_payments.append_vec(other_rewards);
+
+        let caller = self.blockchain().get_caller();
+        self.send_non_zero_payments(&caller, &output_payments);
+        self.emit_withdraw_event(&caller, payment, output_payments.clone());
+
+        output_payments.into()
+    }
+
+    /// Claims the rewards of the vault position and compounds them. Anyone can harvest.
+    #[endpoint]
+    fn harvest(&self) {
+        require!(!self.farm_position().is_empty(), ERROR_NO_POSITION);
+
+        self.harvest_position();
+    }
+
+    /// The rewards are kept for a later harvest if they are below the minimum compound
+    /// amount, if the pair price is too far from its safe price, or if the LP farm has an
+    /// exit penalty. Only the reward token is compounded, the other farm rewards, like the
+    /// reward streams of LP farms, are kept until withdrawn.
+    fn harvest_position(&self) {
+        let farm_position_mapper = self.farm_position();
+        if farm_position_mapper.is_empty() {
+            return;
+        }
+
+        let claim_result = self.farm_claim_rewards(farm_position_mapper.get());
+        let claimed_amount = claim_result.rewards.amount.clone();
+        let rewards = self.take_performance_fee(claim_result.rewards);
+        self.add_pending_amount(&rewards);
+        self.add_pending_other_rewards(&claim_result.other_rewards);
+
+        let position = self.compound_pending_rewards(claim_result.farm_tokens);
+        farm_position_mapper.set(&position);
+
+        let performance_fee = claimed_amount - &rewards.amount;
+        self.emit_harvest_event(rewards, performance_fee, position);
+    }
+
+    fn compound_pending_rewards(&self, position: EsdtTokenPayment) -> EsdtTokenPayment {
+        let reward_token_id = self.reward_token_id().get();
+        let pending_rewards = self.pending_amount(&reward_token_id).get();
+        if pending_rewards == 0 || pending_rewards < self.min_compound_amount().get() {
+            return position;
+        }
+        if self.farm_has_exit_penalty() {
+            return position;
+        }
+
+        let opt_farming_tokens = match self.farm_type().get() {
+            FarmType::LpFarm => self.swap_and_add_liquidity(reward_token_id, pending_rewards),
+            FarmType::StakingFarm => {
+                self.pending_amount(&reward_token_id).clear();
+                Some(EsdtTokenPayment::new(reward_token_id, 0, pending_rewards))
+            }
+        };
+        let farming_tokens = match opt_farming_tokens {
+            Some(farming_tokens) => farming_tokens,
+            None => return position,
+        };
+
+        let enter_result = self.farm_enter(farming_tokens, Some(position));
+        self.add_pending_amount(&enter_result.boosted_rewards);
+
+        enter_result.farm_tokens
+    }
+
+    /// Swaps half of the rewards for the other pair token and adds liquidity with both.
+    /// The amounts not added as liquidity are kept for the next harvest.
+    fn swap_and_add_liquidity(
+        &self,
+        reward_token_id: TokenIdentifier,
+        pending_rewards: BigUint,
+    ) -> Option<EsdtTokenPayment> {
+        let pair_address = self.pair_address().get();
+        let other_token_id = self.get_other_pair_token_id(&reward_token_id);
+        let amount_to_swap = &pending_rewards / 2u32;
+        if amount_to_swap == 0 {
+            return None;
+        }
+
+        let amount_out_min = self.get_swap_amount_out(
+            pair_address.clone(),
+            reward_token_id.clone(),
+            amount_to_swap.clone(),
+        )?;
+        let swap_output = self.pair_swap(
+            pair_address.clone(),
+            EsdtTokenPayment::new(reward_token_id.clone(), 0, amount_to_swap.clone()),
+            other_token_id.clone(),
+            amount_out_min,
+        );
+
+        let reward_payment =
+            EsdtTokenPayment::new(reward_token_id.clone(), 0, pending_rewards - amount_to_swap);
+        let other_payment = EsdtTokenPayment::new(
+            other_token_id.clone(),
+            0,
+            swap_output.amount + self.pending_amount(&other_token_id).get(),
+        );
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let (first_payment, second_payment) = if reward_token_id == first_token_id {
+            (reward_payment, other_payment)
+        } else {
+            (other_payment, reward_payment)
+        };
+
+        let add_liq_result = self.pair_add_liquidity(pair_address, first_payment, second_payment);
+        for leftover in [
+            add_liq_result.first_token_leftover,
+            add_liq_result.second_token_leftover,
+        ] {
+            self.pending_amount(&leftover.token_identifier)
+                .set(leftover.amount);
+        }
+
+        Some(add_liq_result.lp_tokens)
+    }
+
+    /// The pending amounts belong to the current shares. For LP farms, a pair token amount
+    /// is worth `amount * lp_supply / (2 * reserve)` LP tokens.
+    fn get_pending_farming_token_amount(&self) -> BigUint {
+        let reward_token_id = self.reward_token_id().get();
+        let pending_rewards = self.pending_amount(&reward_token_id).get();
+        if self.farm_type().get() == FarmType::StakingFarm {
+            return pending_rewards;
+        }
+
+        let other_token_id = self.get_other_pair_token_id(&reward_token_id);
+        let pending_other_tokens = self.pending_amount(&other_token_id).get();
+        if pending_rewards == 0 && pending_other_tokens == 0 {
+            return BigUint::zero();
+        }
+
+        let pair_address = self.pair_address().get();
+        let (first_token_reserve, second_token_reserve, lp_supply) =
+            self.get_pair_reserves_and_lp_supply(&pair_address);
+        let first_token_id = self.get_first_token_id_mapper(pair_address).get();
+        let (reward_token_reserve, other_token_reserve) = if reward_token_id == first_token_id {
+            (first_token_reserve, second_token_reserve)
+        } else {
+            (second_token_reserve, first_token_reserve)
+        };
+        if reward_token_reserve == 0 || other_token_reserve == 0 {
+            return BigUint::zero();
+        }
+
+        (pending_rewards * &lp_supply / reward_token_reserve
+            + pending_other_tokens * &lp_supply / other_token_reserve)
+            / 2u32
+    }
+
+    fn take_performance_fee(&self, mut rewards: EsdtTokenPayment) -> EsdtTokenPayment {
+        let fee_amount = &rewards.amount * self.performance_fee_percent().get() / MAX_PERCENTAGE;
+        if fee_amount > 0 {
+            self.send().direct_esdt(
+                &self.fee_receiver().get(),
+                &rewards.token_identifier,
+                0,
+                &fee_amount,
+            );
+            rewards.amount -= fee_amount;
+        }
+
+        rewards
+    }
+
+    fn add_pending_amount(&self, payment: &EsdtTokenPayment) {
+        if payment.amount > 0 {
+            self.pending_amount(&payment.token_identifier)
+                .update(|pending_amount| *pending_amount += &payment.amount);
+        }
+    }
+
+    /// The other pair token is compounded along with the rewards,
+    /// the other tokens are only paid out on withdraw.
+    fn add_pending_other_rewards(&self, other_rewards: &ManagedVec<EsdtTokenPayment>) {
+        if other_rewards.is_empty() {
+            return;
+        }
+
+        let reward_token_id = self.reward_token_id().get();
+        let other_token_id = self.get_other_pair_token_id(&reward_token_id);
+        let mut pending_other_reward_tokens = self.pending_other_reward_tokens();
+        for other_reward in other_rewards.iter() {
+            self.add_pending_amount(&other_reward);
+            if other_reward.token_identifier != other_token_id {
+                let _ = pending_other_reward_tokens.insert(other_reward.token_identifier.clone());
+            }
+        }
+    }
+
+    fn take_pending_amount_share(
+        &self,
+        token_id: &TokenIdentifier,
+        shares_amount: &BigUint,
+        share_supply: &BigUint,
+    ) -> BigUint {
+        let pending_amount_mapper = self.pending_amount(token_id);
+        let amount = pending_amount_mapper.get() * shares_amount / share_supply;
+        pending_amount_mapper.update(|pending_amount| *pending_amount -= &amount);
+
+        amount
+    }
+
+    fn get_other_pair_token_id(&self, token_id: &TokenIdentifier) -> TokenIdentifier {
+        let pair_address = self.pair_address().get();
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        if token_id == &first_token_id {
+            self.get_second_token_id_mapper(pair_address).get()
+        } else {
+            first_token_id
+        }
+    }
+
+    fn send_non_zero_payments(&self, to: &ManagedAddress, payments: &ManagedVec<EsdtTokenPayment>) {
+        let mut non_zero_payments = ManagedVec::new();
+        for payment in payments.iter() {
+            if payment.amount > 0 {
+                non_zero_payments.push(payment.clone());
+            }
+        }
+
+        if !non_zero_payments.is_empty() {
+            self.send().direct_multi(to, &non_zero_payments);
+        }
+    }
+
+    #[view(getFarmingTokensForShares)]
+    fn get_farming_tokens_for_shares(&self, shares_amount: BigUint) -> BigUint {
+        let share_supply = self.share_token_supply().get();
+        if share_supply == 0 || self.farm_position().is_empty() {
+            return BigUint::zero();
+        }
+
+        shares_amount * self.farm_position().get().amount / share_supply
+    }
+
+    #[view(getFarmPosition)]
+    #[storage_mapper("farm_position")]
+    fn farm_position(&self) -> SingleValueMapper<EsdtTokenPayment>;
+
+    /// Rewards and pair tokens kept for the next harvest.
+    #[view(getPendingAmount)]
+    #[storage_mapper("pending_amount")]
+    fn pending_amount(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    /// The tokens of the farm rewards other than the reward token and the pair tokens.
+    #[view(getPendingOtherRewardTokens)]
+    #[storage_mapper("pending_other_reward_tokens")]
+    fn pending_other_reward_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+}
+
+fn add_payment<M: ManagedTypeApi>(
+    payments: &mut ManagedVec<M, EsdtTokenPayment<M>>,
+    payment: EsdtTokenPayment<M>,
+) {
+    for i in 0..payments.len() {
+        let mut existing_payment = payments.get(i).clone();
+        if existing_payment.token_identifier == payment.token_identifier {
+            existing_payment.amount += payment.amount;
+            let _ = payments.set(i, existing_payment);
+            return;
+        }
+    }
+
+    payments.push(payment);
+}
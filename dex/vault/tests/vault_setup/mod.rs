@@ -0,0 +1,347 @@
+use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+
+pub const VAULT_WASM_PATH: &str = "vault/output/vault.wasm";
+pub const FARM_WASM_PATH: &str = "farm/output/farm.wasm";
+pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const LP_TOKEN_ID: &[u8] = b"LPTOK-abcdef";
+pub const FARM_TOKEN_ID: &[u8] = b"FARM-abcdef";
+pub const SHARE_TOKEN_ID: &[u8] = b"VLPTOK-abcdef";
+
+pub const USER_TOTAL_TOKENS: u64 = 1_000_000_000;
+pub const INITIAL_LIQUIDITY: u64 = 100_000_000;
+pub const DIVISION_SAFETY_CONSTANT: u64 = 1_000_000_000_000;
+pub const PER_BLOCK_REWARD_AMOUNT: u64 = 5_000;
+pub const MIN_FARMING_EPOCHS: u64 = 2;
+pub const PENALTY_PERCENT: u64 = 100;
+pub const PARTNER_TOKEN_ID: &[u8] = b"PARTNER-abcdef";
+pub const PERFORMANCE_FEE_PERCENT: u64 = 1_000;
+pub const MAX_SWAP_SLIPPAGE_PERCENT: u64 = 500;
+
+use config::ConfigModule as FarmConfigModule;
+use farm::exit_penalty::ExitPenaltyModule;
+use farm::reward_streams::RewardStreamsModule;
+use farm::Farm;
+use farm_token::FarmTokenModule;
+use pair::config::ConfigModule as PairConfigModule;
+use pair::pair_actions::add_liq::AddLiquidityModule;
+use pair::Pair;
+use pausable::{PausableModule, State};
+use vault::config::{ConfigModule, FarmType};
+use vault::share_token::ShareTokenModule;
+use vault::vault_actions::VaultActionsModule;
+use vault::Vault;
+
+#[allow(dead_code)]
+pub struct VaultSetup<VaultObjBuilder, FarmObjBuilder, PairObjBuilder>
+where
+    VaultObjBuilder: 'static + Copy + Fn() -> vault::ContractObj<DebugApi>,
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub first_user_address: Address,
+    pub second_user_address: Address,
+    pub fee_receiver_address: Address,
+    pub vault_wrapper: ContractObjWrapper<vault::ContractObj<DebugApi>, VaultObjBuilder>,
+    pub farm_wrapper: ContractObjWrapper<farm::ContractObj<DebugApi>, FarmObjBuilder>,
+    pub pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+}
+
+#[allow(dead_code)]
+impl<VaultObjBuilder, FarmObjBuilder, PairObjBuilder>
+    VaultSetup<VaultObjBuilder, FarmObjBuilder, PairObjBuilder>
+where
+    VaultObjBuilder: 'static + Copy + Fn() -> vault::ContractObj<DebugApi>,
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    pub fn new(
+        vault_builder: VaultObjBuilder,
+        farm_builder: FarmObjBuilder,
+        pair_builder: PairObjBuilder,
+    ) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let first_user_addr = b_mock.create_user_account(&rust_zero);
+        let second_user_addr = b_mock.create_user_account(&rust_zero);
+        let fee_receiver_addr = b_mock.create_user_account(&rust_zero);
+        for token_id in [WEGLD_TOKEN_ID, MEX_TOKEN_ID] {
+            b_mock.set_esdt_balance(&owner_addr, token_id, &rust_biguint!(USER_TOTAL_TOKENS));
+        }
+        for user_addr in [&first_user_addr, &second_user_addr] {
+            b_mock.set_esdt_balance(user_addr, LP_TOKEN_ID, &rust_biguint!(USER_TOTAL_TOKENS));
+        }
+
+        let pair_wrapper =
+            b_mock.create_sc_account(&rust_zero, Some(&owner_addr), pair_builder, PAIR_WASM_PATH);
+        b_mock
+            .execute_tx(&owner_addr, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    300,
+                    50,
+                    ManagedAddress::<DebugApi>::zero(),
+                    MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+                );
+
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(LP_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(pair_wrapper.address_ref(), LP_TOKEN_ID, &lp_token_roles[..]);
+
+        let liquidity_payments = [WEGLD_TOKEN_ID, MEX_TOKEN_ID].map(|token_id| TxTokenTransfer {
+            token_identifier: token_id.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(INITIAL_LIQUIDITY),
+        });
+        b_mock
+            .execute_esdt_multi_transfer(&owner_addr, &pair_wrapper, &liquidity_payments, |sc| {
+                sc.add_liquidity(managed_biguint!(1), managed_biguint!(1));
+            })
+            .assert_ok();
+
+        let farm_wrapper =
+            b_mock.create_sc_account(&rust_zero, Some(&owner_addr), farm_builder, FARM_WASM_PATH);
+        b_mock
+            .execute_tx(&owner_addr, &farm_wrapper, &rust_zero, |sc| {
+                let mut admins = MultiValueEncoded::new();
+                admins.push(managed_address!(&owner_addr));
+                sc.init(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_token_id!(LP_TOKEN_ID),
+                    managed_biguint!(DIVISION_SAFETY_CONSTANT),
+                    ManagedAddress::<DebugApi>::zero(),
+                    ManagedAddress::<DebugApi>::zero(),
+                    admins,
+                );
+
+                sc.farm_token()
+                    .set_token_id(managed_token_id!(FARM_TOKEN_ID));
+                sc.per_block_reward_amount()
+                    .set(&managed_biguint!(PER_BLOCK_REWARD_AMOUNT));
+                // the vault does not accept LP farms with an exit penalty
+                sc.minimum_farming_epochs().set(MIN_FARMING_EPOCHS);
+                sc.penalty_percent().set(0);
+
+                sc.state().set(State::Active);
+                sc.produce_rewards_enabled().set(true);
+            })
+            .assert_ok();
+
+        let farm_token_roles = [
+            EsdtLocalRole::NftCreate,
+            EsdtLocalRole::NftAddQuantity,
+            EsdtLocalRole::NftBurn,
+        ];
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            FARM_TOKEN_ID,
+            &farm_token_roles[..],
+        );
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            LP_TOKEN_ID,
+            &[EsdtLocalRole::Burn][..],
+        );
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            MEX_TOKEN_ID,
+            &[EsdtLocalRole::Mint][..],
+        );
+
+        let vault_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            vault_builder,
+            VAULT_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_addr, &vault_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    FarmType::LpFarm,
+                    managed_address!(farm_wrapper.address_ref()),
+                    PERFORMANCE_FEE_PERCENT,
+                    managed_address!(&fee_receiver_addr),
+                    MAX_SWAP_SLIPPAGE_PERCENT,
+                    OptionalValue::Some(managed_address!(pair_wrapper.address_ref())),
+                );
+
+                sc.share_token()
+                    .set_token_id(managed_token_id!(SHARE_TOKEN_ID));
+            })
+            .assert_ok();
+
+        let share_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(
+            vault_wrapper.address_ref(),
+            SHARE_TOKEN_ID,
+            &share_token_roles[..],
+        );
+
+        VaultSetup {
+            b_mock,
+            owner_address: owner_addr,
+            first_user_address: first_user_addr,
+            second_user_address: second_user_addr,
+            fee_receiver_address: fee_receiver_addr,
+            vault_wrapper,
+            farm_wrapper,
+            pair_wrapper,
+        }
+    }
+
+    pub fn deposit(&mut self, user: &Address, token_id: &[u8], amount: u64) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            user,
+            &self.vault_wrapper,
+            token_id,
+            0,
+            &rust_biguint!(amount),
+            |sc| {
+                let _ = sc.deposit();
+            },
+        )
+    }
+
+    pub fn withdraw(&mut self, user: &Address, shares_amount: u64) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            user,
+            &self.vault_wrapper,
+            SHARE_TOKEN_ID,
+            0,
+            &rust_biguint!(shares_amount),
+            |sc| {
+                let _ = sc.withdraw();
+            },
+        )
+    }
+
+    pub fn harvest(&mut self, caller: &Address) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.vault_wrapper, &rust_biguint!(0), |sc| {
+                sc.harvest();
+            })
+    }
+
+    pub fn set_farm_penalty_percent(&mut self, penalty_percent: u64) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.farm_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.penalty_percent().set(penalty_percent);
+                },
+            )
+            .assert_ok();
+    }
+
+    /// Adds a stream of 1_000 partner tokens per block, funded by the owner.
+    pub fn add_farm_reward_stream(&mut self, start_block: u64, end_block: u64, reserve: u64) {
+        self.b_mock.set_esdt_balance(
+            &self.owner_address,
+            PARTNER_TOKEN_ID,
+            &rust_biguint!(reserve),
+        );
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.farm_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.add_reward_stream(
+                        managed_token_id!(PARTNER_TOKEN_ID),
+                        managed_biguint!(1_000),
+                        start_block,
+                        end_block,
+                    );
+                },
+            )
+            .assert_ok();
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.owner_address,
+                &self.farm_wrapper,
+                PARTNER_TOKEN_ID,
+                0,
+                &rust_biguint!(reserve),
+                |sc| {
+                    sc.fund_reward_stream();
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn set_min_compound_amount(&mut self, min_compound_amount: u64) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.vault_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.set_min_compound_amount(managed_biguint!(min_compound_amount));
+                },
+            )
+            .assert_ok();
+    }
+
+    /// Returns the farm token amount of the vault position
+    pub fn get_farm_position_amount(&mut self) -> u64 {
+        let mut amount = 0;
+        self.b_mock
+            .execute_query(&self.vault_wrapper, |sc| {
+                if !sc.farm_position().is_empty() {
+                    amount = sc.farm_position().get().amount.to_u64().unwrap();
+                }
+            })
+            .assert_ok();
+
+        amount
+    }
+
+    pub fn get_farming_tokens_for_shares(&mut self, shares_amount: u64) -> u64 {
+        let mut amount = 0;
+        self.b_mock
+            .execute_query(&self.vault_wrapper, |sc| {
+                amount = sc
+                    .get_farming_tokens_for_shares(managed_biguint!(shares_amount))
+                    .to_u64()
+                    .unwrap();
+            })
+            .assert_ok();
+
+        amount
+    }
+
+    pub fn get_pending_amount(&mut self, token_id: &[u8]) -> u64 {
+        let mut amount = 0;
+        self.b_mock
+            .execute_query(&self.vault_wrapper, |sc| {
+                amount = sc
+                    .pending_amount(&managed_token_id!(token_id))
+                    .get()
+                    .to_u64()
+                    .unwrap();
+            })
+            .assert_ok();
+
+        amount
+    }
+}
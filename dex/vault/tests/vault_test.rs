@@ -0,0 +1,214 @@
+#![allow(deprecated)]
+
+mod vault_setup;
+use multiversx_sc_scenario::rust_biguint;
+use vault_setup::*;
+
+#[test]
+fn test_vault_setup() {
+    let _ = VaultSetup::new(vault::contract_obj, farm::contract_obj, pair::contract_obj);
+}
+
+#[test]
+fn test_vault_deposit_harvest_withdraw() {
+    let mut setup = VaultSetup::new(vault::contract_obj, farm::contract_obj, pair::contract_obj);
+    let first_user = setup.first_user_address.clone();
+    let second_user = setup.second_user_address.clone();
+
+    setup
+        .deposit(&first_user, LP_TOKEN_ID, 1_000_000)
+        .assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, SHARE_TOKEN_ID, &rust_biguint!(1_000_000));
+    assert_eq!(setup.get_farm_position_amount(), 1_000_000);
+
+    // 50_000 rewards, 5_000 go to the fee receiver, the rest is compounded
+    setup.b_mock.set_block_nonce(10);
+    setup.harvest(&second_user).assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &setup.fee_receiver_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(5_000),
+    );
+    assert_eq!(setup.get_farm_position_amount(), 1_022_431);
+    assert_eq!(setup.get_farming_tokens_for_shares(1_000_000), 1_022_431);
+    assert_eq!(setup.get_pending_amount(MEX_TOKEN_ID), 63);
+    assert_eq!(setup.get_pending_amount(WEGLD_TOKEN_ID), 0);
+
+    // shares are priced at the compounded position
+    setup
+        .deposit(&second_user, LP_TOKEN_ID, 1_000_000)
+        .assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&second_user, SHARE_TOKEN_ID, &rust_biguint!(978_033));
+
+    setup.withdraw(&first_user, 1_000_000).assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        LP_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS + 22_460),
+    );
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, MEX_TOKEN_ID, &rust_biguint!(1));
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, SHARE_TOKEN_ID, &rust_biguint!(0));
+
+    setup.withdraw(&second_user, 978_033).assert_ok();
+    assert_eq!(setup.get_farm_position_amount(), 0);
+    setup
+        .b_mock
+        .check_esdt_balance(&second_user, LP_TOKEN_ID, &rust_biguint!(USER_TOTAL_TOKENS));
+}
+
+#[test]
+fn test_vault_min_compound_amount() {
+    let mut setup = VaultSetup::new(vault::contract_obj, farm::contract_obj, pair::contract_obj);
+    let first_user = setup.first_user_address.clone();
+    let second_user = setup.second_user_address.clone();
+
+    setup
+        .harvest(&second_user)
+        .assert_user_error("Vault has no farm position");
+    setup
+        .b_mock
+        .set_esdt_balance(&first_user, MEX_TOKEN_ID, &rust_biguint!(1_000));
+    setup
+        .deposit(&first_user, MEX_TOKEN_ID, 1_000)
+        .assert_user_error("Bad payment token");
+
+    setup.set_min_compound_amount(100_000);
+    setup
+        .deposit(&first_user, LP_TOKEN_ID, 1_000_000)
+        .assert_ok();
+
+    // rewards are kept until they reach the minimum compound amount
+    setup.b_mock.set_block_nonce(10);
+    setup.harvest(&second_user).assert_ok();
+    assert_eq!(setup.get_farm_position_amount(), 1_000_000);
+    assert_eq!(setup.get_pending_amount(MEX_TOKEN_ID), 45_000);
+
+    setup.b_mock.set_block_nonce(30);
+    setup.harvest(&second_user).assert_ok();
+    assert_eq!(setup.get_farm_position_amount(), 1_067_296);
+    assert_eq!(setup.get_pending_amount(MEX_TOKEN_ID), 158);
+    setup.b_mock.check_esdt_balance(
+        &setup.fee_receiver_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(15_000),
+    );
+}
+
+#[test]
+fn test_vault_deposit_with_pending_rewards() {
+    let mut setup = VaultSetup::new(vault::contract_obj, farm::contract_obj, pair::contract_obj);
+    let first_user = setup.first_user_address.clone();
+    let second_user = setup.second_user_address.clone();
+
+    setup.set_min_compound_amount(100_000);
+    setup
+        .deposit(&first_user, LP_TOKEN_ID, 1_000_000)
+        .assert_ok();
+
+    setup.b_mock.set_block_nonce(10);
+    setup.harvest(&second_user).assert_ok();
+    assert_eq!(setup.get_pending_amount(MEX_TOKEN_ID), 45_000);
+
+    // the pending 45_000 MEX are worth 22_500 LP tokens, at a 1:1 pair
+    setup
+        .deposit(&second_user, LP_TOKEN_ID, 1_000_000)
+        .assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&second_user, SHARE_TOKEN_ID, &rust_biguint!(977_995));
+}
+
+#[test]
+fn test_vault_withdraw_after_harvest() {
+    let mut setup = VaultSetup::new(vault::contract_obj, farm::contract_obj, pair::contract_obj);
+    let first_user = setup.first_user_address.clone();
+    let second_user = setup.second_user_address.clone();
+
+    setup
+        .deposit(&first_user, LP_TOKEN_ID, 1_000_000)
+        .assert_ok();
+
+    // compounding does not make the vault position pay any exit penalty
+    setup.b_mock.set_block_nonce(10);
+    setup.harvest(&second_user).assert_ok();
+    assert_eq!(setup.get_farm_position_amount(), 1_022_431);
+
+    setup.withdraw(&first_user, 1_000_000).assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        LP_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_TOKENS + 22_431),
+    );
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, MEX_TOKEN_ID, &rust_biguint!(63));
+}
+
+#[test]
+fn test_vault_farm_exit_penalty() {
+    let mut setup = VaultSetup::new(vault::contract_obj, farm::contract_obj, pair::contract_obj);
+    let first_user = setup.first_user_address.clone();
+    let second_user = setup.second_user_address.clone();
+
+    setup
+        .deposit(&first_user, LP_TOKEN_ID, 1_000_000)
+        .assert_ok();
+
+    // once the farm has an exit penalty, deposits are closed and rewards are not compounded
+    setup.set_farm_penalty_percent(PENALTY_PERCENT);
+    setup
+        .deposit(&second_user, LP_TOKEN_ID, 1_000_000)
+        .assert_user_error("Farm has an exit penalty");
+
+    setup.b_mock.set_block_nonce(10);
+    setup.harvest(&second_user).assert_ok();
+    assert_eq!(setup.get_farm_position_amount(), 1_000_000);
+    assert_eq!(setup.get_pending_amount(MEX_TOKEN_ID), 45_000);
+
+    setup.b_mock.set_block_epoch(MIN_FARMING_EPOCHS);
+    setup.withdraw(&first_user, 1_000_000).assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, LP_TOKEN_ID, &rust_biguint!(USER_TOTAL_TOKENS));
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, MEX_TOKEN_ID, &rust_biguint!(45_000));
+}
+
+#[test]
+fn test_vault_reward_streams() {
+    let mut setup = VaultSetup::new(vault::contract_obj, farm::contract_obj, pair::contract_obj);
+    let first_user = setup.first_user_address.clone();
+    let second_user = setup.second_user_address.clone();
+
+    setup.add_farm_reward_stream(0, 100, 100_000);
+    setup
+        .deposit(&first_user, LP_TOKEN_ID, 1_000_000)
+        .assert_ok();
+
+    // the stream rewards are kept for the share holders
+    setup.b_mock.set_block_nonce(10);
+    setup.harvest(&second_user).assert_ok();
+    assert_eq!(setup.get_pending_amount(PARTNER_TOKEN_ID), 10_000);
+    setup.b_mock.check_esdt_balance(
+        &setup.fee_receiver_address,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+
+    // half of the pending stream rewards, and those of the exited farm tokens, rounded down
+    setup.b_mock.set_block_nonce(20);
+    setup.withdraw(&first_user, 500_000).assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, PARTNER_TOKEN_ID, &rust_biguint!(9_999));
+    assert_eq!(setup.get_pending_amount(PARTNER_TOKEN_ID), 5_000);
+}
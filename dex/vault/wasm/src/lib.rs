@@ -0,0 +1,49 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Upgrade:                              1
+// Endpoints:                           23
+// Async Callback:                       1
+// Total number of exported functions:  26
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    vault
+    (
+        init => init
+        upgrade => upgrade
+        setPerformanceFee => set_performance_fee_endpoint
+        setMaxSwapSlippage => set_max_swap_slippage
+        setMinCompoundAmount => set_min_compound_amount
+        getFarmType => farm_type
+        getFarmAddress => farm_address
+        getPairAddress => pair_address
+        getFarmingTokenId => farming_token_id
+        getRewardTokenId => reward_token_id
+        getFarmTokenId => farm_token_id
+        getPerformanceFeePercent => performance_fee_percent
+        getFeeReceiver => fee_receiver
+        getMaxSwapSlippagePercent => max_swap_slippage_percent
+        getMinCompoundAmount => min_compound_amount
+        issueShareToken => issue_share_token
+        getShareTokenId => share_token
+        getShareTokenSupply => share_token_supply
+        deposit => deposit
+        withdraw => withdraw
+        harvest => harvest
+        getFarmingTokensForShares => get_farming_tokens_for_shares
+        getFarmPosition => farm_position
+        getPendingAmount => pending_amount
+        getPendingOtherRewardTokens => pending_other_reward_tokens
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback! { vault }
@@ -0,0 +1,97 @@
+multiversx_sc::imports!();
+
+use pausable::State;
+
+use crate::errors::*;
+use crate::weighted_math::to_fixed;
+
+pub const MAX_PERCENTAGE: u64 = 100_000;
+pub const MAX_FEE_PERCENTAGE: u64 = 5_000;
+pub const MIN_POOL_TOKENS: usize = 3;
+pub const MAX_POOL_TOKENS: usize = 8;
+pub const MIN_TOKEN_WEIGHT: u64 = 2_000;
+pub const MAX_IN_RATIO: u64 = 50_000;
+pub const MAX_OUT_RATIO: u64 = 33_333;
+
+#[multiversx_sc::module]
+pub trait ConfigModule:
+    token_send::TokenSendModule + permissions_module::PermissionsModule + pausable::PausableModule
+{
+    #[endpoint(setStateActiveNoSwaps)]
+    fn set_state_active_no_swaps(&self) {
+        self.require_caller_has_owner_permissions();
+        self.state().set(State::PartialActive);
+    }
+
+    #[endpoint(setFeePercent)]
+    fn set_fee_percent_endpoint(&self, total_fee_percent: u64) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.set_fee_percent(total_fee_percent);
+    }
+
+    fn set_fee_percent(&self, total_fee_percent: u64) {
+        require!(total_fee_percent <= MAX_FEE_PERCENTAGE, ERROR_BAD_PERCENTS);
+        self.total_fee_percent().set(total_fee_percent);
+    }
+
+    /// Whitelisted addresses, like farms, can call removeLiquidityAndBuyBackAndBurnToken
+    #[endpoint(whitelist)]
+    fn whitelist_endpoint(&self, address: ManagedAddress) {
+        self.require_caller_has_owner_permissions();
+        let _ = self.whitelist().insert(address);
+    }
+
+    #[endpoint(removeWhitelist)]
+    fn remove_whitelist(&self, address: ManagedAddress) {
+        self.require_caller_has_owner_permissions();
+        let _ = self.whitelist().swap_remove(&address);
+    }
+
+    /// Returns the weight of a pool token, failing for tokens outside the pool
+    fn get_token_weight(&self, token_id: &TokenIdentifier) -> u64 {
+        let weight_mapper = self.token_weight(token_id);
+        require!(!weight_mapper.is_empty(), ERROR_UNKNOWN_TOKEN);
+
+        weight_mapper.get()
+    }
+
+    fn get_fee_fixed(&self) -> BigUint {
+        to_fixed(self.total_fee_percent().get(), MAX_PERCENTAGE)
+    }
+
+    fn is_state_active(&self, state: State) -> bool {
+        state == State::Active || state == State::PartialActive
+    }
+
+    fn can_swap(&self, state: State) -> bool {
+        state == State::Active
+    }
+
+    #[view(getTotalFeePercent)]
+    #[storage_mapper("total_fee_percent")]
+    fn total_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getRouterManagedAddress)]
+    #[storage_mapper("router_address")]
+    fn router_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("pool_tokens")]
+    fn pool_tokens(&self) -> VecMapper<TokenIdentifier>;
+
+    /// Token weights are percentages, adding up to MAX_PERCENTAGE
+    #[view(getTokenWeight)]
+    #[storage_mapper("token_weight")]
+    fn token_weight(&self, token_id: &TokenIdentifier) -> SingleValueMapper<u64>;
+
+    #[view(getReserve)]
+    #[storage_mapper("reserve")]
+    fn pool_reserve(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalSupply)]
+    #[storage_mapper("lp_token_supply")]
+    fn lp_token_supply(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getWhitelistedManagedAddresses)]
+    #[storage_mapper("whitelist")]
+    fn whitelist(&self) -> UnorderedSetMapper<ManagedAddress>;
+}
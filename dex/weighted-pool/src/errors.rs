@@ -0,0 +1,25 @@
+pub static ERROR_ACTIVE: &[u8] = b"Active state";
+pub static ERROR_NOT_ACTIVE: &[u8] = b"Not active";
+pub static ERROR_NOT_AN_ESDT: &[u8] = b"Not a valid esdt id";
+pub static ERROR_SAME_TOKENS: &[u8] = b"Exchange tokens cannot be the same";
+pub static ERROR_BAD_PERCENTS: &[u8] = b"Bad percents";
+pub static ERROR_INVALID_ARGS: &[u8] = b"Invalid args";
+pub static ERROR_BAD_PAYMENT_TOKENS: &[u8] = b"Bad payment tokens";
+pub static ERROR_ZERO_AMOUNT: &[u8] = b"Zero amount";
+pub static ERROR_UNKNOWN_TOKEN: &[u8] = b"Unknown token";
+pub static ERROR_NOT_WHITELISTED: &[u8] = b"Not whitelisted";
+
+pub static ERROR_BAD_NUMBER_OF_TOKENS: &[u8] = b"Bad number of tokens";
+pub static ERROR_BAD_WEIGHTS: &[u8] = b"Bad token weights";
+
+pub static ERROR_LP_TOKEN_NOT_ISSUED: &[u8] = b"LP token not issued";
+pub static ERROR_INITIAL_LIQUIDITY_NOT_ADDED: &[u8] = b"Initial liquidity was not added";
+pub static ERROR_INITIAL_LIQUIDITY_ALREADY_ADDED: &[u8] = b"Initial liquidity was already added";
+pub static ERROR_INSUFFICIENT_LIQUIDITY: &[u8] = b"Insufficient liquidity minted";
+pub static ERROR_NOT_ENOUGH_LP: &[u8] = b"Not enough LP token supply";
+
+pub static ERROR_SWAP_NOT_ENABLED: &[u8] = b"Swap is not enabled";
+pub static ERROR_SLIPPAGE_EXCEEDED: &[u8] = b"Slippage exceeded";
+pub static ERROR_MAX_IN_RATIO: &[u8] = b"Amount in is too large compared to the reserve";
+pub static ERROR_MAX_OUT_RATIO: &[u8] = b"Amount out is too large compared to the reserve";
+pub static ERROR_BAD_POW_BASE: &[u8] = b"Power base out of bounds";
@@ -0,0 +1,149 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct SwapEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    token_id_in: TokenIdentifier<M>,
+    token_amount_in: BigUint<M>,
+    token_id_out: TokenIdentifier<M>,
+    token_amount_out: BigUint<M>,
+    fee_amount: BigUint<M>,
+    token_in_reserve: BigUint<M>,
+    token_out_reserve: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode)]
+pub struct LiquidityEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    tokens: ManagedVec<M, EsdtTokenPayment<M>>,
+    lp_token_id: TokenIdentifier<M>,
+    lp_token_amount: BigUint<M>,
+    lp_supply: BigUint<M>,
+    reserves: ManagedVec<M, EsdtTokenPayment<M>>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule:
+    crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    fn emit_swap_event(
+        &self,
+        payment_in: EsdtTokenPayment,
+        payment_out: EsdtTokenPayment,
+        fee_amount: BigUint,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.swap_event(
+            &payment_in.token_identifier.clone(),
+            &payment_out.token_identifier.clone(),
+            &caller,
+            epoch,
+            &SwapEvent {
+                caller: caller.clone(),
+                token_in_reserve: self.pool_reserve(&payment_in.token_identifier).get(),
+                token_out_reserve: self.pool_reserve(&payment_out.token_identifier).get(),
+                token_id_in: payment_in.token_identifier,
+                token_amount_in: payment_in.amount,
+                token_id_out: payment_out.token_identifier,
+                token_amount_out: payment_out.amount,
+                fee_amount,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_add_liquidity_event(
+        &self,
+        tokens: ManagedVec<EsdtTokenPayment>,
+        lp_payment: EsdtTokenPayment,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.add_liquidity_event(
+            &caller,
+            epoch,
+            &self.build_liquidity_event(caller.clone(), tokens, lp_payment, epoch),
+        )
+    }
+
+    fn emit_remove_liquidity_event(
+        &self,
+        tokens: ManagedVec<EsdtTokenPayment>,
+        lp_payment: EsdtTokenPayment,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.remove_liquidity_event(
+            &caller,
+            epoch,
+            &self.build_liquidity_event(caller.clone(), tokens, lp_payment, epoch),
+        )
+    }
+
+    fn build_liquidity_event(
+        &self,
+        caller: ManagedAddress,
+        tokens: ManagedVec<EsdtTokenPayment>,
+        lp_payment: EsdtTokenPayment,
+        epoch: u64,
+    ) -> LiquidityEvent<Self::Api> {
+        let mut reserves = ManagedVec::new();
+        for token_id in self.pool_tokens().iter() {
+            let reserve = self.pool_reserve(&token_id).get();
+            reserves.push(EsdtTokenPayment::new(token_id, 0, reserve));
+        }
+
+        LiquidityEvent {
+            caller,
+            tokens,
+            lp_token_id: lp_payment.token_identifier,
+            lp_token_amount: lp_payment.amount,
+            lp_supply: self.lp_token_supply().get(),
+            reserves,
+            block: self.blockchain().get_block_nonce(),
+            epoch,
+            timestamp: self.blockchain().get_block_timestamp(),
+        }
+    }
+
+    #[event("swap")]
+    fn swap_event(
+        &self,
+        #[indexed] token_in: &TokenIdentifier,
+        #[indexed] token_out: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        swap_event: &SwapEvent<Self::Api>,
+    );
+
+    #[event("add_liquidity")]
+    fn add_liquidity_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        add_liquidity_event: &LiquidityEvent<Self::Api>,
+    );
+
+    #[event("remove_liquidity")]
+    fn remove_liquidity_event(
+        &self,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        remove_liquidity_event: &LiquidityEvent<Self::Api>,
+    );
+}
@@ -0,0 +1,88 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod config;
+pub mod errors;
+mod events;
+pub mod liquidity;
+pub mod lp_token;
+pub mod swap;
+pub mod views;
+pub mod weighted_math;
+
+use crate::config::{MAX_PERCENTAGE, MAX_POOL_TOKENS, MIN_POOL_TOKENS, MIN_TOKEN_WEIGHT};
+use crate::errors::*;
+
+use pausable::State;
+use permissions_module::Permissions;
+
+/// Pool of 3 to 8 tokens, each with its own weight in the value of the pool.
+/// Liquidity can be added and removed either proportionally or with a single token,
+/// and the LP token can be farmed like the pair LP tokens.
+#[multiversx_sc::contract]
+pub trait WeightedPool:
+    config::ConfigModule
+    + events::EventsModule
+    + lp_token::LpTokenModule
+    + liquidity::LiquidityModule
+    + swap::SwapModule
+    + views::ViewsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// Token weights are percentages that have to add up to 100_000
+    #[init]
+    fn init(
+        &self,
+        router_address: ManagedAddress,
+        router_owner_address: ManagedAddress,
+        total_fee_percent: u64,
+        tokens_and_weights: MultiValueEncoded<MultiValue2<TokenIdentifier, u64>>,
+    ) {
+        self.set_fee_percent(total_fee_percent);
+        self.state().set(State::Inactive);
+        self.router_address().set(&router_address);
+
+        if self.pool_tokens().is_empty() {
+            self.set_pool_tokens(tokens_and_weights);
+        }
+
+        let all_permissions = Permissions::OWNER | Permissions::ADMIN | Permissions::PAUSE;
+        self.add_permissions(router_address, all_permissions.clone());
+        self.add_permissions(router_owner_address, all_permissions);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+
+    fn set_pool_tokens(
+        &self,
+        tokens_and_weights: MultiValueEncoded<MultiValue2<TokenIdentifier, u64>>,
+    ) {
+        let nr_tokens = tokens_and_weights.len();
+        require!(
+            (MIN_POOL_TOKENS..=MAX_POOL_TOKENS).contains(&nr_tokens),
+            ERROR_BAD_NUMBER_OF_TOKENS
+        );
+
+        let mut total_weight = 0;
+        for token_and_weight in tokens_and_weights {
+            let (token_id, weight) = token_and_weight.into_tuple();
+            require!(token_id.is_valid_esdt_identifier(), ERROR_NOT_AN_ESDT);
+            require!(self.token_weight(&token_id).is_empty(), ERROR_SAME_TOKENS);
+            require!(
+                (MIN_TOKEN_WEIGHT..=MAX_PERCENTAGE).contains(&weight),
+                ERROR_BAD_WEIGHTS
+            );
+
+            total_weight += weight;
+            self.token_weight(&token_id).set(weight);
+            let _ = self.pool_tokens().push(&token_id);
+        }
+
+        require!(total_weight == MAX_PERCENTAGE, ERROR_BAD_WEIGHTS);
+    }
+}
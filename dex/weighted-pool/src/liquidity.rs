@@ -0,0 +1,344 @@
+multiversx_sc::imports!();
+
+use pausable::State;
+
+use crate::config::{MAX_IN_RATIO, MAX_OUT_RATIO, MAX_PERCENTAGE};
+use crate::errors::*;
+use crate::weighted_math::{
+    compute_lp_out_given_single_in, compute_single_out_given_lp_in, to_fixed, ONE,
+};
+
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
+/// The first liquidity provider receives one LP token, whatever the amounts added
+pub const INITIAL_LP_SUPPLY: u64 = ONE;
+
+pub type AddLiquidityResultType<M> =
+    MultiValue2<EsdtTokenPayment<M>, ManagedVec<M, EsdtTokenPayment<M>>>;
+
+#[multiversx_sc::module]
+pub trait LiquidityModule:
+    crate::config::ConfigModule
+    + crate::lp_token::LpTokenModule
+    + crate::events::EventsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// Sets the initial prices of the pool. All the pool tokens have to be sent.
+    #[payable("*")]
+    #[endpoint(addInitialLiquidity)]
+    fn add_initial_liquidity(&self) -> EsdtTokenPayment {
+        self.require_caller_has_owner_permissions();
+        let _ = self.require_lp_token_issued();
+        require!(!self.is_state_active(self.state().get()), ERROR_ACTIVE);
+        require!(
+            self.lp_token_supply().get() == 0,
+            ERROR_INITIAL_LIQUIDITY_ALREADY_ADDED
+        );
+
+        let payments = self.get_payments_in_pool_order();
+        for payment in payments.iter() {
+            self.pool_reserve(&payment.token_identifier)
+                .set(&payment.amount);
+        }
+
+        let lp_supply = BigUint::from(INITIAL_LP_SUPPLY);
+        self.lp_token_supply().set(&lp_supply);
+
+        // the minimum liquidity stays in the pool, so the LP supply never reaches zero
+        let _ = self.lp_token().mint(BigUint::from(MINIMUM_LIQUIDITY));
+        let caller = self.blockchain().get_caller();
+        let lp_payment = self
+            .lp_token()
+            .mint_and_send(&caller, lp_supply - MINIMUM_LIQUIDITY);
+
+        self.state().set(State::PartialActive);
+        self.emit_add_liquidity_event(payments, lp_payment.clone());
+
+        lp_payment
+    }
+
+    /// Adds all the pool tokens, in the pool proportions.
+    /// Returns the LP tokens and the amounts that were not used.
+    #[payable("*")]
+    #[endpoint(addLiquidity)]
+    fn add_liquidity(&self, lp_amount_min: BigUint) -> AddLiquidityResultType<Self::Api> {
+        let _ = self.require_lp_token_issued();
+        require!(self.is_state_active(self.state().get()), ERROR_NOT_ACTIVE);
+
+        let lp_supply = self.lp_token_supply().get();
+        require!(lp_supply > 0, ERROR_INITIAL_LIQUIDITY_NOT_ADDED);
+
+        let payments = self.get_payments_in_pool_order();
+        let mut lp_amount: Option<BigUint> = None;
+        for payment in payments.iter() {
+            let reserve = self.pool_reserve(&payment.token_identifier).get();
+            let potential_lp_amount = &payment.amount * &lp_supply / &reserve;
+            lp_amount = match lp_amount {
+                Some(amount) => Some(core::cmp::min(amount, potential_lp_amount)),
+                None => Some(potential_lp_amount),
+            };
+        }
+
+        let lp_amount = lp_amount.unwrap_or_default();
+        require!(lp_amount > 0, ERROR_INSUFFICIENT_LIQUIDITY);
+        require!(lp_amount >= lp_amount_min, ERROR_SLIPPAGE_EXCEEDED);
+
+        let mut used_payments = ManagedVec::new();
+        let mut refunds = ManagedVec::new();
+        for payment in payments.iter() {
+            let reserve_mapper = self.pool_reserve(&payment.token_identifier);
+            let reserve = reserve_mapper.get();
+            let used_amount = (&lp_amount * &reserve + &lp_supply - 1u64) / &lp_supply;
+            reserve_mapper.set(reserve + &used_amount);
+
+            if payment.amount > used_amount {
+                refunds.push(EsdtTokenPayment::new(
+                    payment.token_identifier.clone(),
+                    0,
+                    &payment.amount - &used_amount,
+                ));
+            }
+            used_payments.push(EsdtTokenPayment::new(
+                payment.token_identifier.clone(),
+                0,
+                used_amount,
+            ));
+        }
+
+        self.lp_token_supply().set(lp_supply + &lp_amount);
+
+        let caller = self.blockchain().get_caller();
+        let lp_payment = self.lp_token().mint_and_send(&caller, lp_amount);
+        self.send_multiple_tokens_if_not_zero(&caller, &refunds);
+
+        self.emit_add_liquidity_event(used_payments, lp_payment.clone());
+
+        (lp_payment, refunds).into()
+    }
+
+    /// Adds a single pool token. The fee is charged on the part of the deposit
+    /// that is not already in the pool proportions.
+    #[payable("*")]
+    #[endpoint(addSingleTokenLiquidity)]
+    fn add_single_token_liquidity(&self, lp_amount_min: BigUint) -> EsdtTokenPayment {
+        let _ = self.require_lp_token_issued();
+        require!(self.is_state_active(self.state().get()), ERROR_NOT_ACTIVE);
+
+        let lp_supply = self.lp_token_supply().get();
+        require!(lp_supply > 0, ERROR_INITIAL_LIQUIDITY_NOT_ADDED);
+
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            payment.token_nonce == 0 && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+        let weight = self.get_token_weight(&payment.token_identifier);
+
+        let reserve_mapper = self.pool_reserve(&payment.token_identifier);
+        let reserve = reserve_mapper.get();
+        require!(
+            payment.amount <= &reserve * MAX_IN_RATIO / MAX_PERCENTAGE,
+            ERROR_MAX_IN_RATIO
+        );
+
+        let lp_amount = compute_lp_out_given_single_in(
+            &reserve,
+            &to_fixed(weight, MAX_PERCENTAGE),
+            &lp_supply,
+            &payment.amount,
+            &self.get_fee_fixed(),
+        );
+        require!(lp_amount > 0, ERROR_INSUFFICIENT_LIQUIDITY);
+        require!(lp_amount >= lp_amount_min, ERROR_SLIPPAGE_EXCEEDED);
+
+        reserve_mapper.set(reserve + &payment.amount);
+        self.lp_token_supply().set(lp_supply + &lp_amount);
+
+        let caller = self.blockchain().get_caller();
+        let lp_payment = self.lp_token().mint_and_send(&caller, lp_amount);
+        self.emit_add_liquidity_event(ManagedVec::from_single_item(payment), lp_payment.clone());
+
+        lp_payment
+    }
+
+    /// Removes liquidity in the pool proportions.
+    /// `amounts_min` holds the minimum amount of each token, in the pool order.
+    #[payable("*")]
+    #[endpoint(removeLiquidity)]
+    fn remove_liquidity(
+        &self,
+        amounts_min: MultiValueEncoded<BigUint>,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        let payment = self.require_lp_token_payment();
+        require!(self.is_state_active(self.state().get()), ERROR_NOT_ACTIVE);
+
+        let amounts_min = amounts_min.to_vec();
+        require!(
+            amounts_min.len() == self.pool_tokens().len(),
+            ERROR_INVALID_ARGS
+        );
+
+        let output_payments = self.pool_remove_liquidity(&payment.amount);
+        for (output_payment, amount_min) in output_payments.iter().zip(amounts_min.iter()) {
+            require!(output_payment.amount > 0, ERROR_ZERO_AMOUNT);
+            require!(
+                output_payment.amount >= *amount_min,
+                ERROR_SLIPPAGE_EXCEEDED
+            );
+        }
+
+        self.lp_token().burn(&payment.amount);
+
+        let caller = self.blockchain().get_caller();
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+        self.emit_remove_liquidity_event(output_payments.clone(), payment);
+
+        output_payments
+    }
+
+    #[payable("*")]
+    #[endpoint(removeLiquiditySingleToken)]
+    fn remove_liquidity_single_token(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment {
+        let payment = self.require_lp_token_payment();
+        require!(self.is_state_active(self.state().get()), ERROR_NOT_ACTIVE);
+
+        let output_payment = self.pool_remove_liquidity_single_token(token_out, &payment.amount);
+        require!(
+            output_payment.amount >= amount_out_min,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+
+        self.lp_token().burn(&payment.amount);
+
+        let caller = self.blockchain().get_caller();
+        self.send_payment_non_zero(&caller, &output_payment);
+        self.emit_remove_liquidity_event(
+            ManagedVec::from_single_item(output_payment.clone()),
+            payment,
+        );
+
+        output_payment
+    }
+
+    /// Used by farms to burn the penalty taken from their farming tokens.
+    /// If the token is not in the pool, only the LP tokens are burned,
+    /// and their share of the reserves goes to the remaining liquidity providers.
+    #[payable("*")]
+    #[endpoint(removeLiquidityAndBuyBackAndBurnToken)]
+    fn remove_liquidity_and_burn_token(&self, token_to_buyback_and_burn: TokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+        require!(self.whitelist().contains(&caller), ERROR_NOT_WHITELISTED);
+
+        let payment = self.require_lp_token_payment();
+        if self.token_weight(&token_to_buyback_and_burn).is_empty() {
+            self.lp_token_supply()
+                .update(|supply| *supply -= &payment.amount);
+        } else {
+            let output_payment =
+                self.pool_remove_liquidity_single_token(token_to_buyback_and_burn, &payment.amount);
+            if output_payment.amount > 0 {
+                self.send().esdt_local_burn(
+                    &output_payment.token_identifier,
+                    0,
+                    &output_payment.amount,
+                );
+            }
+        }
+
+        self.lp_token().burn(&payment.amount);
+    }
+
+    fn pool_remove_liquidity(&self, lp_amount: &BigUint) -> ManagedVec<EsdtTokenPayment> {
+        let lp_supply = self.lp_token_supply().get();
+        require!(&lp_supply > lp_amount, ERROR_NOT_ENOUGH_LP);
+
+        let mut output_payments = ManagedVec::new();
+        for token_id in self.pool_tokens().iter() {
+            let reserve_mapper = self.pool_reserve(&token_id);
+            let reserve = reserve_mapper.get();
+            let amount = lp_amount * &reserve / &lp_supply;
+            reserve_mapper.set(reserve - &amount);
+
+            output_payments.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+
+        self.lp_token_supply().set(lp_supply - lp_amount);
+
+        output_payments
+    }
+
+    fn pool_remove_liquidity_single_token(
+        &self,
+        token_out: TokenIdentifier,
+        lp_amount: &BigUint,
+    ) -> EsdtTokenPayment {
+        let weight = self.get_token_weight(&token_out);
+        let lp_supply = self.lp_token_supply().get();
+        require!(&lp_supply > lp_amount, ERROR_NOT_ENOUGH_LP);
+
+        let reserve_mapper = self.pool_reserve(&token_out);
+        let reserve = reserve_mapper.get();
+        let amount_out = compute_single_out_given_lp_in(
+            &reserve,
+            &to_fixed(weight, MAX_PERCENTAGE),
+            &lp_supply,
+            lp_amount,
+            &self.get_fee_fixed(),
+        );
+        require!(
+            amount_out <= &reserve * MAX_OUT_RATIO / MAX_PERCENTAGE,
+            ERROR_MAX_OUT_RATIO
+        );
+
+        reserve_mapper.set(reserve - &amount_out);
+        self.lp_token_supply().set(lp_supply - lp_amount);
+
+        EsdtTokenPayment::new(token_out, 0, amount_out)
+    }
+
+    fn require_lp_token_payment(&self) -> EsdtTokenPayment {
+        let lp_token_id = self.require_lp_token_issued();
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            payment.token_identifier == lp_token_id && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+
+        payment
+    }
+
+    /// Returns the payments sorted in the pool order. Each pool token has to be paid exactly once.
+    fn get_payments_in_pool_order(&self) -> ManagedVec<EsdtTokenPayment> {
+        let payments = self.call_value().all_esdt_transfers().clone();
+        let pool_tokens = self.pool_tokens();
+        require!(
+            payments.len() == pool_tokens.len(),
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+
+        let mut sorted_payments = ManagedVec::new();
+        for token_id in pool_tokens.iter() {
+            let opt_payment = payments
+                .iter()
+                .find(|payment| payment.token_identifier == token_id);
+            match opt_payment {
+                Some(payment) => {
+                    require!(
+                        payment.token_nonce == 0 && payment.amount > 0,
+                        ERROR_BAD_PAYMENT_TOKENS
+                    );
+                    sorted_payments.push(payment.clone());
+                }
+                None => sc_panic!(ERROR_BAD_PAYMENT_TOKENS),
+            }
+        }
+
+        sorted_payments
+    }
+}
@@ -0,0 +1,38 @@
+multiversx_sc::imports!();
+
+use crate::errors::ERROR_LP_TOKEN_NOT_ISSUED;
+
+pub const LP_TOKEN_DECIMALS: usize = 18;
+
+#[multiversx_sc::module]
+pub trait LpTokenModule:
+    permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// Issues the fungible LP token, with mint and burn roles for the pool.
+    /// Like the pair LP tokens, it can be used as the farming token of a farm.
+    #[payable("EGLD")]
+    #[endpoint(issueLpToken)]
+    fn issue_lp_token(&self, token_display_name: ManagedBuffer, token_ticker: ManagedBuffer) {
+        self.require_caller_has_owner_permissions();
+
+        let payment_amount = self.call_value().egld().clone_value();
+        self.lp_token().issue_and_set_all_roles(
+            payment_amount,
+            token_display_name,
+            token_ticker,
+            LP_TOKEN_DECIMALS,
+            None,
+        );
+    }
+
+    fn require_lp_token_issued(&self) -> TokenIdentifier {
+        require!(!self.lp_token().is_empty(), ERROR_LP_TOKEN_NOT_ISSUED);
+
+        self.lp_token().get_token_id()
+    }
+
+    #[view(getLpTokenIdentifier)]
+    #[storage_mapper("lpTokenIdentifier")]
+    fn lp_token(&self) -> FungibleTokenMapper;
+}
@@ -0,0 +1,171 @@
+multiversx_sc::imports!();
+
+use crate::config::{MAX_IN_RATIO, MAX_OUT_RATIO, MAX_PERCENTAGE};
+use crate::errors::*;
+use crate::weighted_math::{compute_amount_in, compute_amount_out, to_fixed};
+
+pub type SwapTokensFixedOutputResultType<M> = MultiValue2<EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
+
+#[multiversx_sc::module]
+pub trait SwapModule:
+    crate::config::ConfigModule
+    + crate::events::EventsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInput)]
+    fn swap_tokens_fixed_input(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment {
+        require!(amount_out_min > 0, ERROR_INVALID_ARGS);
+        require!(self.can_swap(self.state().get()), ERROR_SWAP_NOT_ENABLED);
+
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            payment.token_nonce == 0 && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+        require!(payment.token_identifier != token_out, ERROR_SAME_TOKENS);
+
+        let amount_out =
+            self.get_amount_out(&payment.token_identifier, &token_out, &payment.amount);
+        require!(amount_out >= amount_out_min, ERROR_SLIPPAGE_EXCEEDED);
+
+        self.pool_reserve(&payment.token_identifier)
+            .update(|reserve| *reserve += &payment.amount);
+        self.pool_reserve(&token_out)
+            .update(|reserve| *reserve -= &amount_out);
+
+        let caller = self.blockchain().get_caller();
+        let output_payment = EsdtTokenPayment::new(token_out, 0, amount_out);
+        self.send_payment_non_zero(&caller, &output_payment);
+
+        let fee_amount = self.get_fee_amount(&payment.amount);
+        self.emit_swap_event(payment, output_payment.clone(), fee_amount);
+
+        output_payment
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedOutput)]
+    fn swap_tokens_fixed_output(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out: BigUint,
+    ) -> SwapTokensFixedOutputResultType<Self::Api> {
+        require!(amount_out > 0, ERROR_INVALID_ARGS);
+        require!(self.can_swap(self.state().get()), ERROR_SWAP_NOT_ENABLED);
+
+        let payment = self.call_value().single_esdt().clone();
+        require!(
+            payment.token_nonce == 0 && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+        require!(payment.token_identifier != token_out, ERROR_SAME_TOKENS);
+
+        let amount_in = self.get_amount_in(&payment.token_identifier, &token_out, &amount_out);
+        require!(amount_in <= payment.amount, ERROR_SLIPPAGE_EXCEEDED);
+
+        self.pool_reserve(&payment.token_identifier)
+            .update(|reserve| *reserve += &amount_in);
+        self.pool_reserve(&token_out)
+            .update(|reserve| *reserve -= &amount_out);
+
+        let caller = self.blockchain().get_caller();
+        let output_payment = EsdtTokenPayment::new(token_out, 0, amount_out);
+        let residuum = EsdtTokenPayment::new(
+            payment.token_identifier.clone(),
+            0,
+            &payment.amount - &amount_in,
+        );
+        self.send_payment_non_zero(&caller, &output_payment);
+        self.send_payment_non_zero(&caller, &residuum);
+
+        let fee_amount = self.get_fee_amount(&amount_in);
+        self.emit_swap_event(
+            EsdtTokenPayment::new(payment.token_identifier, 0, amount_in),
+            output_payment.clone(),
+            fee_amount,
+        );
+
+        (output_payment, residuum).into()
+    }
+
+    fn get_amount_out(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_in: &BigUint,
+    ) -> BigUint {
+        let weight_in = self.get_token_weight(token_in);
+        let weight_out = self.get_token_weight(token_out);
+        let reserve_in = self.pool_reserve(token_in).get();
+        let reserve_out = self.pool_reserve(token_out).get();
+        require!(
+            reserve_in > 0 && reserve_out > 0,
+            ERROR_INITIAL_LIQUIDITY_NOT_ADDED
+        );
+        require!(
+            amount_in <= &(&reserve_in * MAX_IN_RATIO / MAX_PERCENTAGE),
+            ERROR_MAX_IN_RATIO
+        );
+
+        let amount_out = compute_amount_out(
+            &reserve_in,
+            &to_fixed(weight_in, MAX_PERCENTAGE),
+            &reserve_out,
+            &to_fixed(weight_out, MAX_PERCENTAGE),
+            amount_in,
+            &self.get_fee_fixed(),
+        );
+        require!(
+            amount_out <= &reserve_out * MAX_OUT_RATIO / MAX_PERCENTAGE,
+            ERROR_MAX_OUT_RATIO
+        );
+
+        amount_out
+    }
+
+    fn get_amount_in(
+        &self,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+        amount_out: &BigUint,
+    ) -> BigUint {
+        let weight_in = self.get_token_weight(token_in);
+        let weight_out = self.get_token_weight(token_out);
+        let reserve_in = self.pool_reserve(token_in).get();
+        let reserve_out = self.pool_reserve(token_out).get();
+        require!(
+            reserve_in > 0 && reserve_out > 0,
+            ERROR_INITIAL_LIQUIDITY_NOT_ADDED
+        );
+        require!(
+            amount_out <= &(&reserve_out * MAX_OUT_RATIO / MAX_PERCENTAGE),
+            ERROR_MAX_OUT_RATIO
+        );
+
+        let amount_in = compute_amount_in(
+            &reserve_in,
+            &to_fixed(weight_in, MAX_PERCENTAGE),
+            &reserve_out,
+            &to_fixed(weight_out, MAX_PERCENTAGE),
+            amount_out,
+            &self.get_fee_fixed(),
+        );
+        require!(
+            amount_in <= &reserve_in * MAX_IN_RATIO / MAX_PERCENTAGE,
+            ERROR_MAX_IN_RATIO
+        );
+
+        amount_in
+    }
+
+    fn get_fee_amount(&self, amount_in: &BigUint) -> BigUint {
+        amount_in * self.total_fee_percent().get() / MAX_PERCENTAGE
+    }
+}
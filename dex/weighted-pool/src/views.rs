@@ -0,0 +1,77 @@
+multiversx_sc::imports!();
+
+use crate::config::MAX_PERCENTAGE;
+use crate::weighted_math::{compute_spot_price, to_fixed};
+
+#[multiversx_sc::module]
+pub trait ViewsModule:
+    crate::config::ConfigModule
+    + crate::swap::SwapModule
+    + crate::events::EventsModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    #[view(getAmountOut)]
+    fn get_amount_out_view(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+        amount_in: BigUint,
+    ) -> BigUint {
+        self.get_amount_out(&token_in, &token_out, &amount_in)
+    }
+
+    #[view(getAmountIn)]
+    fn get_amount_in_view(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+        amount_out: BigUint,
+    ) -> BigUint {
+        self.get_amount_in(&token_in, &token_out, &amount_out)
+    }
+
+    /// Price of `token_out` in terms of `token_in`, without fees, with 18 decimals
+    #[view(getSpotPrice)]
+    fn get_spot_price(&self, token_in: TokenIdentifier, token_out: TokenIdentifier) -> BigUint {
+        let weight_in = self.get_token_weight(&token_in);
+        let weight_out = self.get_token_weight(&token_out);
+
+        compute_spot_price(
+            &self.pool_reserve(&token_in).get(),
+            &to_fixed(weight_in, MAX_PERCENTAGE),
+            &self.pool_reserve(&token_out).get(),
+            &to_fixed(weight_out, MAX_PERCENTAGE),
+        )
+    }
+
+    #[view(getTokensForGivenPosition)]
+    fn get_tokens_for_given_position(&self, liquidity: BigUint) -> ManagedVec<EsdtTokenPayment> {
+        let lp_supply = self.lp_token_supply().get();
+        let mut payments = ManagedVec::new();
+        for token_id in self.pool_tokens().iter() {
+            let amount = if lp_supply > 0 {
+                &liquidity * &self.pool_reserve(&token_id).get() / &lp_supply
+            } else {
+                BigUint::zero()
+            };
+            payments.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+
+        payments
+    }
+
+    /// Returns the pool tokens, with their weights and reserves
+    #[view(getPoolTokens)]
+    fn get_pool_tokens(&self) -> MultiValueEncoded<MultiValue3<TokenIdentifier, u64, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for token_id in self.pool_tokens().iter() {
+            let weight = self.token_weight(&token_id).get();
+            let reserve = self.pool_reserve(&token_id).get();
+            result.push((token_id, weight, reserve).into());
+        }
+
+        result
+    }
+}
@@ -0,0 +1,198 @@
+multiversx_sc::imports!();
+
+use crate::errors::ERROR_BAD_POW_BASE;
+
+/// Fixed point numbers used by the weighted math have 18 decimals
+pub const ONE: u64 = 1_000_000_000_000_000_000;
+const POW_PRECISION: u64 = 100_000_000;
+const MAX_POW_APPROX_ITERATIONS: u64 = 100;
+
+pub fn one<M: ManagedTypeApi>() -> BigUint<M> {
+    BigUint::from(ONE)
+}
+
+pub fn mul_fixed<M: ManagedTypeApi>(a: &BigUint<M>, b: &BigUint<M>) -> BigUint<M> {
+    (a * b + ONE / 2) / ONE
+}
+
+pub fn div_fixed<M: ManagedTypeApi>(a: &BigUint<M>, b: &BigUint<M>) -> BigUint<M> {
+    (a * ONE + b / 2u64) / b
+}
+
+/// Converts `numerator / denominator` to a fixed point number
+pub fn to_fixed<M: ManagedTypeApi>(numerator: u64, denominator: u64) -> BigUint<M> {
+    BigUint::from(numerator) * ONE / denominator
+}
+
+/// Raises a fixed point number to an integer power
+fn pow_int<M: ManagedTypeApi>(base: &BigUint<M>, mut exponent: u64) -> BigUint<M> {
+    let mut result = one();
+    let mut base = base.clone();
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result = mul_fixed(&result, &base);
+        }
+        base = mul_fixed(&base, &base);
+        exponent /= 2;
+    }
+
+    result
+}
+
+/// Raises a fixed point number in (0, 2) to a fixed point power.
+/// The fractional part of the exponent is approximated with the binomial series.
+pub fn pow_fixed<M: ManagedTypeApi>(base: &BigUint<M>, exponent: &BigUint<M>) -> BigUint<M> {
+    if base == &0u64 || base >= &(one::<M>() * 2u64) {
+        M::error_api_impl().signal_error(ERROR_BAD_POW_BASE);
+    }
+
+    let whole = exponent / ONE;
+    let remain = exponent - &(&whole * ONE);
+    let whole_pow = pow_int(base, whole.to_u64().unwrap_or_default());
+    if remain == 0u64 {
+        return whole_pow;
+    }
+
+    let partial_pow = pow_approx(base, &remain);
+    mul_fixed(&whole_pow, &partial_pow)
+}
+
+/// (1 + x)^a = 1 + a * x + a * (a - 1) / 2! * x^2 + ..., with x = base - 1 and a < 1
+fn pow_approx<M: ManagedTypeApi>(base: &BigUint<M>, exponent: &BigUint<M>) -> BigUint<M> {
+    let one = one::<M>();
+    let (x, x_negative) = signed_sub(base, &one);
+
+    let mut term = one.clone();
+    let mut sum = one.clone();
+    let mut negative = false;
+    for i in 1..=MAX_POW_APPROX_ITERATIONS {
+        let big_k = BigUint::from(i) * ONE;
+        let (c, c_negative) = signed_sub(exponent, &(&big_k - &one));
+        term = mul_fixed(&term, &mul_fixed(&c, &x));
+        term = div_fixed(&term, &big_k);
+        if term == 0u64 {
+            break;
+        }
+
+        if x_negative {
+            negative = !negative;
+        }
+        if c_negative {
+            negative = !negative;
+        }
+        if negative {
+            sum -= &term;
+        } else {
+            sum += &term;
+        }
+
+        if term < POW_PRECISION {
+            break;
+        }
+    }
+
+    sum
+}
+
+fn signed_sub<M: ManagedTypeApi>(a: &BigUint<M>, b: &BigUint<M>) -> (BigUint<M>, bool) {
+    if a >= b {
+        (a - b, false)
+    } else {
+        (b - a, true)
+    }
+}
+
+/// Amount of `token_out` received for `amount_in` of `token_in`:
+/// out = balance_out * (1 - (balance_in / (balance_in + amount_in * (1 - fee))) ^ (weight_in / weight_out))
+pub fn compute_amount_out<M: ManagedTypeApi>(
+    balance_in: &BigUint<M>,
+    weight_in: &BigUint<M>,
+    balance_out: &BigUint<M>,
+    weight_out: &BigUint<M>,
+    amount_in: &BigUint<M>,
+    fee: &BigUint<M>,
+) -> BigUint<M> {
+    let weight_ratio = div_fixed(weight_in, weight_out);
+    let adjusted_in = mul_fixed(amount_in, &(one::<M>() - fee));
+    let base = div_fixed(balance_in, &(balance_in + &adjusted_in));
+    let power = pow_fixed(&base, &weight_ratio);
+    if power >= one::<M>() {
+        return BigUint::zero();
+    }
+
+    mul_fixed(balance_out, &(one::<M>() - power))
+}
+
+/// Amount of `token_in` needed to receive `amount_out` of `token_out`:
+/// in = balance_in * ((balance_out / (balance_out - amount_out)) ^ (weight_out / weight_in) - 1) / (1 - fee)
+pub fn compute_amount_in<M: ManagedTypeApi>(
+    balance_in: &BigUint<M>,
+    weight_in: &BigUint<M>,
+    balance_out: &BigUint<M>,
+    weight_out: &BigUint<M>,
+    amount_out: &BigUint<M>,
+    fee: &BigUint<M>,
+) -> BigUint<M> {
+    let weight_ratio = div_fixed(weight_out, weight_in);
+    let base = div_fixed(balance_out, &(balance_out - amount_out));
+    let power = pow_fixed(&base, &weight_ratio) - one::<M>();
+    let amount_in = mul_fixed(balance_in, &power);
+
+    div_fixed(&amount_in, &(one::<M>() - fee)) + 1u64
+}
+
+/// LP tokens minted for a single sided deposit. The fee is only charged on the part
+/// of the deposit that would have to be swapped for the other tokens of the pool.
+pub fn compute_lp_out_given_single_in<M: ManagedTypeApi>(
+    balance_in: &BigUint<M>,
+    normalized_weight_in: &BigUint<M>,
+    lp_supply: &BigUint<M>,
+    amount_in: &BigUint<M>,
+    fee: &BigUint<M>,
+) -> BigUint<M> {
+    let one = one::<M>();
+    let taxed_part = mul_fixed(&(&one - normalized_weight_in), fee);
+    let amount_in_after_fee = mul_fixed(amount_in, &(&one - &taxed_part));
+    let balance_ratio = div_fixed(&(balance_in + &amount_in_after_fee), balance_in);
+    let supply_ratio = pow_fixed(&balance_ratio, normalized_weight_in);
+    let new_lp_supply = mul_fixed(&supply_ratio, lp_supply);
+    if &new_lp_supply <= lp_supply {
+        return BigUint::zero();
+    }
+
+    new_lp_supply - lp_supply
+}
+
+/// Amount of a single token received when burning `lp_amount`,
+/// charging the fee on the part that would have been swapped
+pub fn compute_single_out_given_lp_in<M: ManagedTypeApi>(
+    balance_out: &BigUint<M>,
+    normalized_weight_out: &BigUint<M>,
+    lp_supply: &BigUint<M>,
+    lp_amount: &BigUint<M>,
+    fee: &BigUint<M>,
+) -> BigUint<M> {
+    let one = one::<M>();
+    let supply_ratio = div_fixed(&(lp_supply - lp_amount), lp_supply);
+    let balance_ratio = pow_fixed(&supply_ratio, &div_fixed(&one, normalized_weight_out));
+    let new_balance_out = mul_fixed(&balance_ratio, balance_out);
+    if &new_balance_out >= balance_out {
+        return BigUint::zero();
+    }
+
+    let amount_out_before_fee = balance_out - &new_balance_out;
+    let taxed_part = mul_fixed(&(&one - normalized_weight_out), fee);
+    mul_fixed(&amount_out_before_fee, &(&one - &taxed_part))
+}
+
+/// Price of `token_out` in terms of `token_in`, as a fixed point number
+pub fn compute_spot_price<M: ManagedTypeApi>(
+    balance_in: &BigUint<M>,
+    weight_in: &BigUint<M>,
+    balance_out: &BigUint<M>,
+    weight_out: &BigUint<M>,
+) -> BigUint<M> {
+    let numerator = div_fixed(balance_in, weight_in);
+    let denominator = div_fixed(balance_out, weight_out);
+    div_fixed(&numerator, &denominator)
+}
@@ -0,0 +1,319 @@
+use multiversx_sc::types::{
+    Address, EsdtLocalRole, EsdtTokenPayment, ManagedVec, MultiValueEncoded, TokenIdentifier,
+};
+use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+};
+
+pub const WEIGHTED_POOL_WASM_PATH: &str = "weighted-pool/output/weighted-pool.wasm";
+pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+pub const USDC_TOKEN_ID: &[u8] = b"USDC-abcdef";
+pub const LP_TOKEN_ID: &[u8] = b"WLP-abcdef";
+
+pub const POOL_TOKENS: [&[u8]; 3] = [WEGLD_TOKEN_ID, MEX_TOKEN_ID, USDC_TOKEN_ID];
+pub const POOL_WEIGHTS: [u64; 3] = [50_000, 25_000, 25_000];
+pub const TOTAL_FEE_PERCENT: u64 = 300;
+pub const USER_TOTAL_TOKENS: u64 = 1_000_000_000_000;
+
+use pausable::{PausableModule, State};
+use weighted_pool::config::ConfigModule;
+use weighted_pool::liquidity::LiquidityModule;
+use weighted_pool::lp_token::LpTokenModule;
+use weighted_pool::swap::SwapModule;
+use weighted_pool::*;
+
+#[allow(dead_code)]
+pub struct WeightedPoolSetup<PoolObjBuilder>
+where
+    PoolObjBuilder: 'static + Copy + Fn() -> weighted_pool::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub user_address: Address,
+    pub pool_wrapper: ContractObjWrapper<weighted_pool::ContractObj<DebugApi>, PoolObjBuilder>,
+}
+
+#[allow(dead_code)]
+impl<PoolObjBuilder> WeightedPoolSetup<PoolObjBuilder>
+where
+    PoolObjBuilder: 'static + Copy + Fn() -> weighted_pool::ContractObj<DebugApi>,
+{
+    pub fn new(pool_builder: PoolObjBuilder) -> Self {
+        let rust_zero = rust_biguint!(0u64);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_addr = b_mock.create_user_account(&rust_zero);
+        let pool_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_addr),
+            pool_builder,
+            WEIGHTED_POOL_WASM_PATH,
+        );
+
+        b_mock
+            .execute_tx(&owner_addr, &pool_wrapper, &rust_zero, |sc| {
+                let mut tokens_and_weights = MultiValueEncoded::new();
+                for (token_id, weight) in POOL_TOKENS.iter().zip(POOL_WEIGHTS) {
+                    tokens_and_weights.push((managed_token_id!(*token_id), weight).into());
+                }
+
+                sc.init(
+                    managed_address!(&owner_addr),
+                    managed_address!(&owner_addr),
+                    TOTAL_FEE_PERCENT,
+                    tokens_and_weights,
+                );
+
+                sc.lp_token().set_token_id(managed_token_id!(LP_TOKEN_ID));
+            })
+            .assert_ok();
+
+        let lp_token_roles = [EsdtLocalRole::Mint, EsdtLocalRole::Burn];
+        b_mock.set_esdt_local_roles(pool_wrapper.address_ref(), LP_TOKEN_ID, &lp_token_roles[..]);
+
+        let user_addr = b_mock.create_user_account(&rust_zero);
+        for token_id in POOL_TOKENS {
+            b_mock.set_esdt_balance(&owner_addr, token_id, &rust_biguint!(USER_TOTAL_TOKENS));
+            b_mock.set_esdt_balance(&user_addr, token_id, &rust_biguint!(USER_TOTAL_TOKENS));
+        }
+
+        WeightedPoolSetup {
+            b_mock,
+            owner_address: owner_addr,
+            user_address: user_addr,
+            pool_wrapper,
+        }
+    }
+
+    /// Adds the initial liquidity as the owner and enables swaps
+    pub fn add_initial_liquidity(&mut self, amounts: [u64; 3]) -> u64 {
+        let payments = Self::build_payments(amounts);
+        let mut lp_amount = 0;
+        self.b_mock
+            .execute_esdt_multi_transfer(&self.owner_address, &self.pool_wrapper, &payments, |sc| {
+                let lp_payment = sc.add_initial_liquidity();
+                assert_eq!(lp_payment.token_identifier, managed_token_id!(LP_TOKEN_ID));
+                lp_amount = lp_payment.amount.to_u64().unwrap();
+
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+
+        lp_amount
+    }
+
+    /// Returns the LP tokens received and the refunded amounts, in the pool order
+    pub fn add_liquidity(&mut self, amounts: [u64; 3]) -> (u64, [u64; 3]) {
+        let payments = Self::build_payments(amounts);
+        let mut result = (0, [0; 3]);
+        self.b_mock
+            .execute_esdt_multi_transfer(&self.user_address, &self.pool_wrapper, &payments, |sc| {
+                let (lp_payment, refunds) = sc.add_liquidity(managed_biguint!(1)).into_tuple();
+                result.0 = lp_payment.amount.to_u64().unwrap();
+                for refund in refunds.iter() {
+                    let index = Self::token_index(&refund.token_identifier);
+                    result.1[index] = refund.amount.to_u64().unwrap();
+                }
+            })
+            .assert_ok();
+
+        result
+    }
+
+    pub fn add_single_token_liquidity(&mut self, token_id: &[u8], amount: u64) -> u64 {
+        let mut lp_amount = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pool_wrapper,
+                token_id,
+                0,
+                &rust_biguint!(amount),
+                |sc| {
+                    let lp_payment = sc.add_single_token_liquidity(managed_biguint!(1));
+                    lp_amount = lp_payment.amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        lp_amount
+    }
+
+    /// Returns the amounts received, in the pool order
+    pub fn remove_liquidity(&mut self, lp_amount: u64) -> [u64; 3] {
+        let mut amounts = [0; 3];
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pool_wrapper,
+                LP_TOKEN_ID,
+                0,
+                &rust_biguint!(lp_amount),
+                |sc| {
+                    let mut amounts_min = MultiValueEncoded::new();
+                    for _ in POOL_TOKENS {
+                        amounts_min.push(managed_biguint!(1));
+                    }
+
+                    let output_payments = sc.remove_liquidity(amounts_min);
+                    amounts = Self::to_amounts(&output_payments);
+                },
+            )
+            .assert_ok();
+
+        amounts
+    }
+
+    pub fn remove_liquidity_single_token(&mut self, lp_amount: u64, token_id: &[u8]) -> u64 {
+        let mut amount_out = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pool_wrapper,
+                LP_TOKEN_ID,
+                0,
+                &rust_biguint!(lp_amount),
+                |sc| {
+                    let output_payment = sc.remove_liquidity_single_token(
+                        managed_token_id!(token_id),
+                        managed_biguint!(1),
+                    );
+                    amount_out = output_payment.amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        amount_out
+    }
+
+    pub fn remove_liquidity_and_burn_token(&mut self, lp_amount: u64, token_id: &[u8]) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.user_address,
+            &self.pool_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(lp_amount),
+            |sc| {
+                sc.remove_liquidity_and_burn_token(managed_token_id!(token_id));
+            },
+        )
+    }
+
+    pub fn swap_fixed_input(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        desired_token_id: &[u8],
+    ) -> u64 {
+        let mut amount_out = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pool_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount),
+                |sc| {
+                    let ret = sc.swap_tokens_fixed_input(
+                        managed_token_id!(desired_token_id),
+                        managed_biguint!(1),
+                    );
+
+                    assert_eq!(ret.token_identifier, managed_token_id!(desired_token_id));
+                    amount_out = ret.amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        amount_out
+    }
+
+    /// Returns the amount of the payment token that was sent back
+    pub fn swap_fixed_output(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount_max: u64,
+        desired_token_id: &[u8],
+        desired_amount: u64,
+    ) -> u64 {
+        let mut residuum = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.pool_wrapper,
+                payment_token_id,
+                0,
+                &rust_biguint!(payment_amount_max),
+                |sc| {
+                    let (output, payment_residuum) = sc
+                        .swap_tokens_fixed_output(
+                            managed_token_id!(desired_token_id),
+                            managed_biguint!(desired_amount),
+                        )
+                        .into_tuple();
+
+                    assert_eq!(output.amount, managed_biguint!(desired_amount));
+                    residuum = payment_residuum.amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        residuum
+    }
+
+    pub fn get_reserve(&mut self, token_id: &[u8]) -> u64 {
+        let mut reserve = 0;
+        self.b_mock
+            .execute_query(&self.pool_wrapper, |sc| {
+                reserve = sc
+                    .pool_reserve(&TokenIdentifier::from(token_id))
+                    .get()
+                    .to_u64()
+                    .unwrap();
+            })
+            .assert_ok();
+
+        reserve
+    }
+
+    pub fn get_lp_supply(&mut self) -> u64 {
+        let mut lp_supply = 0;
+        self.b_mock
+            .execute_query(&self.pool_wrapper, |sc| {
+                lp_supply = sc.lp_token_supply().get().to_u64().unwrap();
+            })
+            .assert_ok();
+
+        lp_supply
+    }
+
+    fn build_payments(amounts: [u64; 3]) -> Vec<TxTokenTransfer> {
+        POOL_TOKENS
+            .iter()
+            .zip(amounts)
+            .map(|(token_id, amount)| TxTokenTransfer {
+                token_identifier: token_id.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(amount),
+            })
+            .collect()
+    }
+
+    fn token_index(token_id: &TokenIdentifier<DebugApi>) -> usize {
+        POOL_TOKENS
+            .iter()
+            .position(|pool_token_id| token_id == &managed_token_id!(*pool_token_id))
+            .unwrap()
+    }
+
+    fn to_amounts(payments: &ManagedVec<DebugApi, EsdtTokenPayment<DebugApi>>) -> [u64; 3] {
+        let mut amounts = [0; 3];
+        for payment in payments.iter() {
+            amounts[Self::token_index(&payment.token_identifier)] =
+                payment.amount.to_u64().unwrap();
+        }
+
+        amounts
+    }
+}
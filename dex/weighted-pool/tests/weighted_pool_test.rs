@@ -0,0 +1,296 @@
+#![allow(deprecated)]
+
+mod weighted_pool_setup;
+use multiversx_sc::types::{EsdtLocalRole, MultiValueEncoded};
+use multiversx_sc_scenario::{managed_address, managed_biguint, managed_token_id, rust_biguint};
+use weighted_pool::{
+    config::ConfigModule, liquidity::LiquidityModule, swap::SwapModule, views::ViewsModule,
+    weighted_math::ONE, WeightedPool,
+};
+use weighted_pool_setup::*;
+
+const INITIAL_AMOUNTS: [u64; 3] = [1_000_000_000, 2_000_000_000, 500_000_000];
+
+#[test]
+fn test_weighted_pool_setup() {
+    let _ = WeightedPoolSetup::new(weighted_pool::contract_obj);
+}
+
+#[test]
+fn test_init_bad_tokens() {
+    let mut pool_setup = WeightedPoolSetup::new(weighted_pool::contract_obj);
+    let owner_address = pool_setup.owner_address.clone();
+    let other_pool_wrapper = pool_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&owner_address),
+        weighted_pool::contract_obj,
+        WEIGHTED_POOL_WASM_PATH,
+    );
+
+    let mut init_pool = |tokens: &[(&[u8], u64)]| {
+        pool_setup.b_mock.execute_tx(
+            &owner_address,
+            &other_pool_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut tokens_and_weights = MultiValueEncoded::new();
+                for (token_id, weight) in tokens.iter() {
+                    tokens_and_weights.push((managed_token_id!(*token_id), *weight).into());
+                }
+                sc.init(
+                    managed_address!(&owner_address),
+                    managed_address!(&owner_address),
+                    TOTAL_FEE_PERCENT,
+                    tokens_and_weights,
+                );
+            },
+        )
+    };
+
+    init_pool(&[(WEGLD_TOKEN_ID, 50_000), (MEX_TOKEN_ID, 50_000)])
+        .assert_user_error("Bad number of tokens");
+    init_pool(&[
+        (WEGLD_TOKEN_ID, 50_000),
+        (MEX_TOKEN_ID, 25_000),
+        (USDC_TOKEN_ID, 20_000),
+    ])
+    .assert_user_error("Bad token weights");
+    init_pool(&[
+        (WEGLD_TOKEN_ID, 50_000),
+        (MEX_TOKEN_ID, 25_000),
+        (WEGLD_TOKEN_ID, 25_000),
+    ])
+    .assert_user_error("Exchange tokens cannot be the same");
+}
+
+#[test]
+fn test_add_and_remove_liquidity() {
+    let mut pool_setup = WeightedPoolSetup::new(weighted_pool::contract_obj);
+    let initial_lp_amount = pool_setup.add_initial_liquidity(INITIAL_AMOUNTS);
+    assert_eq!(initial_lp_amount, ONE - 1_000);
+
+    // only the amounts in the pool proportions are used
+    let (lp_amount, refunds) = pool_setup.add_liquidity([10_000_000, 30_000_000, 5_000_000]);
+    assert_eq!(lp_amount, ONE / 100);
+    assert_eq!(refunds, [0, 10_000_000, 0]);
+    assert_eq!(pool_setup.get_reserve(MEX_TOKEN_ID), 2_020_000_000);
+    assert_eq!(pool_setup.get_lp_supply(), ONE + ONE / 100);
+
+    let amounts = pool_setup.remove_liquidity(lp_amount);
+    assert_eq!(amounts, [10_000_000, 20_000_000, 5_000_000]);
+    for (token_id, initial_amount) in POOL_TOKENS.iter().zip(INITIAL_AMOUNTS) {
+        assert_eq!(pool_setup.get_reserve(token_id), initial_amount);
+    }
+    pool_setup
+        .b_mock
+        .check_esdt_balance(&pool_setup.user_address, LP_TOKEN_ID, &rust_biguint!(0));
+
+    // all the pool tokens have to be sent
+    pool_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pool_setup.user_address,
+            &pool_setup.pool_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.add_liquidity(managed_biguint!(1));
+            },
+        )
+        .assert_user_error("Bad payment tokens");
+}
+
+#[test]
+fn test_single_token_liquidity() {
+    let mut pool_setup = WeightedPoolSetup::new(weighted_pool::contract_obj);
+    let _ = pool_setup.add_initial_liquidity(INITIAL_AMOUNTS);
+
+    // a single sided deposit costs part of the fee, so exiting right away returns less
+    let amount_in = 10_000_000;
+    let lp_amount = pool_setup.add_single_token_liquidity(WEGLD_TOKEN_ID, amount_in);
+    assert!(lp_amount < ONE / 200);
+    assert!(lp_amount > ONE / 200 * 99 / 100);
+
+    let amount_out = pool_setup.remove_liquidity_single_token(lp_amount, WEGLD_TOKEN_ID);
+    assert!(amount_out < amount_in);
+    assert!(amount_out > amount_in * 99 / 100);
+    assert_eq!(
+        pool_setup.get_reserve(WEGLD_TOKEN_ID),
+        INITIAL_AMOUNTS[0] + amount_in - amount_out
+    );
+    assert_eq!(pool_setup.get_lp_supply(), ONE);
+
+    pool_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pool_setup.user_address,
+            &pool_setup.pool_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(INITIAL_AMOUNTS[0]),
+            |sc| {
+                let _ = sc.add_single_token_liquidity(managed_biguint!(1));
+            },
+        )
+        .assert_user_error("Amount in is too large compared to the reserve");
+}
+
+#[test]
+fn test_swap() {
+    let mut pool_setup = WeightedPoolSetup::new(weighted_pool::contract_obj);
+    let _ = pool_setup.add_initial_liquidity(INITIAL_AMOUNTS);
+
+    // WEGLD has twice the weight of MEX, so one WEGLD is worth four MEX
+    pool_setup
+        .b_mock
+        .execute_query(&pool_setup.pool_wrapper, |sc| {
+            let spot_price = sc.get_spot_price(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(WEGLD_TOKEN_ID),
+            );
+            assert_eq!(spot_price, managed_biguint!(4) * ONE);
+        })
+        .assert_ok();
+
+    let amount_in = 1_000_000u64;
+    let mut expected_amount_out = 0;
+    pool_setup
+        .b_mock
+        .execute_query(&pool_setup.pool_wrapper, |sc| {
+            expected_amount_out = sc
+                .get_amount_out_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(amount_in),
+                )
+                .to_u64()
+                .unwrap();
+        })
+        .assert_ok();
+
+    let amount_out = pool_setup.swap_fixed_input(WEGLD_TOKEN_ID, amount_in, MEX_TOKEN_ID);
+    assert_eq!(amount_out, expected_amount_out);
+
+    let balance_in = INITIAL_AMOUNTS[0] as f64;
+    let balance_out = INITIAL_AMOUNTS[1] as f64;
+    let amount_in_after_fee = amount_in as f64 * 0.997;
+    let exact_amount_out =
+        balance_out * (1.0 - (balance_in / (balance_in + amount_in_after_fee)).powi(2));
+    assert!((amount_out as f64 - exact_amount_out).abs() < 2.0);
+
+    assert_eq!(
+        pool_setup.get_reserve(WEGLD_TOKEN_ID),
+        INITIAL_AMOUNTS[0] + amount_in
+    );
+    assert_eq!(
+        pool_setup.get_reserve(MEX_TOKEN_ID),
+        INITIAL_AMOUNTS[1] - amount_out
+    );
+
+    // fixed output, with a fractional weight ratio
+    let amount_wanted = 1_000_000;
+    let mut expected_amount_in = 0;
+    pool_setup
+        .b_mock
+        .execute_query(&pool_setup.pool_wrapper, |sc| {
+            expected_amount_in = sc
+                .get_amount_in_view(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_biguint!(amount_wanted),
+                )
+                .to_u64()
+                .unwrap();
+        })
+        .assert_ok();
+
+    let balance_in = (INITIAL_AMOUNTS[0] + amount_in) as f64;
+    let balance_out = INITIAL_AMOUNTS[2] as f64;
+    let exact_amount_in =
+        balance_in * ((balance_out / (balance_out - amount_wanted as f64)).powf(0.5) - 1.0) / 0.997;
+    assert!((expected_amount_in as f64 - exact_amount_in).abs() < 2.0);
+
+    let payment_amount_max = 2 * expected_amount_in;
+    let residuum = pool_setup.swap_fixed_output(
+        WEGLD_TOKEN_ID,
+        payment_amount_max,
+        USDC_TOKEN_ID,
+        amount_wanted,
+    );
+    assert_eq!(residuum, payment_amount_max - expected_amount_in);
+
+    pool_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pool_setup.user_address,
+            &pool_setup.pool_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(INITIAL_AMOUNTS[1]),
+            |sc| {
+                let _ = sc
+                    .swap_tokens_fixed_input(managed_token_id!(USDC_TOKEN_ID), managed_biguint!(1));
+            },
+        )
+        .assert_user_error("Amount in is too large compared to the reserve");
+}
+
+#[test]
+fn test_remove_liquidity_and_burn_token() {
+    let mut pool_setup = WeightedPoolSetup::new(weighted_pool::contract_obj);
+    let _ = pool_setup.add_initial_liquidity(INITIAL_AMOUNTS);
+    let (lp_amount, _) = pool_setup.add_liquidity([10_000_000, 20_000_000, 5_000_000]);
+
+    pool_setup
+        .remove_liquidity_and_burn_token(lp_amount / 2, MEX_TOKEN_ID)
+        .assert_user_error("Not whitelisted");
+
+    // the user plays the role of a farm
+    let user_address = pool_setup.user_address.clone();
+    pool_setup
+        .b_mock
+        .execute_tx(
+            &pool_setup.owner_address,
+            &pool_setup.pool_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.whitelist_endpoint(managed_address!(&user_address));
+            },
+        )
+        .assert_ok();
+    pool_setup.b_mock.set_esdt_local_roles(
+        pool_setup.pool_wrapper.address_ref(),
+        MEX_TOKEN_ID,
+        &[EsdtLocalRole::Burn][..],
+    );
+
+    let mex_reserve_before = pool_setup.get_reserve(MEX_TOKEN_ID);
+    pool_setup
+        .remove_liquidity_and_burn_token(lp_amount / 2, MEX_TOKEN_ID)
+        .assert_ok();
+    let mex_burned = mex_reserve_before - pool_setup.get_reserve(MEX_TOKEN_ID);
+    assert!(mex_burned > 0);
+    pool_setup.b_mock.check_esdt_balance(
+        pool_setup.pool_wrapper.address_ref(),
+        MEX_TOKEN_ID,
+        &rust_biguint!(INITIAL_AMOUNTS[1] + 20_000_000 - mex_burned),
+    );
+
+    // for tokens outside the pool, only the LP tokens are burned
+    let reserves_before = POOL_TOKENS.map(|token_id| pool_setup.get_reserve(token_id));
+    let lp_supply_before = pool_setup.get_lp_supply();
+    pool_setup
+        .remove_liquidity_and_burn_token(lp_amount / 2, b"OTHER-abcdef")
+        .assert_ok();
+    assert_eq!(
+        POOL_TOKENS.map(|token_id| pool_setup.get_reserve(token_id)),
+        reserves_before
+    );
+    assert_eq!(pool_setup.get_lp_supply(), lp_supply_before - lp_amount / 2);
+    pool_setup.b_mock.check_esdt_balance(
+        &pool_setup.user_address,
+        LP_TOKEN_ID,
+        &rust_biguint!(lp_amount - 2 * (lp_amount / 2)),
+    );
+}
@@ -0,0 +1,60 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Upgrade:                              1
+// Endpoints:                           34
+// Async Callback:                       1
+// Total number of exported functions:  37
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    weighted_pool
+    (
+        init => init
+        upgrade => upgrade
+        setStateActiveNoSwaps => set_state_active_no_swaps
+        setFeePercent => set_fee_percent_endpoint
+        whitelist => whitelist_endpoint
+        removeWhitelist => remove_whitelist
+        getTotalFeePercent => total_fee_percent
+        getRouterManagedAddress => router_address
+        getTokenWeight => token_weight
+        getReserve => pool_reserve
+        getTotalSupply => lp_token_supply
+        getWhitelistedManagedAddresses => whitelist
+        issueLpToken => issue_lp_token
+        getLpTokenIdentifier => lp_token
+        addInitialLiquidity => add_initial_liquidity
+        addLiquidity => add_liquidity
+        addSingleTokenLiquidity => add_single_token_liquidity
+        removeLiquidity => remove_liquidity
+        removeLiquiditySingleToken => remove_liquidity_single_token
+        removeLiquidityAndBuyBackAndBurnToken => remove_liquidity_and_burn_token
+        swapTokensFixedInput => swap_tokens_fixed_input
+        swapTokensFixedOutput => swap_tokens_fixed_output
+        getAmountOut => get_amount_out_view
+        getAmountIn => get_amount_in_view
+        getSpotPrice => get_spot_price
+        getTokensForGivenPosition => get_tokens_for_given_position
+        getPoolTokens => get_pool_tokens
+        addAdmin => add_admin_endpoint
+        removeAdmin => remove_admin_endpoint
+        updateOwnerOrAdmin => update_owner_or_admin_endpoint
+        getPermissions => permissions
+        addToPauseWhitelist => add_to_pause_whitelist
+        removeFromPauseWhitelist => remove_from_pause_whitelist
+        pause => pause
+        resume => resume
+        getState => state
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback! { weighted_pool }
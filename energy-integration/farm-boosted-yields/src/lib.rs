@@ -1,10 +1,11 @@
 #![no_std]
 
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
 
 use core::cmp;
 
-use boosted_yields_factors::BoostedYieldsConfig;
+use boosted_yields_factors::{BoostedYieldsConfig, BoostedYieldsFactors};
 use common_types::PaymentsVec;
 use multiversx_sc::api::ErrorApi;
 use week_timekeeping::Week;
@@ -30,6 +31,24 @@ impl<M: ManagedTypeApi> SplitReward<M> {
     }
 }
 
+#[type_abi]
+#[derive(
+    ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug,
+)]
+pub struct WeeklyBoostedRewards<M: ManagedTypeApi> {
+    pub week: Week,
+    pub amount: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct UserBoostedYieldsInfo<M: ManagedTypeApi> {
+    pub energy: BigUint<M>,
+    pub total_farm_position: BigUint<M>,
+    pub rewards_per_week: ManagedVec<M, WeeklyBoostedRewards<M>>,
+    pub factors: Option<BoostedYieldsFactors<M>>,
+}
+
 #[multiversx_sc::module]
 pub trait FarmBoostedYieldsModule:
     boosted_yields_factors::BoostedYieldsFactorsModule
@@ -116,6 +135,67 @@ pub trait FarmBoostedYieldsModule:
         total
     }
 
+    /// The boosted rewards are paid per user, for all of their farm positions, with the
+    /// first claim of any of them. Goes through the same steps as the claim,
+    /// so it may only be used in queries.
+    fn get_user_boosted_yields_info(
+        &self,
+        user: &ManagedAddress,
+    ) -> UserBoostedYieldsInfo<Self::Api> {
+        let total_farm_position = self.user_total_farm_position(user).get();
+        let opt_config = self.try_get_boosted_yields_config();
+        let factors = opt_config
+            .as_ref()
+            .map(|config| config.get_latest_factors());
+        let rewards_per_week = match opt_config {
+            Some(config) => {
+                let wrapper = FarmBoostedYieldsWrapper::new(total_farm_position.clone(), config);
+                self.calculate_boosted_rewards_per_week(&wrapper, user)
+            }
+            None => ManagedVec::new(),
+        };
+
+        UserBoostedYieldsInfo {
+            energy: self.get_energy_amount(user),
+            total_farm_position,
+            rewards_per_week,
+            factors,
+        }
+    }
+
+    /// Same weeks as `claim_multi`, without updating the claim progress of the user.
+    fn calculate_boosted_rewards_per_week(
+        &self,
+        wrapper: &FarmBoostedYieldsWrapper<Self>,
+        user: &ManagedAddress,
+    ) -> ManagedVec<WeeklyBoostedRewards<Self::Api>> {
+        let mut rewards_per_week = ManagedVec::new();
+        let claim_progress_mapper = wrapper.get_claim_progress_mapper(self, user);
+        if claim_progress_mapper.is_empty() {
+            return rewards_per_week;
+        }
+
+        let current_week = self.get_current_week();
+        let mut claim_progress = claim_progress_mapper.get();
+        let total_weeks_to_claim = current_week - claim_progress.week;
+        if total_weeks_to_claim > USER_MAX_CLAIM_WEEKS {
+            let extra_weeks = total_weeks_to_claim - USER_MAX_CLAIM_WEEKS;
+            claim_progress.advance_multiple_weeks(extra_weeks);
+        }
+
+        while claim_progress.week < current_week {
+            let week = claim_progress.week;
+            let mut amount = BigUint::zero();
+            for reward in self.claim_single(wrapper, &mut claim_progress).iter() {
+                amount += &reward.amount;
+            }
+
+            rewards_per_week.push(WeeklyBoostedRewards { week, amount });
+        }
+
+        rewards_per_week
+    }
+
     fn set_farm_supply_for_current_week(&self, farm_supply: &BigUint) {
         let current_week = self.get_current_week();
         self.farm_supply_for_week(current_week).set(farm_supply);
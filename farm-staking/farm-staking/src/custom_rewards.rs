@@ -1,9 +1,10 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use common_structs::Epoch;
+use common_structs::{Epoch, Nonce};
 use contexts::storage_cache::StorageCache;
 use farm_base_impl::base_traits_impl::FarmContract;
+use rewards::{EmissionDecayCurve, EmissionSchedule, EmissionSegment};
 
 use crate::base_impl_wrapper::FarmStakingWrapper;
 
@@ -94,6 +95,57 @@ pub trait CustomRewardsModule:
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
+    /// Pairs of (start block, per block amount), sorted by start block.
+    #[endpoint(setEmissionSegments)]
+    fn set_emission_segments(&self, segments: MultiValueEncoded<MultiValue2<Nonce, BigUint>>) {
+        self.require_caller_has_admin_permissions();
+
+        let mut emission_segments = ManagedVec::new();
+        for segment in segments {
+            let (start_block, per_block_amount) = segment.into_tuple();
+            emission_segments.push(EmissionSegment {
+                start_block,
+                per_block_amount,
+            });
+        }
+        self.update_emission_schedule(Some(EmissionSchedule::Segments(emission_segments)));
+    }
+
+    #[endpoint(setEmissionDecayCurve)]
+    fn set_emission_decay_curve(
+        &self,
+        start_block: Nonce,
+        initial_per_block_amount: BigUint,
+        period_blocks: Nonce,
+        decay_percent: u64,
+    ) {
+        self.require_caller_has_admin_permissions();
+
+        let curve = EmissionDecayCurve {
+            start_block,
+            initial_per_block_amount,
+            period_blocks,
+            decay_percent,
+        };
+        self.update_emission_schedule(Some(EmissionSchedule::DecayCurve(curve)));
+    }
+
+    #[endpoint(clearEmissionSchedule)]
+    fn clear_emission_schedule(&self) {
+        self.require_caller_has_admin_permissions();
+        self.update_emission_schedule(None);
+    }
+
+    fn update_emission_schedule(&self, opt_schedule: Option<EmissionSchedule<Self::Api>>) {
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        match opt_schedule {
+            Some(schedule) => self.set_emission_schedule(schedule),
+            None => self.emission_schedule().clear(),
+        }
+    }
+
     #[endpoint(setMaxApr)]
     fn set_max_apr(&self, max_apr: BigUint) {
         self.require_caller_has_admin_permissions();
@@ -5,9 +5,11 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use base_impl_wrapper::FarmStakingWrapper;
+use common_structs::{Epoch, Nonce};
 use contexts::storage_cache::StorageCache;
 use farm::{base_functions::DoubleMultiPayment, MAX_PERCENT};
 use farm_base_impl::base_traits_impl::FarmContract;
+use farm_boosted_yields::UserBoostedYieldsInfo;
 use fixed_supply_token::FixedSupplyToken;
 use token_attributes::StakingFarmTokenAttributes;
 
@@ -24,6 +26,18 @@ pub mod token_attributes;
 pub mod unbond_farm;
 pub mod unstake_farm;
 
+/// The rewards of a staking position, as of the current block. Staking positions have
+/// no entering epoch and no exit penalty, the unstaked tokens are locked until `unbond_epoch`.
+#[type_abi]
+#[derive(TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct StakingPositionInfo<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub farming_token_amount: BigUint<M>,
+    pub base_rewards: BigUint<M>,
+    pub unbond_epoch: Epoch,
+    pub owner_boosted_yields: UserBoostedYieldsInfo<M>,
+}
+
 #[multiversx_sc::contract]
 pub trait FarmStaking:
     custom_rewards::CustomRewardsModule
@@ -178,6 +192,39 @@ pub trait FarmStaking:
         )
     }
 
+    /// The attributes are read from the farm token. The boosted rewards are the ones
+    /// of the position owner, which are not tied to any single position.
+    #[view(getStakingPositionInfo)]
+    fn get_staking_position_info(
+        &self,
+        farm_token_nonce: Nonce,
+        farm_token_amount: BigUint,
+    ) -> StakingPositionInfo<Self::Api> {
+        self.require_queried();
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+
+        let token_attributes: StakingFarmTokenAttributes<Self::Api> =
+            self.farm_token().get_token_attributes(farm_token_nonce);
+        let attributes = token_attributes.into_part(&farm_token_amount);
+
+        let base_rewards = FarmStakingWrapper::<Self>::calculate_base_farm_rewards(
+            &farm_token_amount,
+            &attributes,
+            &storage_cache,
+        );
+        let owner_boosted_yields = self.get_user_boosted_yields_info(&attributes.original_owner);
+
+        StakingPositionInfo {
+            farming_token_amount: attributes.get_total_supply(),
+            owner: attributes.original_owner,
+            base_rewards,
+            unbond_epoch: self.blockchain().get_block_epoch() + self.min_unbond_epochs().get(),
+            owner_boosted_yields,
+        }
+    }
+
     fn require_queried(&self) {
         let caller = self.blockchain().get_caller();
         let sc_address = self.blockchain().get_sc_address();
@@ -71,6 +71,59 @@ fn farm_staking_boosted_rewards_no_energy_test() {
     fs_setup.check_farm_token_supply(farm_in_amount);
 }
 
+#[test]
+fn farm_staking_position_info_test() {
+    DebugApi::dummy();
+    let mut fs_setup =
+        FarmStakingSetup::new(farm_staking::contract_obj, energy_factory::contract_obj);
+
+    let user_address = fs_setup.user_address.clone();
+
+    fs_setup.set_boosted_yields_factors();
+    fs_setup.set_boosted_yields_rewards_percentage(BOOSTED_YIELDS_PERCENTAGE);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    fs_setup.stake_farm(
+        &user_address,
+        farm_in_amount,
+        &[],
+        expected_farm_token_nonce,
+        0,
+        0,
+    );
+
+    fs_setup.set_block_epoch(5);
+    fs_setup.set_block_nonce(10);
+
+    fs_setup
+        .b_mock
+        .execute_query(&fs_setup.farm_wrapper, |sc| {
+            let position_info = sc.get_staking_position_info(
+                expected_farm_token_nonce,
+                managed_biguint!(farm_in_amount / 2),
+            );
+            assert_eq!(position_info.owner, managed_address!(&user_address));
+            assert_eq!(
+                position_info.farming_token_amount,
+                managed_biguint!(farm_in_amount / 2)
+            );
+            assert_eq!(position_info.base_rewards, managed_biguint!(15));
+            assert_eq!(position_info.unbond_epoch, 5 + MIN_UNBOND_EPOCHS);
+
+            // no energy, so no boosted rewards
+            let boosted_yields = position_info.owner_boosted_yields;
+            assert_eq!(boosted_yields.energy, managed_biguint!(0));
+            assert_eq!(
+                boosted_yields.total_farm_position,
+                managed_biguint!(farm_in_amount)
+            );
+            assert!(boosted_yields.rewards_per_week.is_empty());
+            assert!(boosted_yields.factors.is_some());
+        })
+        .assert_ok();
+}
+
 #[test]
 fn farm_staking_other_user_enter_negative_test() {
     DebugApi::dummy();
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           69
+// Endpoints:                           75
 // Async Callback:                       1
-// Total number of exported functions:  72
+// Total number of exported functions:  78
 
 #![no_std]
 
@@ -23,10 +23,14 @@ multiversx_sc_wasm_adapter::endpoints! {
         mergeFarmTokens => merge_farm_tokens_endpoint
         setBoostedYieldsRewardsPercentage => set_boosted_yields_rewards_percentage
         calculateRewardsForGivenPosition => calculate_rewards_for_given_position
+        getStakingPositionInfo => get_staking_position_info
         topUpRewards => top_up_rewards
         withdrawRewards => withdraw_rewards
         endProduceRewards => end_produce_rewards
         setPerBlockRewardAmount => set_per_block_rewards
+        setEmissionSegments => set_emission_segments
+        setEmissionDecayCurve => set_emission_decay_curve
+        clearEmissionSchedule => clear_emission_schedule
         setMaxApr => set_max_apr
         setMinUnbondEpochs => set_min_unbond_epochs_endpoint
         startProduceRewards => start_produce_rewards_endpoint
@@ -34,6 +38,8 @@ multiversx_sc_wasm_adapter::endpoints! {
         getRewardCapacity => reward_capacity
         getAnnualPercentageRewards => max_annual_percentage_rewards
         getMinUnbondEpochs => min_unbond_epochs
+        getEmissionForBlocks => calculate_emission
+        getEmissionSchedule => emission_schedule
         getRewardPerShare => reward_per_share
         getRewardReserve => reward_reserve
         getFarmingTokenId => farming_token_id
@@ -1,14 +1,22 @@
 use common_structs::{RawResultWrapper, RawResultsType};
 
+use crate::proxy_farm::FarmType;
+
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 type EnterFarmResultType<BigUint> =
     MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
-type ExitFarmResultType<BigUint> =
-    MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
-type ClaimRewardsResultType<BigUint> =
-    MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
+type ExitFarmResultType<BigUint> = MultiValue3<
+    EsdtTokenPayment<BigUint>,
+    EsdtTokenPayment<BigUint>,
+    MultiValueEncoded<BigUint, EsdtTokenPayment<BigUint>>,
+>;
+type ClaimRewardsResultType<BigUint> = MultiValue3<
+    EsdtTokenPayment<BigUint>,
+    EsdtTokenPayment<BigUint>,
+    MultiValueEncoded<BigUint, EsdtTokenPayment<BigUint>>,
+>;
 
 const ENTER_FARM_RESULTS_LEN: usize = 2;
 const EXIT_FARM_RESULTS_LEN: usize = 2;
@@ -22,11 +30,13 @@ pub struct EnterFarmResultWrapper<M: ManagedTypeApi> {
 pub struct ExitFarmResultWrapper<M: ManagedTypeApi> {
     pub initial_farming_tokens: EsdtTokenPayment<M>,
     pub reward_tokens: EsdtTokenPayment<M>,
+    pub reward_stream_tokens: ManagedVec<M, EsdtTokenPayment<M>>,
 }
 
 pub struct FarmClaimRewardsResultWrapper<M: ManagedTypeApi> {
     pub new_farm_tokens: EsdtTokenPayment<M>,
     pub reward_tokens: EsdtTokenPayment<M>,
+    pub reward_stream_tokens: ManagedVec<M, EsdtTokenPayment<M>>,
 }
 
 pub struct FarmCompoundRewardsResultWrapper<M: ManagedTypeApi> {
@@ -59,6 +69,13 @@ mod farm_proxy {
             &self,
             opt_orig_caller: OptionalValue<ManagedAddress>,
         ) -> ClaimRewardsResultType<Self::Api>;
+
+        #[view(getRewardStreamRewardsForPosition)]
+        fn get_reward_stream_rewards_for_position(
+            &self,
+            farm_token_nonce: u64,
+            farm_token_amount: BigUint,
+        ) -> MultiValueEncoded<EsdtTokenPayment>;
     }
 }
 
@@ -101,11 +118,18 @@ pub trait FarmInteractionsModule {
     fn call_farm_exit(
         &self,
         farm_address: ManagedAddress,
+        farm_type: FarmType,
         farm_token: TokenIdentifier,
         farm_token_nonce: u64,
         farm_token_amount: BigUint,
         caller: ManagedAddress,
     ) -> ExitFarmResultWrapper<Self::Api> {
+        let reward_streams_len = self.get_reward_streams_results_len(
+            &farm_address,
+            farm_type,
+            farm_token_nonce,
+            &farm_token_amount,
+        );
         let raw_results: RawResultsType<Self::Api> = self
             .farm_proxy(farm_address)
             .exit_farm(OptionalValue::Some(caller))
@@ -117,25 +141,35 @@ pub trait FarmInteractionsModule {
             .execute_on_dest_context();
 
         let mut results_wrapper = RawResultWrapper::new(raw_results);
-        results_wrapper.trim_results_front(EXIT_FARM_RESULTS_LEN);
+        results_wrapper.trim_results_front(EXIT_FARM_RESULTS_LEN + reward_streams_len);
 
         let initial_farming_tokens = results_wrapper.decode_next_result();
         let reward_tokens = results_wrapper.decode_next_result();
+        let reward_stream_tokens =
+            self.decode_reward_stream_results(&mut results_wrapper, reward_streams_len);
 
         ExitFarmResultWrapper {
             initial_farming_tokens,
             reward_tokens,
+            reward_stream_tokens,
         }
     }
 
     fn call_farm_claim_rewards(
         &self,
         farm_address: ManagedAddress,
+        farm_type: FarmType,
         farm_token: TokenIdentifier,
         farm_token_nonce: u64,
         farm_token_amount: BigUint,
         caller: ManagedAddress,
     ) -> FarmClaimRewardsResultWrapper<Self::Api> {
+        let reward_streams_len = self.get_reward_streams_results_len(
+            &farm_address,
+            farm_type,
+            farm_token_nonce,
+            &farm_token_amount,
+        );
         let raw_results: RawResultsType<Self::Api> = self
             .farm_proxy(farm_address)
             .claim_rewards(OptionalValue::Some(caller))
@@ -147,15 +181,52 @@ pub trait FarmInteractionsModule {
             .execute_on_dest_context();
 
         let mut results_wrapper = RawResultWrapper::new(raw_results);
-        results_wrapper.trim_results_front(CLAIM_REWARDS_RESULTS_LEN);
+        results_wrapper.trim_results_front(CLAIM_REWARDS_RESULTS_LEN + reward_streams_len);
 
         let new_farm_tokens = results_wrapper.decode_next_result();
         let reward_tokens = results_wrapper.decode_next_result();
+        let reward_stream_tokens =
+            self.decode_reward_stream_results(&mut results_wrapper, reward_streams_len);
 
         FarmClaimRewardsResultWrapper {
             new_farm_tokens,
             reward_tokens,
+            reward_stream_tokens,
+        }
+    }
+
+    /// Simple farms return the rewards of their reward streams after the base rewards,
+    /// one result for each stream which pays anything to the position.
+    fn get_reward_streams_results_len(
+        &self,
+        farm_address: &ManagedAddress,
+        farm_type: FarmType,
+        farm_token_nonce: u64,
+        farm_token_amount: &BigUint,
+    ) -> usize {
+        if farm_type != FarmType::SimpleFarm {
+            return 0;
         }
+
+        let stream_rewards: MultiValueEncoded<EsdtTokenPayment> = self
+            .farm_proxy(farm_address.clone())
+            .get_reward_stream_rewards_for_position(farm_token_nonce, farm_token_amount.clone())
+            .execute_on_dest_context();
+
+        stream_rewards.len()
+    }
+
+    fn decode_reward_stream_results(
+        &self,
+        results_wrapper: &mut RawResultWrapper<Self::Api>,
+        reward_streams_len: usize,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        let mut reward_stream_tokens = ManagedVec::new();
+        for _ in 0..reward_streams_len {
+            reward_stream_tokens.push(results_wrapper.decode_next_result());
+        }
+
+        reward_stream_tokens
     }
 
     #[proxy]
@@ -197,6 +197,7 @@ pub trait ProxyFarmModule:
     /// Output Payments:
     /// - original farming tokens
     /// - farm reward tokens
+    /// - the reward stream tokens of simple farms, if any
     #[payable("*")]
     #[endpoint(exitFarmLockedToken)]
     fn exit_farm_locked_token(&self) -> ExitFarmThroughProxyResultType<Self::Api> {
@@ -213,6 +214,7 @@ pub trait ProxyFarmModule:
         let caller = self.blockchain().get_caller();
         let exit_farm_result = self.call_farm_exit(
             farm_address,
+            farm_proxy_token_attributes.farm_type,
             farm_proxy_token_attributes.farm_token_id,
             farm_proxy_token_attributes.farm_token_nonce,
             payment.amount.clone(),
@@ -254,6 +256,10 @@ pub trait ProxyFarmModule:
                 &exit_farm_result.reward_tokens.amount,
             );
         }
+        if !exit_farm_result.reward_stream_tokens.is_empty() {
+            self.send()
+                .direct_multi(&caller, &exit_farm_result.reward_stream_tokens);
+        }
 
         (lp_proxy_token_payment, exit_farm_result.reward_tokens).into()
     }
@@ -267,6 +273,7 @@ pub trait ProxyFarmModule:
     /// Output payments:
     /// - a new FARM_PROXY token
     /// - farm reward tokens
+    /// - the reward stream tokens of simple farms, if any
     #[payable("*")]
     #[endpoint(farmClaimRewardsLockedToken)]
     fn farm_claim_rewards_locked_token(&self) -> FarmClaimRewardsThroughProxyResultType<Self::Api> {
@@ -282,6 +289,7 @@ pub trait ProxyFarmModule:
         let caller = self.blockchain().get_caller();
         let claim_rewards_result = self.call_farm_claim_rewards(
             farm_address,
+            farm_proxy_token_attributes.farm_type,
             farm_proxy_token_attributes.farm_token_id.clone(),
             farm_proxy_token_attributes.farm_token_nonce,
             payment.amount.clone(),
@@ -310,6 +318,10 @@ pub trait ProxyFarmModule:
                 &claim_rewards_result.reward_tokens.amount,
             );
         }
+        if !claim_rewards_result.reward_stream_tokens.is_empty() {
+            self.send()
+                .direct_multi(&caller, &claim_rewards_result.reward_stream_tokens);
+        }
 
         (new_proxy_token_payment, claim_rewards_result.reward_tokens).into()
     }